@@ -489,6 +489,48 @@ impl KclvmServiceImpl {
             yaml_result: result.yaml_result,
             log_message: result.log_message,
             err_message: result.err_message,
+            source_map: result
+                .source_map
+                .into_iter()
+                .map(|(path, (filename, line, column, end_line, end_column))| {
+                    (
+                        path,
+                        SourceRange {
+                            start: Some(Position {
+                                filename: filename.clone(),
+                                line: line as i64,
+                                column: column as i64,
+                            }),
+                            end: Some(Position {
+                                filename,
+                                line: end_line as i64,
+                                column: end_column as i64,
+                            }),
+                        },
+                    )
+                })
+                .collect(),
+            process_peak_memory_bytes: result.process_peak_memory_bytes.unwrap_or_default(),
+            cache_stats: result
+                .cache_stats
+                .into_iter()
+                .map(|(pkgpath, stats)| {
+                    (
+                        pkgpath,
+                        PackageCacheStats {
+                            ast_cached: stats.ast_cached,
+                            lib_cached: stats.lib_cached.unwrap_or_default(),
+                        },
+                    )
+                })
+                .collect(),
+            timings: result.timings.map(|timings| PhaseTimings {
+                load: timings.load.as_micros() as u64,
+                resolve: timings.resolve.as_micros() as u64,
+                codegen: timings.codegen.unwrap_or_default().as_micros() as u64,
+                eval: timings.eval.as_micros() as u64,
+            }),
+            split_outputs: result.split_outputs.into_iter().collect(),
         })
     }
 
@@ -564,6 +606,7 @@ impl KclvmServiceImpl {
             yaml_result: result.yaml_result,
             log_message: result.log_message,
             err_message: result.err_message,
+            ..Default::default()
         })
     }
 
@@ -767,6 +810,7 @@ impl KclvmServiceImpl {
                 is_stdout: false,
                 recursively: false,
                 omit_errors: true,
+                ..Default::default()
             },
         )?;
         Ok(FormatCodeResult {
@@ -804,6 +848,7 @@ impl KclvmServiceImpl {
                 recursively,
                 is_stdout: false,
                 omit_errors: true,
+                ..Default::default()
             },
         )?;
         Ok(FormatPathResult { changed_paths })
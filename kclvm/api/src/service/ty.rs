@@ -20,11 +20,13 @@ pub(crate) fn kcl_ty_to_pb_ty(ty: &Type) -> KclType {
         kclvm_sema::ty::TypeKind::Union(types) => KclType {
             r#type: "union".to_string(),
             union_types: types.iter().map(|ty| kcl_ty_to_pb_ty(ty)).collect(),
+            enum_values: ty.literal_values().unwrap_or_default(),
             ..Default::default()
         },
         kclvm_sema::ty::TypeKind::Schema(schema_ty) => kcl_schema_ty_to_pb_ty(schema_ty),
         _ => KclType {
             r#type: ty.ty_str(),
+            enum_values: ty.literal_values().unwrap_or_default(),
             ..Default::default()
         },
     }
@@ -119,3 +121,32 @@ fn get_schema_ty_required_attributes(schema_ty: &SchemaType) -> Vec<String> {
     }
     attr_set.iter().cloned().collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kclvm_query::{get_schema_type, GetSchemaOption};
+
+    #[test]
+    fn test_kcl_schema_ty_to_pb_ty_enum_values() {
+        let code = r#"
+schema Service:
+    protocol: "TCP" | "UDP"
+    port: int
+"#;
+        let types = get_schema_type(
+            "main.k",
+            Some(code),
+            Some("Service"),
+            GetSchemaOption::Definitions,
+        )
+        .unwrap();
+        let ty = kcl_schema_ty_to_pb_ty(&types["Service"]);
+        assert_eq!(ty.properties["protocol"].r#type, "union");
+        assert_eq!(
+            ty.properties["protocol"].enum_values,
+            vec!["\"TCP\"".to_string(), "\"UDP\"".to_string()]
+        );
+        assert!(ty.properties["port"].enum_values.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::gpyrpc::ExecProgramArgs;
+use kclvm_error::ErrorFormat;
 
 /// Transform the str with zero value into [`Option<String>`]
 #[inline]
@@ -18,7 +19,36 @@ pub(crate) fn transform_exec_para(
     let mut args = match exec_args {
         Some(exec_args) => {
             let args_json = serde_json::to_string(exec_args)?;
-            kclvm_runner::ExecProgramArgs::from_str(args_json.as_str())
+            let mut args = kclvm_runner::ExecProgramArgs::from_str(args_json.as_str());
+            // The zero value denotes unlimited in the protobuf message.
+            args.max_depth = (exec_args.max_depth > 0).then_some(exec_args.max_depth as usize);
+            args.max_output_bytes =
+                (exec_args.max_output_bytes > 0).then_some(exec_args.max_output_bytes as usize);
+            args.max_collection_len =
+                (exec_args.max_collection_len > 0).then_some(exec_args.max_collection_len as usize);
+            args.max_string_len =
+                (exec_args.max_string_len > 0).then_some(exec_args.max_string_len as usize);
+            // The frozen input and input values are JSON strings in the protobuf message.
+            args.frozen_inputs = exec_args
+                .frozen_inputs
+                .iter()
+                .map(|(k, v)| Ok((k.to_string(), serde_json::from_str(v)?)))
+                .collect::<anyhow::Result<_>>()?;
+            args.inputs = exec_args
+                .inputs
+                .iter()
+                .map(|(k, v)| Ok((k.to_string(), serde_json::from_str(v)?)))
+                .collect::<anyhow::Result<_>>()?;
+            // The empty field path denotes not splitting the output.
+            args.split_output_by =
+                (!exec_args.split_output_by.is_empty()).then(|| exec_args.split_output_by.clone());
+            // The empty package path denotes evaluating the main package.
+            args.eval_pkg = (!exec_args.eval_pkg.is_empty()).then(|| exec_args.eval_pkg.clone());
+            // The empty error format denotes the default human readable format.
+            if !exec_args.error_format.is_empty() {
+                args.error_format = ErrorFormat::from_name(&exec_args.error_format)?;
+            }
+            args
         }
         None => kclvm_runner::ExecProgramArgs::default(),
     };
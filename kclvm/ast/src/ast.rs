@@ -507,6 +507,22 @@ impl Program {
         }
         result
     }
+
+    /// Get the merged view of all the modules in the package, which concatenates the
+    /// module bodies in the file order following the package merge semantics. Every
+    /// statement keeps its source filename in [Node::filename] as the provenance.
+    pub fn merged_package_module(&self, pkgpath: &str) -> Module {
+        let mut merged = Module::default();
+        for module in self.get_modules_for_pkg(pkgpath) {
+            let module = module.read().expect("Failed to acquire module lock");
+            if merged.doc.is_none() {
+                merged.doc = module.doc.clone();
+            }
+            merged.body.extend(module.body.iter().cloned());
+            merged.comments.extend(module.comments.iter().cloned());
+        }
+        merged
+    }
 }
 
 /// Module is an abstract syntax tree for a single KCL file.
@@ -516,6 +532,11 @@ pub struct Module {
     pub doc: Option<NodeRef<String>>,
     pub body: Vec<NodeRef<Stmt>>,
     pub comments: Vec<NodeRef<Comment>>,
+    /// The interior comments of the list and config expressions attached to the nearest
+    /// node, which are only collected in the `AttachComments` parse mode. These comments
+    /// are also contained in [Module::comments].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expr_comments: Vec<ExprComment>,
 }
 
 impl Module {
@@ -654,6 +675,7 @@ pub struct IfStmt {
 /// ImportStmt, e.g.
 /// ```kcl
 /// import pkg as pkg_alias
+/// from pkg import Name, name
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ImportStmt {
@@ -670,6 +692,26 @@ pub struct ImportStmt {
     /// 2. If the current import statement indexs to the external kcl packages, `pkg_name` is the name of the package.
     /// if `import k8s.example.apps`, `k8s` is another kcl package, `pkg_name` is `k8s`.
     pub pkg_name: String,
+    /// `selected_names` is the names exposed by the selective import e.g., `Name` and `name`
+    /// in `from pkg import Name, name`, which is empty for the whole package import.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected_names: Vec<Node<String>>,
+}
+
+impl ImportStmt {
+    /// Whether the import statement only exposes the selected names of the package
+    /// e.g., `from pkg import Name`.
+    #[inline]
+    pub fn is_selective(&self) -> bool {
+        !self.selected_names.is_empty()
+    }
+
+    /// The internal name the selective import `from path import Name` binds the package
+    /// to, which can't be referred in the source code, so the package name is not exposed.
+    #[inline]
+    pub fn selective_import_name(path: &str) -> String {
+        format!("@{}", path)
+    }
 }
 
 /// SchemaStmt, e.g.
@@ -1438,11 +1480,17 @@ pub enum NumberLitValue {
 /// 1m
 /// 1K
 /// 1Mi
+/// 0xFF
+/// 1_000
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct NumberLit {
     pub binary_suffix: Option<NumberBinarySuffix>,
     pub value: NumberLitValue,
+    /// The original text of the literal without the binary suffix e.g., `0xFF` and `1_000`,
+    /// which is only set when it differs from the canonical form of the value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_value: Option<String>,
 }
 
 impl ToString for NumberLit {
@@ -1563,6 +1611,38 @@ pub struct Comment {
     pub text: String,
 }
 
+/// ExprComment denotes an interior comment of a list or config expression, which is
+/// attached to the nearest element node e.g., the list element or the config entry.
+///
+/// ```kcl
+/// a = [
+///     # Leading comment of the element `1`
+///     1,  # Trailing comment of the element `1`
+///     2
+///     # Dangling comment of the list
+/// ]
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExprComment {
+    /// The id of the attached node, which is the list or config expression itself
+    /// for the dangling comment.
+    #[serde(skip_deserializing, default)]
+    pub node_id: AstIndex,
+    pub kind: ExprCommentKind,
+    pub comment: NodeRef<Comment>,
+}
+
+/// ExprCommentKind denotes how the comment is placed relative to the attached node.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExprCommentKind {
+    /// The comment is on the lines before the node.
+    Leading,
+    /// The comment is on the same line after the node.
+    Trailing,
+    /// The comment is after all the elements of the list or config expression.
+    Dangling,
+}
+
 /*
  * Operators and context
  */
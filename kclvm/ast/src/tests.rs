@@ -182,6 +182,7 @@ fn test_filter_schema_with_no_schema() {
         doc: Some(node_ref!("".to_string())),
         body: vec![],
         comments: vec![],
+        expr_comments: vec![],
     };
     let schema_stmts = ast_mod.filter_schema_stmt_from_module();
     assert_eq!(schema_stmts.len(), 0);
@@ -194,6 +195,7 @@ fn test_filter_schema_with_one_schema() {
         doc: Some(node_ref!("".to_string())),
         body: vec![],
         comments: vec![],
+        expr_comments: vec![],
     };
     let mut gen_schema_stmts = gen_schema_stmt(1);
     ast_mod.body.append(&mut gen_schema_stmts);
@@ -209,6 +211,7 @@ fn test_filter_schema_with_mult_schema() {
         doc: Some(node_ref!("".to_string())),
         body: vec![],
         comments: vec![],
+        expr_comments: vec![],
     };
     let mut gen_schema_stmts = gen_schema_stmt(10);
     ast_mod.body.append(&mut gen_schema_stmts);
@@ -25,7 +25,7 @@ pub enum Indentation {
 }
 
 /// Printer config
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Config {
     pub tab_len: usize,
     pub indent_len: usize,
@@ -284,6 +284,14 @@ pub fn print_ast_module(module: &Module) -> String {
     printer.out
 }
 
+/// Print AST to string with the printer config e.g., the indentation width and
+/// whether to indent with tabs.
+pub fn print_ast_module_with_config(module: &Module, cfg: Config) -> String {
+    let mut printer = Printer::new(cfg, &NoHook);
+    printer.write_module(module);
+    printer.out
+}
+
 /// Print AST to string
 pub fn print_ast_node(node: ASTNode) -> String {
     let mut printer = Printer::default();
@@ -297,3 +305,41 @@ pub fn print_schema_expr(schema_expr: &ast::SchemaExpr) -> String {
     printer.walk_schema_expr(schema_expr);
     printer.out
 }
+
+/// Print the AST back to the formatted KCL source code, which is useful for the tools
+/// that construct or modify the AST programmatically and emit the KCL source.
+pub trait ToKclSource {
+    type Output;
+
+    fn to_kcl_source(&self) -> Self::Output;
+}
+
+impl ToKclSource for Module {
+    type Output = String;
+
+    /// Print the module to the KCL source code.
+    #[inline]
+    fn to_kcl_source(&self) -> Self::Output {
+        print_ast_module(self)
+    }
+}
+
+impl ToKclSource for ast::Program {
+    type Output = IndexMap<String, String>;
+
+    /// Print all the modules in the program to the KCL source code keyed by the module
+    /// filename, which is ordered by the package path and the module order in the package.
+    fn to_kcl_source(&self) -> Self::Output {
+        let mut pkgpaths: Vec<&String> = self.pkgs.keys().collect();
+        pkgpaths.sort();
+        let mut sources = IndexMap::new();
+        for pkgpath in pkgpaths {
+            for filename in &self.pkgs[pkgpath] {
+                if let Ok(Some(module)) = self.get_module(filename) {
+                    sources.insert(filename.clone(), print_ast_module(&module));
+                }
+            }
+        }
+        sources
+    }
+}
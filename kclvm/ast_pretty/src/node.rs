@@ -149,6 +149,19 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
     }
 
     fn walk_import_stmt(&mut self, import_stmt: &'ctx ast::ImportStmt) -> Self::Result {
+        if import_stmt.is_selective() {
+            self.write("from ");
+            self.write(&import_stmt.rawpath);
+            self.write(" import ");
+            let names: Vec<&str> = import_stmt
+                .selected_names
+                .iter()
+                .map(|name| name.node.as_str())
+                .collect();
+            self.write(&names.join(", "));
+            self.write_newline_without_fill();
+            return;
+        }
         self.write("import ");
         // use `import_stmt.rawpath` to write the raw path
         // otherwise, use `import_stmt.path` will replace `import .xxx` with `import xxx`
@@ -401,16 +414,21 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
         if matches!(unary_expr.op, ast::UnaryOp::Not) {
             self.write_space();
         }
-        self.expr(&unary_expr.operand);
+        let operand = &unary_expr.operand;
+        self.expr_with_paren(operand, expr_precedence(operand) <= PREFIX_PRECEDENCE);
     }
 
     fn walk_binary_expr(&mut self, binary_expr: &'ctx ast::BinaryExpr) -> Self::Result {
         let symbol = binary_expr.op.symbol();
-        self.expr(&binary_expr.left);
+        let precedence = bin_op_precedence(&binary_expr.op);
+        let left = &binary_expr.left;
+        let right = &binary_expr.right;
+        // All the binary operators are left associative.
+        self.expr_with_paren(left, expr_precedence(left) < precedence);
         self.write_space();
         self.write(symbol);
         self.write_space();
-        self.expr(&binary_expr.right);
+        self.expr_with_paren(right, expr_precedence(right) <= precedence);
     }
 
     fn walk_selector_expr(&mut self, selector_expr: &'ctx ast::SelectorExpr) -> Self::Result {
@@ -768,12 +786,20 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
     }
 
     fn walk_compare(&mut self, compare: &'ctx ast::Compare) -> Self::Result {
-        self.expr(&compare.left);
+        let left = &compare.left;
+        // The chained comparison is flattened into one compare expression, thus
+        // the left compare expression always comes from the parentheses.
+        let left_paren = matches!(left.node, ast::Expr::Compare(_))
+            || compare
+                .ops
+                .first()
+                .map_or(false, |op| expr_precedence(left) < cmp_op_precedence(op));
+        self.expr_with_paren(left, left_paren);
         for (op, expr) in compare.ops.iter().zip(compare.comparators.iter()) {
             self.write_space();
             self.write(op.symbol());
             self.write_space();
-            self.expr(expr);
+            self.expr_with_paren(expr, expr_precedence(expr) <= cmp_op_precedence(op));
         }
     }
 
@@ -801,9 +827,11 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
     }
 
     fn walk_number_lit(&mut self, number_lit: &'ctx ast::NumberLit) -> Self::Result {
-        match &number_lit.value {
-            ast::NumberLitValue::Int(int_val) => self.write(&int_val.to_string()),
-            ast::NumberLitValue::Float(float_val) => self.write(&float_val.to_string()),
+        match (&number_lit.raw_value, &number_lit.value) {
+            // Keep the original form e.g., `0xFF` and `1_000`
+            (Some(raw_value), _) => self.write(raw_value),
+            (None, ast::NumberLitValue::Int(int_val)) => self.write(&int_val.to_string()),
+            (None, ast::NumberLitValue::Float(float_val)) => self.write(&float_val.to_string()),
         }
         // Number suffix e.g., 1Gi
         if let Some(binary_suffix) = &number_lit.binary_suffix {
@@ -968,6 +996,18 @@ impl<'p> Printer<'p> {
         self.hook.post(self, super::ASTNode::Expr(expr));
     }
 
+    /// Print the expression wrapped with parentheses when `paren` is true, which is
+    /// used to keep the operator precedence of the AST constructed without parentheses.
+    pub fn expr_with_paren(&mut self, expr: &ast::NodeRef<ast::Expr>, paren: bool) {
+        if paren {
+            self.write_token(TokenKind::OpenDelim(DelimToken::Paren));
+            self.expr(expr);
+            self.write_token(TokenKind::CloseDelim(DelimToken::Paren));
+        } else {
+            self.expr(expr);
+        }
+    }
+
     pub fn stmt(&mut self, stmt: &ast::NodeRef<ast::Stmt>) {
         self.hook.pre(self, super::ASTNode::Stmt(stmt));
         self.fill("");
@@ -1012,3 +1052,49 @@ impl<'p> Printer<'p> {
         }
     }
 }
+
+/// The precedence of the unary operators, which is higher than all the binary operators.
+const PREFIX_PRECEDENCE: u8 = 15;
+
+/// Returns the precedence of the binary operator, which is consistent with the parser.
+fn bin_op_precedence(op: &ast::BinOp) -> u8 {
+    match op {
+        ast::BinOp::As => 1,
+        ast::BinOp::Or => 2,
+        ast::BinOp::And => 3,
+        ast::BinOp::BitOr => 8,
+        ast::BinOp::BitXor => 9,
+        ast::BinOp::BitAnd => 10,
+        ast::BinOp::LShift | ast::BinOp::RShift => 11,
+        ast::BinOp::Add | ast::BinOp::Sub => 12,
+        ast::BinOp::Mul | ast::BinOp::Div | ast::BinOp::Mod | ast::BinOp::FloorDiv => 13,
+        ast::BinOp::Pow => 14,
+    }
+}
+
+/// Returns the precedence of the comparison operator, which is consistent with the parser.
+fn cmp_op_precedence(op: &ast::CmpOp) -> u8 {
+    match op {
+        ast::CmpOp::Eq | ast::CmpOp::NotEq => 4,
+        ast::CmpOp::In | ast::CmpOp::NotIn | ast::CmpOp::Not => 5,
+        ast::CmpOp::Is | ast::CmpOp::IsNot => 6,
+        ast::CmpOp::Lt | ast::CmpOp::LtE | ast::CmpOp::Gt | ast::CmpOp::GtE => 7,
+    }
+}
+
+/// Returns the precedence of the expression as an operand, the primary expressions
+/// have the highest precedence and never need the parentheses.
+fn expr_precedence(expr: &ast::NodeRef<ast::Expr>) -> u8 {
+    match &expr.node {
+        ast::Expr::If(_) => 0,
+        ast::Expr::Binary(binary_expr) => bin_op_precedence(&binary_expr.op),
+        ast::Expr::Compare(compare) => compare
+            .ops
+            .iter()
+            .map(cmp_op_precedence)
+            .min()
+            .unwrap_or(u8::MAX),
+        ast::Expr::Unary(_) => PREFIX_PRECEDENCE,
+        _ => u8::MAX,
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use super::print_ast_module;
+use super::{print_ast_module, ToKclSource};
+use kclvm_ast::ast::*;
+use kclvm_ast::node_ref;
 use kclvm_parser::parse_file_force_errors;
 use pretty_assertions::assert_eq;
 
@@ -63,3 +65,88 @@ fn test_ast_printer() {
         assert_eq!(data_input, data_output, "Test failed on {}", case);
     }
 }
+
+#[test]
+fn test_to_kcl_source() {
+    // Construct the AST programmatically: `replicas = 1 + 2 * 3`
+    let number = |v| {
+        node_ref!(Expr::NumberLit(NumberLit {
+            binary_suffix: None,
+            value: NumberLitValue::Int(v),
+            raw_value: None,
+        }))
+    };
+    let module = Module {
+        body: vec![node_ref!(Stmt::Assign(AssignStmt {
+            targets: vec![node_ref!(Target {
+                name: Node::dummy_node("replicas".to_string()),
+                paths: vec![],
+                pkgpath: "".to_string(),
+            })],
+            value: node_ref!(Expr::Binary(BinaryExpr {
+                left: number(1),
+                op: BinOp::Add,
+                right: node_ref!(Expr::Binary(BinaryExpr {
+                    left: number(2),
+                    op: BinOp::Mul,
+                    right: number(3),
+                })),
+            })),
+            ty: None,
+        }))],
+        ..Default::default()
+    };
+    let source = module.to_kcl_source();
+    assert_eq!(source, "replicas = 1 + 2 * 3\n");
+    // The emitted source re-parses to the equivalent AST.
+    let reparsed = parse_file_force_errors("", Some(source.clone())).unwrap();
+    assert_eq!(reparsed.to_kcl_source(), source);
+}
+
+#[test]
+fn test_to_kcl_source_with_precedence() {
+    let name = |name: &str| {
+        node_ref!(Expr::Identifier(Identifier {
+            names: vec![Node::dummy_node(name.to_string())],
+            pkgpath: "".to_string(),
+            ctx: ExprContext::Load,
+        }))
+    };
+    let binary = |left, op, right| node_ref!(Expr::Binary(BinaryExpr { left, op, right }));
+    let cases = [
+        (
+            binary(
+                binary(name("a"), BinOp::Add, name("b")),
+                BinOp::Mul,
+                name("c"),
+            ),
+            "(a + b) * c",
+        ),
+        (
+            binary(
+                name("a"),
+                BinOp::Sub,
+                binary(name("b"), BinOp::Sub, name("c")),
+            ),
+            "a - (b - c)",
+        ),
+        (
+            binary(
+                binary(name("a"), BinOp::Sub, name("b")),
+                BinOp::Sub,
+                name("c"),
+            ),
+            "a - b - c",
+        ),
+        (
+            node_ref!(Expr::Unary(UnaryExpr {
+                op: UnaryOp::USub,
+                operand: binary(name("a"), BinOp::Add, name("b")),
+            })),
+            "-(a + b)",
+        ),
+    ];
+    for (expr, expected) in cases {
+        assert_eq!(super::print_ast_node(super::ASTNode::Expr(&expr)), expected);
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_parser::{lex_tokens, LexedToken};
+use std::io::{Read, Write};
+
+use crate::util::bool_from_matches;
+
+/// The filename of the source read from the stdin.
+const STDIN_FILENAME: &str = "<stdin>";
+
+/// Run the KCL debug command, which contains the developer aids for investigating
+/// the compiler issues.
+pub fn debug_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    match matches.subcommand() {
+        Some(("tokens", sub_matches)) => tokens_command(sub_matches, writer),
+        _ => Ok(()),
+    }
+}
+
+/// Dump the token stream of a file or the stdin including the `newline`, `indent`
+/// and `dedent` markers.
+fn tokens_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let (filename, code) = match matches.get_one::<String>("input") {
+        Some(input) if input != "-" => (input.to_string(), None),
+        _ => {
+            let mut code = String::new();
+            std::io::stdin().read_to_string(&mut code)?;
+            (STDIN_FILENAME.to_string(), Some(code))
+        }
+    };
+    let tokens = lex_tokens(&filename, code)?;
+    if bool_from_matches(matches, "json").unwrap_or_default() {
+        let tokens: Vec<serde_json::Value> = tokens.iter().map(token_to_json).collect();
+        writeln!(writer, "{}", serde_json::to_string_pretty(&tokens)?)?;
+    } else {
+        for token in &tokens {
+            let (start, end) = &token.range;
+            writeln!(
+                writer,
+                "{}:{}-{}:{}\t{}\t{:?}",
+                start.line,
+                start.column.unwrap_or_default(),
+                end.line,
+                end.column.unwrap_or_default(),
+                token.kind,
+                token.text
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn token_to_json(token: &LexedToken) -> serde_json::Value {
+    let (start, end) = &token.range;
+    serde_json::json!({
+        "kind": token.kind,
+        "text": token.text,
+        "start": {"line": start.line, "column": start.column.unwrap_or_default()},
+        "end": {"line": end.line, "column": end.column.unwrap_or_default()},
+    })
+}
@@ -0,0 +1,161 @@
+//! Explain the KCL run output with the provenance of each output field, which maps
+//! every top-level field and nested config key to the `file:line` that set it.
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use kclvm_ast::ast::PosTuple;
+use std::path::Path;
+
+/// The output format of the `--explain-output` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExplainFormat {
+    /// Annotate each YAML key line with a `# file:line` comment.
+    Yaml,
+    /// Emit a JSON sidecar report that maps each output path to its source position.
+    Json,
+}
+
+impl ExplainFormat {
+    /// All the format names accepted by the `--explain-output` option.
+    pub(crate) const NAMES: [&'static str; 2] = ["yaml", "json"];
+
+    pub(crate) fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "yaml" => Ok(ExplainFormat::Yaml),
+            "json" => Ok(ExplainFormat::Json),
+            _ => Err(anyhow::anyhow!(
+                "Invalid explain output format '{name}', expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// Get the `file:line` string of the source position, and the filename is relative
+/// to the current working directory when possible.
+fn provenance(pos: &PosTuple) -> String {
+    let filename = Path::new(&pos.0);
+    let filename = match std::env::current_dir() {
+        Ok(cwd) => filename.strip_prefix(cwd).unwrap_or(filename),
+        Err(_) => filename,
+    };
+    format!("{}:{}", filename.display(), pos.1)
+}
+
+/// Annotate each key line of the YAML output with a `# file:line` comment when its
+/// output path e.g., `deployment.spec.replicas` or `deployment.containers.0.name`
+/// is found in the source map.
+pub(crate) fn annotate_yaml(yaml: &str, source_map: &IndexMap<String, PosTuple>) -> String {
+    // The indentation and the key of the enclosing mappings and list items, and whether
+    // it is a list item whose key is the index.
+    let mut stack: Vec<(usize, String, bool)> = vec![];
+    // The indentation of the key whose value is a block scalar e.g., `|-`.
+    let mut block_indent: Option<usize> = None;
+    let mut lines = vec![];
+    for line in yaml.lines() {
+        let trimmed = line.trim_start_matches(' ');
+        let mut indent = line.len() - trimmed.len();
+        if let Some(block) = block_indent {
+            if indent > block || trimmed.is_empty() {
+                lines.push(line.to_string());
+                continue;
+            }
+            block_indent = None;
+        }
+        if trimmed == "---" {
+            stack.clear();
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+            lines.push(line.to_string());
+            continue;
+        }
+        let mut rest = trimmed;
+        if rest.starts_with("- ") || rest == "-" {
+            // The list item sits on the same indentation as its parent key, and follows
+            // the previous item of the same list.
+            let mut index = 0;
+            while matches!(stack.last(), Some((i, _, is_item)) if *i > indent || (*i == indent && *is_item))
+            {
+                if let Some((i, key, true)) = stack.pop() {
+                    if i == indent {
+                        index = key.parse::<usize>().map_or(0, |index| index + 1);
+                    }
+                }
+            }
+            while rest.starts_with("- ") || rest == "-" {
+                stack.push((indent, index.to_string(), true));
+                index = 0;
+                indent += 2;
+                rest = rest.get(2..).unwrap_or_default();
+            }
+        } else {
+            while matches!(stack.last(), Some((i, _, _)) if *i >= indent) {
+                stack.pop();
+            }
+        }
+        let (key, value) = match split_key(rest) {
+            Some(kv) => kv,
+            None => {
+                lines.push(line.to_string());
+                continue;
+            }
+        };
+        if value.starts_with('|') || value.starts_with('>') {
+            block_indent = Some(indent);
+        }
+        let mut keys: Vec<&str> = stack.iter().map(|(_, k, _)| k.as_str()).collect();
+        keys.push(&key);
+        match source_map.get(&keys.join(".")) {
+            Some(pos) => lines.push(format!("{line}  # {}", provenance(pos))),
+            None => lines.push(line.to_string()),
+        }
+        stack.push((indent, key, false));
+    }
+    let mut out = lines.join("\n");
+    if yaml.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Split a YAML mapping line into the unquoted key and the value.
+fn split_key(line: &str) -> Option<(String, &str)> {
+    let quote = line.chars().next().filter(|c| *c == '\'' || *c == '"');
+    match quote {
+        Some(quote) => {
+            let end = line[1..].find(quote)? + 1;
+            let value = line[end + 1..].strip_prefix(':')?;
+            if value.is_empty() || value.starts_with(' ') {
+                Some((line[1..end].to_string(), value.trim_start()))
+            } else {
+                None
+            }
+        }
+        None => {
+            if let Some((key, value)) = line.split_once(": ") {
+                Some((key.to_string(), value))
+            } else {
+                line.strip_suffix(':').map(|key| (key.to_string(), ""))
+            }
+        }
+    }
+}
+
+/// Get the JSON sidecar report which maps each output path to its source position.
+pub(crate) fn explain_json(source_map: &IndexMap<String, PosTuple>) -> Result<String> {
+    let report: serde_json::Map<String, serde_json::Value> = source_map
+        .iter()
+        .map(|(path, pos)| {
+            (
+                path.clone(),
+                serde_json::json!({
+                    "filename": pos.0,
+                    "line": pos.1,
+                    "column": pos.2,
+                    "provenance": provenance(pos),
+                }),
+            )
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&report)?)
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_config::settings::{build_settings_pathbuf, Config, SettingsFile};
+use kclvm_runner::ExecProgramArgs;
+use kclvm_tools::fix::fix_program;
+use similar::TextDiff;
+use std::{fs, io::Write};
+
+use crate::util::*;
+
+/// Run the KCL fix command, which applies the machine-applicable suggested
+/// replacements of the compiler diagnostics to the source files.
+pub fn fix_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let files: Vec<&str> = match matches.get_many::<String>("input") {
+        Some(files) => files.into_iter().map(|f| f.as_str()).collect::<Vec<&str>>(),
+        None => vec![],
+    };
+    let setting_files = matches
+        .get_many::<String>("setting")
+        .map(|files| files.into_iter().map(|f| f.as_str()).collect::<Vec<&str>>());
+    let package_maps = hashmaps_from_matches(matches, "package_map").transpose()?;
+    let settings = build_settings_pathbuf(
+        files.as_slice(),
+        setting_files,
+        Some(SettingsFile {
+            kcl_cli_configs: Some(Config {
+                package_maps,
+                ..Default::default()
+            }),
+            kcl_options: None,
+        }),
+    )?;
+    let args: ExecProgramArgs = settings.try_into()?;
+    let result = fix_program(&args.get_files(), Some(args.get_load_program_options()))?;
+    let dry_run = matches.get_flag("dry_run");
+    for (file, (original, fixed)) in &result.changes {
+        if dry_run {
+            let diff = TextDiff::from_lines(original, fixed);
+            write!(
+                writer,
+                "{}",
+                diff.unified_diff()
+                    .header(&format!("a/{}", file), &format!("b/{}", file))
+            )?;
+        } else {
+            fs::write(file, fixed)?;
+        }
+    }
+    for suggestion in &result.applied {
+        writeln!(
+            writer,
+            "{}: {}",
+            if dry_run { "would fix" } else { "fixed" },
+            suggestion.message
+        )?;
+    }
+    for note in &result.skipped {
+        writeln!(writer, "note: {}", note)?;
+    }
+    writeln!(
+        writer,
+        "{} {} fix(es) in {} file(s)",
+        if dry_run { "Would apply" } else { "Applied" },
+        result.applied.len(),
+        result.changes.len()
+    )?;
+    Ok(())
+}
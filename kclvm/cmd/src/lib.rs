@@ -3,8 +3,13 @@
 #[macro_use]
 extern crate clap;
 
+pub mod debug;
+pub(crate) mod explain;
+pub mod fix;
+pub mod lint;
 pub mod run;
 pub mod settings;
+pub mod test;
 pub(crate) mod util;
 
 #[cfg(test)]
@@ -15,7 +20,13 @@ use clap::{ArgAction, Command};
 use std::io;
 
 use anyhow::Result;
+use debug::debug_command;
+use explain::ExplainFormat;
+use fix::fix_command;
+use kclvm_error::ErrorFormat;
+use lint::lint_command;
 use run::run_command;
+use test::test_command;
 
 /// Run the KCL main command.
 pub fn main(args: &[&str]) -> Result<()> {
@@ -23,6 +34,10 @@ pub fn main(args: &[&str]) -> Result<()> {
     // Sub commands
     match matches.subcommand() {
         Some(("run", sub_matches)) => run_command(sub_matches, &mut io::stdout()),
+        Some(("lint", sub_matches)) => lint_command(sub_matches, &mut io::stdout()),
+        Some(("fix", sub_matches)) => fix_command(sub_matches, &mut io::stdout()),
+        Some(("test", sub_matches)) => test_command(sub_matches, &mut io::stdout()),
+        Some(("debug", sub_matches)) => debug_command(sub_matches, &mut io::stdout()),
         Some(("version", _)) => {
             println!("{}", kclvm_version::get_version_info());
             Ok(())
@@ -42,7 +57,7 @@ pub fn app() -> Command {
         .subcommand(
             Command::new("run")
             .about("run")
-            .arg(arg!([input] ... "Specify the input files to run").num_args(0..))
+            .arg(arg!([input] ... "Specify the input files to run, which may be the tar, tar.gz or zip archives e.g., bundle.tar.gz").num_args(0..))
             .arg(arg!(output: -o --output <output> "Specify the YAML output file path"))
             .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
             .arg(arg!(verbose: -v --verbose "Print test information verbosely").action(ArgAction::Count))
@@ -52,13 +67,67 @@ pub fn app() -> Command {
             .arg(arg!(sort_keys: -k --sort_keys "Sort result keys"))
             .arg(arg!(show_hidden: -H --show_hidden "Display hidden attributes"))
             .arg(arg!(fast_eval: -K --fast_eval "Use the fast evaluation mode"))
+            .arg(arg!(strict_number: --strict_number "Emit the integral unit values e.g., 1Ki as integers in the output"))
             .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
+            .arg(arg!(inputs: --input <inputs> ... "Bind the top-level name to the JSON value or the JSON file e.g., data=@values.json, which is not emitted in the output").num_args(1..))
             .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
             .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value").num_args(1..))
+            .arg(arg!(overrides_file: --"overrides-file" <overrides_file> "Specify a file of configuration override specs, one per line"))
+            .arg(arg!(env_prefix: --"env-prefix" <env_prefix> "Read the top-level arguments <prefix>OPT_<key> and the override specs <prefix>OVERRIDE_<path> from the environment variables e.g., KCL_"))
             .arg(arg!(target: --target <target> "Specify the target type"))
             .arg(arg!(recursive: -R --recursive "Compile the files directory recursively"))
+            .arg(arg!(explain_output: --"explain-output" <explain_output> "Annotate the output fields with the file:line that set them, as YAML comments or a JSON sidecar report").num_args(0..=1).default_missing_value("yaml").value_parser(ExplainFormat::NAMES))
+            .arg(arg!(watch: -w --watch "Watch the input files and their dependencies and re-run on changes"))
+            .arg(arg!(profile: --profile "Print the time spent in the load, resolve, codegen and evaluation phases to stderr"))
+            .arg(arg!(split_output_by: --"split-output-by" <split_output_by> "Write each output document to <output>/<value>.yaml named by the field path value e.g., metadata.name, the output is a directory defaulting to the current directory"))
+            .arg(arg!(eval_pkg: --"eval-pkg" <eval_pkg> "Evaluate and output the package e.g., pkg.a in the program instead of the main package"))
+            .arg(arg!(annotate_provenance: --"annotate-provenance" "Annotate the output Kubernetes objects with the config hash and the KCL source files in metadata.annotations"))
+            .arg(arg!(allow_file_read: --"allow-file-read" "Allow the program to read the files under the workspace e.g., with file.read and file.read_json"))
+            .arg(arg!(sandbox: --sandbox "Evaluate without any side effects, which disables the plugins, the file system and environment access and the nondeterministic functions e.g., datetime.now"))
+            .arg(arg!(if_present: --"if-present" "Skip the missing input files, and exit successfully without any output when all the input files are missing"))
+            .arg(arg!(treat_dir_as_package: --"dir-as-package" "Compile the KCL files except the tests at the top level of the input directory as the main package without a kcl.mod"))
+            .arg(arg!(error_format: --"error-format" <error_format> "Specify the format of the compile and runtime errors, 'short' prints one 'file:line:col: severity: message [code]' line per diagnostic").value_parser(ErrorFormat::NAMES).default_value("human"))
             .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
         )
+    .subcommand(
+        Command::new("lint")
+        .about("Check the KCL files with the lints and report the warnings and errors without running them")
+        .arg(arg!([input] ... "Specify the input files to lint").num_args(0..))
+        .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
+        .arg(arg!(disable: --disable <disable> ... "Disable the lints by their names or codes e.g., W0411").num_args(1..))
+        .arg(arg!(deny: --deny <deny> ... "Report the lints by their names or codes as errors e.g., W0416").num_args(1..))
+        .arg(arg!(list: --list "List all the available lints"))
+        .arg(arg!(error_format: --"error-format" <error_format> "Specify the format of the diagnostics, 'short' prints one 'file:line:col: severity: message [code]' line per diagnostic").value_parser(ErrorFormat::NAMES).default_value("human"))
+        .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
+    )
+    .subcommand(
+        Command::new("fix")
+        .about("Apply the machine-applicable suggested fixes of the compiler diagnostics")
+        .arg(arg!([input] ... "Specify the input files to fix").num_args(0..))
+        .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
+        .arg(arg!(dry_run: --"dry-run" "Print the diff of the fixes without writing the files"))
+        .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
+    )
+    .subcommand(
+        Command::new("test")
+        .about("Run the test cases in the _test.k files of the packages")
+        .arg(arg!([input] ... "Specify the packages to test e.g., ./pkg/..., default is the current directory").num_args(0..))
+        .arg(arg!(run: --run <run> "Only run the test cases matching the regular expression"))
+        .arg(arg!(fail_fast: --"fail-fast" "Stop running the test cases after the first failure"))
+        .arg(arg!(tag: --tag <tag> ... "Only run the test cases tagged by the '# @tags: ...' comment with any of the tags"))
+        .arg(arg!(skip_tag: --"skip-tag" <skip_tag> ... "Skip the test cases tagged by the '# @tags: ...' comment with any of the tags"))
+        .arg(arg!(output: -o --output <output> "Specify the output format of the test results").value_parser(["text", "json"]).default_value("text")),
+    )
+    .subcommand(
+        Command::new("debug")
+        .about("Developer aids for investigating the compiler issues")
+        .subcommand(
+            Command::new("tokens")
+            .about("Dump the token stream of a file including the newline, indent and dedent markers")
+            .arg(arg!([input] "Specify the input file, read from the stdin when it is omitted or '-'"))
+            .arg(arg!(json: --json "Output the tokens in the JSON format")),
+        ),
+    )
     .subcommand(Command::new("server").about("Start a rpc server for APIs"))
     .subcommand(Command::new("version").about("Show the KCL version"))
 }
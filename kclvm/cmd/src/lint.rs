@@ -0,0 +1,83 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_config::settings::{build_settings_pathbuf, Config, SettingsFile};
+use kclvm_error::{ErrorFormat, Handler};
+use kclvm_runner::ExecProgramArgs;
+use kclvm_tools::lint::{get_lints, lint_files_with_opts, LintOptions};
+use std::io::Write;
+
+use crate::util::*;
+
+/// Run the KCL lint command.
+pub fn lint_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    if matches.get_flag("list") {
+        for lint in get_lints() {
+            writeln!(
+                writer,
+                "{} {}: {}",
+                lint.code,
+                lint.name.trim_matches('"'),
+                lint.desc
+            )?;
+        }
+        return Ok(());
+    }
+    let files: Vec<&str> = match matches.get_many::<String>("input") {
+        Some(files) => files.into_iter().map(|f| f.as_str()).collect::<Vec<&str>>(),
+        None => vec![],
+    };
+    let setting_files = matches
+        .get_many::<String>("setting")
+        .map(|files| files.into_iter().map(|f| f.as_str()).collect::<Vec<&str>>());
+    let package_maps = hashmaps_from_matches(matches, "package_map").transpose()?;
+    let settings = build_settings_pathbuf(
+        files.as_slice(),
+        setting_files,
+        Some(SettingsFile {
+            kcl_cli_configs: Some(Config {
+                package_maps,
+                ..Default::default()
+            }),
+            kcl_options: None,
+        }),
+    )?;
+    let args: ExecProgramArgs = settings.try_into()?;
+    let opts = LintOptions {
+        disabled_lints: strings_from_matches(matches, "disable").unwrap_or_default(),
+        denied_lints: strings_from_matches(matches, "deny").unwrap_or_default(),
+    };
+    let lints = get_lints();
+    if let Some(name) = opts
+        .disabled_lints
+        .iter()
+        .chain(opts.denied_lints.iter())
+        .find(|name| !lints.iter().any(|lint| lint.matches(name)))
+    {
+        return Err(anyhow::anyhow!(
+            "unknown lint '{}', run 'kcl lint --list' to list all the available lints",
+            name
+        ));
+    }
+    let (errors, warnings) = lint_files_with_opts(
+        &args.get_files(),
+        Some(args.get_load_program_options()),
+        opts,
+    );
+    let error_format = matches
+        .get_one::<String>("error_format")
+        .map(|name| ErrorFormat::from_name(name))
+        .transpose()?
+        .unwrap_or_default();
+    let mut handler = Handler::default().with_error_format(error_format);
+    handler.diagnostics = errors.iter().chain(warnings.iter()).cloned().collect();
+    let output = handler.emit_to_string()?;
+    match error_format {
+        ErrorFormat::Short if !output.is_empty() => writeln!(writer, "{}", output)?,
+        _ => write!(writer, "{}", output)?,
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("{} lint error(s) found", errors.len()))
+    }
+}
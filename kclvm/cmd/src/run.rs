@@ -1,43 +1,72 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::ArgMatches;
-use kclvm_error::StringError;
-use kclvm_parser::ParseSession;
-use kclvm_runner::exec_program;
+use kclvm_error::{ErrorFormat, StringError};
+use kclvm_parser::{
+    load_program, split_missing_entries, KCLModuleCache, ModuleCache, ParseSession, ParseSessionRef,
+};
+use kclvm_runner::{
+    exec_program, exec_program_with_module_cache, write_split_outputs, ExecProgramArgs,
+    ExecProgramResult, MapErrorResult, PhaseTimings,
+};
+use notify::{RecursiveMode, Watcher};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::io::Write;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
+use crate::explain::{annotate_yaml, explain_json, ExplainFormat};
 use crate::settings::must_build_settings;
+use crate::util::{bool_from_matches, strings_from_matches};
+
+/// Rapid changes within the debounce duration trigger only one re-run.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+/// The ANSI escape sequence to clear the screen and move the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
 
 /// Run the KCL run command.
 pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
-    // Config settings building
-    let settings = must_build_settings(matches);
-    let output = settings.output();
+    if bool_from_matches(matches, "watch").unwrap_or_default() {
+        return watch_command(matches, writer);
+    }
+    let opts = match run_options_from_matches(matches)? {
+        Some(opts) => opts,
+        // Nothing to run when all the input files are missing.
+        None => return Ok(()),
+    };
+    let error_format = opts.args.error_format;
     let sess = Arc::new(ParseSession::default());
-    match exec_program(sess.clone(), &settings.try_into()?) {
+    sess.1.write().error_format = error_format;
+    match exec_program(sess.clone(), &opts.args) {
         Ok(result) => {
+            // The timings are printed to stderr, so they don't mix with the output.
+            if let Some(timings) = &result.timings {
+                eprintln!("{}", format_timings(timings));
+            }
             // Output log message
             if !result.log_message.is_empty() {
                 write!(writer, "{}", result.log_message)?;
             }
             // Output execute error message
             if !result.err_message.is_empty() {
+                // The short diagnostics are printed as they are without the error header.
+                if error_format == ErrorFormat::Short {
+                    return Err(anyhow!(result.err_message));
+                }
                 if !sess.0.diag_handler.has_errors()? {
                     sess.0.add_err(StringError(result.err_message))?;
                 }
                 sess.0.emit_stashed_diagnostics_and_abort()?;
             }
-            if !result.yaml_result.is_empty() {
-                match output {
-                    Some(o) => std::fs::write(o, result.yaml_result)?,
-                    // [`println!`] is not a good way to output content to stdout,
-                    // using [`writeln`] can be better to redirect the output.
-                    None => writeln!(writer, "{}", result.yaml_result)?,
-                }
-            }
+            write_outputs(result, &opts, writer)?;
         }
         // Other error message
         Err(msg) => {
+            if error_format == ErrorFormat::Short {
+                return Err(msg);
+            }
             if !sess.0.diag_handler.has_errors()? {
                 sess.0.add_err(StringError(msg.to_string()))?;
             }
@@ -46,3 +75,321 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     }
     Ok(())
 }
+
+/// The options of the run command built from the settings files and the flags, which
+/// are shared by the one-shot run and the watch mode.
+pub(crate) struct RunOptions {
+    pub(crate) args: ExecProgramArgs,
+    /// The output file, or the output directory when the output is split.
+    pub(crate) output: Option<String>,
+    pub(crate) explain: Option<ExplainFormat>,
+}
+
+/// Build the run options from the settings files and the flags. `None` is returned when
+/// all the input files are missing with `--if-present`, i.e., there is nothing to run.
+pub(crate) fn run_options_from_matches(matches: &ArgMatches) -> Result<Option<RunOptions>> {
+    // Config settings building
+    let settings = must_build_settings(matches);
+    let output = settings.output();
+    let explain = matches
+        .get_one::<String>("explain_output")
+        .map(|name| ExplainFormat::from_name(name))
+        .transpose()?;
+    let error_format = matches
+        .get_one::<String>("error_format")
+        .map(|name| ErrorFormat::from_name(name))
+        .transpose()?
+        .unwrap_or_default();
+    let mut args: ExecProgramArgs = settings.try_into()?;
+    args.inputs = inputs_from_matches(matches)?;
+    args.error_format = error_format;
+    args.emit_source_map = explain.is_some();
+    args.profile = bool_from_matches(matches, "profile").unwrap_or_default();
+    args.allow_file_read = bool_from_matches(matches, "allow_file_read").unwrap_or_default();
+    args.sandbox = bool_from_matches(matches, "sandbox").unwrap_or_default();
+    args.treat_dir_as_package =
+        bool_from_matches(matches, "treat_dir_as_package").unwrap_or_default();
+    args.eval_pkg = matches
+        .get_one::<String>("eval_pkg")
+        .map(|pkgpath| pkgpath.to_string());
+    args.annotate_provenance =
+        bool_from_matches(matches, "annotate_provenance").unwrap_or_default();
+    args.split_output_by = matches
+        .get_one::<String>("split_output_by")
+        .map(|field_path| field_path.to_string());
+    if bool_from_matches(matches, "if_present").unwrap_or_default() {
+        let (paths, missing_paths) = split_missing_entries(
+            args.k_filename_list.clone(),
+            &args.get_load_program_options(),
+        );
+        for path in &missing_paths {
+            eprintln!("info: skip the missing input file {path}");
+        }
+        if paths.is_empty() && !missing_paths.is_empty() {
+            return Ok(None);
+        }
+        args.k_filename_list = paths;
+    }
+    Ok(Some(RunOptions {
+        args,
+        output,
+        explain,
+    }))
+}
+
+/// Write the outputs of a successful run, i.e., the split output files, the YAML output
+/// and the explain report, to the output path or the writer.
+fn write_outputs<W: Write>(
+    result: ExecProgramResult,
+    opts: &RunOptions,
+    writer: &mut W,
+) -> Result<()> {
+    if opts.args.split_output_by.is_some() {
+        // The output is a directory of the split documents.
+        write_split_outputs(&result, opts.output.as_deref().unwrap_or("."))?;
+    } else if !result.yaml_result.is_empty() {
+        let yaml_result = match opts.explain {
+            Some(ExplainFormat::Yaml) => annotate_yaml(&result.yaml_result, &result.source_map),
+            _ => result.yaml_result,
+        };
+        match &opts.output {
+            Some(o) => std::fs::write(o, yaml_result)?,
+            // [`println!`] is not a good way to output content to stdout,
+            // using [`writeln`] can be better to redirect the output.
+            None => writeln!(writer, "{}", yaml_result)?,
+        }
+    }
+    // The JSON sidecar report is written next to the output file or after the output.
+    if let Some(ExplainFormat::Json) = opts.explain {
+        let report = explain_json(&result.source_map)?;
+        match &opts.output {
+            Some(o) => std::fs::write(format!("{o}.explain.json"), report)?,
+            None => writeln!(writer, "{}", report)?,
+        }
+    }
+    Ok(())
+}
+
+/// Parse the `--input name=value` flags, where the value is a JSON value e.g., `'{"a": 1}'`
+/// or a JSON file prefixed with `@` e.g., `@values.json`.
+pub(crate) fn inputs_from_matches(
+    matches: &ArgMatches,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let mut inputs = HashMap::new();
+    for input in strings_from_matches(matches, "inputs").unwrap_or_default() {
+        let (name, value) = input
+            .split_once('=')
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| anyhow!("invalid input '{input}', expected 'name=value'"))?;
+        let value = match value.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("failed to read the input file '{path}': {e}"))?,
+            None => value.to_string(),
+        };
+        let value = serde_json::from_str(&value)
+            .map_err(|e| anyhow!("invalid JSON value for the input '{name}': {e}"))?;
+        inputs.insert(name.trim().to_string(), value);
+    }
+    Ok(inputs)
+}
+
+/// Format the phase timings in one line e.g.,
+/// `load: 1.200ms, resolve: 3.400ms, eval: 5.600ms`, the codegen phase is
+/// only shown when the program is compiled to a native lib.
+pub(crate) fn format_timings(timings: &PhaseTimings) -> String {
+    let mut phases = vec![("load", timings.load), ("resolve", timings.resolve)];
+    if let Some(codegen) = timings.codegen {
+        phases.push(("codegen", codegen));
+    }
+    phases.push(("eval", timings.eval));
+    phases
+        .iter()
+        .map(|(name, duration)| format!("{name}: {:.3}ms", duration.as_secs_f64() * 1000.0))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Run the KCL program and re-run it whenever the input files or their transitive
+/// dependencies change. The module cache is shared between runs, so only the changed
+/// files are re-parsed. The screen is cleared before every run, and diagnostics are
+/// emitted on every run without aborting. The files written by the run e.g., the output
+/// file and the override write-back in the debug mode don't trigger a re-run. All the
+/// flags of the run command apply to every run.
+fn watch_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let opts = match run_options_from_matches(matches)? {
+        Some(opts) => opts,
+        // Nothing to watch when all the input files are missing.
+        None => return Ok(()),
+    };
+    let args = &opts.args;
+    let module_cache = KCLModuleCache::default();
+    let mut inputs: Vec<PathBuf> = args.k_filename_list.iter().map(PathBuf::from).collect();
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mut watched_dirs: HashSet<PathBuf> = HashSet::new();
+    let output_path = opts.output.as_deref().map(|o| watch_path(Path::new(o)));
+    loop {
+        write!(writer, "{CLEAR_SCREEN}")?;
+        let sess = Arc::new(ParseSession::default());
+        sess.1.write().error_format = args.error_format;
+        let result = exec_program_with_module_cache(sess.clone(), args, module_cache.clone());
+        let succeeded = emit_watch_result(&sess, result, &opts, writer)?;
+        writeln!(
+            writer,
+            "[{}] {}, watching for changes...",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            if succeeded {
+                "Run succeeded"
+            } else {
+                "Run failed"
+            }
+        )?;
+        writer.flush()?;
+        // All the files are cached at this point, so the load only collects the inputs.
+        let load_opts = args.get_load_program_options();
+        let paths: Vec<&str> = args.k_filename_list.iter().map(|s| s.as_str()).collect();
+        if let Ok(result) = load_program(
+            Arc::new(ParseSession::default()),
+            &paths,
+            Some(load_opts),
+            Some(module_cache.clone()),
+        ) {
+            inputs = result.inputs_read;
+        }
+        // The watched paths to the input paths, which are the keys of the module cache.
+        let watched: HashMap<PathBuf, PathBuf> = inputs
+            .iter()
+            .map(|p| (watch_path(p), p.clone()))
+            .filter(|(p, _)| Some(p) != output_path.as_ref())
+            .collect();
+        // The parent directories are watched, so the files replaced by the editors e.g.,
+        // with a rename are still watched.
+        let dirs: HashSet<PathBuf> = watched
+            .keys()
+            .filter_map(|p| p.parent().map(|dir| dir.to_path_buf()))
+            .collect();
+        for dir in watched_dirs.difference(&dirs) {
+            let _ = watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&watched_dirs) {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        watched_dirs = dirs;
+        // The file contents are taken after the run, so the files rewritten by the run
+        // with the same contents are not treated as changed.
+        let paths: Vec<PathBuf> = watched.keys().cloned().collect();
+        let changed = wait_for_changes(&rx, &file_hashes(&paths))?;
+        let mut module_cache = module_cache
+            .write()
+            .map_err(|e| anyhow!("Failed to acquire module cache lock: {e}"))?;
+        for path in changed.iter().filter_map(|p| watched.get(p)) {
+            if path.extension().map_or(false, |ext| ext == "k") {
+                module_cache.clear(path);
+            } else {
+                // Package manifests e.g., `kcl.mod` may change the package resolution.
+                *module_cache = ModuleCache::default();
+            }
+        }
+    }
+}
+
+/// Emit the result of one run in the watch mode and return whether it succeeded.
+fn emit_watch_result<W: Write>(
+    sess: &ParseSessionRef,
+    result: Result<ExecProgramResult>,
+    opts: &RunOptions,
+    writer: &mut W,
+) -> Result<bool> {
+    let result = match result.map_err_to_result() {
+        Ok(result) => result,
+        Err(err) => {
+            // The short diagnostics are printed as they are without the error header.
+            if opts.args.error_format == ErrorFormat::Short {
+                eprintln!("{err}");
+                return Ok(false);
+            }
+            if !sess.0.diag_handler.has_errors()? {
+                sess.0.add_err(StringError(err.to_string()))?;
+            }
+            sess.0.emit_stashed_diagnostics()?;
+            return Ok(false);
+        }
+    };
+    if let Some(timings) = &result.timings {
+        eprintln!("{}", format_timings(timings));
+    }
+    if !result.log_message.is_empty() {
+        write!(writer, "{}", result.log_message)?;
+    }
+    write_outputs(result, opts, writer)?;
+    Ok(true)
+}
+
+/// Get the absolute path of the file to match the paths of the file events, the parent
+/// directory is canonicalized because the file may not exist.
+fn watch_path(path: &Path) -> PathBuf {
+    let path = match std::env::current_dir() {
+        Ok(cwd) if path.is_relative() => cwd.join(path),
+        _ => path.to_path_buf(),
+    };
+    if let (Some(dir), Some(name)) = (path.parent(), path.file_name()) {
+        if let Ok(dir) = dir.canonicalize() {
+            return dir.join(name);
+        }
+    }
+    path
+}
+
+/// Get the content hash of each file, `None` denotes the file does not exist.
+pub(crate) fn file_hashes(paths: &[PathBuf]) -> HashMap<PathBuf, Option<u64>> {
+    paths
+        .iter()
+        .map(|p| {
+            let hash = std::fs::read(p).ok().map(|content| {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                hasher.finish()
+            });
+            (p.clone(), hash)
+        })
+        .collect()
+}
+
+/// Get the files whose contents changed since the snapshot was taken.
+pub(crate) fn changed_files(snapshot: &HashMap<PathBuf, Option<u64>>) -> Vec<PathBuf> {
+    let paths: Vec<PathBuf> = snapshot.keys().cloned().collect();
+    let mut changed: Vec<PathBuf> = file_hashes(&paths)
+        .into_iter()
+        .filter(|(p, hash)| snapshot.get(p) != Some(hash))
+        .map(|(p, _)| p)
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Block until any of the files in the snapshot changes and return all the files
+/// changed after the file events settle down.
+fn wait_for_changes(
+    rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+    snapshot: &HashMap<PathBuf, Option<u64>>,
+) -> Result<Vec<PathBuf>> {
+    loop {
+        let event = match rx.recv()? {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+        if !event
+            .paths
+            .iter()
+            .any(|p| snapshot.contains_key(&watch_path(p)))
+        {
+            continue;
+        }
+        // Debounce the rapid changes e.g., an editor writing a file in multiple steps.
+        while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+        let changed = changed_files(snapshot);
+        if !changed.is_empty() {
+            return Ok(changed);
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::util::*;
 use anyhow::Result;
 use clap::ArgMatches;
-use kclvm_config::settings::{build_settings_pathbuf, Config, SettingsFile, SettingsPathBuf};
+use kclvm_config::settings::{
+    build_settings_pathbuf_with_env, Config, SettingsFile, SettingsPathBuf,
+};
 use kclvm_driver::arguments::parse_key_value_pair;
 use kclvm_error::Handler;
+use kclvm_query::load_override_specs_file;
 use kclvm_runtime::PanicInfo;
 
 /// Build settings from arg matches.
@@ -39,13 +42,25 @@ pub(crate) fn build_settings(matches: &ArgMatches) -> Result<SettingsPathBuf> {
 
     let package_maps = hashmaps_from_matches(matches, "package_map").transpose()?;
 
-    build_settings_pathbuf(
+    // Overrides in the file are applied first, so the inline `-O` overrides win.
+    let overrides = match matches.get_one::<String>("overrides_file") {
+        Some(path) => {
+            let mut overrides = load_override_specs_file(path)?;
+            overrides.extend(strings_from_matches(matches, "overrides").unwrap_or_default());
+            Some(overrides)
+        }
+        None => strings_from_matches(matches, "overrides"),
+    };
+
+    // The environment variables are only read when the prefix is specified, and
+    // they have lower precedence than the explicit flags.
+    build_settings_pathbuf_with_env(
         files.as_slice(),
         setting_files,
         Some(SettingsFile {
             kcl_cli_configs: Some(Config {
                 output: matches.get_one::<String>("output").map(|v| v.to_string()),
-                overrides: strings_from_matches(matches, "overrides"),
+                overrides,
                 path_selector: strings_from_matches(matches, "path_selector"),
                 strict_range_check: bool_from_matches(matches, "strict_range_check"),
                 disable_none: bool_from_matches(matches, "disable_none"),
@@ -54,6 +69,7 @@ pub(crate) fn build_settings(matches: &ArgMatches) -> Result<SettingsPathBuf> {
                 sort_keys: bool_from_matches(matches, "sort_keys"),
                 show_hidden: bool_from_matches(matches, "show_hidden"),
                 fast_eval: bool_from_matches(matches, "fast_eval"),
+                strict_number: bool_from_matches(matches, "strict_number"),
                 package_maps,
                 ..Default::default()
             }),
@@ -69,5 +85,8 @@ pub(crate) fn build_settings(matches: &ArgMatches) -> Result<SettingsPathBuf> {
                 None
             },
         }),
+        matches
+            .get_one::<String>("env_prefix")
+            .map(|prefix| prefix.as_str()),
     )
 }
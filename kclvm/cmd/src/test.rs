@@ -0,0 +1,48 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_tools::testing::{
+    load_test_suites, JsonReporter, Reporter, TestOptions, TestRun, TestSummary, TextReporter,
+};
+use std::io::Write;
+
+use crate::util::*;
+
+/// Run the KCL test command, which runs the test cases in the `_test.k` files
+/// of the input packages and reports the results as a table or a JSON object.
+pub fn test_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let pkgs = strings_from_matches(matches, "input").unwrap_or_else(|| vec![".".to_string()]);
+    let opts = TestOptions {
+        run_regexp: matches
+            .get_one::<String>("run")
+            .cloned()
+            .unwrap_or_default(),
+        fail_fast: matches.get_flag("fail_fast"),
+        tags: strings_from_matches(matches, "tag").unwrap_or_default(),
+        skip_tags: strings_from_matches(matches, "skip_tag").unwrap_or_default(),
+        ..Default::default()
+    };
+    let mut results = vec![];
+    'pkgs: for pkg in &pkgs {
+        for suite in load_test_suites(pkg, &opts)? {
+            let result = suite.run(&opts)?;
+            let failed = result.info.values().any(|info| info.error.is_some());
+            results.push((suite, result));
+            if failed && opts.fail_fast {
+                break 'pkgs;
+            }
+        }
+    }
+    let reporter: Box<dyn Reporter> = match matches.get_one::<String>("output").map(|s| s.as_str())
+    {
+        Some("json") => Box::new(JsonReporter),
+        _ => Box::new(TextReporter),
+    };
+    reporter.report(&results, writer)?;
+    // The exit code reflects the failures regardless of the output format.
+    let summary = TestSummary::new(&results);
+    if summary.is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("{} test(s) failed", summary.failed))
+    }
+}
@@ -3,16 +3,23 @@ use std::{
     fs::{self, remove_file},
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use kclvm_config::modfile::KCL_PKG_PATH;
+use kclvm_error::ErrorFormat;
 use kclvm_parser::ParseSession;
-use kclvm_runner::{exec_program, MapErrorResult};
+use kclvm_runner::{exec_program, MapErrorResult, PhaseTimings};
 
 use crate::{
     app,
-    run::run_command,
+    debug::debug_command,
+    explain::{annotate_yaml, ExplainFormat},
+    fix::fix_command,
+    lint::lint_command,
+    run::{changed_files, file_hashes, format_timings, run_command, run_options_from_matches},
     settings::{build_settings, must_build_settings},
+    test::test_command,
     util::hashmaps_from_matches,
 };
 
@@ -59,6 +66,30 @@ fn test_build_settings() {
     assert_eq!(s.settings().input(), vec!["hello.k".to_string()]);
 }
 
+#[test]
+fn test_build_settings_with_overrides_file() {
+    let overrides_file = work_dir().join("overrides.txt");
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        "hello.k",
+        "--overrides-file",
+        &overrides_file.display().to_string(),
+        "-O",
+        "c.b=2",
+    ]);
+    let matches = matches.subcommand_matches("run").unwrap();
+    let s = build_settings(matches).unwrap();
+    assert_eq!(
+        s.settings().kcl_cli_configs.as_ref().unwrap().overrides,
+        Some(vec![
+            "c.a=1".to_string(),
+            "c.b=1".to_string(),
+            "c.b=2".to_string()
+        ])
+    );
+}
+
 #[test]
 fn test_build_settings_fail() {
     let matches = app().get_matches_from(settings_arguments(work_dir().join("error_kcl.yaml")));
@@ -107,6 +138,55 @@ fn test_version_cmd() {
     assert!(matches.subcommand_matches("version").is_some())
 }
 
+#[test]
+fn test_lint_cmd() {
+    let file = "./src/test_data/lint/unused_import.k";
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", file]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    assert!(String::from_utf8(buf)
+        .unwrap()
+        .contains("Module 'math' imported but unused"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", file, "--disable", "W0411"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    assert!(!String::from_utf8(buf)
+        .unwrap()
+        .contains("Module 'math' imported but unused"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", file, "--disable", "W9999"]);
+    let mut buf = Vec::new();
+    let err = lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown lint 'W9999', run 'kcl lint --list' to list all the available lints"
+    );
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", file, "--deny", "unused"]);
+    let mut buf = Vec::new();
+    assert!(lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).is_err());
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", "--list"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    assert!(String::from_utf8(buf)
+        .unwrap()
+        .contains("W0411 UNUSED_IMPORT: Check for unused importstmt"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "lint", file, "--error-format", "short"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 1, "{output}");
+    assert!(
+        lines[0]
+            .ends_with("unused_import.k:1:1: warning: Module 'math' imported but unused [W1001]"),
+        "{output}"
+    );
+}
+
 #[test]
 fn test_multi_external_cmd() {
     let matches = app().get_matches_from(&[
@@ -587,3 +667,276 @@ fn test_keyword_argument_error_message() {
         }
     }
 }
+
+#[test]
+fn test_watch_changed_files() {
+    let work_dir = PathBuf::from("./src/test_data/watch");
+    let main_file = work_dir.join("main.k");
+    let temp_file = work_dir.join("temp.k");
+    fs::write(&temp_file, "a = 1\n").unwrap();
+    let snapshot = file_hashes(&[main_file.clone(), temp_file.clone()]);
+    assert!(changed_files(&snapshot).is_empty());
+    // The file rewritten with the same content e.g., by the override write-back is unchanged.
+    fs::write(&temp_file, "a = 1\n").unwrap();
+    assert!(changed_files(&snapshot).is_empty());
+    fs::write(&temp_file, "a = 2\n").unwrap();
+    assert_eq!(changed_files(&snapshot), vec![temp_file.clone()]);
+    remove_file(&temp_file).unwrap();
+    assert_eq!(changed_files(&snapshot), vec![temp_file]);
+}
+
+#[test]
+fn test_watch_run_options() {
+    // The watch mode runs with the same options as the one-shot run.
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        "./src/test_data/watch/main.k",
+        "--watch",
+        "--sandbox",
+        "--profile",
+        "--error-format",
+        "short",
+        "--split-output-by",
+        "metadata.name",
+        "--explain-output",
+        "-o",
+        "out",
+    ]);
+    let opts = run_options_from_matches(matches.subcommand_matches("run").unwrap())
+        .unwrap()
+        .unwrap();
+    assert!(opts.args.sandbox);
+    assert!(opts.args.profile);
+    assert!(opts.args.emit_source_map);
+    assert_eq!(opts.args.error_format, ErrorFormat::Short);
+    assert_eq!(opts.args.split_output_by, Some("metadata.name".to_string()));
+    assert_eq!(opts.explain, Some(ExplainFormat::Yaml));
+    assert_eq!(opts.output, Some("out".to_string()));
+
+    // Nothing to run when all the input files are missing with --if-present.
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        "./src/test_data/watch/missing.k",
+        "--watch",
+        "--if-present",
+    ]);
+    assert!(
+        run_options_from_matches(matches.subcommand_matches("run").unwrap())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_annotate_yaml() {
+    let source_map = indexmap::IndexMap::from([
+        ("a".to_string(), ("main.k".to_string(), 1, 0, 1, 5)),
+        ("b".to_string(), ("main.k".to_string(), 2, 0, 5, 1)),
+        ("b.c".to_string(), ("main.k".to_string(), 3, 4, 3, 9)),
+        ("b.d".to_string(), ("base.k".to_string(), 4, 4, 4, 9)),
+        ("b.d.e".to_string(), ("base.k".to_string(), 5, 4, 5, 9)),
+        ("b.d.1.e".to_string(), ("base.k".to_string(), 6, 4, 6, 9)),
+    ]);
+    let yaml = "a: 1\nb:\n  c: |-\n    e: 1\n  d:\n  - e: 1\n  - e: 2\n";
+    assert_eq!(
+        annotate_yaml(yaml, &source_map),
+        "a: 1  # main.k:1\nb:  # main.k:2\n  c: |-  # main.k:3\n    e: 1\n  d:  # base.k:4\n  - e: 1\n  - e: 2  # base.k:6\n"
+    );
+}
+
+#[test]
+fn test_run_command_with_explain_output() {
+    let file = "./src/test_data/explain/main.k";
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", file, "--explain-output"]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("deployment:  # "));
+    assert!(output
+        .lines()
+        .any(|l| l.contains("replicas: 3  # ") && l.ends_with("explain/main.k:8")));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", file, "--explain-output=json"]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("replicas: 3\n"));
+    assert!(output.contains("explain/main.k:8\""));
+}
+
+#[test]
+fn test_run_command_with_if_present() {
+    let dir = PathBuf::from("./src/test_data/if_present");
+    let main = dir.join("main.k").display().to_string();
+    let missing = dir.join("missing.k").display().to_string();
+    let broken = dir.join("broken.k").display().to_string();
+
+    // The missing input file is an error by default.
+    let matches =
+        app().get_matches_from(&[ROOT_CMD, "run", &main, &missing, "--error-format", "short"]);
+    let mut buf = Vec::new();
+    assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+
+    // The missing input file is skipped.
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", &main, &missing, "--if-present"]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "a: 1\n");
+
+    // No-op when all the input files are missing.
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", &missing, "--if-present"]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    assert!(buf.is_empty());
+
+    // The present but broken input file is still an error.
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        &broken,
+        &missing,
+        "--if-present",
+        "--error-format",
+        "short",
+    ]);
+    let mut buf = Vec::new();
+    assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+}
+
+#[test]
+fn test_run_command_with_inputs() {
+    let dir = PathBuf::from("./src/test_data/inputs");
+    let main = dir.join("main.k").display().to_string();
+    let data = format!("data=@{}", dir.join("values.json").display());
+    let matches = app().get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        &main,
+        "--input",
+        &data,
+        "--input",
+        "env=\"prod\"",
+    ]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "app:\n  name: app\n  replicas: 3\n  env: prod\n"
+    );
+
+    // The input value must be a valid JSON value.
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", &main, "--input", "env=prod"]);
+    let mut buf = Vec::new();
+    let err = run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("invalid JSON value for the input 'env'"));
+}
+
+#[test]
+fn test_run_command_with_dir_as_package() {
+    let dir = PathBuf::from("./src/test_data/dir_as_package")
+        .canonicalize()
+        .unwrap()
+        .display()
+        .to_string();
+    let matches = app().get_matches_from(&[ROOT_CMD, "run", &dir, "--dir-as-package"]);
+    let mut buf = Vec::new();
+    run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "a: 1\nb: 2\n");
+}
+
+#[test]
+fn test_debug_tokens_cmd() {
+    let file = "./src/test_data/debug/tokens.k";
+    let matches = app().get_matches_from(&[ROOT_CMD, "debug", "tokens", file]);
+    let mut buf = Vec::new();
+    debug_command(matches.subcommand_matches("debug").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.starts_with("1:0-1:6\tidentifier\t\"schema\"\n"));
+    assert!(output.contains("\tindent\t"));
+    assert!(output.contains("\tdedent\t"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "debug", "tokens", file, "--json"]);
+    let mut buf = Vec::new();
+    debug_command(matches.subcommand_matches("debug").unwrap(), &mut buf).unwrap();
+    let tokens: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    assert_eq!(tokens[0]["kind"], "identifier");
+    assert_eq!(tokens[0]["text"], "schema");
+    assert_eq!(tokens[0]["end"]["column"], 6);
+}
+
+#[test]
+fn test_fix_cmd() {
+    let file = "./src/test_data/fix/main.k";
+    let original = fs::read_to_string(file).unwrap();
+    let matches = app().get_matches_from(&[ROOT_CMD, "fix", file, "--dry-run"]);
+    let mut buf = Vec::new();
+    fix_command(matches.subcommand_matches("fix").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("-    nmae = \"Alice\""));
+    assert!(output.contains("+    name = \"Alice\""));
+    assert!(output.ends_with("Would apply 1 fix(es) in 1 file(s)\n"));
+    assert_eq!(fs::read_to_string(file).unwrap(), original);
+
+    let tmp_file = env::temp_dir().join("kcl_fix_cmd_main.k");
+    fs::write(&tmp_file, &original).unwrap();
+    let matches = app().get_matches_from(&[ROOT_CMD, "fix", tmp_file.to_str().unwrap()]);
+    let mut buf = Vec::new();
+    fix_command(matches.subcommand_matches("fix").unwrap(), &mut buf).unwrap();
+    assert_eq!(
+        fs::read_to_string(&tmp_file).unwrap(),
+        original.replace("nmae", "name")
+    );
+    remove_file(&tmp_file).unwrap();
+}
+
+#[test]
+fn test_test_cmd() {
+    let pkg = "./src/test_data/test/pkg";
+    let matches = app().get_matches_from(&[ROOT_CMD, "test", pkg, "--output", "json"]);
+    let mut buf = Vec::new();
+    let err = test_command(matches.subcommand_matches("test").unwrap(), &mut buf).unwrap_err();
+    assert_eq!(err.to_string(), "1 test(s) failed");
+    let report: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    assert_eq!(report["total"], 2);
+    assert_eq!(report["passed"], 1);
+    assert_eq!(report["failed"], 1);
+    assert_eq!(report["skipped"], 0);
+    let cases = &report["suites"][0]["cases"];
+    assert_eq!(cases[0]["name"], "test_double");
+    assert_eq!(cases[0]["status"], "passed");
+    assert_eq!(cases[1]["status"], "failed");
+    assert!(cases[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("unexpected double"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "test", pkg, "--run", "test_double$"]);
+    let mut buf = Vec::new();
+    test_command(matches.subcommand_matches("test").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.starts_with("PASSED"));
+    assert!(output.contains("ok: 1 total, 1 passed, 0 failed, 0 skipped"));
+}
+
+#[test]
+fn test_format_timings() {
+    let mut timings = PhaseTimings {
+        load: Duration::from_micros(1200),
+        resolve: Duration::from_micros(3400),
+        codegen: None,
+        eval: Duration::from_micros(5600),
+    };
+    assert_eq!(
+        format_timings(&timings),
+        "load: 1.200ms, resolve: 3.400ms, eval: 5.600ms"
+    );
+    timings.codegen = Some(Duration::from_millis(10));
+    assert_eq!(
+        format_timings(&timings),
+        "load: 1.200ms, resolve: 3.400ms, codegen: 10.000ms, eval: 5.600ms"
+    );
+}
@@ -5,7 +5,9 @@ use inkwell::module::Module;
 use inkwell::{context::Context, memory_buffer::MemoryBuffer};
 use kclvm_ast::ast;
 use once_cell::sync::OnceCell;
+use std::cell::RefCell;
 use std::error;
+use std::mem::ManuallyDrop;
 
 use crate::codegen::{EmitOptions, MODULE_NAME};
 
@@ -13,6 +15,11 @@ use super::context::LLVMCodeGenContext;
 
 static LLVM_INIT: OnceCell<()> = OnceCell::new();
 static RUNTIME_LLVM_BC: &[u8] = include_bytes!("../../../../runtime/src/_kclvm.bc");
+/// The number of code generations sharing the cached LLVM context before it is recreated.
+/// The LLVM context owns the types, constants and metadata interned by all the modules
+/// created in it until it is dropped, thus it is recreated periodically to bound the
+/// memory of the long-running processes e.g., the language server and the API server.
+const MAX_RUNTIME_CONTEXT_USES: usize = 32;
 
 /// Load runtime libraries and parse it to a module.
 fn load_runtime(context: &'_ Context) -> Module<'_> {
@@ -20,6 +27,48 @@ fn load_runtime(context: &'_ Context) -> Module<'_> {
     Module::parse_bitcode_from_buffer(&memory, context).unwrap()
 }
 
+/// The LLVM context and the runtime module parsed in it. LLVM modules are bound to their
+/// context and can only be cloned within it, so the context is kept alive with the module
+/// and both are freed when the cache is dropped.
+struct RuntimeCache {
+    context: &'static Context,
+    module: ManuallyDrop<Module<'static>>,
+    /// The number of code generations using the context.
+    uses: usize,
+}
+
+impl RuntimeCache {
+    fn new() -> Self {
+        let context: &'static Context = Box::leak(Box::new(Context::create()));
+        Self {
+            context,
+            module: ManuallyDrop::new(load_runtime(context)),
+            uses: 0,
+        }
+    }
+}
+
+impl Drop for RuntimeCache {
+    fn drop(&mut self) {
+        // SAFETY: The context is leaked from the box in [RuntimeCache::new] and only
+        // referenced by the module, which is dropped before the context is freed.
+        unsafe {
+            ManuallyDrop::drop(&mut self.module);
+            drop(Box::from_raw(
+                self.context as *const Context as *mut Context,
+            ));
+        }
+    }
+}
+
+thread_local! {
+    /// The runtime module parsed per thread, which is freed when the thread exits. Every
+    /// code generation on the thread clones the runtime module instead of parsing the runtime
+    /// bitcode again, and the runtime module is parsed again in a new context after
+    /// [MAX_RUNTIME_CONTEXT_USES] code generations.
+    static RUNTIME: RefCell<Option<RuntimeCache>> = RefCell::new(None);
+}
+
 /// Generate LLVM IR of KCL ast module.
 pub fn emit_code(
     program: &ast::Program,
@@ -38,17 +87,52 @@ pub fn emit_code(
         #[cfg(not(any(target_os = "linux", target_arch = "wasm32")))]
         inkwell::targets::Target::initialize_all(&Default::default());
     });
-    // Create a LLVM context
-    let context = Context::create();
-    // Create a LLVM module using an exist LLVM bitcode file
-    let module = if let Some(path) = &opts.from_path {
-        Module::parse_bitcode_from_path(std::path::Path::new(path), &context).unwrap()
-    } else {
-        load_runtime(&context)
-    };
+    match &opts.from_path {
+        // Create a LLVM module using an exist LLVM bitcode file
+        Some(path) => {
+            let context = Context::create();
+            let module =
+                Module::parse_bitcode_from_path(std::path::Path::new(path), &context).unwrap();
+            emit_code_with_module(&context, module, program, workdir, import_names, opts)
+        }
+        // Clone the cached runtime module in the cached LLVM context of the thread
+        None => RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            if runtime
+                .as_ref()
+                .map_or(true, |runtime| runtime.uses >= MAX_RUNTIME_CONTEXT_USES)
+            {
+                // Free the old context before creating the new one. All the modules
+                // created in it are dropped at the end of their code generation.
+                *runtime = None;
+                *runtime = Some(RuntimeCache::new());
+            }
+            let runtime = runtime.as_mut().expect("the runtime cache is initialized");
+            runtime.uses += 1;
+            emit_code_with_module(
+                runtime.context,
+                (*runtime.module).clone(),
+                program,
+                workdir,
+                import_names,
+                opts,
+            )
+        }),
+    }
+}
+
+/// Generate LLVM IR of KCL ast module into the LLVM module created in the context.
+fn emit_code_with_module<'ctx>(
+    context: &'ctx Context,
+    module: Module<'ctx>,
+    program: &ast::Program,
+    workdir: String,
+    import_names: IndexMap<String, IndexMap<String, String>>,
+    opts: &EmitOptions,
+) -> Result<(), Box<dyn error::Error>> {
     // Create a KCL LLVM code generator using the KCL AST and the LLVM module
     let ctx = LLVMCodeGenContext::new(
-        &context,
+        context,
         module,
         program,
         import_names,
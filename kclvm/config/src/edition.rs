@@ -0,0 +1,77 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The compiler edition pins the edition-specific parser and resolver behaviors, so a
+//! package can keep compiling with the same language rules when the compiler is upgraded.
+//! The edition is set with the `edition` compile option or the `language_edition` field in
+//! the `[compile]` section of `kcl.mod`, e.g.
+//!
+//! ```toml
+//! [compile]
+//! language_edition = "2023"
+//! ```
+//!
+//! | Edition | Behavior                                                                  |
+//! |---------|---------------------------------------------------------------------------|
+//! | `2023`  | The baseline edition without the selective import `from pkg import Name`. |
+//! | `2024`  | The current edition, which is the default.                                |
+
+use std::{fmt, str::FromStr};
+
+/// The compiler edition.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edition {
+    /// The baseline edition.
+    Edition2023,
+    /// The current edition.
+    #[default]
+    Edition2024,
+}
+
+impl Edition {
+    /// All the known editions in the ascending order.
+    pub const ALL: &'static [Edition] = &[Edition::Edition2023, Edition::Edition2024];
+    /// The edition used when no edition is set.
+    pub const CURRENT: Edition = Edition::Edition2024;
+
+    /// The edition name e.g., `2024`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Edition::Edition2023 => "2023",
+            Edition::Edition2024 => "2024",
+        }
+    }
+
+    /// Whether the selective import statement `from pkg import Name` is supported.
+    #[inline]
+    pub fn supports_selective_import(&self) -> bool {
+        *self >= Edition::Edition2024
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Edition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Edition::ALL
+            .iter()
+            .find(|edition| edition.as_str() == s.trim())
+            .copied()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown edition '{}', expected one of {}",
+                    s,
+                    Edition::ALL
+                        .iter()
+                        .map(|edition| format!("'{}'", edition))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })
+    }
+}
@@ -1,6 +1,7 @@
 //! Copyright The KCL Authors. All rights reserved.
 
 pub mod cache;
+pub mod edition;
 pub mod modfile;
 pub mod path;
 pub mod settings;
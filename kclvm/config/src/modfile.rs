@@ -29,6 +29,7 @@ pub const DEFAULT_KPM_SUBDIR: &str = "kpm";
 pub struct ModFile {
     pub package: Option<Package>,
     pub profile: Option<Profile>,
+    pub compile: Option<Compile>,
     pub dependencies: Option<Dependencies>,
 }
 
@@ -73,6 +74,17 @@ pub struct Profile {
     pub options: Option<Vec<String>>,
 }
 
+/// Compile is the compile section of 'kcl.mod'.
+/// It is used to override the global compile options for the modules of the current package.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Compile {
+    /// Flag that, when set, overrides the global `strict_range_check` option for the package.
+    pub strict_range_check: Option<bool>,
+    /// The language edition of the package e.g., `2024`, which overrides the global `edition`
+    /// option for the package. See [`crate::edition::Edition`] for the known editions.
+    pub language_edition: Option<String>,
+}
+
 /// A map of package names to their respective dependency specifications.
 pub type Dependencies = HashMap<String, Dependency>;
 pub type LockDependencies = HashMap<String, LockDependency>;
@@ -181,6 +193,11 @@ impl ModFile {
     }
 }
 
+/// Parse kcl mod file from the file content.
+pub fn parse_mod_file(content: &str) -> Result<ModFile> {
+    toml::from_str(content).map_err(|e| anyhow::anyhow!(e))
+}
+
 /// Load kcl mod file from path
 pub fn load_mod_file<P: AsRef<Path>>(path: P) -> Result<ModFile> {
     let file_path = path.as_ref().join(KCL_MOD_FILE);
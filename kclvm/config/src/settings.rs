@@ -8,6 +8,13 @@ use std::{collections::HashMap, ops::Deref, path::PathBuf};
 
 /// Default settings file `kcl.yaml`
 pub const DEFAULT_SETTING_FILE: &str = "kcl.yaml";
+/// The environment variables `<prefix>OPT_<key>` are the top-level arguments.
+pub const ENV_OPTION_MARKER: &str = "OPT_";
+/// The environment variables `<prefix>OVERRIDE_<path>` are the override specs.
+pub const ENV_OVERRIDE_MARKER: &str = "OVERRIDE_";
+/// The separator in the environment variable names denoting the `.` of the override
+/// paths, because the `.` is not allowed in the environment variable names of most shells.
+pub const ENV_PATH_SEPARATOR: &str = "__";
 
 /// Readonly settings with the filepath.
 #[derive(Debug, Default, Clone)]
@@ -63,6 +70,8 @@ pub struct Config {
     pub show_hidden: Option<bool>,
     /// Whether including schema type in JSON/YAML result.
     pub include_schema_type_path: Option<bool>,
+    /// Whether to emit the integral unit values e.g., `1Ki` as integers in JSON/YAML result.
+    pub strict_number: Option<bool>,
     /// kcl needs a mapping between the package name and the package path
     /// to determine the source code path corresponding to different version package.
     pub package_maps: Option<HashMap<String, String>>,
@@ -87,6 +96,7 @@ impl SettingsFile {
                 show_hidden: Some(false),
                 fast_eval: Some(false),
                 include_schema_type_path: Some(false),
+                strict_number: Some(false),
                 package_maps: Some(HashMap::default()),
             }),
             kcl_options: Some(vec![]),
@@ -388,6 +398,7 @@ pub fn merge_settings(settings: &[SettingsFile]) -> SettingsFile {
                 set_if!(result_kcl_cli_configs, sort_keys, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, show_hidden, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, fast_eval, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, strict_number, kcl_cli_configs);
                 set_if!(
                     result_kcl_cli_configs,
                     include_schema_type_path,
@@ -410,11 +421,110 @@ pub fn merge_settings(settings: &[SettingsFile]) -> SettingsFile {
     result
 }
 
+/// Build the settings from the environment variables with the prefix, the variable names
+/// after the prefix are mapped as follows:
+///
+/// + `<prefix>OPT_<key>=<value>` is the top-level argument `-D <key>=<value>`, the value
+///   is used as is when it is a valid JSON value e.g., `3`, `true` or `["a", "b"]`, and
+///   is regarded as a string otherwise.
+/// + `<prefix>OVERRIDE_<path>=<value>` is the override spec `-O <path>=<value>`, and the
+///   `__` in the path denotes the `.` e.g., `KCL_OVERRIDE_app__replicas=3` is
+///   `-O app.replicas=3`.
+///
+/// The keys are case sensitive, and the variables are sorted by the names, so the
+/// arguments and the overrides are in a deterministic order.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_config::settings::settings_from_env_vars;
+///
+/// let vars = [
+///     ("KCL_OPT_replicas", "3"),
+///     ("KCL_OPT_env", "prod"),
+///     ("KCL_OVERRIDE_app__image", "\"nginx\""),
+///     ("HOME", "/root"),
+/// ];
+/// let settings = settings_from_env_vars(
+///     "KCL_",
+///     vars.iter().map(|(k, v)| (k.to_string(), v.to_string())),
+/// );
+/// let options = settings.kcl_options.unwrap();
+/// assert_eq!(options[0].key, "env");
+/// assert_eq!(options[0].value.0, "\"prod\"");
+/// assert_eq!(options[1].key, "replicas");
+/// assert_eq!(options[1].value.0, "3");
+/// let overrides = settings.kcl_cli_configs.unwrap().overrides.unwrap();
+/// assert_eq!(overrides, vec!["app.image=\"nginx\"".to_string()]);
+/// ```
+pub fn settings_from_env_vars<I: IntoIterator<Item = (String, String)>>(
+    prefix: &str,
+    vars: I,
+) -> SettingsFile {
+    let mut vars: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .collect();
+    vars.sort();
+    let mut options = vec![];
+    let mut overrides = vec![];
+    for (name, value) in vars {
+        let name = &name[prefix.len()..];
+        if let Some(key) = name.strip_prefix(ENV_OPTION_MARKER) {
+            if key.is_empty() {
+                continue;
+            }
+            let value = if serde_json::from_str::<serde_json::Value>(&value).is_ok() {
+                value
+            } else {
+                serde_json::Value::String(value).to_string()
+            };
+            options.push(KeyValuePair {
+                key: key.to_string(),
+                value: value.into(),
+            });
+        } else if let Some(path) = name.strip_prefix(ENV_OVERRIDE_MARKER) {
+            if path.is_empty() {
+                continue;
+            }
+            overrides.push(format!(
+                "{}={}",
+                path.replace(ENV_PATH_SEPARATOR, "."),
+                value
+            ));
+        }
+    }
+    SettingsFile {
+        kcl_cli_configs: Some(Config {
+            overrides: (!overrides.is_empty()).then_some(overrides),
+            ..Default::default()
+        }),
+        kcl_options: Some(options),
+    }
+}
+
 /// Build SettingsPathBuf from args.
 pub fn build_settings_pathbuf(
     files: &[&str],
     setting_files: Option<Vec<&str>>,
     setting_config: Option<SettingsFile>,
+) -> Result<SettingsPathBuf> {
+    build_settings_pathbuf_with_env(files, setting_files, setting_config, None)
+}
+
+/// Build SettingsPathBuf from args like [build_settings_pathbuf], and ingest the
+/// environment variables with the prefix e.g., `KCL_` when it is set, see
+/// [settings_from_env_vars] for the variable name mapping rules.
+///
+/// The settings are merged with the precedence from low to high: the settings files,
+/// the environment variables and the `setting_config` e.g., the explicit CLI flags.
+/// The top-level arguments of the same key with the higher precedence win, and the
+/// environment override specs are applied before the explicit ones.
+pub fn build_settings_pathbuf_with_env(
+    files: &[&str],
+    setting_files: Option<Vec<&str>>,
+    setting_config: Option<SettingsFile>,
+    env_prefix: Option<&str>,
 ) -> Result<SettingsPathBuf> {
     let mut path = None;
     let settings = if let Some(files) = setting_files {
@@ -446,6 +556,13 @@ pub fn build_settings_pathbuf(
     } else {
         SettingsFile::default()
     };
+    let (settings, setting_config) = match env_prefix {
+        Some(prefix) => {
+            let env_settings = settings_from_env_vars(prefix, std::env::vars());
+            merge_env_settings(settings, env_settings, setting_config)
+        }
+        None => (settings, setting_config),
+    };
     let mut settings = if let Some(setting_config) = setting_config {
         merge_settings(&[settings, setting_config])
     } else {
@@ -459,6 +576,45 @@ pub fn build_settings_pathbuf(
     Ok(SettingsPathBuf::new(path, settings))
 }
 
+/// Merge the environment settings into the file settings, and put the environment override
+/// specs before the explicit ones, because the explicit override specs replace the others
+/// when the settings are merged.
+fn merge_env_settings(
+    settings: SettingsFile,
+    env_settings: SettingsFile,
+    setting_config: Option<SettingsFile>,
+) -> (SettingsFile, Option<SettingsFile>) {
+    let env_overrides = env_settings
+        .kcl_cli_configs
+        .as_ref()
+        .and_then(|c| c.overrides.clone())
+        .unwrap_or_default();
+    let mut settings = settings;
+    if !env_overrides.is_empty() {
+        settings
+            .kcl_cli_configs
+            .get_or_insert_with(Config::default)
+            .overrides
+            .get_or_insert_with(Vec::new)
+            .extend(env_overrides.iter().cloned());
+    }
+    if let Some(options) = &env_settings.kcl_options {
+        settings
+            .kcl_options
+            .get_or_insert_with(Vec::new)
+            .extend(options.iter().cloned());
+    }
+    let setting_config = setting_config.map(|mut setting_config| {
+        if let Some(config) = &mut setting_config.kcl_cli_configs {
+            if let Some(overrides) = &mut config.overrides {
+                overrides.splice(0..0, env_overrides);
+            }
+        }
+        setting_config
+    });
+    (settings, setting_config)
+}
+
 #[cfg(test)]
 mod settings_test {
     use crate::settings::*;
@@ -486,6 +642,7 @@ mod settings_test {
             assert!(kcl_cli_configs.include_schema_type_path.is_none());
             assert!(kcl_cli_configs.show_hidden.is_none());
             assert!(kcl_cli_configs.fast_eval.is_none());
+            assert!(kcl_cli_configs.strict_number.is_none());
             assert_eq!(kcl_cli_configs.sort_keys, Some(true));
             if let Some(config_files) = kcl_cli_configs.files {
                 assert!(config_files == files);
@@ -523,4 +680,58 @@ mod settings_test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_build_settings_with_env() -> anyhow::Result<()> {
+        std::env::set_var("KCL_SETTINGS_TEST_OPT_image", "nginx:env");
+        std::env::set_var("KCL_SETTINGS_TEST_OPT_replicas", "3");
+        std::env::set_var("KCL_SETTINGS_TEST_OVERRIDE_app__replicas", "3");
+        let setting_config = SettingsFile {
+            kcl_cli_configs: Some(Config {
+                overrides: Some(vec!["app.replicas=5".to_string()]),
+                ..Default::default()
+            }),
+            kcl_options: Some(vec![KeyValuePair {
+                key: "replicas".to_string(),
+                value: "4".into(),
+            }]),
+        };
+        let settings = build_settings_pathbuf_with_env(
+            &[],
+            Some(vec![SETTINGS_FILE]),
+            Some(setting_config),
+            Some("KCL_SETTINGS_TEST_"),
+        )?;
+        let settings = settings.settings();
+        // The options are in the order of the settings file, the environment
+        // variables and the explicit arguments, so the latter ones win.
+        let options: Vec<(&str, &str)> = settings
+            .kcl_options
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|option| (option.key.as_str(), option.value.as_str()))
+            .skip(6)
+            .collect();
+        assert_eq!(
+            options,
+            vec![
+                ("image", "\"nginx:env\""),
+                ("replicas", "3"),
+                ("replicas", "4")
+            ]
+        );
+        // The environment override specs are applied before the explicit ones.
+        assert_eq!(
+            settings.kcl_cli_configs.as_ref().unwrap().overrides,
+            Some(vec![
+                "app.replicas=3".to_string(),
+                "app.replicas=5".to_string()
+            ])
+        );
+        // The environment variables are ignored without the prefix.
+        let settings = build_settings_pathbuf(&[], Some(vec![SETTINGS_FILE]), None)?;
+        assert_eq!(settings.settings().kcl_options.as_ref().unwrap().len(), 6);
+        Ok(())
+    }
 }
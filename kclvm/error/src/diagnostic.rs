@@ -123,6 +123,7 @@ impl Diagnostic {
                 message: message.to_string(),
                 note: note.map(String::from),
                 suggested_replacement: suggestions,
+                ..Default::default()
             }],
             code,
         }
@@ -132,6 +133,11 @@ impl Diagnostic {
     pub fn is_error(&self) -> bool {
         matches!(self.level, Level::Error)
     }
+
+    #[inline]
+    pub fn is_warning(&self) -> bool {
+        matches!(self.level, Level::Warning)
+    }
 }
 
 pub type Range = (Position, Position);
@@ -142,13 +148,31 @@ pub fn dummy_range() -> Range {
     (Position::dummy_pos(), Position::dummy_pos())
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Message {
     pub range: Range,
     pub style: Style,
     pub message: String,
     pub note: Option<String>,
     pub suggested_replacement: Option<Vec<String>>,
+    /// The secondary ranges with their labels e.g., `also defined here` of the message
+    /// involving multiple source locations such as the duplicate definitions.
+    pub related: Vec<(Range, String)>,
+}
+
+/// A diagnostic message paired with its suggested replacements, used to
+/// apply fixes to the source in one pass, e.g., by a `kcl fix` command.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixableDiagnostic {
+    pub level: Level,
+    pub code: Option<DiagnosticId>,
+    pub range: Range,
+    pub message: String,
+    /// Candidate texts to replace the source text in `range` with.
+    pub replacements: Vec<String>,
+    /// Whether the fix can be applied without user intervention. Informational
+    /// suggestions such as `kcl mod add` hints and ambiguous candidates are not.
+    pub machine_applicable: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -186,8 +210,9 @@ impl fmt::Display for Level {
 /// Style indicates the style of error message:
 /// - `LineAndColumn` is <filename>:<line>:<column>
 /// - `Line` is <filename>:<line>
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub enum Style {
+    #[default]
     Empty,
     LineAndColumn,
     Line,
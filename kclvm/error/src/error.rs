@@ -144,6 +144,10 @@ pub enum WarningKind {
     UnusedImportWarning,
     ReimportWarning,
     ImportPositionWarning,
+    DeprecatedWarning,
+    IncompatibleComparisonWarning,
+    RedundantAttributeWarning,
+    UnknownLintWarning,
 }
 
 /// Test warning `fmt`
@@ -158,6 +162,7 @@ pub enum WarningKind {
 ///         message: "Module 'a' imported but unused.".to_string(),
 ///         note: None,
 ///         suggested_replacement: None,
+///         ..Default::default()
 ///     }],
 /// );
 /// for diag in &handler.diagnostics {
@@ -185,6 +190,7 @@ impl std::fmt::Display for WarningKind {
 ///         message: "Module 'a' imported but unused.".to_string(),
 ///         note: None,
 ///         suggested_replacement: None,
+///         ..Default::default()
 ///     }],
 /// );
 /// for diag in &handler.diagnostics {
@@ -5,6 +5,7 @@
 
 pub mod diagnostic;
 mod error;
+mod sarif;
 
 use annotate_snippets::{
     display_list::DisplayList,
@@ -23,10 +24,12 @@ use compiler_base_span::{span::new_byte_pos, Span};
 use diagnostic::Range;
 use indexmap::IndexSet;
 use kclvm_runtime::PanicInfo;
-use std::{any::Any, sync::Arc};
+use std::{any::Any, io::Write, path::Path, sync::Arc};
 use thiserror::Error;
 
-pub use diagnostic::{Diagnostic, DiagnosticId, Level, Message, Position, Style};
+pub use diagnostic::{
+    Diagnostic, DiagnosticId, FixableDiagnostic, Level, Message, Position, Style,
+};
 pub use error::*;
 
 /// A handler deals with errors and other compiler output.
@@ -35,6 +38,70 @@ pub use error::*;
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Handler {
     pub diagnostics: IndexSet<Diagnostic>,
+    /// When set, the file paths in the emitted diagnostics are rendered relative
+    /// to this directory e.g., the program root. Absolute paths are rendered by default.
+    pub base_dir: Option<String>,
+    /// The format of the emitted diagnostics, the rich [ErrorFormat::Human] format by default.
+    pub error_format: ErrorFormat,
+    /// The callback invoked with each diagnostic added into the handler.
+    sink: DiagnosticSink,
+}
+
+/// The format of the diagnostics emitted by the [Handler].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Render each diagnostic with the code snippets of its messages over multiple lines.
+    #[default]
+    Human,
+    /// Render each diagnostic on one line `file:line:col: severity: message [code]`,
+    /// which is easy to pipe and grep.
+    Short,
+}
+
+impl ErrorFormat {
+    /// All the format names accepted by the `--error-format` option.
+    pub const NAMES: [&'static str; 2] = ["human", "short"];
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "human" => Ok(ErrorFormat::Human),
+            "short" => Ok(ErrorFormat::Short),
+            _ => Err(anyhow::anyhow!(
+                "Invalid error format '{name}', expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// The callback invoked with each diagnostic added into the [Handler].
+pub type DiagnosticCallback = Box<dyn FnMut(&Diagnostic) + Send + Sync>;
+
+/// An optional [DiagnosticCallback]. The callback is not cloned with the handler, and
+/// it is ignored when comparing the handlers.
+#[derive(Default)]
+struct DiagnosticSink(Option<DiagnosticCallback>);
+
+impl Clone for DiagnosticSink {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl PartialEq for DiagnosticSink {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DiagnosticSink {}
+
+impl std::fmt::Debug for DiagnosticSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DiagnosticSink")
+            .field(&self.0.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 impl Handler {
@@ -42,9 +109,106 @@ impl Handler {
     pub fn new() -> Self {
         Self {
             diagnostics: Default::default(),
+            base_dir: None,
+            error_format: ErrorFormat::default(),
+            sink: Default::default(),
         }
     }
 
+    /// Register the callback invoked whenever a new diagnostic is added into the handler,
+    /// which is useful to stream the diagnostics during a long compilation. The diagnostics
+    /// are still accumulated in [Handler::diagnostics], and a duplicated diagnostic is not
+    /// passed to the callback again.
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let received = Arc::new(Mutex::new(vec![]));
+    /// let mut handler = Handler::default();
+    /// let sink = received.clone();
+    /// handler.set_diagnostic_sink(Box::new(move |diag| sink.lock().unwrap().push(diag.clone())));
+    /// handler.add_error(ErrorKind::TypeError, &[Message {
+    ///     range: (Position::dummy_pos(), Position::dummy_pos()),
+    ///     style: Style::Line,
+    ///     message: "expected int".to_string(),
+    ///     note: None,
+    ///     suggested_replacement: None,
+    ///     ..Default::default()
+    /// }]);
+    /// assert_eq!(received.lock().unwrap().len(), 1);
+    /// assert_eq!(handler.diagnostics.len(), 1);
+    /// ```
+    #[inline]
+    pub fn set_diagnostic_sink(&mut self, callback: DiagnosticCallback) -> &mut Self {
+        self.sink = DiagnosticSink(Some(callback));
+        self
+    }
+
+    /// Remove the registered diagnostic callback and return it.
+    #[inline]
+    pub fn take_diagnostic_sink(&mut self) -> Option<DiagnosticCallback> {
+        self.sink.0.take()
+    }
+
+    /// Render the file paths in the emitted diagnostics relative to the base directory.
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    ///
+    /// let mut handler = Handler::default().with_base_dir("/work");
+    /// let pos = Position {
+    ///     filename: "/work/pkg/main.k".to_string(),
+    ///     line: 1,
+    ///     column: Some(0),
+    /// };
+    /// handler.add_error(ErrorKind::TypeError, &[Message {
+    ///     range: (pos.clone(), pos),
+    ///     style: Style::LineAndColumn,
+    ///     message: "expected int".to_string(),
+    ///     note: None,
+    ///     suggested_replacement: None,
+    ///     ..Default::default()
+    /// }]);
+    /// let msg = handler.emit_to_string().unwrap();
+    /// assert!(msg.contains("---> File pkg/main.k:1:1"), "{msg}");
+    /// ```
+    #[inline]
+    pub fn with_base_dir(mut self, base_dir: &str) -> Self {
+        self.base_dir = Some(base_dir.to_string());
+        self
+    }
+
+    /// Emit the diagnostics in the format, see [ErrorFormat].
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    ///
+    /// let mut handler = Handler::default().with_error_format(ErrorFormat::Short);
+    /// let pos = Position {
+    ///     filename: "main.k".to_string(),
+    ///     line: 2,
+    ///     column: Some(4),
+    /// };
+    /// handler.add_error(ErrorKind::TypeError, &[Message {
+    ///     range: (pos.clone(), pos),
+    ///     style: Style::LineAndColumn,
+    ///     message: "expected int, got str".to_string(),
+    ///     note: None,
+    ///     suggested_replacement: None,
+    ///     ..Default::default()
+    /// }]);
+    /// assert_eq!(
+    ///     handler.emit_to_string().unwrap(),
+    ///     "main.k:2:5: error: expected int, got str [E2G22]"
+    /// );
+    /// ```
+    #[inline]
+    pub fn with_error_format(mut self, error_format: ErrorFormat) -> Self {
+        self.error_format = error_format;
+        self
+    }
+
     /// Panic program and report a bug
     #[inline]
     pub fn bug(&self, msg: &str) -> ! {
@@ -60,9 +224,18 @@ impl Handler {
 
     /// Emit all diagnostics and return whether has errors.
     pub fn emit(&mut self) -> Result<bool> {
+        if self.error_format == ErrorFormat::Short {
+            for diag in &self.diagnostics {
+                eprintln!(
+                    "{}",
+                    DiagnosticWithBaseDir(diag.clone(), self.base_dir.clone()).short()
+                );
+            }
+            return Ok(self.has_errors());
+        }
         let sess = Session::default();
         for diag in &self.diagnostics {
-            sess.add_err(diag.clone())?;
+            sess.add_err(DiagnosticWithBaseDir(diag.clone(), self.base_dir.clone()))?;
         }
         sess.emit_stashed_diagnostics()?;
         Ok(self.has_errors())
@@ -70,9 +243,17 @@ impl Handler {
 
     /// Emit diagnostic to string.
     pub fn emit_to_string(&mut self) -> Result<String> {
+        if self.error_format == ErrorFormat::Short {
+            return Ok(self
+                .diagnostics
+                .iter()
+                .map(|diag| DiagnosticWithBaseDir(diag.clone(), self.base_dir.clone()).short())
+                .collect::<Vec<String>>()
+                .join("\n"));
+        }
         let sess = Session::default();
         for diag in &self.diagnostics {
-            sess.add_err(diag.clone())?;
+            sess.add_err(DiagnosticWithBaseDir(diag.clone(), self.base_dir.clone()))?;
         }
         let errors = sess.emit_all_diags_into_string()?;
         let mut error_strings = vec![];
@@ -82,6 +263,153 @@ impl Handler {
         Ok(error_strings.join("\n"))
     }
 
+    /// Emit all diagnostics grouped by file and return whether has errors. Unlike
+    /// [Handler::emit], the diagnostics are sorted by the file and then the position
+    /// of their first message and printed under a header line of each file, which
+    /// is easier to scan for many files. Diagnostics without any position e.g., the
+    /// suggestions are emitted at last.
+    pub fn emit_grouped(&mut self) -> Result<bool> {
+        for (filename, diags) in self.grouped_diagnostics() {
+            if let Some(filename) = filename {
+                eprintln!("{}", self.group_header(&filename));
+            }
+            let sess = Session::default();
+            for diag in diags {
+                sess.add_err(DiagnosticWithBaseDir(diag, self.base_dir.clone()))?;
+            }
+            sess.emit_stashed_diagnostics()?;
+        }
+        Ok(self.has_errors())
+    }
+
+    /// Emit all diagnostics grouped by file to string, see [Handler::emit_grouped].
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    ///
+    /// let mut handler = Handler::default();
+    /// for (filename, line) in [("b.k", 2), ("a.k", 3), ("b.k", 1)] {
+    ///     let pos = Position {
+    ///         filename: filename.to_string(),
+    ///         line,
+    ///         column: None,
+    ///     };
+    ///     handler.add_error(ErrorKind::TypeError, &[Message {
+    ///         range: (pos.clone(), pos),
+    ///         style: Style::Line,
+    ///         message: format!("error in {filename}:{line}"),
+    ///         note: None,
+    ///         suggested_replacement: None,
+    ///         ..Default::default()
+    ///     }]);
+    /// }
+    /// let msg = handler.emit_grouped_to_string().unwrap();
+    /// let order = ["==> a.k", "error in a.k:3", "==> b.k", "error in b.k:1", "error in b.k:2"]
+    ///     .map(|s| msg.find(s).unwrap());
+    /// assert!(order.windows(2).all(|w| w[0] < w[1]), "{msg}");
+    /// ```
+    pub fn emit_grouped_to_string(&mut self) -> Result<String> {
+        let mut group_strings = vec![];
+        for (filename, diags) in self.grouped_diagnostics() {
+            let sess = Session::default();
+            for diag in diags {
+                sess.add_err(DiagnosticWithBaseDir(diag, self.base_dir.clone()))?;
+            }
+            let mut error_strings = vec![];
+            if let Some(filename) = filename {
+                error_strings.push(self.group_header(&filename));
+            }
+            for error in sess.emit_all_diags_into_string()? {
+                error_strings.push(error?);
+            }
+            group_strings.push(error_strings.join("\n"));
+        }
+        Ok(group_strings.join("\n"))
+    }
+
+    /// Emit all diagnostics as a SARIF 2.1.0 log to the writer, which can be uploaded
+    /// to the code scanning dashboards. The error and warning kinds are mapped to the
+    /// rules, the message ranges to the regions and the suggested replacements to the fixes.
+    pub fn emit_sarif(&self, writer: &mut dyn Write) -> Result<()> {
+        let log = sarif::sarif_log(&self.diagnostics, &self.base_dir);
+        writeln!(writer, "{}", serde_json::to_string_pretty(&log)?)?;
+        Ok(())
+    }
+
+    /// Emit all diagnostics as a SARIF 2.1.0 log to string, see [Handler::emit_sarif].
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    ///
+    /// let mut handler = Handler::default().with_base_dir("/work");
+    /// let pos = |column| Position {
+    ///     filename: "/work/main.k".to_string(),
+    ///     line: 2,
+    ///     column: Some(column),
+    /// };
+    /// handler.add_error(ErrorKind::CompileError, &[Message {
+    ///     range: (pos(4), pos(8)),
+    ///     style: Style::LineAndColumn,
+    ///     message: "name 'nmae' is not defined".to_string(),
+    ///     note: None,
+    ///     suggested_replacement: Some(vec!["name".to_string()]),
+    ///     ..Default::default()
+    /// }]);
+    /// let log: serde_json::Value =
+    ///     serde_json::from_str(&handler.emit_sarif_to_string().unwrap()).unwrap();
+    /// let result = &log["runs"][0]["results"][0];
+    /// assert_eq!(log["version"], "2.1.0");
+    /// assert_eq!(log["runs"][0]["tool"]["driver"]["rules"][0]["id"], "CompileError");
+    /// assert_eq!(result["ruleId"], "CompileError");
+    /// assert_eq!(result["level"], "error");
+    /// let location = &result["locations"][0]["physicalLocation"];
+    /// assert_eq!(location["artifactLocation"]["uri"], "main.k");
+    /// assert_eq!(location["region"]["startColumn"], 5);
+    /// assert_eq!(location["region"]["endColumn"], 9);
+    /// let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    /// assert_eq!(replacement["insertedContent"]["text"], "name");
+    /// ```
+    pub fn emit_sarif_to_string(&self) -> Result<String> {
+        let mut buf = vec![];
+        self.emit_sarif(&mut buf)?;
+        Ok(String::from_utf8(buf)?)
+    }
+
+    /// Group the diagnostics by the file of their first message. The groups and the
+    /// diagnostics in each group are sorted by the file and the position with a
+    /// stable sort, and the diagnostics without any position are put in the last group.
+    fn grouped_diagnostics(&self) -> Vec<(Option<String>, Vec<Diagnostic>)> {
+        let mut diags: Vec<Diagnostic> = self.diagnostics.iter().cloned().collect();
+        let pos_key = |diag: &Diagnostic| {
+            diag.messages.first().map(|msg| {
+                let pos = &msg.range.0;
+                (
+                    pos.filename.clone(),
+                    pos.line,
+                    pos.column.unwrap_or_default(),
+                )
+            })
+        };
+        diags.sort_by(|a, b| match (pos_key(a), pos_key(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        });
+        let mut groups: Vec<(Option<String>, Vec<Diagnostic>)> = vec![];
+        for diag in diags {
+            let filename = pos_key(&diag).map(|(filename, _, _)| filename);
+            match groups.last_mut() {
+                Some((last, group)) if *last == filename => group.push(diag),
+                _ => groups.push((filename, vec![diag])),
+            }
+        }
+        groups
+    }
+
+    /// The header line of the diagnostics group of the file.
+    fn group_header(&self, filename: &str) -> String {
+        format!("==> {}", display_filename(filename, &self.base_dir))
+    }
+
     /// Emit all diagnostics and abort if has any errors.
     pub fn abort_if_any_errors(&mut self) {
         match self.emit() {
@@ -149,6 +477,28 @@ impl Handler {
         self
     }
 
+    /// Construct a compile error with the related ranges and their labels e.g., the
+    /// previous definition of a duplicate name, and put it into the handler diagnostic buffer.
+    pub fn add_compile_error_with_related(
+        &mut self,
+        msg: &str,
+        range: Range,
+        related: Vec<(Range, String)>,
+    ) -> &mut Self {
+        let mut diag = Diagnostic::new_with_code(
+            Level::Error,
+            msg,
+            None,
+            range,
+            Some(DiagnosticId::Error(E2L23.kind)),
+            None,
+        );
+        diag.messages[0].related = related;
+        self.add_diagnostic(diag);
+
+        self
+    }
+
     /// Put a runtime panic info the handler diagnostic buffer.
     pub fn add_panic_info(&mut self, panic_info: &PanicInfo) -> &mut Self {
         self.add_diagnostic(panic_info.clone().into());
@@ -167,6 +517,7 @@ impl Handler {
     ///         message: "Invalid syntax: expected '+', got '-'".to_string(),
     ///         note: None,
     ///         suggested_replacement: None,
+    ///         ..Default::default()
     ///     }
     /// ]);
     /// ```
@@ -191,6 +542,7 @@ impl Handler {
                     message: s.to_string(),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
                 code: Some(DiagnosticId::Suggestions),
             });
@@ -210,6 +562,7 @@ impl Handler {
     ///         message: "Module 'a' imported but unused.".to_string(),
     ///         note: None,
     ///         suggested_replacement: None,
+    ///         ..Default::default()
     ///     }],
     /// );
     /// ```
@@ -239,6 +592,63 @@ impl Handler {
         (errs, warnings)
     }
 
+    /// Collect all diagnostic messages that carry suggested replacements.
+    /// A message is machine applicable when it has a valid range and exactly
+    /// one replacement. Diagnostics of [Level::Suggestions] are collected as
+    /// informational entries without replacements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    /// let mut handler = Handler::default();
+    /// let pos = Position {
+    ///     filename: "main.k".to_string(),
+    ///     line: 1,
+    ///     column: Some(0),
+    /// };
+    /// handler.add_error(ErrorKind::CompileError, &[Message {
+    ///     range: (pos.clone(), pos),
+    ///     style: Style::LineAndColumn,
+    ///     message: "name 'nmae' is not defined".to_string(),
+    ///     note: None,
+    ///     suggested_replacement: Some(vec!["name".to_string()]),
+    ///     ..Default::default()
+    /// }]);
+    /// handler.add_suggestions(vec!["try 'kcl mod add k8s'".to_string()]);
+    /// let fixes = handler.collect_fixable_diagnostics();
+    /// assert_eq!(fixes.len(), 2);
+    /// assert!(fixes[0].machine_applicable);
+    /// assert_eq!(fixes[0].replacements, vec!["name".to_string()]);
+    /// assert!(!fixes[1].machine_applicable);
+    /// ```
+    pub fn collect_fixable_diagnostics(&self) -> Vec<FixableDiagnostic> {
+        let mut fixes = vec![];
+        for diag in &self.diagnostics {
+            for msg in &diag.messages {
+                let informational = diag.level == Level::Suggestions;
+                let replacements = match &msg.suggested_replacement {
+                    Some(replacements) if !replacements.is_empty() => replacements.clone(),
+                    _ if informational => vec![],
+                    _ => continue,
+                };
+                let machine_applicable = !informational
+                    && replacements.len() == 1
+                    && !msg.range.0.filename.is_empty()
+                    && msg.range.0.is_valid();
+                fixes.push(FixableDiagnostic {
+                    level: diag.level,
+                    code: diag.code.clone(),
+                    range: msg.range.clone(),
+                    message: msg.message.clone(),
+                    replacements,
+                    machine_applicable,
+                });
+            }
+        }
+        fixes
+    }
+
     /// Store a diagnostics into the handler.
     ///
     /// # Example
@@ -250,6 +660,11 @@ impl Handler {
     /// ```
     #[inline]
     pub fn add_diagnostic(&mut self, diagnostic: Diagnostic) -> &mut Self {
+        if let Some(callback) = &mut self.sink.0 {
+            if !self.diagnostics.contains(&diagnostic) {
+                callback(&diagnostic);
+            }
+        }
         self.diagnostics.insert(diagnostic);
 
         self
@@ -348,6 +763,12 @@ pub enum ParseErrorMessage {
     InvalidJoinedStringSpec,
     #[error("invalid joined string")]
     InvalidJoinedStringExpr,
+    #[error("the check block must be at the end of the schema body, consider moving it after the following statements")]
+    CheckBlockNotAtEnd,
+    #[error("the check block must be indented in the schema body")]
+    UnindentedCheckBlock,
+    #[error("the check block must be at the same indentation as the schema attributes")]
+    OverIndentedCheckBlock,
 }
 
 #[derive(Debug, Clone)]
@@ -473,9 +894,147 @@ impl Component<DiagnosticStyle> for SuggestionsLabel {
 }
 
 impl SessionDiagnostic for Diagnostic {
+    #[inline]
+    fn into_diagnostic(self, sess: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
+        DiagnosticWithBaseDir(self, None).into_diagnostic(sess)
+    }
+}
+
+/// Get the file path relative to the base directory, or the file path itself
+/// when it is not under the base directory.
+fn display_filename(filename: &str, base_dir: &Option<String>) -> String {
+    match base_dir {
+        Some(base_dir) => Path::new(filename)
+            .strip_prefix(base_dir)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| filename.to_string()),
+        None => filename.to_string(),
+    }
+}
+
+/// A diagnostic whose file paths are rendered relative to the base directory if any.
+struct DiagnosticWithBaseDir(Diagnostic, Option<String>);
+
+impl DiagnosticWithBaseDir {
+    #[inline]
+    fn display_filename(&self, filename: &str) -> String {
+        display_filename(filename, &self.1)
+    }
+
+    fn display_info(&self, pos: &Position) -> String {
+        Position {
+            filename: self.display_filename(&pos.filename),
+            ..pos.clone()
+        }
+        .info()
+    }
+
+    /// Render the diagnostic on one line `file:line:col: severity: message [code]` with
+    /// its first message, and the position or the code is omitted when absent.
+    fn short(&self) -> String {
+        let mut line = String::new();
+        let msg = self.0.messages.first();
+        if let Some(msg) = msg {
+            let pos = &msg.range.0;
+            if !pos.filename.is_empty() {
+                line += &format!("{}:{}", self.display_filename(&pos.filename), pos.line);
+                if let Some(column) = pos.column {
+                    line += &format!(":{}", column + 1);
+                }
+                line += ": ";
+            }
+        }
+        line += self.0.level.to_str();
+        if let Some(msg) = msg {
+            // The multi-line messages e.g., the runtime errors are joined into one line.
+            line += ": ";
+            line += &msg.message.lines().collect::<Vec<&str>>().join(" ");
+        }
+        match &self.0.code {
+            Some(DiagnosticId::Error(kind)) => line += &format!(" [{}]", kind.code()),
+            Some(DiagnosticId::Warning(kind)) => line += &format!(" [{}]", kind.code()),
+            Some(DiagnosticId::Suggestions) | None => {}
+        }
+        line
+    }
+
+    /// Display the source line at the start of the range with the annotation label, or
+    /// the position info with the label when the source is not available.
+    fn display_snippet(
+        &self,
+        range: &Range,
+        label: &str,
+        annotation_type: AnnotationType,
+    ) -> String {
+        let display_info = || {
+            let info = self.display_info(&range.0);
+            if !info.is_empty() {
+                format!("{}: {}\n", info, label)
+            } else {
+                format!("{}\n", label)
+            }
+        };
+        let filename = self.display_filename(&range.0.filename);
+        match Session::new_with_file_and_code(&range.0.filename, None) {
+            Ok(sess) => {
+                let source = sess.sm.lookup_source_file(new_byte_pos(0));
+                let line = source.get_line(
+                    (if range.0.line >= 1 {
+                        range.0.line - 1
+                    } else {
+                        0
+                    }) as usize,
+                );
+                match line.as_ref() {
+                    Some(content) => {
+                        let length = content.chars().count();
+                        let snippet = Snippet {
+                            title: None,
+                            footer: vec![],
+                            slices: vec![Slice {
+                                source: content,
+                                line_start: range.0.line as usize,
+                                origin: Some(&filename),
+                                annotations: vec![SourceAnnotation {
+                                    range: match range.0.column {
+                                        Some(column) if length >= 1 => {
+                                            let column = column as usize;
+                                            // If the position exceeds the length of the content,
+                                            // put the annotation at the end of the line.
+                                            if column >= length {
+                                                (length - 1, length)
+                                            } else {
+                                                (column, column + 1)
+                                            }
+                                        }
+                                        _ => (0, 0),
+                                    },
+                                    label,
+                                    annotation_type,
+                                }],
+                                fold: true,
+                            }],
+                            opt: FormatOptions {
+                                color: true,
+                                anonymized_line_numbers: false,
+                                margin: None,
+                            },
+                        };
+                        let dl = DisplayList::from(snippet);
+                        format!("{dl}\n")
+                    }
+                    None => display_info(),
+                }
+            }
+            Err(_) => display_info(),
+        }
+    }
+}
+
+impl SessionDiagnostic for DiagnosticWithBaseDir {
     fn into_diagnostic(self, _: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
         let mut diag = DiagnosticTrait::<DiagnosticStyle>::new();
-        match self.code {
+        match self.0.code.clone() {
             Some(id) => match id {
                 DiagnosticId::Error(error) => {
                     diag.append_component(Box::new(Label::Error(error.code())));
@@ -489,7 +1048,7 @@ impl SessionDiagnostic for Diagnostic {
                     diag.append_component(Box::new(SuggestionsLabel));
                 }
             },
-            None => match self.level {
+            None => match self.0.level {
                 Level::Error => {
                     diag.append_component(Box::new(format!("{}\n", ErrorKind::EvaluationError)));
                 }
@@ -504,81 +1063,26 @@ impl SessionDiagnostic for Diagnostic {
                 }
             },
         }
-        for msg in &self.messages {
-            match Session::new_with_file_and_code(&msg.range.0.filename, None) {
-                Ok(sess) => {
-                    let source = sess.sm.lookup_source_file(new_byte_pos(0));
-                    let line = source.get_line(
-                        (if msg.range.0.line >= 1 {
-                            msg.range.0.line - 1
-                        } else {
-                            0
-                        }) as usize,
-                    );
-                    match line.as_ref() {
-                        Some(content) => {
-                            let length = content.chars().count();
-                            let snippet = Snippet {
-                                title: None,
-                                footer: vec![],
-                                slices: vec![Slice {
-                                    source: content,
-                                    line_start: msg.range.0.line as usize,
-                                    origin: Some(&msg.range.0.filename),
-                                    annotations: vec![SourceAnnotation {
-                                        range: match msg.range.0.column {
-                                            Some(column) if length >= 1 => {
-                                                let column = column as usize;
-                                                // If the position exceeds the length of the content,
-                                                // put the annotation at the end of the line.
-                                                if column >= length {
-                                                    (length - 1, length)
-                                                } else {
-                                                    (column, column + 1)
-                                                }
-                                            }
-                                            _ => (0, 0),
-                                        },
-                                        label: &msg.message,
-                                        annotation_type: AnnotationType::Error,
-                                    }],
-                                    fold: true,
-                                }],
-                                opt: FormatOptions {
-                                    color: true,
-                                    anonymized_line_numbers: false,
-                                    margin: None,
-                                },
-                            };
-                            let dl = DisplayList::from(snippet);
-                            diag.append_component(Box::new(format!("{dl}\n")));
-                        }
-                        None => {
-                            let info = msg.range.0.info();
-                            if !info.is_empty() {
-                                diag.append_component(Box::new(format!(
-                                    "{}: {}\n",
-                                    info, msg.message
-                                )));
-                            } else {
-                                diag.append_component(Box::new(format!("{}\n", msg.message)));
-                            }
-                        }
-                    };
-                }
-                Err(_) => {
-                    let info = msg.range.0.info();
-                    if !info.is_empty() {
-                        diag.append_component(Box::new(format!("{}: {}\n", info, msg.message)));
-                    } else {
-                        diag.append_component(Box::new(format!("{}\n", msg.message)));
-                    }
-                }
-            };
+        for msg in &self.0.messages {
+            diag.append_component(Box::new(self.display_snippet(
+                &msg.range,
+                &msg.message,
+                AnnotationType::Error,
+            )));
             if let Some(note) = &msg.note {
                 diag.append_component(Box::new(Label::Note));
                 diag.append_component(Box::new(format!(": {note}\n")));
             }
+            // The label of the related range is rendered once with its source line.
+            for (range, label) in &msg.related {
+                diag.append_component(Box::new(Label::Note));
+                diag.append_component(Box::new(String::from(":\n")));
+                diag.append_component(Box::new(self.display_snippet(
+                    range,
+                    label,
+                    AnnotationType::Note,
+                )));
+            }
             // Append a new line.
             diag.append_component(Box::new(String::from("\n")));
         }
@@ -0,0 +1,202 @@
+//! Serialize the diagnostics into the SARIF 2.1.0 log, which is the standard format
+//! of the static analysis results consumed by the code scanning dashboards.
+//!
+//! Reference: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use crate::{display_filename, Diagnostic, DiagnosticId, Level, Position, Range};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "kcl";
+const TOOL_INFORMATION_URI: &str = "https://kcl-lang.io";
+
+/// Build the SARIF log of the diagnostics with one run. The error and warning kinds
+/// are mapped to the rules of the tool driver, and the file paths are rendered
+/// relative to the base directory if any.
+pub(crate) fn sarif_log<'a>(
+    diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+    base_dir: &Option<String>,
+) -> Value {
+    let mut rules: IndexMap<String, Value> = IndexMap::new();
+    let mut results = vec![];
+    for diag in diagnostics {
+        let mut result = json!({
+            "level": sarif_level(diag.level),
+        });
+        if let Some((id, code)) = rule_id_and_code(&diag.code) {
+            let rule_index = match rules.get_index_of(&id) {
+                Some(index) => index,
+                None => {
+                    rules.insert(
+                        id.clone(),
+                        json!({
+                            "id": id,
+                            "properties": {"code": code},
+                        }),
+                    );
+                    rules.len() - 1
+                }
+            };
+            result["ruleId"] = json!(id);
+            result["ruleIndex"] = json!(rule_index);
+        }
+        let mut messages = diag.messages.iter();
+        if let Some(msg) = messages.next() {
+            let text = match &msg.note {
+                Some(note) => format!("{}\n{}", msg.message, note),
+                None => msg.message.clone(),
+            };
+            result["message"] = json!({ "text": text });
+            if let Some(location) = sarif_location(&msg.range, base_dir) {
+                result["locations"] = json!([location]);
+            }
+        } else {
+            result["message"] = json!({ "text": "" });
+        }
+        // The following messages and the related ranges of all messages are the related
+        // locations of the result.
+        let related_locations: Vec<Value> = messages
+            .map(|msg| (&msg.range, &msg.message))
+            .chain(
+                diag.messages
+                    .iter()
+                    .flat_map(|msg| msg.related.iter().map(|(range, label)| (range, label))),
+            )
+            .filter_map(|(range, text)| {
+                let mut location = sarif_location(range, base_dir)?;
+                location["message"] = json!({ "text": text });
+                Some(location)
+            })
+            .collect();
+        if !related_locations.is_empty() {
+            result["relatedLocations"] = json!(related_locations);
+        }
+        let fixes = sarif_fixes(diag, base_dir);
+        if !fixes.is_empty() {
+            result["fixes"] = json!(fixes);
+        }
+        results.push(result);
+    }
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": TOOL_INFORMATION_URI,
+                    "rules": rules.into_values().collect::<Vec<Value>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// The rule id is the error or warning kind name e.g., `TypeError`, and the error
+/// code e.g., `E2G22` is recorded in the rule properties.
+fn rule_id_and_code(code: &Option<DiagnosticId>) -> Option<(String, String)> {
+    match code {
+        Some(DiagnosticId::Error(kind)) => Some((kind.name(), kind.code())),
+        Some(DiagnosticId::Warning(kind)) => Some((kind.name(), kind.code())),
+        Some(DiagnosticId::Suggestions) | None => None,
+    }
+}
+
+#[inline]
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::Suggestions => "note",
+    }
+}
+
+/// The diagnostic suggested replacements of the messages, each replacement is a fix
+/// which replaces the source text in the message range.
+fn sarif_fixes(diag: &Diagnostic, base_dir: &Option<String>) -> Vec<Value> {
+    if diag.level == Level::Suggestions {
+        return vec![];
+    }
+    let mut fixes = vec![];
+    for msg in &diag.messages {
+        let replacements = match &msg.suggested_replacement {
+            Some(replacements) => replacements,
+            None => continue,
+        };
+        let region = match sarif_region(&msg.range) {
+            Some(region) if !msg.range.0.filename.is_empty() => region,
+            _ => continue,
+        };
+        for replacement in replacements {
+            fixes.push(json!({
+                "description": {"text": format!("replace with '{}'", replacement)},
+                "artifactChanges": [{
+                    "artifactLocation": {"uri": sarif_uri(&msg.range.0.filename, base_dir)},
+                    "replacements": [{
+                        "deletedRegion": region,
+                        "insertedContent": {"text": replacement},
+                    }],
+                }],
+            }));
+        }
+    }
+    fixes
+}
+
+fn sarif_location(range: &Range, base_dir: &Option<String>) -> Option<Value> {
+    let (start, _) = range;
+    if start.filename.is_empty() {
+        return None;
+    }
+    let mut physical_location = json!({
+        "artifactLocation": {"uri": sarif_uri(&start.filename, base_dir)},
+    });
+    if let Some(region) = sarif_region(range) {
+        physical_location["region"] = region;
+    }
+    Some(json!({ "physicalLocation": physical_location }))
+}
+
+/// The SARIF line and column numbers are both 1-based, while the column of the
+/// diagnostic position is 0-based.
+fn sarif_region(range: &Range) -> Option<Value> {
+    let (start, end) = range;
+    if !start.is_valid() {
+        return None;
+    }
+    let mut region = json!({ "startLine": start.line });
+    if let Some(column) = start.column {
+        region["startColumn"] = json!(column + 1);
+    }
+    if is_valid_end(start, end) {
+        region["endLine"] = json!(end.line);
+        if let Some(column) = end.column {
+            region["endColumn"] = json!(column + 1);
+        }
+    }
+    Some(region)
+}
+
+#[inline]
+fn is_valid_end(start: &Position, end: &Position) -> bool {
+    end.is_valid() && end.filename == start.filename && start.less_equal(end)
+}
+
+/// The relative paths are emitted as the relative URI references, and the absolute
+/// paths are emitted as the file URIs.
+fn sarif_uri(filename: &str, base_dir: &Option<String>) -> String {
+    let filename = display_filename(filename, base_dir).replace('\\', "/");
+    if filename.starts_with('/') {
+        format!("file://{}", filename)
+    } else if filename.chars().nth(1) == Some(':') {
+        // Windows absolute path e.g., `C:/work/main.k`.
+        format!("file:///{}", filename)
+    } else {
+        filename
+    }
+}
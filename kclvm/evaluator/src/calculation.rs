@@ -64,13 +64,17 @@ impl<'ctx> Evaluator<'ctx> {
         if let (Value::int_value(a), Value::int_value(b)) = (&*lhs.rc.borrow(), &*rhs.rc.borrow()) {
             return ValueRef::int(*a | *b);
         };
-        union_entry(
+        let value = union_entry(
             self,
             &mut lhs.deep_copy(),
             &rhs,
             true,
             &UnionOptions::default(),
-        )
+        );
+        if value.is_list_or_config() {
+            self.runtime_ctx.borrow_mut().check_collection_len(value.len());
+        }
+        value
     }
     /// lhs ^ rhs
     #[inline]
@@ -189,12 +193,14 @@ impl<'ctx> Evaluator<'ctx> {
     /// Append a item into the list.
     #[inline]
     pub(crate) fn list_append(&self, list: &mut ValueRef, item: &ValueRef) {
-        list.list_append(item)
+        list.list_append(item);
+        self.runtime_ctx.borrow_mut().check_collection_len(list.len());
     }
     /// Append a list item and unpack it into the list.
     #[inline]
     pub(crate) fn list_append_unpack(&self, list: &mut ValueRef, item: &ValueRef) {
-        list.list_append_unpack(item)
+        list.list_append_unpack(item);
+        self.runtime_ctx.borrow_mut().check_collection_len(list.len());
     }
     #[inline]
     pub(crate) fn dict_get_value(&self, dict: &ValueRef, key: &str) -> ValueRef {
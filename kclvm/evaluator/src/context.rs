@@ -207,7 +207,9 @@ impl<'ctx> Evaluator<'ctx> {
     #[inline]
     pub(crate) fn push_pkgpath(&self, pkgpath: &str) {
         self.pkgpath_stack.borrow_mut().push(pkgpath.to_string());
-        self.runtime_ctx.borrow_mut().set_kcl_pkgpath(pkgpath);
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        ctx.set_kcl_pkgpath(pkgpath);
+        ctx.set_pkg_strict_range_check(pkgpath);
     }
 
     #[inline]
@@ -215,6 +217,12 @@ impl<'ctx> Evaluator<'ctx> {
         if let Some(pkgpath) = self.pkgpath_stack.borrow_mut().pop() {
             self.runtime_ctx.borrow_mut().set_kcl_pkgpath(&pkgpath);
         }
+        // Restore the package options of the caller package.
+        if let Some(pkgpath) = self.pkgpath_stack.borrow().last() {
+            self.runtime_ctx
+                .borrow_mut()
+                .set_pkg_strict_range_check(pkgpath);
+        }
     }
 
     /// Append a global body into the scope.
@@ -259,6 +267,15 @@ impl<'ctx> Evaluator<'ctx> {
 
     pub(crate) fn push_backtrace(&self, frame: &Frame) {
         let ctx = &mut self.runtime_ctx.borrow_mut();
+        *self.call_depth.borrow_mut() += 1;
+        if let Some(max_depth) = ctx.cfg.max_depth {
+            if *self.call_depth.borrow() > max_depth {
+                panic!(
+                    "maximum call depth {max_depth} exceeded when calling '{}'",
+                    frame.proxy.get_name()
+                );
+            }
+        }
         if ctx.cfg.debug_mode {
             let backtrace_frame = BacktraceFrame::from_panic_info(&ctx.panic_info);
             ctx.backtrace.push(backtrace_frame);
@@ -268,6 +285,7 @@ impl<'ctx> Evaluator<'ctx> {
 
     pub(crate) fn pop_backtrace(&self) {
         let ctx = &mut self.runtime_ctx.borrow_mut();
+        *self.call_depth.borrow_mut() -= 1;
         if ctx.cfg.debug_mode {
             if let Some(backtrace_frame) = ctx.backtrace.pop() {
                 ctx.panic_info.kcl_func = backtrace_frame.func;
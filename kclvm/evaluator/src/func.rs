@@ -8,6 +8,7 @@ use kclvm_runtime::ValueRef;
 use scopeguard::defer;
 
 use crate::proxy::Proxy;
+use crate::source_map::SourceScope;
 use crate::ty::type_pack_and_check;
 use crate::Evaluator;
 use crate::{error as kcl_error, EvalContext};
@@ -29,6 +30,81 @@ pub struct FunctionEvalContext {
     pub closure: ClosureMap,
     /// The scope level of the function definition.
     pub level: usize,
+    /// The arguments bound by the partial application keyed by the parameter names.
+    pub bound_args: IndexMap<String, ValueRef>,
+}
+
+impl FunctionEvalContext {
+    /// Get the parameter names and whether each parameter has a default value.
+    fn params(&self) -> Vec<(String, bool)> {
+        match &self.node.args {
+            Some(args) => args
+                .node
+                .args
+                .iter()
+                .zip(&args.node.defaults)
+                .map(|(arg, default)| (arg.node.get_name(), default.is_some()))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Bind the call arguments to the parameter names after the arguments bound by the
+    /// partial application, in which the positional arguments are bound to the unbound
+    /// parameters in order. It also returns whether any required parameter is still
+    /// unbound i.e., the call can be a partial application.
+    fn bind_arguments(
+        &self,
+        args: &ValueRef,
+        kwargs: &ValueRef,
+    ) -> (IndexMap<String, ValueRef>, bool) {
+        let params = self.params();
+        let mut bound_args = self.bound_args.clone();
+        let unbound_params: Vec<&String> = params
+            .iter()
+            .map(|(name, _)| name)
+            .filter(|name| !bound_args.contains_key(*name))
+            .collect();
+        let args = args.as_list_ref();
+        if args.values.len() > unbound_params.len() {
+            panic!(
+                "lambda takes {} positional arguments but {} were given",
+                unbound_params.len(),
+                args.values.len()
+            );
+        }
+        for (name, value) in unbound_params.into_iter().zip(&args.values) {
+            bound_args.insert(name.to_string(), value.clone());
+        }
+        for (name, value) in &kwargs.as_dict_ref().values {
+            bound_args.insert(name.to_string(), value.clone());
+        }
+        let is_partial = params
+            .iter()
+            .any(|(name, has_default)| !has_default && !bound_args.contains_key(name));
+        (bound_args, is_partial)
+    }
+
+    /// Convert the bound arguments into the positional arguments of the leading bound
+    /// parameters and the keyword arguments of the others, thus the positional arguments
+    /// are still type checked.
+    fn bound_arguments_value(
+        &self,
+        mut bound_args: IndexMap<String, ValueRef>,
+    ) -> (ValueRef, ValueRef) {
+        let mut args = ValueRef::list(None);
+        for (name, _) in self.params() {
+            match bound_args.shift_remove(&name) {
+                Some(value) => args.list_append(&value),
+                None => break,
+            }
+        }
+        let mut kwargs = ValueRef::dict(None);
+        for (name, value) in bound_args {
+            kwargs.dict_update_key_value(&name, value);
+        }
+        (args, kwargs)
+    }
 }
 
 #[derive(Clone)]
@@ -82,12 +158,17 @@ impl FunctionCaller {
 }
 
 impl<'ctx> Evaluator<'ctx> {
+    /// Invoke the function, schema or rule of the proxy. A lambda called without all the
+    /// required arguments is only partially applied when `allow_partial` is set, i.e., the
+    /// call is marked as a partial application by the resolver, otherwise the missing
+    /// argument error is raised.
     #[inline]
     pub(crate) fn invoke_proxy_function(
         &'ctx self,
         proxy_index: Index,
         args: &ValueRef,
         kwargs: &ValueRef,
+        allow_partial: bool,
     ) -> ValueRef {
         let frame = {
             let frames = self.frames.borrow();
@@ -109,11 +190,26 @@ impl<'ctx> Evaluator<'ctx> {
         let value = match &frame.proxy {
             // Call a function and return the value
             Proxy::Lambda(lambda) => {
+                let (args, kwargs) = if allow_partial || !lambda.ctx.bound_args.is_empty() {
+                    let (bound_args, is_partial) = lambda.ctx.bind_arguments(args, kwargs);
+                    if allow_partial && is_partial {
+                        // Calling a lambda without all the required arguments returns a new
+                        // lambda with the given arguments bound i.e., the partial application.
+                        let mut ctx = lambda.ctx.as_ref().clone();
+                        ctx.bound_args = bound_args;
+                        let index =
+                            self.add_function(FunctionCaller::new(ctx, lambda.body.clone()));
+                        return self.proxy_function_value(index);
+                    }
+                    lambda.ctx.bound_arguments_value(bound_args)
+                } else {
+                    (args.clone(), kwargs.clone())
+                };
                 // Push the current lambda scope level in the lambda stack.
                 let pkgpath = self.current_pkgpath();
                 let level = self.scope_level();
                 self.push_lambda(lambda.ctx.clone(), &pkgpath, &frame.pkgpath, level);
-                let value = (lambda.body)(self, &lambda.ctx, args, kwargs);
+                let value = (lambda.body)(self, &lambda.ctx, &args, &kwargs);
                 self.pop_lambda(lambda.ctx.clone(), &pkgpath, &frame.pkgpath, level);
                 value
             }
@@ -144,7 +240,9 @@ pub fn func_body(
     kwargs: &ValueRef,
 ) -> ValueRef {
     s.enter_scope();
+    s.push_source_scope(SourceScope::Lambda);
     defer! {
+        s.pop_source_scope();
         s.leave_scope();
     }
     // Evaluate arguments and keyword arguments and store values to local variables.
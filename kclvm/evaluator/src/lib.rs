@@ -16,6 +16,7 @@ mod rule;
 mod runtime;
 mod schema;
 mod scope;
+mod source_map;
 mod ty;
 mod union;
 mod value;
@@ -31,6 +32,7 @@ use proxy::{Frame, Proxy};
 use rule::RuleEvalContextRef;
 use schema::SchemaEvalContextRef;
 use scope::Scope;
+pub use source_map::{SourceMap, SourceMapRecorder};
 use std::collections::{HashMap, HashSet};
 use std::panic::RefUnwindSafe;
 use std::rc::Rc;
@@ -40,7 +42,7 @@ use std::{cell::RefCell, panic::UnwindSafe};
 use crate::error as kcl_error;
 use anyhow::Result;
 use kclvm_ast::ast::{self, AstIndex};
-use kclvm_runtime::{Context, ValueRef};
+use kclvm_runtime::{Context, ValueRef, PKG_PATH_PREFIX};
 
 /// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
 const SCALAR_KEY: &str = "";
@@ -91,6 +93,17 @@ pub struct Evaluator<'ctx> {
     pub backtrack_meta: RefCell<Vec<BacktrackMeta>>,
     /// Current AST id for the evaluator walker.
     pub ast_id: RefCell<AstIndex>,
+    /// Current depth of the function, schema and rule calls.
+    pub call_depth: RefCell<usize>,
+    /// The AST ids of the callee expressions of the calls marked by the resolver as the
+    /// partial application of lambdas.
+    pub partial_calls: HashSet<AstIndex>,
+    /// The source map recorder, the source map is recorded during the evaluation only
+    /// when it is set, see [Evaluator::source_map].
+    pub source_map_recorder: Option<RefCell<SourceMapRecorder>>,
+    /// The package whose top-level variables are output by [Evaluator::run], which is
+    /// the main package by default.
+    pub output_pkgpath: String,
 }
 
 #[derive(Clone)]
@@ -156,15 +169,64 @@ impl<'ctx> Evaluator<'ctx> {
             backtrack_meta: RefCell::new(Default::default()),
             ast_id: RefCell::new(AstIndex::default()),
             ctx_stack: RefCell::new(Default::default()),
+            call_depth: RefCell::new(0),
+            partial_calls: Default::default(),
+            source_map_recorder: None,
+            output_pkgpath: kclvm_ast::MAIN_PKG.to_string(),
         }
     }
 
-    /// Evaluate the program and return the JSON and YAML result.
+    /// Evaluate the program and return the JSON and YAML result of the output package.
+    /// The output package other than the main package is evaluated under its own package
+    /// path as it is imported, and the main package is not evaluated.
     pub fn run(self: &Evaluator<'ctx>) -> Result<(String, String)> {
+        let pkgpath = self.output_pkgpath.as_str();
+        let modules = self.program.get_modules_for_pkg(pkgpath);
+        if pkgpath == kclvm_ast::MAIN_PKG {
+            self.init_scope(pkgpath);
+            self.compile_ast_modules(&modules);
+            return Ok(self.plan_globals_to_string());
+        }
+        let prefixed_pkgpath = format!("{}{}", PKG_PATH_PREFIX, pkgpath);
+        self.push_pkgpath(&prefixed_pkgpath);
+        self.init_scope(&prefixed_pkgpath);
+        self.compile_ast_modules(&modules);
+        self.mark_imported(pkgpath);
+        let result = self.plan_globals_to_string();
+        self.pop_pkgpath();
+        Ok(result)
+    }
+
+    /// Evaluate the program and return the value at the dotted path of the output
+    /// e.g., `c.spec.replicas` or `c.items.0.name`, which returns an error when the
+    /// path does not exist.
+    pub fn eval_path(self: &Evaluator<'ctx>, path: &str) -> Result<ValueRef> {
         let modules = self.program.get_modules_for_pkg(kclvm_ast::MAIN_PKG);
         self.init_scope(kclvm_ast::MAIN_PKG);
         self.compile_ast_modules(&modules);
-        Ok(self.plan_globals_to_string())
+        let (name, attr_path) = match path.split_once('.') {
+            Some((name, attr_path)) => (name, Some(attr_path)),
+            None => (path, None),
+        };
+        let current_pkgpath = self.current_pkgpath();
+        let value = self
+            .pkg_scopes
+            .borrow()
+            .get(&current_pkgpath)
+            .and_then(|scopes| scopes.last())
+            .and_then(|scope| scope.variables.get(name).cloned());
+        let value = match (value, attr_path) {
+            (Some(value), Some(attr_path)) => value.get_by_path(attr_path),
+            (value, None) => value,
+            (None, _) => None,
+        };
+        match value {
+            Some(value) if !value.is_undefined() => Ok(value),
+            _ => Err(anyhow::anyhow!(
+                "path '{}' is not found in the output",
+                path
+            )),
+        }
     }
 
     /// Evaluate the program with the function mode and return the JSON and YAML result,
@@ -239,5 +301,12 @@ impl<'ctx> Evaluator<'ctx> {
     }
 }
 
+/// Evaluate the program and return the value at the dotted path of the output
+/// e.g., `c.spec.replicas` or `c.items.0.name`.
+#[inline]
+pub fn eval_path(program: &ast::Program, path: &str) -> Result<ValueRef> {
+    Evaluator::new(program).eval_path(path)
+}
+
 impl UnwindSafe for Evaluator<'_> {}
 impl RefUnwindSafe for Evaluator<'_> {}
@@ -7,10 +7,11 @@ use std::sync::{Arc, RwLock};
 use anyhow::Ok;
 use generational_arena::Index;
 use kclvm_ast::ast::{self, CallExpr, ConfigEntry, Module, NodeRef};
+use kclvm_ast::pos::GetPos;
 use kclvm_ast::walker::TypedResultWalker;
 use kclvm_runtime::{
-    schema_assert, schema_runtime_type, ConfigEntryOperationKind, DecoratorValue, RuntimeErrorType,
-    UnionOptions, ValueRef, PKG_PATH_PREFIX,
+    schema_assert, schema_runtime_type, AssertionRecord, ConfigEntryOperationKind, DecoratorValue,
+    RuntimeErrorType, UnionOptions, ValueRef, PKG_PATH_PREFIX,
 };
 use kclvm_sema::{builtin, pkgpath_without_prefix, plugin};
 use scopeguard::defer;
@@ -22,6 +23,7 @@ use crate::proxy::Proxy;
 use crate::rule::{rule_body, rule_check, RuleCaller, RuleEvalContext};
 use crate::runtime::invoke_function;
 use crate::schema::{schema_body, schema_check, SchemaCaller, SchemaEvalContext};
+use crate::source_map::SourceScope;
 use crate::ty::type_pack_and_check;
 use crate::union::union_entry;
 use crate::{backtrack_break_here, backtrack_update_break};
@@ -43,15 +45,20 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         let value = match &stmt.node {
             ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
             ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
-            ast::Stmt::Unification(unification_stmt) => {
-                self.walk_unification_stmt(unification_stmt)
+            ast::Stmt::Unification(unification_stmt) => self
+                .walk_stmt_with_source(stmt, || self.walk_unification_stmt(unification_stmt)),
+            ast::Stmt::Assign(assign_stmt) => {
+                self.walk_stmt_with_source(stmt, || self.walk_assign_stmt(assign_stmt))
+            }
+            ast::Stmt::AugAssign(aug_assign_stmt) => {
+                self.walk_stmt_with_source(stmt, || self.walk_aug_assign_stmt(aug_assign_stmt))
             }
-            ast::Stmt::Assign(assign_stmt) => self.walk_assign_stmt(assign_stmt),
-            ast::Stmt::AugAssign(aug_assign_stmt) => self.walk_aug_assign_stmt(aug_assign_stmt),
             ast::Stmt::Assert(assert_stmt) => self.walk_assert_stmt(assert_stmt),
             ast::Stmt::If(if_stmt) => self.walk_if_stmt(if_stmt),
             ast::Stmt::Import(import_stmt) => self.walk_import_stmt(import_stmt),
-            ast::Stmt::SchemaAttr(schema_attr) => self.walk_schema_attr(schema_attr),
+            ast::Stmt::SchemaAttr(schema_attr) => {
+                self.walk_stmt_with_source(stmt, || self.walk_schema_attr(schema_attr))
+            }
             ast::Stmt::Schema(schema_stmt) => self.walk_schema_stmt(schema_stmt),
             ast::Stmt::Rule(rule_stmt) => self.walk_rule_stmt(rule_stmt),
         };
@@ -170,8 +177,30 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                     self.string_value("")
                 }
             };
-            if !assert_result.is_truthy() {
-                let mut ctx = self.runtime_ctx.borrow_mut();
+            let passed = assert_result.is_truthy();
+            let mut ctx = self.runtime_ctx.borrow_mut();
+            if ctx.cfg.record_assertions {
+                let (filename, line, column, end_line, end_column) = assert_stmt.test.pos();
+                ctx.assertions.push(AssertionRecord {
+                    filename,
+                    line,
+                    column,
+                    end_line,
+                    end_column,
+                    passed,
+                    message: msg.as_str(),
+                });
+                // Record the failure with its location and continue the evaluation.
+                if !passed {
+                    let mut failure = ctx.panic_info.clone();
+                    failure.__kcl_PanicInfo__ = true;
+                    failure.err_type_code = RuntimeErrorType::AssertionError as i32;
+                    failure.message = msg.as_str();
+                    ctx.check_failures.push(failure);
+                }
+                return;
+            }
+            if !passed {
                 ctx.set_err_type(&RuntimeErrorType::AssertionError);
                 let msg = msg.as_str();
                 panic!("{}", msg);
@@ -645,27 +674,36 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
 
     fn walk_call_expr(&self, call_expr: &'ctx ast::CallExpr) -> Self::Result {
         let func = self.walk_expr(&call_expr.func)?;
-        // args
-        let mut list_value = self.list_value();
-        for arg in &call_expr.args {
-            let value = self.walk_expr(arg)?;
-            self.list_append(&mut list_value, &value);
-        }
-        let mut dict_value = self.dict_value();
-        // keyword arguments
-        for keyword in &call_expr.keywords {
-            let name = &keyword.node.arg.node.names[0];
-            let value = if let Some(value) = &keyword.node.value {
-                self.walk_expr(value)?
-            } else {
-                self.none_value()
-            };
-            self.dict_insert_value(&mut dict_value, name.node.as_str(), &value);
-        }
+        let (mut list_value, dict_value) = {
+            // The arguments are not a part of the output.
+            self.push_source_path(None);
+            defer! {
+                self.pop_source_path();
+            }
+            // args
+            let mut list_value = self.list_value();
+            for arg in &call_expr.args {
+                let value = self.walk_expr(arg)?;
+                self.list_append(&mut list_value, &value);
+            }
+            let mut dict_value = self.dict_value();
+            // keyword arguments
+            for keyword in &call_expr.keywords {
+                let name = &keyword.node.arg.node.names[0];
+                let value = if let Some(value) = &keyword.node.value {
+                    self.walk_expr(value)?
+                } else {
+                    self.none_value()
+                };
+                self.dict_insert_value(&mut dict_value, name.node.as_str(), &value);
+            }
+            (list_value, dict_value)
+        };
         let vars = self.clean_and_cloned_local_vars();
         let result = if let Some(proxy) = func.try_get_proxy() {
             // Invoke user defined functions, schemas or rules.
-            Ok(self.invoke_proxy_function(proxy, &list_value, &dict_value))
+            let allow_partial = self.partial_calls.contains(&call_expr.func.id);
+            Ok(self.invoke_proxy_function(proxy, &list_value, &dict_value, allow_partial))
         } else {
             // Invoke runtime builtin functions or external plugin functions.
             Ok(invoke_function(self, &func, &mut list_value, &dict_value))
@@ -726,13 +764,21 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_list_expr(&self, list_expr: &'ctx ast::ListExpr) -> Self::Result {
         let mut list_value = self.list_value();
         for item in &list_expr.elts {
-            let value = self.walk_expr(item)?;
+            let source_path = match &item.node {
+                ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => None,
+                _ => self.child_source_path(&list_value.len().to_string()),
+            };
+            self.push_source_path(source_path.clone());
+            let value = self.walk_expr(item);
+            self.pop_source_path();
+            let value = value?;
             match &item.node {
                 ast::Expr::Starred(_) | ast::Expr::ListIfItem(_) => {
                     self.list_append_unpack(&mut list_value, &value);
                 }
                 _ => self.list_append(&mut list_value, &value),
             };
+            self.record_source(&source_path, item.get_span_pos());
         }
         Ok(list_value)
     }
@@ -766,7 +812,10 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_list_comp(&self, list_comp: &'ctx ast::ListComp) -> Self::Result {
         let mut collection_value = self.list_value();
         self.enter_scope();
+        // The comprehension items are not recorded in the source map.
+        self.push_source_path(None);
         defer! {
+            self.pop_source_path();
             self.leave_scope();
         }
         self.walk_generator(
@@ -784,7 +833,10 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_dict_comp(&self, dict_comp: &'ctx ast::DictComp) -> Self::Result {
         let mut collection_value = self.dict_value();
         self.enter_scope();
+        // The comprehension entries are not recorded in the source map.
+        self.push_source_path(None);
         defer! {
+            self.pop_source_path();
             self.leave_scope();
         }
         let key = dict_comp
@@ -848,21 +900,29 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
             _ => panic!("invalid schema config expr"),
         };
         let config_meta = self.construct_schema_config_meta(Some(&schema_expr.name), config_expr);
-        let mut list_value = self.list_value();
-        for arg in &schema_expr.args {
-            let value = self.walk_expr(arg)?;
-            self.list_append(&mut list_value, &value);
-        }
-        let mut dict_value = self.dict_value();
-        for keyword in &schema_expr.kwargs {
-            let name = &keyword.node.arg.node.names[0];
-            let value = if let Some(value) = &keyword.node.value {
-                self.walk_expr(value)?
-            } else {
-                self.none_value()
-            };
-            self.dict_insert_merge_value(&mut dict_value, name.node.as_str(), &value);
-        }
+        let (list_value, dict_value) = {
+            // The arguments are not a part of the output.
+            self.push_source_path(None);
+            defer! {
+                self.pop_source_path();
+            }
+            let mut list_value = self.list_value();
+            for arg in &schema_expr.args {
+                let value = self.walk_expr(arg)?;
+                self.list_append(&mut list_value, &value);
+            }
+            let mut dict_value = self.dict_value();
+            for keyword in &schema_expr.kwargs {
+                let name = &keyword.node.arg.node.names[0];
+                let value = if let Some(value) = &keyword.node.value {
+                    self.walk_expr(value)?
+                } else {
+                    self.none_value()
+                };
+                self.dict_insert_merge_value(&mut dict_value, name.node.as_str(), &value);
+            }
+            (list_value, dict_value)
+        };
         let schema = if let Some(index) = schema_type.try_get_proxy() {
             let frame = {
                 let frames = self.frames.borrow();
@@ -970,6 +1030,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                     }),
                 closure: self.get_current_closure_map(),
                 level: self.scope_level() + 1,
+                bound_args: Default::default(),
             },
             func,
         );
@@ -1130,6 +1191,12 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
 
 impl<'ctx> Evaluator<'ctx> {
     pub fn walk_stmts_except_import(&self, stmts: &'ctx [Box<ast::Node<ast::Stmt>>]) -> EvalResult {
+        self.push_source_scope(SourceScope::Global(
+            self.is_output_pkgpath(&self.current_pkgpath()),
+        ));
+        defer! {
+            self.pop_source_scope();
+        }
         let mut result = self.ok_result();
         for stmt in stmts {
             if !matches!(&stmt.node, ast::Stmt::Import(..)) {
@@ -1171,7 +1238,11 @@ impl<'ctx> Evaluator<'ctx> {
                             .expect(&format!("module {:?} not found in program", module));
                         if let Some(stmt) = module.body.get(setter.stmt) {
                             self.push_backtrack_meta(setter);
+                            self.push_source_scope(SourceScope::Global(
+                                self.is_output_pkgpath(&frame.pkgpath),
+                            ));
                             self.walk_stmt(stmt).expect(INTERNAL_ERROR_MSG);
+                            self.pop_source_scope();
                             self.pop_backtrack_meta();
                         }
                     }
@@ -1588,6 +1659,9 @@ impl<'ctx> Evaluator<'ctx> {
                             op,
                             None,
                         );
+                        self.runtime_ctx
+                            .borrow_mut()
+                            .check_collection_len(collection_value.len());
                     }
                 }
             } else {
@@ -1610,7 +1684,31 @@ impl<'ctx> Evaluator<'ctx> {
     pub(crate) fn walk_config_entries(&self, items: &'ctx [NodeRef<ConfigEntry>]) -> EvalResult {
         let mut config_value = self.dict_value();
         for item in items {
-            let value = self.walk_expr(&item.node.value)?;
+            // The output path of the entry value, the entries with the dynamic keys and
+            // the list insert entries are not recorded in the source map.
+            let source_path = match (&item.node.key, &item.node.operation) {
+                (None, _) => self.current_source_path(),
+                (Some(_), ast::ConfigEntryOperation::Insert) => None,
+                (Some(key_node), _) => match &key_node.node {
+                    ast::Expr::Identifier(identifier) => {
+                        self.child_source_path(&identifier.get_name())
+                    }
+                    ast::Expr::StringLit(string_lit) => self.child_source_path(&string_lit.value),
+                    _ => None,
+                },
+            };
+            if item.node.key.is_some()
+                && matches!(item.node.operation, ast::ConfigEntryOperation::Override)
+            {
+                self.clear_nested_sources(&source_path);
+            }
+            self.push_source_path(source_path.clone());
+            let value = self.walk_expr(&item.node.value);
+            self.pop_source_path();
+            let value = value?;
+            if item.node.key.is_some() {
+                self.record_source(&source_path, item.get_span_pos());
+            }
             if let Some(key_node) = &item.node.key {
                 let mut insert_index = None;
                 let optional_name = match &key_node.node {
@@ -1662,6 +1760,9 @@ impl<'ctx> Evaluator<'ctx> {
                 config_value.dict_insert_unpack(&mut self.runtime_ctx.borrow_mut(), &value)
             }
         }
+        self.runtime_ctx
+            .borrow_mut()
+            .check_collection_len(config_value.len());
         Ok(config_value)
     }
 }
@@ -56,7 +56,7 @@ pub fn runtime_catch(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> Value
             if let Some(proxy) = func.try_get_proxy() {
                 let args = ValueRef::list(None);
                 let kwargs = ValueRef::dict(None);
-                s.invoke_proxy_function(proxy, &args, &kwargs);
+                s.invoke_proxy_function(proxy, &args, &kwargs, false);
             }
         });
         let result = catch_unwind(AssertUnwindSafe(|| unsafe {
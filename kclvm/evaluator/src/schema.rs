@@ -12,6 +12,7 @@ use scopeguard::defer;
 use crate::lazy::{merge_variables_and_setters, LazyEvalScope, LazyEvalScopeRef};
 use crate::proxy::{call_schema_body, call_schema_check};
 use crate::rule::RuleEvalContext;
+use crate::source_map::SourceScope;
 use crate::ty::type_pack_and_check;
 use crate::{error as kcl_error, Proxy};
 use crate::{Evaluator, INNER_LEVEL};
@@ -458,7 +459,10 @@ pub(crate) fn schema_body(
     let schema_name = { ctx.borrow().node.name.node.to_string() };
     s.push_schema(crate::EvalContext::Schema(ctx.clone()));
     s.enter_schema_scope(true);
+    // The schema attributes are recorded under the output path of the schema instance.
+    s.push_source_scope(SourceScope::Schema(s.current_source_path()));
     defer! {
+        s.pop_source_scope();
         s.leave_scope();
         s.pop_schema();
     }
@@ -653,6 +657,22 @@ pub(crate) fn schema_with_config(
             .collect()
     };
     let runtime_type = schema_runtime_type(&name, &pkgpath);
+    // Record the schema attributes assigned by the config.
+    if s.runtime_ctx.borrow().cfg.record_attr_coverage {
+        let mut runtime_ctx = s.runtime_ctx.borrow_mut();
+        let coverage = runtime_ctx
+            .attr_coverage
+            .entry(runtime_type.clone())
+            .or_default();
+        for stmt in &ctx.borrow().node.body {
+            if let ast::Stmt::SchemaAttr(schema_attr) = &stmt.node {
+                let count = coverage.entry(schema_attr.name.node.clone()).or_default();
+                if config_keys.contains(&schema_attr.name.node) {
+                    *count += 1;
+                }
+            }
+        }
+    }
     // Instance package path is the last frame calling package path.
     let instance_pkgpath = s.last_pkgpath();
     // Currently, `MySchema.instances()` it is only valid for files in the main package to
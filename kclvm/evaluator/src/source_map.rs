@@ -0,0 +1,211 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_error::diagnostic::Range;
+use kclvm_runtime::val_plan::KCL_PRIVATE_VAR_PREFIX;
+use kclvm_runtime::PKG_PATH_PREFIX;
+
+use crate::{EvalResult, Evaluator};
+
+/// SourceMap maps the output path e.g., `deployment.spec.replicas` to the
+/// source range of the assignment that set it.
+pub type SourceMap = IndexMap<String, Range>;
+
+/// SourceMapRecorder records the source map while the program is evaluated, thus
+/// only the assignments actually executed e.g., the taken branch of an if statement
+/// are recorded.
+#[derive(Debug, Default)]
+pub struct SourceMapRecorder {
+    source_map: SourceMap,
+    /// The output path stack of the values being evaluated, `None` denotes the value
+    /// is not a part of the output e.g., a function argument or a local variable.
+    paths: Vec<Option<String>>,
+    /// The statement scope stack being evaluated.
+    scopes: Vec<SourceScope>,
+}
+
+/// The scope of the statements being evaluated, which decides the output path of
+/// the assignment targets.
+#[derive(Debug, Clone)]
+pub(crate) enum SourceScope {
+    /// The top level statements of a package, whose variables are emitted only when
+    /// the package is the output package.
+    Global(bool),
+    /// The schema body with the output path of the schema instance.
+    Schema(Option<String>),
+    /// The lambda body, whose local variables are never emitted.
+    Lambda,
+}
+
+impl<'ctx> Evaluator<'ctx> {
+    /// Get the source map of the output package, which links every output path to
+    /// the source range of the statement, config entry or schema attribute default
+    /// value that set it. When a path is set multiple times, the last executed
+    /// assignment wins. Values loaded from the variables of the other packages are
+    /// mapped to the statement referencing them e.g., `a = pkg.a`, and the source map
+    /// is empty unless [Evaluator::source_map_recorder] is set before the evaluation.
+    pub fn source_map(&self) -> SourceMap {
+        self.source_map_recorder
+            .as_ref()
+            .map(|recorder| recorder.borrow().source_map.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether the package path with or without the `@` prefix is the output package.
+    #[inline]
+    pub(crate) fn is_output_pkgpath(&self, pkgpath: &str) -> bool {
+        pkgpath.strip_prefix(PKG_PATH_PREFIX).unwrap_or(pkgpath) == self.output_pkgpath
+    }
+
+    #[inline]
+    pub(crate) fn push_source_scope(&self, scope: SourceScope) {
+        if let Some(recorder) = &self.source_map_recorder {
+            recorder.borrow_mut().scopes.push(scope);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn pop_source_scope(&self) {
+        if let Some(recorder) = &self.source_map_recorder {
+            recorder.borrow_mut().scopes.pop();
+        }
+    }
+
+    /// Push the output path of the value to be evaluated.
+    #[inline]
+    pub(crate) fn push_source_path(&self, path: Option<String>) {
+        if let Some(recorder) = &self.source_map_recorder {
+            recorder.borrow_mut().paths.push(path);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn pop_source_path(&self) {
+        if let Some(recorder) = &self.source_map_recorder {
+            recorder.borrow_mut().paths.pop();
+        }
+    }
+
+    /// Get the output path of the value being evaluated.
+    pub(crate) fn current_source_path(&self) -> Option<String> {
+        self.source_map_recorder
+            .as_ref()
+            .and_then(|recorder| recorder.borrow().paths.last().cloned().flatten())
+    }
+
+    /// Get the output path of the key in the value being evaluated.
+    #[inline]
+    pub(crate) fn child_source_path(&self, key: &str) -> Option<String> {
+        self.current_source_path().map(|path| format!("{}.{}", path, key))
+    }
+
+    /// Get the output path of the assignment target `name` in the current statement scope.
+    pub(crate) fn target_source_path(&self, name: &str) -> Option<String> {
+        let recorder = self.source_map_recorder.as_ref()?;
+        let scope = recorder.borrow().scopes.last().cloned()?;
+        match scope {
+            SourceScope::Global(true) => {
+                let show_hidden = self.runtime_ctx.borrow().plan_opts.show_hidden;
+                if show_hidden || !name.starts_with(KCL_PRIVATE_VAR_PREFIX) {
+                    Some(name.to_string())
+                } else {
+                    None
+                }
+            }
+            SourceScope::Schema(Some(path)) => {
+                // The config value of the schema instance takes precedence over the
+                // values set in the schema body.
+                let in_config = self
+                    .get_schema_or_rule_config_info()
+                    .map_or(false, |(_, config, _)| config.dict_get_entry(name).is_some());
+                (!in_config).then(|| format!("{}.{}", path, name))
+            }
+            SourceScope::Global(false) | SourceScope::Schema(None) | SourceScope::Lambda => None,
+        }
+    }
+
+    /// Record the source range of the value set at the output path.
+    pub(crate) fn record_source(&self, path: &Option<String>, range: Range) {
+        if let (Some(recorder), Some(path)) = (&self.source_map_recorder, path) {
+            recorder.borrow_mut().source_map.insert(path.clone(), range);
+        }
+    }
+
+    /// Remove the source ranges of the nested paths of the output path, which is used
+    /// when the value at the path is overridden as a whole.
+    pub(crate) fn clear_nested_sources(&self, path: &Option<String>) {
+        if let (Some(recorder), Some(path)) = (&self.source_map_recorder, path) {
+            let prefix = format!("{}.", path);
+            recorder
+                .borrow_mut()
+                .source_map
+                .retain(|key, _| !key.starts_with(&prefix));
+        }
+    }
+
+    /// Evaluate the assignment statement with the walk function, and record the
+    /// source range of the statement for its targets in the output.
+    pub(crate) fn walk_stmt_with_source(
+        &self,
+        stmt: &ast::Node<ast::Stmt>,
+        walk: impl FnOnce() -> EvalResult,
+    ) -> EvalResult {
+        if self.source_map_recorder.is_none() {
+            return walk();
+        }
+        // The target names, whether the value is set at the target path, and whether
+        // the value overrides the previous target value as a whole.
+        let (names, value_at_target, is_override) = match &stmt.node {
+            ast::Stmt::Assign(assign_stmt) => (
+                assign_stmt
+                    .targets
+                    .iter()
+                    .filter(|target| target.node.paths.is_empty())
+                    .map(|target| target.node.get_name())
+                    .collect(),
+                assign_stmt.targets.len() == 1,
+                true,
+            ),
+            ast::Stmt::AugAssign(aug_assign_stmt)
+                if aug_assign_stmt.target.node.paths.is_empty() =>
+            {
+                (
+                    vec![aug_assign_stmt.target.node.get_name()],
+                    matches!(aug_assign_stmt.op, ast::AugOp::BitOr),
+                    false,
+                )
+            }
+            ast::Stmt::Unification(unification_stmt) => (
+                vec![unification_stmt.target.node.names[0].node.as_str()],
+                true,
+                false,
+            ),
+            ast::Stmt::SchemaAttr(schema_attr) if schema_attr.value.is_some() => {
+                (vec![schema_attr.name.node.as_str()], true, true)
+            }
+            _ => (vec![], false, false),
+        };
+        let paths: Vec<Option<String>> = names
+            .iter()
+            .map(|name| self.target_source_path(name))
+            .collect();
+        if is_override {
+            for path in &paths {
+                self.clear_nested_sources(path);
+            }
+        }
+        let value_path = match paths.as_slice() {
+            [path] if value_at_target => path.clone(),
+            _ => None,
+        };
+        self.push_source_path(value_path);
+        let result = walk();
+        self.pop_source_path();
+        for path in &paths {
+            self.record_source(path, stmt.get_span_pos());
+        }
+        result
+    }
+}
@@ -1,8 +1,9 @@
 use crate::Evaluator;
 use kclvm_ast::MAIN_PKG;
 use kclvm_loader::{load_packages, LoadPackageOptions};
-use kclvm_parser::LoadProgramOptions;
+use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
 use kclvm_runtime::{Context, ValueRef};
+use kclvm_sema::resolver::resolve_program;
 
 #[macro_export]
 macro_rules! evaluator_snapshot {
@@ -413,6 +414,60 @@ my_dict = {
 }
 "#}
 
+#[test]
+fn lambda_7() {
+    let src = r#"
+add = lambda x: int, y: int, z: int = 0 {
+    x + y + z
+}
+inc = add(1)
+a = inc(2)
+b = add(y=2)(1, 3)
+c = [add(10)(i) for i in [1, 2]]
+d = add(1)(2)
+"#;
+    let mut program = load_program(
+        Arc::new(ParseSession::default()),
+        &["test.k"],
+        Some(LoadProgramOptions {
+            k_code_list: vec![src.to_string()],
+            ..Default::default()
+        }),
+        None,
+    )
+    .unwrap()
+    .program;
+    // The lambdas are only partially applied by the calls marked by the resolver.
+    let scope = resolve_program(&mut program);
+    let mut evaluator = Evaluator::new(&program);
+    evaluator.partial_calls = scope.partial_calls.iter().cloned().collect();
+    insta::assert_snapshot!(format!("{}", evaluator.run().unwrap().1));
+}
+
+#[test]
+fn lambda_missing_argument_without_partial_call() {
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec![r#"
+add = lambda x: int, y: int {
+    x + y
+}
+a = add(1)
+"#
+            .to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    // The call not marked as a partial application reports the missing argument.
+    let evaluator = Evaluator::new(&p.program);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| evaluator.run()));
+    assert!(!matches!(result, Ok(Ok(_))));
+}
+
 evaluator_snapshot! {schema_0, r#"
 schema Person:
     name: str = "Alice"
@@ -619,3 +674,48 @@ sum = testing.add(1, 1)
     let evaluator = Evaluator::new_with_runtime_ctx(&p.program, context_with_plugin());
     insta::assert_snapshot!(format!("{}", evaluator.run().unwrap().1));
 }
+
+#[test]
+fn test_eval_path() {
+    let src = r#"
+schema Spec:
+    replicas: int
+    items: [{str:str}]
+
+c = {
+    spec = Spec {
+        replicas = 3
+        items = [{name = "a"}, {name = "b"}]
+    }
+}
+"#;
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec![src.to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        crate::eval_path(&p.program, "c.spec.replicas")
+            .unwrap()
+            .as_int(),
+        3
+    );
+    assert_eq!(
+        crate::eval_path(&p.program, "c.spec.items.1.name")
+            .unwrap()
+            .as_str(),
+        "b"
+    );
+    assert!(crate::eval_path(&p.program, "c.spec").unwrap().is_schema());
+    assert_eq!(
+        crate::eval_path(&p.program, "c.spec.name")
+            .unwrap_err()
+            .to_string(),
+        "path 'c.spec.name' is not found in the output"
+    );
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use kclvm_ast::{ast, walker::MutSelfWalker};
 use kclvm_sema::builtin::BUILTIN_FUNCTIONS;
-use kclvm_sema::{builtin::option::OptionHelp, resolver::scope::NodeKey};
+use kclvm_sema::{
+    builtin::option::{parse_option_type, OptionHelp},
+    resolver::scope::NodeKey,
+};
 
 use crate::util::{get_call_args_string, get_call_args_strip_string};
 use crate::{load_packages, util::get_call_args_bool, LoadPackageOptions, Packages};
@@ -29,9 +32,16 @@ impl<'ctx> MutSelfWalker for OptionHelpExtractor<'ctx> {
                     && symbol.ty.is_func()
                     && symbol.ty.ty_str() == builtin_option_type.ty_str()
                 {
+                    let ty = get_call_args_strip_string(call_expr, 1, Some("type"));
+                    let ty_ref = if ty.is_empty() {
+                        None
+                    } else {
+                        parse_option_type(&ty).ok()
+                    };
                     self.options.push(OptionHelp {
                         name: get_call_args_strip_string(call_expr, 0, Some("key")),
-                        ty: get_call_args_strip_string(call_expr, 1, Some("type")),
+                        ty,
+                        ty_ref,
                         required: get_call_args_bool(call_expr, 2, Some("required")),
                         default_value: get_call_args_string(call_expr, 3, Some("default")),
                         help: get_call_args_strip_string(call_expr, 3, Some("help")),
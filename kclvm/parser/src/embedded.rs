@@ -0,0 +1,107 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! Parse the KCL code embedded in other files e.g., the fenced code blocks in Markdown.
+
+use anyhow::Result;
+use kclvm_ast::ast;
+use kclvm_span::create_session_globals_then;
+
+use crate::{parse_file_with_session, ParseFileResult, ParseSessionRef};
+
+/// A code block embedded in the host file.
+#[derive(Debug, Clone)]
+struct EmbeddedBlock {
+    /// The 0-based line index of the first code line in the host file.
+    line_offset: usize,
+    code: String,
+}
+
+/// A code fence line with the fence char, the fence length, the indentation and the info string.
+type Fence<'a> = (char, usize, usize, &'a str);
+
+/// Parse the fenced code blocks of the language `lang` e.g., ```` ```kcl ```` in the Markdown
+/// `source` of the host file `filename`. Each block is parsed as a standalone KCL file named
+/// `filename` and the positions of the AST nodes and parse errors are in the coordinates of
+/// the host file. The indentation of an indented fence is stripped from the code lines, which
+/// shifts their columns by the fence indentation.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::parse_embedded;
+///
+/// let source = "# Example\n\n```kcl\na = 1\n```\n";
+/// let results = parse_embedded("README.md", source, "kcl").unwrap();
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(results[0].module.body[0].line, 4);
+/// ```
+pub fn parse_embedded(filename: &str, source: &str, lang: &str) -> Result<Vec<ParseFileResult>> {
+    extract_blocks(source, lang)
+        .into_iter()
+        .map(|block| {
+            // Pad the preceding lines of the host file to keep its line numbers.
+            let code = "\n".repeat(block.line_offset) + &block.code;
+            let sess = ParseSessionRef::default();
+            let module: ast::Module = create_session_globals_then(|| {
+                parse_file_with_session(sess.clone(), filename, Some(code))
+            })?;
+            let errors = sess.1.read().diagnostics.clone();
+            Ok(ParseFileResult {
+                module,
+                errors,
+                // The embedded blocks are not loaded as packages.
+                deps: vec![],
+            })
+        })
+        .collect()
+}
+
+fn extract_blocks(source: &str, lang: &str) -> Vec<EmbeddedBlock> {
+    let mut blocks = vec![];
+    let mut lines = source.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let (fence_char, fence_len, indent, info) = match parse_fence(line) {
+            Some(fence) => fence,
+            None => continue,
+        };
+        let mut code_lines = vec![];
+        for (_, line) in lines.by_ref() {
+            if let Some((c, len, _, rest)) = parse_fence(line) {
+                if c == fence_char && len >= fence_len && rest.is_empty() {
+                    break;
+                }
+            }
+            code_lines.push(strip_indent(line, indent));
+        }
+        // An unclosed block runs to the end of the host file.
+        if info.split_whitespace().next() == Some(lang) {
+            blocks.push(EmbeddedBlock {
+                line_offset: index + 1,
+                code: code_lines.join("\n") + "\n",
+            });
+        }
+    }
+    blocks
+}
+
+fn parse_fence(line: &str) -> Option<Fence> {
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    if indent > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next()?;
+    if fence_char != '`' && fence_char != '~' {
+        return None;
+    }
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+    Some((fence_char, fence_len, indent, trimmed[fence_len..].trim()))
+}
+
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let spaces = line.len() - line.trim_start_matches(' ').len();
+    &line[spaces.min(indent)..]
+}
@@ -1,16 +1,14 @@
 use anyhow::Result;
 use glob::glob;
-use kclvm_config::modfile::get_pkg_root;
-use kclvm_config::modfile::KCL_FILE_SUFFIX;
+use kclvm_config::modfile::{KCL_FILE_SUFFIX, KCL_MOD_FILE};
 use kclvm_config::path::ModRelativePath;
+use kclvm_utils::path::is_absolute;
 use kclvm_utils::path::PathPrefix;
-use kclvm_utils::path::{is_absolute, is_dir, path_exist};
 use std::collections::VecDeque;
-use std::fs;
 use std::path::Path;
-use std::path::PathBuf;
 
 use crate::LoadProgramOptions;
+use crate::{is_archive_file, FileSystemProvider, SourceProvider};
 
 /// [`Entries`] is a map of package name to package root path for one compilation
 /// # note
@@ -297,11 +295,12 @@ pub fn get_compile_entries_from_paths(
     if file_paths.is_empty() {
         return Err(anyhow::anyhow!("No input KCL files or paths"));
     }
+    let provider = opts.source_provider.as_ref();
     let mut result = Entries::default();
     let mut k_code_queue = VecDeque::from(opts.k_code_list.clone());
     let file_paths = expand_input_files(file_paths);
     for file in &file_paths {
-        let file = canonicalize_input_file(file, &opts.work_dir);
+        let file = canonicalize_provided_input_file(provider, file, &opts.work_dir);
         let path = ModRelativePath::from(file.to_string());
 
         // If the path is a [`ModRelativePath`] with prefix '${<package_name>:KCL_MOD}',
@@ -313,7 +312,7 @@ pub fn get_compile_entries_from_paths(
         }) {
             // Replace the mod relative path prefix '${<pkg_name>:KCL_MOD}' with the real path.
             let file = path.canonicalize_by_root_path(pkg_path)?;
-            if let Some(root) = get_pkg_root(&file) {
+            if let Some(root) = lookup_pkg_root(provider, &file) {
                 let mut entry: Entry = Entry::new(pkg_name.clone(), root.clone());
                 entry.extend_k_files_and_codes(
                     get_main_files_from_pkg_path(&file, &root, &pkg_name, opts)?,
@@ -329,7 +328,31 @@ pub fn get_compile_entries_from_paths(
             entry.push_k_code(k_code_queue.pop_front());
             result.push_entry(entry);
             continue;
-        } else if let Some(root) = get_pkg_root(&file) {
+        } else if provider.exists(Path::new(&file))
+            && (!Path::new(&file).exists() || is_archive_file(Path::new(&file)))
+        {
+            // The path only provided by the source provider e.g., a file in an archive
+            // or the mounted archive file itself.
+            let root = if opts.treat_dir_as_package && provider.is_dir(Path::new(&file)) {
+                file.clone()
+            } else {
+                get_provided_pkg_root(provider, &file)
+            };
+            let mut entry: Entry = Entry::new(kclvm_ast::MAIN_PKG.to_string(), root);
+            entry.extend_k_files_and_codes(
+                get_provided_main_files(provider, &file)?,
+                &mut k_code_queue,
+            );
+            result.push_entry(entry);
+        } else if opts.treat_dir_as_package && provider.is_dir(Path::new(&file)) {
+            // The directory is the root of the main package without looking up the `kcl.mod`.
+            let mut entry: Entry = Entry::new(kclvm_ast::MAIN_PKG.to_string(), file.clone());
+            entry.extend_k_files_and_codes(
+                get_main_files_from_pkg_path(&file, &file, kclvm_ast::MAIN_PKG, opts)?,
+                &mut k_code_queue,
+            );
+            result.push_entry(entry);
+        } else if let Some(root) = lookup_pkg_root(provider, &file) {
             // If the path is a normal path.
             let mut entry: Entry = Entry::new(kclvm_ast::MAIN_PKG.to_string(), root.clone());
             entry.extend_k_files_and_codes(
@@ -368,7 +391,7 @@ pub fn get_compile_entries_from_paths(
             .to_string()
     } else if main_pkg_paths_count > 1 && !opts.work_dir.is_empty() {
         // If the 'kcl.mod' can be found more than once, the package root path will be the 'work_dir'.
-        if let Some(root_work_dir) = get_pkg_root(&opts.work_dir) {
+        if let Some(root_work_dir) = lookup_pkg_root(provider, &opts.work_dir) {
             root_work_dir
         } else {
             opts.work_dir.to_string()
@@ -395,6 +418,70 @@ pub fn get_compile_entries_from_paths(
     Ok(result)
 }
 
+/// Get the package root of the path with the source provider, which is the nearest directory
+/// containing a `kcl.mod` file, or the directory of the path when it is a KCL file.
+fn lookup_pkg_root(provider: &dyn SourceProvider, path: &str) -> Option<String> {
+    if path.is_empty() {
+        return None;
+    }
+    let module_path = provider.canonicalize(Path::new(path)).ok()?;
+    for dir in module_path.ancestors() {
+        if !provider.exists(dir) {
+            break;
+        }
+        let kcl_mod_path = dir.join(KCL_MOD_FILE);
+        if provider.exists(&kcl_mod_path) && !provider.is_dir(&kcl_mod_path) {
+            return Some(dir.adjust_canonicalization());
+        }
+    }
+    if path.ends_with(KCL_FILE_SUFFIX) {
+        module_path
+            .parent()
+            .map(|dir| dir.adjust_canonicalization())
+    } else {
+        None
+    }
+}
+
+/// Get the package root of the path provided by the source provider, which is the nearest
+/// directory containing a `kcl.mod` up to the mount point of the provided paths on the local
+/// filesystem e.g., the archive file, or the directory of the path when it is not found.
+fn get_provided_pkg_root(provider: &dyn SourceProvider, path: &str) -> String {
+    let path = Path::new(path);
+    for dir in path.ancestors() {
+        if provider.exists(&dir.join(KCL_MOD_FILE)) {
+            return dir.display().to_string();
+        }
+        if dir.exists() {
+            break;
+        }
+    }
+    if provider.is_dir(path) {
+        path.display().to_string()
+    } else {
+        path.parent().unwrap_or(path).display().to_string()
+    }
+}
+
+/// Get the main package files of the path provided by the source provider, which are the
+/// KCL files except the tests at the top level of the directory, or the file itself.
+fn get_provided_main_files(provider: &dyn SourceProvider, path: &str) -> Result<Vec<String>> {
+    if !provider.is_dir(Path::new(path)) {
+        return Ok(vec![path.to_string()]);
+    }
+    let mut files: Vec<String> = provider
+        .list_dir(Path::new(path))?
+        .iter()
+        .filter(|file| {
+            file.file_name()
+                .map_or(false, |name| !is_ignored_file(&name.to_string_lossy()))
+        })
+        .map(|file| file.display().to_string())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
 /// Get files in the main package with the package root.
 fn get_main_files_from_pkg_path(
     pkg_path: &str,
@@ -417,14 +504,14 @@ fn get_main_files_from_pkg_path(
             return Err(anyhow::anyhow!("Can not find {} in the path: {}", s, root));
         }
     }
+    let provider = opts.source_provider.as_ref();
     if !root.is_empty() && !is_absolute(s.as_str()) {
-        let p = std::path::Path::new(s.as_str());
-        if let Ok(x) = std::fs::canonicalize(p) {
+        if let Ok(x) = provider.canonicalize(Path::new(s.as_str())) {
             s = x.adjust_canonicalization();
         }
     }
 
-    match PathBuf::from(s.clone()).canonicalize() {
+    match provider.canonicalize(Path::new(&s)) {
         Ok(path) => {
             path_list.push(path.to_str().unwrap().to_string());
         }
@@ -437,12 +524,12 @@ fn get_main_files_from_pkg_path(
 
     for (i, path) in path_list.iter().enumerate() {
         // read dir/*.k
-        if !path.is_empty() && is_dir(path) {
+        if !path.is_empty() && provider.is_dir(Path::new(path)) {
             if opts.k_code_list.len() > i {
                 return Err(anyhow::anyhow!("Invalid code list for the path {}", path));
             }
             // k_code_list
-            for s in get_dir_files(path, false)? {
+            for s in get_provided_dir_files(provider, path, false)? {
                 k_files.push(s);
             }
             continue;
@@ -461,7 +548,7 @@ fn get_main_files_from_pkg_path(
             continue;
         }
 
-        if !path_exist(filename.as_str()) {
+        if !provider.exists(Path::new(filename)) {
             return Err(anyhow::anyhow!(
                 "Cannot find the kcl file, please check the file path {}",
                 filename.as_str(),
@@ -472,8 +559,18 @@ fn get_main_files_from_pkg_path(
 }
 
 /// Get file list in the directory.
+#[inline]
 pub fn get_dir_files(dir: &str, is_recursive: bool) -> Result<Vec<String>> {
-    if !std::path::Path::new(dir).exists() {
+    get_provided_dir_files(&FileSystemProvider, dir, is_recursive)
+}
+
+/// Get file list in the directory with the source provider.
+fn get_provided_dir_files(
+    provider: &dyn SourceProvider,
+    dir: &str,
+    is_recursive: bool,
+) -> Result<Vec<String>> {
+    if !provider.exists(Path::new(dir)) {
         return Ok(Vec::new());
     }
 
@@ -483,17 +580,14 @@ pub fn get_dir_files(dir: &str, is_recursive: bool) -> Result<Vec<String>> {
     // BFS all the files in the directory.
     while let Some(path) = queue.pop_front() {
         let path = Path::new(&path);
-        if path.is_dir() {
-            match fs::read_dir(path) {
+        if provider.is_dir(path) {
+            match provider.list_dir(path) {
                 Ok(entries) => {
-                    for entry in entries {
-                        if let Ok(entry) = entry {
-                            let path = entry.path();
-                            if path.is_dir() && is_recursive {
-                                queue.push_back(path.to_string_lossy().to_string());
-                            } else if !is_ignored_file(&path.display().to_string()) {
-                                list.push(path.display().to_string());
-                            }
+                    for path in entries {
+                        if provider.is_dir(&path) && is_recursive {
+                            queue.push_back(path.to_string_lossy().to_string());
+                        } else if !is_ignored_file(&path.display().to_string()) {
+                            list.push(path.display().to_string());
                         }
                     }
                 }
@@ -526,7 +620,17 @@ fn is_ignored_file(filename: &str) -> bool {
 }
 
 /// Normalize the input file with the working directory and replace ${KCL_MOD} with the module root path.
+#[inline]
 pub fn canonicalize_input_file(file: &str, work_dir: &str) -> String {
+    canonicalize_provided_input_file(&FileSystemProvider, file, work_dir)
+}
+
+/// Normalize the input file with the working directory with the source provider.
+pub(crate) fn canonicalize_provided_input_file(
+    provider: &dyn SourceProvider,
+    file: &str,
+    work_dir: &str,
+) -> String {
     let path = std::path::Path::new(file);
     let is_absolute = path.is_absolute();
     // If the input file or path is a relative path and it is not a absolute path in the KCL module VFS,
@@ -539,7 +643,7 @@ pub fn canonicalize_input_file(file: &str, work_dir: &str) -> String {
 
     let abs_path = if !is_absolute && !is_relative_path {
         let filepath = std::path::Path::new(work_dir).join(file);
-        match filepath.canonicalize() {
+        match provider.canonicalize(&filepath) {
             Ok(path) => Some(path.adjust_canonicalization()),
             Err(_) => Some(filepath.to_string_lossy().to_string()),
         }
@@ -1,12 +1,13 @@
 use std::{collections::HashMap, path::PathBuf};
 
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use kclvm_ast::ast::Module;
 use kclvm_utils::path::PathPrefix;
-use petgraph::{prelude::StableDiGraph, visit::EdgeRef};
+use petgraph::{prelude::StableDiGraph, visit::EdgeRef, Direction};
+use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 /// File with package info
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PkgFile {
     path: PathBuf,
     pub pkg_path: String,
@@ -28,7 +29,7 @@ impl PkgFile {
     }
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Pkg {
     pub pkg_name: String,
     pub pkg_root: String,
@@ -84,6 +85,31 @@ impl PkgFileGraph {
         dependencies_of(file, &self.graph, &self.path_to_node_index)
     }
 
+    /// Returns the given file and all the files which depend on it directly or
+    /// transitively, i.e., the files affected when the given file changes.
+    /// Returns an empty list if the file is not in the graph.
+    pub fn affected_by(&self, file: &PkgFile) -> Vec<PkgFile> {
+        let node_index = match self.path_to_node_index.get(file) {
+            Some(node_index) => *node_index,
+            None => return vec![],
+        };
+        let mut visited = IndexSet::new();
+        let mut stack = vec![node_index];
+        while let Some(node_index) = stack.pop() {
+            if visited.insert(node_index) {
+                stack.extend(
+                    self.graph
+                        .edges_directed(node_index, Direction::Incoming)
+                        .map(|edge| edge.source()),
+                );
+            }
+        }
+        visited
+            .into_iter()
+            .map(|node_index| self.graph[node_index].clone())
+            .collect()
+    }
+
     /// Returns a list of files in the order they should be compiled
     /// Or a list of files that are part of a cycle, if one exists
     pub fn toposort(&self) -> Result<Vec<PkgFile>, Vec<PkgFile>> {
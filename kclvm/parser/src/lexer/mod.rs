@@ -33,13 +33,27 @@ pub(crate) use string::str_content_eval;
 
 use self::indent::IndentLevel;
 use crate::session::ParseSession;
+use crate::ParseMode;
 
 /// EntryPoint of the lexer.
 /// Parse token streams from an input raw string and a fixed start point.
 /// Return an iterable token stream.
 pub fn parse_token_streams(sess: &ParseSession, src: &str, start_pos: BytePos) -> TokenStream {
+    parse_token_streams_with_mode(sess, src, start_pos, ParseMode::ParseComments)
+}
+
+/// Parse token streams from an input raw string and a fixed start point with the parse mode.
+/// In the [`ParseMode::Strict`] mode, unclosed delimiters at the end of the source are
+/// reported as errors.
+pub fn parse_token_streams_with_mode(
+    sess: &ParseSession,
+    src: &str,
+    start_pos: BytePos,
+    mode: ParseMode,
+) -> TokenStream {
     Lexer {
         sess,
+        strict: matches!(mode, ParseMode::Strict),
         start_pos,
         pos: start_pos,
         tok_start_pos: start_pos,
@@ -131,6 +145,9 @@ struct Lexer<'a> {
 
     /// parse-time session
     pub sess: &'a ParseSession,
+
+    /// Whether to report unclosed delimiters as errors.
+    strict: bool,
 }
 
 struct IndentContext {
@@ -712,6 +729,21 @@ impl<'a> Lexer<'a> {
             buf.push(Token::new(token::Newline, self.span(self.pos, self.pos)));
         }
 
+        if self.strict {
+            while let Some(delim) = self.indent_cxt.delims.pop() {
+                let delim = match delim {
+                    token::OpenDelim(token::Paren) => "(",
+                    token::OpenDelim(token::Brace) => "{",
+                    token::OpenDelim(token::Bracket) => "[",
+                    _ => bug!("Impossible!"),
+                };
+                self.sess.struct_span_error(
+                    &format!("unclosed delimiter '{}'", delim),
+                    self.span(self.pos, self.pos),
+                );
+            }
+        }
+
         while self.indent_cxt.indents.len() > 1 {
             self.indent_cxt.indents.pop();
             buf.push(Token::new(
@@ -1,39 +1,57 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+pub mod embedded;
 pub mod entry;
 pub mod file_graph;
 mod lexer;
+mod module_cache;
 mod parser;
 mod session;
+pub mod source;
 
 #[cfg(test)]
 mod tests;
 
 extern crate kclvm_error;
 
-use crate::entry::get_compile_entries_from_paths;
+pub use crate::embedded::parse_embedded;
+use crate::entry::{
+    canonicalize_provided_input_file, expand_if_file_pattern, get_compile_entries_from_paths,
+};
+pub use crate::module_cache::MODULE_CACHE_FORMAT_VERSION;
 pub use crate::session::{ParseSession, ParseSessionRef};
+pub use crate::source::{
+    is_archive_file, ArchiveProvider, FileSystemProvider, SourceProvider, SourceProviderRef,
+};
 use compiler_base_macros::bug;
 use compiler_base_session::Session;
 use compiler_base_span::span::new_byte_pos;
 use file_graph::{toposort, Pkg, PkgFile, PkgFileGraph, PkgMap};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use kclvm_ast::ast::Module;
+use kclvm_ast::pos::GetPos;
 use kclvm_ast::{ast, MAIN_PKG};
-use kclvm_config::modfile::{get_vendor_home, KCL_FILE_EXTENSION, KCL_FILE_SUFFIX, KCL_MOD_FILE};
+use kclvm_config::edition::Edition;
+use kclvm_config::modfile::{
+    get_vendor_home, parse_mod_file, KCL_FILE_EXTENSION, KCL_FILE_SUFFIX, KCL_MOD_FILE,
+    KCL_MOD_LOCK_FILE,
+};
 use kclvm_error::diagnostic::{Errors, Range};
-use kclvm_error::{ErrorKind, Message, Position, Style};
+use kclvm_error::{ErrorKind, Message, Position, Style, WarningKind};
 use kclvm_sema::plugin::PLUGIN_MODULE_PREFIX;
-use kclvm_utils::path::PathPrefix;
+use kclvm_utils::path::{normalize_path, PathPrefix};
 use kclvm_utils::pkgpath::parse_external_pkg_name;
 use kclvm_utils::pkgpath::rm_external_pkg_name;
 
 use anyhow::Result;
 use lexer::parse_token_streams;
 use parser::Parser;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 use kclvm_span::create_session_globals_then;
 
@@ -72,6 +90,14 @@ impl PkgInfo {
 pub enum ParseMode {
     Null,
     ParseComments,
+    /// Parse comments and report unbalanced delimiters e.g., `{a=2}}` and `((2+3)`
+    /// as errors instead of the silent recovery, which is useful for the tools that
+    /// should reject malformed inputs.
+    Strict,
+    /// Parse comments and attach the interior comments of the list and config expressions
+    /// to the nearest nodes in [ast::Module::expr_comments], which is useful for the tools
+    /// that should keep the comments inside collections e.g., the formatter.
+    AttachComments,
 }
 
 /// LoadProgramResult denotes the result of the whole program and a topological
@@ -80,10 +106,67 @@ pub enum ParseMode {
 pub struct LoadProgramResult {
     /// Program AST
     pub program: ast::Program,
-    /// Parse errors
+    /// Parse errors, which contain the diagnostics of all the levels including warnings.
     pub errors: Errors,
+    /// Parse warnings, which are the subset of [LoadProgramResult::errors] with the warning level.
+    pub warnings: Errors,
     /// The topological ordering of all known files.
     pub paths: Vec<PathBuf>,
+    /// All the files read from the disk during the load including the KCL source
+    /// files and the package manifests e.g., `kcl.mod` and `kcl.mod.lock`. Sources
+    /// provided by the code list or the source code cache are not included.
+    pub inputs_read: Vec<PathBuf>,
+    /// The time spent lexing and parsing each file, sorted in descending order of the
+    /// duration. It is only collected when [LoadProgramOptions::collect_timings] is set,
+    /// and files reused from the module cache are not included.
+    pub parse_timings: Vec<(PathBuf, Duration)>,
+    /// The files whose AST is reused from the module cache instead of being parsed
+    /// again, in the load order.
+    pub cached_files: Vec<PathBuf>,
+}
+
+impl LoadProgramResult {
+    /// Normalize all the paths in the result and the diagnostic filenames with
+    /// [kclvm_utils::path::normalize_path], which is applied by [load_program]. When
+    /// `forward_slash` is true, the paths are made consistent across the platforms e.g.,
+    /// without the Windows verbatim prefix and with the forward slashes, otherwise the
+    /// paths are kept as they are. The module filenames of the program and the AST
+    /// positions are normalized when the files are parsed.
+    pub fn normalize_paths(&mut self, forward_slash: bool) {
+        let normalize = |path: &PathBuf| -> PathBuf {
+            normalize_path(&path.to_string_lossy(), forward_slash).into()
+        };
+        for path in self
+            .paths
+            .iter_mut()
+            .chain(self.inputs_read.iter_mut())
+            .chain(self.cached_files.iter_mut())
+            .chain(self.parse_timings.iter_mut().map(|(path, _)| path))
+        {
+            *path = normalize(path);
+        }
+        self.errors = normalize_diagnostic_paths(std::mem::take(&mut self.errors), forward_slash);
+        self.warnings =
+            normalize_diagnostic_paths(std::mem::take(&mut self.warnings), forward_slash);
+    }
+}
+
+/// Normalize the filenames of the message ranges and the related ranges of the diagnostics.
+fn normalize_diagnostic_paths(errors: Errors, forward_slash: bool) -> Errors {
+    errors
+        .into_iter()
+        .map(|mut diag| {
+            for msg in &mut diag.messages {
+                let ranges = std::iter::once(&mut msg.range)
+                    .chain(msg.related.iter_mut().map(|(range, _)| range));
+                for (start, end) in ranges {
+                    start.filename = normalize_path(&start.filename, forward_slash);
+                    end.filename = normalize_path(&end.filename, forward_slash);
+                }
+            }
+            diag
+        })
+        .collect()
 }
 
 /// ParseFileResult denotes the result of a single file including AST,
@@ -145,7 +228,9 @@ pub fn parse_single_file(filename: &str, code: Option<String>) -> Result<ParseFi
 /// Parse a KCL file to the AST module and return errors when meets parse errors as result.
 pub fn parse_file_force_errors(filename: &str, code: Option<String>) -> Result<ast::Module> {
     let sess = Arc::new(ParseSession::default());
-    let result = parse_file_with_global_session(sess.clone(), filename, code);
+    let result = create_session_globals_then(|| {
+        parse_file_with_session_and_mode(sess.clone(), filename, code, ParseMode::Strict)
+    });
     if sess.0.diag_handler.has_errors()? {
         let err = sess
             .0
@@ -157,11 +242,157 @@ pub fn parse_file_force_errors(filename: &str, code: Option<String>) -> Result<a
     }
 }
 
+/// Parse only the leading import statements of a KCL file, which stops at the first
+/// statement which is not an import statement and is much faster than parsing the
+/// whole file when only the import dependencies are needed. The import statements
+/// keep their source ranges and an error is returned when meets parse errors in the
+/// leading import statements, the errors after them e.g., the lex errors are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::parse_imports;
+///
+/// let imports = parse_imports("main.k", Some("import a.b\nimport c as d\nx = 1".to_string())).unwrap();
+/// assert_eq!(imports.len(), 2);
+/// assert_eq!(imports[0].node.path.node, "a.b");
+/// assert_eq!(imports[1].node.name, "d");
+/// ```
+pub fn parse_imports(
+    filename: &str,
+    code: Option<String>,
+) -> Result<Vec<ast::NodeRef<ast::ImportStmt>>> {
+    let src = match code {
+        Some(src) => src,
+        None => std::fs::read_to_string(filename).map_err(|err| {
+            anyhow::anyhow!("Failed to load KCL file '{filename}'. Because '{err}'")
+        })?,
+    };
+    let (mut sess, mut imports, end) = parse_leading_imports(filename, src.clone());
+    // The whole file is lexed before parsing, so the errors after the leading import
+    // statements are dropped by parsing the source prefix of the import statements again,
+    // which keeps the same source ranges.
+    if sess.0.diag_handler.has_errors()? && end < src.len() {
+        (sess, imports, _) = parse_leading_imports(filename, src[..end].to_string());
+    }
+    if sess.0.diag_handler.has_errors()? {
+        let err = sess
+            .0
+            .emit_nth_diag_into_string(0)?
+            .unwrap_or(Ok(ErrorKind::InvalidSyntax.name()))?;
+        Err(anyhow::anyhow!(err))
+    } else {
+        Ok(imports)
+    }
+}
+
+/// Parse the leading import statements of the source, and return the parse session,
+/// the import statements and the byte offset where the import statements end.
+fn parse_leading_imports(
+    filename: &str,
+    src: String,
+) -> (ParseSessionRef, Vec<ast::NodeRef<ast::ImportStmt>>, usize) {
+    let sess = Arc::new(ParseSession::default());
+    let (imports, end) = create_session_globals_then(|| {
+        let sf = sess
+            .0
+            .sm
+            .new_source_file(PathBuf::from(filename).into(), src);
+        let src_from_sf = match sf.src.as_ref() {
+            Some(src) => src,
+            None => bug!("Internal Bug: Failed to load KCL file '{filename}'."),
+        };
+        let stream = parse_token_streams(&sess, src_from_sf.as_str(), sf.start_pos);
+        let mut p = parser::Parser::new(&sess, stream);
+        let imports = p.parse_imports();
+        let end = (p.token.span.lo().0 - sf.start_pos.0) as usize;
+        (imports, end)
+    });
+    (sess, imports, end)
+}
+
+/// LexedToken denotes a token produced by [lex_tokens] with its kind, source text and range.
+#[derive(Debug, Clone)]
+pub struct LexedToken {
+    /// The token kind name e.g., `identifier`, `string`, `newline`, `indent` and `dedent`.
+    pub kind: String,
+    /// The source text of the token, which is empty for the layout tokens e.g., `indent`.
+    pub text: String,
+    /// The source range of the token.
+    pub range: Range,
+}
+
+/// Lex a KCL file to the token stream including the layout tokens `newline`, `indent`
+/// and `dedent`, which is useful for investigating the lexer and parser issues. An error
+/// is returned when meets lex errors.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::lex_tokens;
+///
+/// let tokens = lex_tokens("main.k", Some("a = 1\n".to_string())).unwrap();
+/// let kinds: Vec<&str> = tokens.iter().map(|t| t.kind.as_str()).collect();
+/// assert_eq!(kinds[..4], ["identifier", "=", "integer", "newline"]);
+/// assert_eq!(tokens[0].text, "a");
+/// ```
+pub fn lex_tokens(filename: &str, code: Option<String>) -> Result<Vec<LexedToken>> {
+    let src = match code {
+        Some(src) => src,
+        None => std::fs::read_to_string(filename).map_err(|err| {
+            anyhow::anyhow!("Failed to load KCL file '{filename}'. Because '{err}'")
+        })?,
+    };
+    let sess = Arc::new(ParseSession::default());
+    let tokens = create_session_globals_then(|| {
+        let sf = sess
+            .0
+            .sm
+            .new_source_file(PathBuf::from(filename).into(), src);
+        let src_from_sf = match sf.src.as_ref() {
+            Some(src) => src,
+            None => bug!("Internal Bug: Failed to load KCL file '{filename}'."),
+        };
+        let stream = parse_token_streams(&sess, src_from_sf.as_str(), sf.start_pos);
+        stream
+            .cursor()
+            .map(|token| LexedToken {
+                kind: token.kind.into(),
+                text: sess.span_to_snippet(token.span),
+                range: (
+                    sess.lookup_char_pos(token.span.lo()).into(),
+                    sess.lookup_char_pos(token.span.hi()).into(),
+                ),
+            })
+            .collect::<Vec<LexedToken>>()
+    });
+    if sess.0.diag_handler.has_errors()? {
+        let err = sess
+            .0
+            .emit_nth_diag_into_string(0)?
+            .unwrap_or(Ok(ErrorKind::InvalidSyntax.name()))?;
+        Err(anyhow::anyhow!(err))
+    } else {
+        Ok(tokens)
+    }
+}
+
 /// Parse a KCL file to the AST module with the parse session .
+#[inline]
 pub fn parse_file_with_session(
     sess: ParseSessionRef,
     filename: &str,
     code: Option<String>,
+) -> Result<ast::Module> {
+    parse_file_with_session_and_mode(sess, filename, code, ParseMode::ParseComments)
+}
+
+/// Parse a KCL file to the AST module with the parse session and the parse mode.
+pub fn parse_file_with_session_and_mode(
+    sess: ParseSessionRef,
+    filename: &str,
+    code: Option<String>,
+    mode: ParseMode,
 ) -> Result<ast::Module> {
     // Code source.
     let src = if let Some(s) = code {
@@ -178,10 +409,20 @@ pub fn parse_file_with_session(
     };
 
     // Build a source map to store file sources.
-    let sf = sess
+    let mut sf = sess
         .0
         .sm
-        .new_source_file(PathBuf::from(filename).into(), src);
+        .new_source_file(PathBuf::from(filename).into(), src.clone());
+    // The source map returns the existing source file with the same filename, which may
+    // hold the stale content e.g., the disk content or the previous overlay code. Register
+    // the code as a new version of the file in the same source map, which is displayed
+    // as the same filename, so that all the positions of the session point at it.
+    if !sf.src_hash.matches(&src) {
+        sf = sess
+            .0
+            .sm
+            .new_source_file(versioned_file_name(filename, &src), src.clone());
+    }
 
     let src_from_sf = match sf.src.as_ref() {
         Some(src) => src,
@@ -192,16 +433,29 @@ pub fn parse_file_with_session(
         }
     };
 
+    let attach_comments = matches!(mode, ParseMode::AttachComments);
     // Lexer
-    let stream = lexer::parse_token_streams(&sess, src_from_sf.as_str(), sf.start_pos);
+    let stream =
+        lexer::parse_token_streams_with_mode(&sess, src_from_sf.as_str(), sf.start_pos, mode);
     // Parser
     let mut p = parser::Parser::new(&sess, stream);
     let mut m = p.parse_module();
+    if attach_comments {
+        m.expr_comments = p.attach_expr_comments();
+    }
     m.filename = filename.to_string().adjust_canonicalization();
 
     Ok(m)
 }
 
+/// The file name of the code version of the file in the source map, which is displayed
+/// as the filename while the versions of different code are distinct source files.
+fn versioned_file_name(filename: &str, code: &str) -> rustc_span::FileName {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    rustc_span::FileName::DocTest(PathBuf::from(filename), hasher.finish() as isize)
+}
+
 /// Parse a KCL file to the AST module with the parse session and the global session
 #[inline]
 pub fn parse_file_with_global_session(
@@ -250,6 +504,54 @@ pub fn parse_expr(src: &str) -> Option<ast::NodeRef<ast::Expr>> {
     }
 }
 
+/// Parse a source string to a type e.g., `int|str` or `[{str:float}]`. When input
+/// empty string or the source string is not a valid type, it will return [None].
+///
+/// # Examples
+/// ```
+/// use kclvm_ast::ast;
+/// use kclvm_parser::parse_type;
+///
+/// let ty = parse_type("int|str").unwrap();
+/// assert!(matches!(ty.node, ast::Type::Union(_)));
+/// let ty = parse_type("[{str:float}]").unwrap();
+/// assert!(matches!(ty.node, ast::Type::List(_)));
+/// assert!(parse_type("").is_none());
+/// assert!(parse_type("[int").is_none());
+/// ```
+pub fn parse_type(src: &str) -> Option<ast::NodeRef<ast::Type>> {
+    if src.trim().is_empty() {
+        None
+    } else {
+        let sess = Arc::new(Session::default());
+        let sf = sess
+            .sm
+            .new_source_file(PathBuf::from("").into(), src.to_string());
+        let src_from_sf = match sf.src.as_ref() {
+            Some(src) => src,
+            None => {
+                bug!("Internal Bug: Failed to load KCL file.");
+            }
+        };
+
+        let sess = &&ParseSession::with_session(sess);
+
+        let (ty, is_complete) = create_session_globals_then(|| {
+            let stream = parse_token_streams(sess, src_from_sf.as_str(), new_byte_pos(0));
+            let mut parser = Parser::new(sess, stream);
+            let ty = parser.parse_type_annotation();
+            parser.skip_newlines();
+            // Any tokens left denote the source string is not a single type.
+            (ty, parser.token.kind == kclvm_ast::token::TokenKind::Eof)
+        });
+        if is_complete && !sess.0.diag_handler.has_errors().unwrap_or(true) {
+            Some(ty)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadProgramOptions {
     pub work_dir: String,
@@ -262,6 +564,28 @@ pub struct LoadProgramOptions {
     pub load_packages: bool,
     /// Whether to load plugins
     pub load_plugins: bool,
+    /// Whether to collect the parse time of each file into [LoadProgramResult::parse_timings].
+    pub collect_timings: bool,
+    /// Whether to skip the missing entry files with a diagnostic instead of returning an error,
+    /// and an empty program is loaded when all the entry files are missing. It is useful for
+    /// the editors which may load a file that has been removed or renamed.
+    pub tolerate_missing_entry: bool,
+    /// The provider through which the loader reads the source files and discovers the
+    /// packages, defaults to the [FileSystemProvider].
+    pub source_provider: SourceProviderRef,
+    /// The compiler edition e.g., `2024`, which can be overridden by the `language_edition`
+    /// field in the `[compile]` section of the package `kcl.mod`. Defaults to [Edition::CURRENT].
+    pub edition: Option<String>,
+    /// Whether to compile all the `.k` files except the tests e.g., `main_test.k` at the top
+    /// level of an input directory together as the main package whose root is the directory,
+    /// regardless of any `kcl.mod` found in the directory or its parents. The subdirectories are
+    /// not compiled except imported as the packages, and an input glob pattern e.g., `dir/**/*.k`
+    /// can be used to compile the files in the nested directories.
+    pub treat_dir_as_package: bool,
+    /// Whether to use the forward slashes as the path separators in the paths of the load
+    /// result, the module filenames of the program and the diagnostic filenames on Windows,
+    /// see [LoadProgramResult::normalize_paths].
+    pub forward_slash_paths: bool,
 }
 
 impl Default for LoadProgramOptions {
@@ -274,6 +598,12 @@ impl Default for LoadProgramOptions {
             mode: ParseMode::ParseComments,
             load_packages: true,
             load_plugins: false,
+            collect_timings: false,
+            tolerate_missing_entry: false,
+            source_provider: Arc::new(FileSystemProvider),
+            edition: None,
+            treat_dir_as_package: false,
+            forward_slash_paths: false,
         }
     }
 }
@@ -309,6 +639,54 @@ pub fn load_program(
     Loader::new(sess, paths, opts, module_cache).load_main()
 }
 
+/// Load each KCL entry as an independent program and return the per-entry
+/// load results in the order of `entries`. Every entry is loaded with a fresh
+/// parse session, so the failure of one entry does not affect the others.
+///
+/// The parsed modules are shared across entries through `module_cache`, if it
+/// is None, a temporary module cache shared by this batch will be used. Files
+/// with diagnostics are evicted from the cache after each entry to ensure that
+/// all the entries depending on them report the same errors.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::load_programs;
+///
+/// let results = load_programs(&["./testdata/import-01.k", "./testdata/not_found.k"], None, None);
+/// assert_eq!(results.len(), 2);
+/// assert!(results[0].1.is_ok());
+/// assert!(results[1].1.is_err());
+/// ```
+pub fn load_programs(
+    entries: &[&str],
+    opts: Option<LoadProgramOptions>,
+    module_cache: Option<KCLModuleCache>,
+) -> Vec<(String, Result<LoadProgramResult>)> {
+    let module_cache = module_cache.unwrap_or_default();
+    entries
+        .iter()
+        .map(|entry| {
+            let result = load_program(
+                ParseSessionRef::default(),
+                &[entry],
+                opts.clone(),
+                Some(module_cache.clone()),
+            );
+            if let Ok(result) = &result {
+                if let Ok(mut m_cache) = module_cache.write() {
+                    for diag in &result.errors {
+                        for msg in &diag.messages {
+                            m_cache.clear(&PathBuf::from(&msg.range.0.filename));
+                        }
+                    }
+                }
+            }
+            (entry.to_string(), result)
+        })
+        .collect()
+}
+
 pub type KCLModuleCache = Arc<RwLock<ModuleCache>>;
 
 #[derive(Default, Debug)]
@@ -321,20 +699,68 @@ pub struct ModuleCache {
     pub dep_cache: IndexMap<PkgFile, PkgMap>,
     /// File source code
     pub source_code: IndexMap<PathBuf, String>,
+    /// The content hash of the source from which the file ast is parsed.
+    pub content_hash: IndexMap<PathBuf, String>,
 
     pub last_compile_input: (Vec<String>, Option<LoadProgramOptions>),
 }
 
 impl ModuleCache {
+    /// Clear the cache of the file, the path may be either the path used to load the
+    /// file or the normalized module filename e.g., with the forward slashes on Windows.
     pub fn clear(&mut self, path: &PathBuf) {
-        self.ast_cache.remove(path);
-        self.source_code.remove(path);
-        if let Some(pkgs) = self.file_pkg.remove(path) {
-            for pkg in &pkgs {
-                self.dep_cache.remove(pkg);
+        for path in self.cache_keys(path) {
+            self.ast_cache.remove(&path);
+            self.source_code.remove(&path);
+            self.content_hash.remove(&path);
+            if let Some(pkgs) = self.file_pkg.remove(&path) {
+                for pkg in &pkgs {
+                    self.dep_cache.remove(pkg);
+                }
             }
         }
     }
+
+    /// Get the cached AST of the file parsed with the same path options. The cached AST
+    /// whose module filename differs from the filename normalized with `forward_slash`
+    /// e.g., parsed by a load with another [LoadProgramOptions::forward_slash_paths],
+    /// is treated as a cache miss, so the file is parsed again with the new filename.
+    pub(crate) fn get_ast(
+        &self,
+        path: &PathBuf,
+        forward_slash: bool,
+    ) -> Option<&Arc<RwLock<ast::Module>>> {
+        let m = self.ast_cache.get(path)?;
+        let filename = normalize_path(&path.to_string_lossy(), forward_slash);
+        (m.read().ok()?.filename == filename).then_some(m)
+    }
+
+    /// Get the cached source code of the file by the path used to load the file or
+    /// its normalized filename.
+    pub(crate) fn get_source(&self, path: &PathBuf) -> Option<&String> {
+        self.source_code.get(path).or_else(|| {
+            self.source_code.get(&PathBuf::from(normalize_path(
+                &path.to_string_lossy(),
+                true,
+            )))
+        })
+    }
+
+    /// Get the cache keys of the file which are the same path after the normalization.
+    fn cache_keys(&self, path: &PathBuf) -> Vec<PathBuf> {
+        let normalized = normalize_path(&path.to_string_lossy(), true);
+        let mut keys: IndexSet<PathBuf> = self
+            .ast_cache
+            .keys()
+            .chain(self.source_code.keys())
+            .chain(self.content_hash.keys())
+            .chain(self.file_pkg.keys())
+            .filter(|key| normalize_path(&key.to_string_lossy(), true) == normalized)
+            .cloned()
+            .collect();
+        keys.insert(path.clone());
+        keys.into_iter().collect()
+    }
 }
 struct Loader {
     sess: ParseSessionRef,
@@ -373,7 +799,7 @@ impl Loader {
     }
 
     fn _load_main(&mut self) -> Result<LoadProgramResult> {
-        parse_program(
+        let mut result = parse_program(
             self.sess.clone(),
             self.paths.clone(),
             self.module_cache.clone(),
@@ -381,7 +807,9 @@ impl Loader {
             &mut self.pkgmap,
             &mut self.parsed_file,
             &self.opts,
-        )
+        )?;
+        result.normalize_paths(self.opts.forward_slash_paths);
+        Ok(result)
     }
 }
 
@@ -424,6 +852,81 @@ fn fix_rel_import_path_with_file(
     }
 }
 
+/// The classification of an import path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    /// The standard system module e.g., `import math`.
+    Builtin,
+    /// The plugin module e.g., `import kcl_plugin.hello`.
+    Plugin,
+    /// The module in the current package e.g., `import .sub` or `import path.to.sub`.
+    Internal,
+    /// The module in an external package e.g., `import k8s.api.core.v1`.
+    External,
+}
+
+/// An import path normalized in the same way as the loader does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedImport {
+    /// The resolved dotted path e.g., `path.to.sub` for `import ..sub` in `path/to/app/main.k`.
+    pub path: String,
+    /// Whether the raw import path is a relative path e.g., `.sub`.
+    pub is_relative: bool,
+    /// The classification of the import path.
+    pub kind: ImportKind,
+    /// The external package name e.g., `k8s`, only set for the [ImportKind::External] kind.
+    pub external_pkg_name: Option<String>,
+}
+
+/// Normalize the raw import path in `current_file` of the package located at `current_pkg`
+/// into the dotted path and classify it. A path is classified as [ImportKind::Internal]
+/// when it is relative or it is found under `current_pkg` by the source `provider` e.g.,
+/// the [LoadProgramOptions::source_provider], otherwise it is treated as an external
+/// package import.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{normalize_import_path, FileSystemProvider, ImportKind};
+///
+/// let provider = FileSystemProvider;
+/// let import = normalize_import_path(&provider, "/konfig", "/konfig/path/to/app/main.k", "..sub");
+/// assert_eq!(import.path, "path.to.sub");
+/// assert_eq!(import.kind, ImportKind::Internal);
+/// let import = normalize_import_path(&provider, "/konfig", "/konfig/path/to/app/main.k", "math");
+/// assert_eq!(import.kind, ImportKind::Builtin);
+/// ```
+pub fn normalize_import_path(
+    provider: &dyn SourceProvider,
+    current_pkg: &str,
+    current_file: &str,
+    raw_path: &str,
+) -> NormalizedImport {
+    let raw_path = raw_path.trim();
+    let is_relative = kclvm_config::vfs::is_rel_pkgpath(raw_path);
+    let path = kclvm_config::vfs::fix_import_path(current_pkg, current_file, raw_path);
+    let kind = if is_plugin_pkg(&path) {
+        ImportKind::Plugin
+    } else if is_builtin_pkg(&path) {
+        ImportKind::Builtin
+    } else if is_relative || pkg_exists_in_path(provider, current_pkg, &path) {
+        ImportKind::Internal
+    } else {
+        ImportKind::External
+    };
+    let external_pkg_name = if kind == ImportKind::External {
+        parse_external_pkg_name(&path).ok()
+    } else {
+        None
+    };
+    NormalizedImport {
+        path,
+        is_relative,
+        kind,
+        external_pkg_name,
+    }
+}
+
 fn is_plugin_pkg(pkgpath: &str) -> bool {
     pkgpath.starts_with(PLUGIN_MODULE_PREFIX)
 }
@@ -456,6 +959,7 @@ fn find_packages(
                     message: format!("the plugin package `{}` is not found, please confirm if plugin mode is enabled", pkg_path),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
         }
@@ -468,7 +972,27 @@ fn find_packages(
     }
 
     // 1. Look for in the current package's directory.
-    let is_internal = is_internal_pkg(pkg_name, pkg_root, pkg_path)?;
+    let is_internal = is_internal_pkg(pkg_name, pkg_root, pkg_path, opts)?;
+    if is_internal.is_some() {
+        if let Some(actual_pkg_path) =
+            pkg_case_mismatch_in_path(opts.source_provider.as_ref(), pkg_root, pkg_path)
+        {
+            sess.1.write().add_warning(
+                WarningKind::CompilerWarning,
+                &[Message {
+                    range: Into::<Range>::into(pos.clone()),
+                    style: Style::Line,
+                    message: format!(
+                        "the package `{}` is resolved to `{}` with a different case, which is not found on the case-sensitive file systems",
+                        pkg_path, actual_pkg_path
+                    ),
+                    note: Some(format!("Consider importing `{}` instead", actual_pkg_path)),
+                    suggested_replacement: None,
+                    ..Default::default()
+                }],
+            );
+        }
+    }
     // 2. Look for in the vendor path.
     let is_external = is_external_pkg(pkg_path, opts)?;
 
@@ -485,6 +1009,7 @@ fn find_packages(
                 ),
                 note: None,
                 suggested_replacement: None,
+                ..Default::default()
             }],
         );
         return Ok(None);
@@ -503,6 +1028,7 @@ fn find_packages(
                     message: format!("pkgpath {} not found in the program", pkg_path),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
             let mut suggestions = vec![format!("browse more packages at 'https://artifacthub.io'")];
@@ -528,19 +1054,58 @@ fn find_packages(
 ///
 /// All paths in [`pkgpath`] must contain the kcl.mod file.
 /// It returns the parent directory of kcl.mod if present, or none if not.
-fn pkg_exists(pkgroots: &[String], pkgpath: &str) -> Option<String> {
+fn pkg_exists(provider: &dyn SourceProvider, pkgroots: &[String], pkgpath: &str) -> Option<String> {
     pkgroots
         .into_iter()
-        .find(|root| pkg_exists_in_path(root, pkgpath))
+        .find(|root| pkg_exists_in_path(provider, root, pkgpath))
         .cloned()
 }
 
 /// Search for [`pkgpath`] under [`path`].
 /// It only returns [`true`] if [`path`]/[`pkgpath`] or [`path`]/[`pkgpath.k`] exists.
-fn pkg_exists_in_path(path: &str, pkgpath: &str) -> bool {
+fn pkg_exists_in_path(provider: &dyn SourceProvider, path: &str, pkgpath: &str) -> bool {
     let mut pathbuf = PathBuf::from(path);
     pkgpath.split('.').for_each(|s| pathbuf.push(s));
-    pathbuf.exists() || pathbuf.with_extension(KCL_FILE_EXTENSION).exists()
+    provider.exists(&pathbuf) || provider.exists(&pathbuf.with_extension(KCL_FILE_EXTENSION))
+}
+
+/// Search for [`pkgpath`] under [`path`] by comparing the directory entries case-insensitively.
+/// It returns the dotted package path with the case on the disk e.g., `mypkg` for `MyPkg` only
+/// when the case of any path segment is different from [`pkgpath`], which can be resolved on the
+/// case-insensitive file systems (macOS and Windows) but not on the case-sensitive ones (Linux).
+fn pkg_case_mismatch_in_path(
+    provider: &dyn SourceProvider,
+    path: &str,
+    pkgpath: &str,
+) -> Option<String> {
+    let mut pathbuf = PathBuf::from(path);
+    let names: Vec<&str> = pkgpath.split('.').collect();
+    let mut actual_names = vec![];
+    let mut mismatched = false;
+    for (i, name) in names.iter().enumerate() {
+        let mut candidates = vec![name.to_string()];
+        if i == names.len() - 1 {
+            candidates.push(format!("{}{}", name, KCL_FILE_SUFFIX));
+        }
+        let entries: Vec<String> = provider
+            .list_dir(&pathbuf)
+            .ok()?
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        let entry = match entries.iter().find(|e| candidates.contains(e)) {
+            Some(entry) => entry,
+            None => {
+                mismatched = true;
+                entries
+                    .iter()
+                    .find(|e| candidates.iter().any(|c| c.eq_ignore_ascii_case(e)))?
+            }
+        };
+        pathbuf.push(entry);
+        actual_names.push(entry.trim_end_matches(KCL_FILE_SUFFIX).to_string());
+    }
+    mismatched.then(|| actual_names.join("."))
 }
 
 /// Look for [`pkgpath`] in the current package's [`pkgroot`].
@@ -549,15 +1114,21 @@ fn pkg_exists_in_path(path: &str, pkgpath: &str) -> bool {
 /// # Error
 ///
 /// [`is_internal_pkg`] will return an error if the package's source files cannot be found.
-fn is_internal_pkg(pkg_name: &str, pkg_root: &str, pkg_path: &str) -> Result<Option<PkgInfo>> {
-    match pkg_exists(&[pkg_root.to_string()], pkg_path) {
+fn is_internal_pkg(
+    pkg_name: &str,
+    pkg_root: &str,
+    pkg_path: &str,
+    opts: &LoadProgramOptions,
+) -> Result<Option<PkgInfo>> {
+    let provider = opts.source_provider.as_ref();
+    match pkg_exists(provider, &[pkg_root.to_string()], pkg_path) {
         Some(internal_pkg_root) => {
             let fullpath = if pkg_name == kclvm_ast::MAIN_PKG {
                 pkg_path.to_string()
             } else {
                 format!("{}.{}", pkg_name, pkg_path)
             };
-            let k_files = get_pkg_kfile_list(pkg_root, pkg_path)?;
+            let k_files = get_pkg_kfile_list(provider, pkg_root, pkg_path)?;
             Ok(Some(PkgInfo::new(
                 pkg_name.to_string(),
                 internal_pkg_root,
@@ -569,7 +1140,11 @@ fn is_internal_pkg(pkg_name: &str, pkg_root: &str, pkg_path: &str) -> Result<Opt
     }
 }
 
-fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
+fn get_pkg_kfile_list(
+    provider: &dyn SourceProvider,
+    pkgroot: &str,
+    pkgpath: &str,
+) -> Result<Vec<String>> {
     // plugin pkgs
     if is_plugin_pkg(pkgpath) {
         return Ok(Vec::new());
@@ -591,16 +1166,16 @@ fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
         pathbuf.push(s);
     }
 
-    let abspath = match pathbuf.canonicalize() {
+    let abspath = match provider.canonicalize(&pathbuf) {
         Ok(p) => p.to_str().unwrap().to_string(),
         Err(_) => pathbuf.as_path().to_str().unwrap().to_string(),
     };
-    if std::path::Path::new(abspath.as_str()).exists() {
-        return get_dir_files(abspath.as_str());
+    if provider.exists(Path::new(abspath.as_str())) {
+        return get_dir_files(provider, abspath.as_str());
     }
 
     let as_k_path = abspath + KCL_FILE_SUFFIX;
-    if std::path::Path::new((as_k_path).as_str()).exists() {
+    if provider.exists(Path::new(as_k_path.as_str())) {
         return Ok(vec![as_k_path]);
     }
 
@@ -608,30 +1183,28 @@ fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
 }
 
 /// Get file list in the directory.
-fn get_dir_files(dir: &str) -> Result<Vec<String>> {
-    if !std::path::Path::new(dir).exists() {
+fn get_dir_files(provider: &dyn SourceProvider, dir: &str) -> Result<Vec<String>> {
+    if !provider.exists(Path::new(dir)) {
         return Ok(Vec::new());
     }
 
     let mut list = Vec::new();
-    for path in std::fs::read_dir(dir)? {
-        let path = path?;
-        if !path
-            .file_name()
-            .to_str()
-            .unwrap()
-            .ends_with(KCL_FILE_SUFFIX)
-        {
+    for path in provider.list_dir(Path::new(dir))? {
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+        if !file_name.ends_with(KCL_FILE_SUFFIX) {
             continue;
         }
-        if path.file_name().to_str().unwrap().ends_with("_test.k") {
+        if file_name.ends_with("_test.k") {
             continue;
         }
-        if path.file_name().to_str().unwrap().starts_with('_') {
+        if file_name.starts_with('_') {
             continue;
         }
 
-        let s = format!("{}", path.path().display());
+        let s = format!("{}", path.display());
         list.push(s);
     }
 
@@ -648,23 +1221,25 @@ fn get_dir_files(dir: &str) -> Result<Vec<String>> {
 /// - The name of the external package could not be resolved from [`pkg_path`].
 fn is_external_pkg(pkg_path: &str, opts: &LoadProgramOptions) -> Result<Option<PkgInfo>> {
     let pkg_name = parse_external_pkg_name(pkg_path)?;
+    let provider = opts.source_provider.as_ref();
     let external_pkg_root = if let Some(root) = opts.package_maps.get(&pkg_name) {
         PathBuf::from(root).join(KCL_MOD_FILE)
     } else {
-        match pkg_exists(&opts.vendor_dirs, pkg_path) {
+        match pkg_exists(provider, &opts.vendor_dirs, pkg_path) {
             Some(path) => PathBuf::from(path).join(&pkg_name).join(KCL_MOD_FILE),
             None => return Ok(None),
         }
     };
 
-    if external_pkg_root.exists() {
+    if provider.exists(&external_pkg_root) {
         return Ok(Some(match external_pkg_root.parent() {
             Some(root) => {
-                let abs_root: String = match root.canonicalize() {
+                let abs_root: String = match provider.canonicalize(root) {
                     Ok(p) => p.to_str().unwrap().to_string(),
                     Err(_) => root.display().to_string(),
                 };
-                let k_files = get_pkg_kfile_list(&abs_root, &rm_external_pkg_name(pkg_path)?)?;
+                let k_files =
+                    get_pkg_kfile_list(provider, &abs_root, &rm_external_pkg_name(pkg_path)?)?;
                 PkgInfo::new(
                     pkg_name.to_string(),
                     abs_root,
@@ -691,16 +1266,38 @@ pub fn parse_file(
     pkgmap: &mut PkgMap,
     file_graph: FileGraphCache,
     opts: &LoadProgramOptions,
+    parse_timings: &mut Vec<(PathBuf, Duration)>,
 ) -> Result<Vec<PkgFile>> {
     let src = match src {
         Some(src) => Some(src),
         None => match &module_cache.read() {
-            Ok(cache) => cache.source_code.get(file.get_path()),
+            Ok(cache) => cache.get_source(file.get_path()),
             Err(_) => None,
         }
         .cloned(),
     };
-    let m = parse_file_with_session(sess.clone(), file.get_path().to_str().unwrap(), src)?;
+    let src = match src {
+        Some(src) => src,
+        None => opts.source_provider.read(file.get_path()).map_err(|err| {
+            anyhow::anyhow!(
+                "Failed to load KCL file '{}'. Because '{err}'",
+                file.get_path().display()
+            )
+        })?,
+    };
+    let src_hash = module_cache::content_hash(src.as_bytes());
+    let start = opts.collect_timings.then(Instant::now);
+    // Parse with the normalized filename, which is used by the AST positions and thus by
+    // the resolver and runtime diagnostics.
+    let m = parse_file_with_session_and_mode(
+        sess.clone(),
+        &normalize_path(file.get_path().to_str().unwrap(), opts.forward_slash_paths),
+        Some(src),
+        opts.mode.clone(),
+    )?;
+    if let Some(start) = start {
+        parse_timings.push((file.get_path().clone(), start.elapsed()));
+    }
     let deps = get_deps(&file, &m, pkgs, pkgmap, opts, sess)?;
     let dep_files = deps.keys().map(|f| f.clone()).collect();
     pkgmap.extend(deps.clone());
@@ -709,6 +1306,9 @@ pub fn parse_file(
             module_cache
                 .ast_cache
                 .insert(file.get_path().clone(), Arc::new(RwLock::new(m)));
+            module_cache
+                .content_hash
+                .insert(file.get_path().clone(), src_hash);
             match module_cache.file_pkg.get_mut(&file.get_path().clone()) {
                 Some(s) => {
                     s.insert(file.clone());
@@ -791,6 +1391,7 @@ pub fn parse_pkg(
     pkgmap: &mut PkgMap,
     file_graph: FileGraphCache,
     opts: &LoadProgramOptions,
+    parse_timings: &mut Vec<(PathBuf, Duration)>,
 ) -> Result<Vec<PkgFile>> {
     let mut dependent = vec![];
     for (file, src) in files {
@@ -803,6 +1404,7 @@ pub fn parse_pkg(
             pkgmap,
             file_graph.clone(),
             opts,
+            parse_timings,
         )?;
         dependent.extend(deps);
     }
@@ -818,6 +1420,8 @@ pub fn parse_entry(
     file_graph: FileGraphCache,
     opts: &LoadProgramOptions,
     parsed_file: &mut HashSet<PkgFile>,
+    parse_timings: &mut Vec<(PathBuf, Duration)>,
+    cached_files: &mut Vec<PathBuf>,
 ) -> Result<HashSet<PkgFile>> {
     let k_files = entry.get_k_files();
     let maybe_k_codes = entry.get_k_codes();
@@ -843,6 +1447,7 @@ pub fn parse_entry(
         pkgmap,
         file_graph.clone(),
         opts,
+        parse_timings,
     )?;
     let mut unparsed_file: VecDeque<PkgFile> = dependent_paths.into();
 
@@ -868,8 +1473,9 @@ pub fn parse_entry(
 
         let module_cache_read = module_cache.read();
         match &module_cache_read {
-            Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
+            Ok(m_cache) => match m_cache.get_ast(file.get_path(), opts.forward_slash_paths) {
                 Some(m) => {
+                    cached_files.push(file.get_path().clone());
                     let deps = m_cache.dep_cache.get(&file).cloned().unwrap_or_else(|| {
                         get_deps(&file, &m.read().unwrap(), pkgs, pkgmap, opts, sess.clone())
                             .unwrap()
@@ -904,6 +1510,7 @@ pub fn parse_entry(
                         pkgmap,
                         file_graph.clone(),
                         &opts,
+                        parse_timings,
                     )?;
                     for dep in deps {
                         if parsed_file.insert(dep.clone()) {
@@ -927,6 +1534,61 @@ pub fn parse_program(
     parsed_file: &mut HashSet<PkgFile>,
     opts: &LoadProgramOptions,
 ) -> Result<LoadProgramResult> {
+    let mounted_opts = mount_archive_entries(&paths, opts)?;
+    let opts = mounted_opts.as_ref().unwrap_or(opts);
+    let paths = if opts.tolerate_missing_entry {
+        let (paths, missing_paths) = split_missing_entries(paths, opts);
+        for path in &missing_paths {
+            sess.1.write().add_error(
+                ErrorKind::CannotFindModule,
+                &[Message {
+                    range: (
+                        Position {
+                            filename: path.clone(),
+                            line: 1,
+                            column: None,
+                        },
+                        Position {
+                            filename: path.clone(),
+                            line: 1,
+                            column: None,
+                        },
+                    ),
+                    style: Style::Line,
+                    message: format!(
+                        "Cannot find the kcl file, please check the file path {}",
+                        path
+                    ),
+                    note: None,
+                    suggested_replacement: None,
+                    ..Default::default()
+                }],
+            );
+        }
+        if paths.is_empty() && !missing_paths.is_empty() {
+            let errors = sess.1.read().diagnostics.clone();
+            let warnings = errors.iter().filter(|d| d.is_warning()).cloned().collect();
+            return Ok(LoadProgramResult {
+                program: ast::Program {
+                    root: opts.work_dir.clone(),
+                    ..Default::default()
+                },
+                errors,
+                warnings,
+                paths: vec![],
+                inputs_read: vec![],
+                parse_timings: vec![],
+                cached_files: vec![],
+            });
+        }
+        paths
+    } else {
+        paths
+    };
+    let edition = match &opts.edition {
+        Some(edition) => edition.parse::<Edition>()?,
+        None => Edition::CURRENT,
+    };
     let compile_entries = get_compile_entries_from_paths(&paths, &opts)?;
     let workdir = compile_entries
         .get_root_path()
@@ -934,6 +1596,8 @@ pub fn parse_program(
         .adjust_canonicalization();
     let mut pkgs: HashMap<String, Vec<String>> = HashMap::new();
     let mut new_files = HashSet::new();
+    let mut parse_timings = vec![];
+    let mut cached_files = vec![];
     for entry in compile_entries.iter() {
         new_files.extend(parse_entry(
             sess.clone(),
@@ -944,6 +1608,8 @@ pub fn parse_program(
             file_graph.clone(),
             &opts,
             parsed_file,
+            &mut parse_timings,
+            &mut cached_files,
         )?);
     }
 
@@ -972,6 +1638,7 @@ pub fn parse_program(
                         ),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
             }
@@ -980,9 +1647,12 @@ pub fn parse_program(
         Err(e) => return Err(anyhow::anyhow!("Parse program failed: {e}")),
     };
 
+    let inputs_read = get_inputs_read(&files, &compile_entries, pkgmap, &module_cache, opts)?;
+    parse_timings.sort_by(|a, b| b.1.cmp(&a.1));
     let mut modules: HashMap<String, Arc<RwLock<Module>>> = HashMap::new();
+    let mut pkg_editions: HashMap<String, Edition> = HashMap::new();
     for file in files.iter() {
-        let filename = file.get_path().to_str().unwrap().to_string();
+        let filename = normalize_path(file.get_path().to_str().unwrap(), opts.forward_slash_paths);
         let m_ref = match module_cache.read() {
             Ok(module_cache) => module_cache
                 .ast_cache
@@ -999,6 +1669,17 @@ pub fn parse_program(
             let mut m = m_ref.write().unwrap();
             fix_rel_import_path_with_file(&pkg.pkg_root, &mut m, file, &pkgmap, opts, sess.clone());
         }
+        if let Some(pkg) = pkgmap.get(file) {
+            let file_edition = match pkg_editions.get(&pkg.pkg_root) {
+                Some(edition) => *edition,
+                None => {
+                    let pkg_edition = get_pkg_edition(&pkg.pkg_root, opts)?.unwrap_or(edition);
+                    pkg_editions.insert(pkg.pkg_root.clone(), pkg_edition);
+                    pkg_edition
+                }
+            };
+            check_module_edition(&m_ref.read().unwrap(), file_edition, sess.clone());
+        }
         modules.insert(filename.clone(), m_ref);
         match pkgs.get_mut(&file.pkg_path) {
             Some(pkg_modules) => {
@@ -1017,13 +1698,191 @@ pub fn parse_program(
         modules_not_imported: HashMap::new(),
     };
 
+    let errors = sess.1.read().diagnostics.clone();
+    let warnings = errors.iter().filter(|d| d.is_warning()).cloned().collect();
     Ok(LoadProgramResult {
         program,
-        errors: sess.1.read().diagnostics.clone(),
+        errors,
+        warnings,
         paths: files.iter().map(|file| file.get_path().clone()).collect(),
+        inputs_read,
+        parse_timings,
+        cached_files,
     })
 }
 
+/// Get the edition of the package from the `language_edition` field in the `[compile]` section
+/// of the package `kcl.mod`. It returns `None` when the package does not set the edition, and
+/// an error when the `kcl.mod` file can't be read or parsed.
+fn get_pkg_edition(pkg_root: &str, opts: &LoadProgramOptions) -> Result<Option<Edition>> {
+    if pkg_root.is_empty() {
+        return Ok(None);
+    }
+    let manifest = Path::new(pkg_root).join(KCL_MOD_FILE);
+    if !opts.source_provider.exists(&manifest) {
+        return Ok(None);
+    }
+    let content = opts
+        .source_provider
+        .read(&manifest)
+        .map_err(|err| anyhow::anyhow!("failed to read {}: {}", manifest.display(), err))?;
+    let mod_file = parse_mod_file(&content)
+        .map_err(|err| anyhow::anyhow!("failed to parse {}: {}", manifest.display(), err))?;
+    match mod_file
+        .compile
+        .and_then(|compile| compile.language_edition)
+    {
+        Some(edition) => edition
+            .parse::<Edition>()
+            .map(Some)
+            .map_err(|err| anyhow::anyhow!("{} in {}", err, manifest.display())),
+        None => Ok(None),
+    }
+}
+
+/// Report the syntax in the module which is not supported by the edition.
+fn check_module_edition(module: &Module, edition: Edition, sess: ParseSessionRef) {
+    if edition.supports_selective_import() {
+        return;
+    }
+    for stmt in &module.body {
+        if let ast::Stmt::Import(import_stmt) = &stmt.node {
+            if import_stmt.is_selective() {
+                sess.1.write().add_error(
+                    ErrorKind::InvalidSyntax,
+                    &[Message {
+                        range: stmt.get_span_pos(),
+                        style: Style::LineAndColumn,
+                        message: format!(
+                            "the selective import statement requires the edition {} or later, got {}",
+                            Edition::Edition2024,
+                            edition
+                        ),
+                        note: Some(format!(
+                            "set language_edition = \"{}\" in the [compile] section of {}",
+                            Edition::Edition2024,
+                            KCL_MOD_FILE
+                        )),
+                        suggested_replacement: None,
+                        ..Default::default()
+                    }],
+                );
+            }
+        }
+    }
+}
+
+/// Mount the archive entry files e.g., `bundle.tar.gz` and `bundle.zip` with the
+/// [ArchiveProvider] over the source provider of the options, thus the archive is
+/// loaded as a directory. It returns `None` when there is no archive entry.
+fn mount_archive_entries(
+    paths: &[String],
+    opts: &LoadProgramOptions,
+) -> Result<Option<LoadProgramOptions>> {
+    let mut mounted_opts: Option<LoadProgramOptions> = None;
+    for path in paths {
+        let file =
+            canonicalize_provided_input_file(opts.source_provider.as_ref(), path, &opts.work_dir);
+        if !is_archive_file(Path::new(&file)) {
+            continue;
+        }
+        let mounted_opts = mounted_opts.get_or_insert_with(|| opts.clone());
+        let provider = ArchiveProvider::from_path(&file)
+            .map_err(|err| anyhow::anyhow!("failed to read the archive {}: {}", file, err))?
+            .with_base(mounted_opts.source_provider.clone());
+        mounted_opts.source_provider = Arc::new(provider);
+    }
+    Ok(mounted_opts)
+}
+
+/// Split the entry paths into the existing paths and the missing paths. The paths provided
+/// with the source code in [LoadProgramOptions::k_code_list] and the paths with the
+/// `${KCL_MOD}` prefix are always treated as the existing paths.
+pub fn split_missing_entries(
+    paths: Vec<String>,
+    opts: &LoadProgramOptions,
+) -> (Vec<String>, Vec<String>) {
+    let mut existing_paths = vec![];
+    let mut missing_paths = vec![];
+    for (i, path) in paths.into_iter().enumerate() {
+        let is_mod_relative = kclvm_config::path::ModRelativePath::from(path.clone())
+            .is_relative_path()
+            .unwrap_or(true);
+        let file =
+            canonicalize_provided_input_file(opts.source_provider.as_ref(), &path, &opts.work_dir);
+        if i < opts.k_code_list.len()
+            || is_mod_relative
+            || !expand_if_file_pattern(path.clone())
+                .unwrap_or_default()
+                .is_empty()
+            || opts.source_provider.exists(Path::new(&file))
+        {
+            existing_paths.push(path);
+        } else {
+            missing_paths.push(file);
+        }
+    }
+    (existing_paths, missing_paths)
+}
+
+/// Get all the files read from the disk during the load, which include the source files
+/// not provided in memory and the package manifests of all the package roots.
+fn get_inputs_read(
+    files: &[PkgFile],
+    compile_entries: &entry::Entries,
+    pkgmap: &PkgMap,
+    module_cache: &KCLModuleCache,
+    opts: &LoadProgramOptions,
+) -> Result<Vec<PathBuf>> {
+    let mut in_memory_files: HashSet<PathBuf> = match module_cache.read() {
+        Ok(module_cache) => module_cache.source_code.keys().cloned().collect(),
+        Err(e) => return Err(anyhow::anyhow!("Parse program failed: {e}")),
+    };
+    let mut pkg_roots: IndexSet<PathBuf> = IndexSet::new();
+    for entry in compile_entries.iter() {
+        for (file, code) in entry.get_k_files().iter().zip(entry.get_k_codes()) {
+            if code.is_some() {
+                in_memory_files.insert(file.adjust_canonicalization().into());
+            }
+        }
+        pkg_roots.insert(entry.path().into());
+    }
+    let mut inputs_read: IndexSet<PathBuf> = IndexSet::new();
+    for file in files {
+        if !in_memory_files.contains(file.get_path()) {
+            inputs_read.insert(file.get_path().clone());
+        }
+        if let Some(pkg) = pkgmap.get(file) {
+            pkg_roots.insert(pkg.pkg_root.clone().into());
+        }
+    }
+    for pkg_root in pkg_roots {
+        if pkg_root.as_os_str().is_empty() {
+            continue;
+        }
+        for manifest in [KCL_MOD_FILE, KCL_MOD_LOCK_FILE] {
+            let manifest = pkg_root.join(manifest);
+            if opts.source_provider.exists(&manifest) {
+                inputs_read.insert(manifest);
+            }
+        }
+    }
+    // The files in the mounted archives are read from the archive files on the disk.
+    let inputs_read: IndexSet<PathBuf> = inputs_read
+        .into_iter()
+        .map(|input| {
+            if input.exists() {
+                return input;
+            }
+            match input.ancestors().find(|dir| is_archive_file(dir)) {
+                Some(archive) => archive.to_path_buf(),
+                None => input,
+            }
+        })
+        .collect();
+    Ok(inputs_read.into_iter().collect())
+}
+
 /// If there are too many files in the directory, it will affect the performance of lsp. Set a maximum number of files
 const MAX_SCAN_FILES: usize = 1000;
 
@@ -1090,7 +1949,9 @@ pub fn load_all_files_under_paths(
                             let pkgfile = PkgFile::new(p.clone(), pkg.clone());
                             let module_cache_read = module_cache.read();
                             match &module_cache_read {
-                                Ok(m_cache) => match m_cache.ast_cache.get(pkgfile.get_path()) {
+                                Ok(m_cache) => match m_cache
+                                    .get_ast(pkgfile.get_path(), loader.opts.forward_slash_paths)
+                                {
                                     Some(_) => continue,
                                     None => {
                                         unparsed_file.push_back(pkgfile);
@@ -1111,7 +1972,9 @@ pub fn load_all_files_under_paths(
                     new_files.insert(file.clone());
                     let module_cache_read = module_cache.read();
                     match &module_cache_read {
-                        Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
+                        Ok(m_cache) => match m_cache
+                            .get_ast(file.get_path(), loader.opts.forward_slash_paths)
+                        {
                             Some(_) => continue,
                             None => {
                                 drop(module_cache_read);
@@ -1124,6 +1987,7 @@ pub fn load_all_files_under_paths(
                                     &mut loader.pkgmap,
                                     loader.file_graph.clone(),
                                     &loader.opts,
+                                    &mut res.parse_timings,
                                 )?;
 
                                 let m_ref = match module_cache.read() {
@@ -1165,7 +2029,10 @@ pub fn load_all_files_under_paths(
                 // Merge unparsed module into res
                 let modules_not_imported = &mut res.program.modules_not_imported;
                 for file in &new_files {
-                    let filename = file.get_path().to_str().unwrap().to_string();
+                    let filename = normalize_path(
+                        file.get_path().to_str().unwrap(),
+                        loader.opts.forward_slash_paths,
+                    );
                     let m_ref = match module_cache.read() {
                         Ok(module_cache) => module_cache
                             .ast_cache
@@ -1187,6 +2054,7 @@ pub fn load_all_files_under_paths(
                         }
                     }
                 }
+                res.parse_timings.sort_by(|a, b| b.1.cmp(&a.1));
                 sess.1.write().diagnostics = diag;
                 return Ok(res);
             }
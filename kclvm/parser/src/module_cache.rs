@@ -0,0 +1,124 @@
+//! Snapshot the [ModuleCache] to disk and restore it, so the parsed ASTs can be reused
+//! across processes e.g., the fresh CI runners with a cached directory. Only the ASTs
+//! are restored and the dependencies of the files are computed again on load, because
+//! they depend on the package directory listings and the `kcl.mod` dependencies which
+//! may change without changing the files.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::ast;
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+
+use crate::ModuleCache;
+
+/// The on-disk format version of the module cache snapshot, which must be bumped when
+/// the snapshot structure changes. The snapshot is also invalidated when the compiler
+/// version changes, because the AST structure may change.
+pub const MODULE_CACHE_FORMAT_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct ModuleCacheSnapshot {
+    format_version: u32,
+    compiler_version: String,
+    entries: Vec<ModuleCacheEntry>,
+}
+
+/// The cached AST of a file.
+#[derive(Serialize, Deserialize)]
+struct ModuleCacheEntry {
+    path: PathBuf,
+    /// The hex MD5 of the source from which the module is parsed.
+    content_hash: String,
+    module: ast::Module,
+}
+
+impl ModuleCache {
+    /// Save the parsed ASTs of the files in the cache to the path, together with the
+    /// content hash of the source recorded when each file is parsed, so a file changed
+    /// after parsing is parsed again on load. The in-memory sources are skipped. The
+    /// snapshot is written to a temporary file first and then renamed, so a concurrent
+    /// [ModuleCache::load] never reads a partially written snapshot.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut entries = vec![];
+        for (filename, module) in &self.ast_cache {
+            // The sources in the cache may differ from the files e.g., the unsaved
+            // editor buffers, which can not be validated on load.
+            if self.source_code.contains_key(filename) {
+                continue;
+            }
+            let content_hash = match self.content_hash.get(filename) {
+                Some(content_hash) => content_hash.clone(),
+                None => continue,
+            };
+            let module = module
+                .read()
+                .map_err(|e| anyhow!("Failed to acquire module lock: {e}"))?
+                .clone();
+            entries.push(ModuleCacheEntry {
+                path: filename.clone(),
+                content_hash,
+                module,
+            });
+        }
+        let snapshot = ModuleCacheSnapshot {
+            format_version: MODULE_CACHE_FORMAT_VERSION,
+            compiler_version: kclvm_version::VERSION.to_string(),
+            entries,
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension(format!("tmp.{}", std::process::id()));
+        std::fs::write(&tmp_path, serde_json::to_vec(&snapshot)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Load the module cache saved by [ModuleCache::save] from the path. The entries
+    /// whose file content no longer matches the saved content hash are ignored, and an
+    /// empty cache is returned when the snapshot does not exist or is saved by another
+    /// format or compiler version, so the stale entries are parsed again. The
+    /// dependencies of the restored files are resolved again when the program is loaded.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = match std::fs::read(path.as_ref()) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        let snapshot: ModuleCacheSnapshot = match serde_json::from_slice(&content) {
+            Ok(snapshot) => snapshot,
+            Err(_) => return Ok(Self::default()),
+        };
+        let mut cache = Self::default();
+        if snapshot.format_version != MODULE_CACHE_FORMAT_VERSION
+            || snapshot.compiler_version != kclvm_version::VERSION
+        {
+            return Ok(cache);
+        }
+        for entry in snapshot.entries {
+            match std::fs::read(&entry.path) {
+                Ok(content) if content_hash(&content) == entry.content_hash => {}
+                _ => continue,
+            }
+            cache
+                .content_hash
+                .insert(entry.path.clone(), entry.content_hash);
+            cache
+                .ast_cache
+                .insert(entry.path, Arc::new(RwLock::new(entry.module)));
+        }
+        Ok(cache)
+    }
+}
+
+/// Returns the hex MD5 of the file content.
+pub(crate) fn content_hash(content: &[u8]) -> String {
+    let mut md5 = Md5::new();
+    md5.input(content);
+    md5.result().iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -1043,13 +1043,15 @@ impl<'a> Parser<'a> {
                 ))
             }
         } else {
-            Box::new(Node::node(
+            let expr = Box::new(Node::node(
                 Expr::List(ListExpr {
                     elts: items,
                     ctx: ExprContext::Load,
                 }),
                 self.sess.struct_token_loc(token, self.prev_token),
-            ))
+            ));
+            self.record_collection(&expr);
+            expr
         }
     }
 
@@ -1372,10 +1374,12 @@ impl<'a> Parser<'a> {
                 ))
             }
         } else {
-            Box::new(Node::node(
+            let expr = Box::new(Node::node(
                 Expr::Config(ConfigExpr { items }),
                 self.sess.struct_token_loc(token, self.prev_token),
-            ))
+            ));
+            self.record_collection(&expr);
+            expr
         }
     }
 
@@ -2299,7 +2303,8 @@ impl<'a> Parser<'a> {
                 }
             }
             token::LitKind::Float => {
-                let result = lk.symbol.as_str().parse();
+                // Digit separators e.g., `1_000.5` are not supported by the Rust float parser.
+                let result = lk.symbol.as_str().replace('_', "").parse();
                 let value = match result {
                     Ok(value) => value,
                     _ => {
@@ -2319,12 +2324,21 @@ impl<'a> Parser<'a> {
             }
         };
 
+        // Keep the original text e.g., `0xFF` and `1_000` for the round-trip formatting.
+        let raw = lk.symbol.as_str();
+        let raw_value = match value {
+            NumberLitValue::Int(v) => v.to_string() != raw,
+            NumberLitValue::Float(v) => v.to_string() != raw,
+        }
+        .then_some(raw);
+
         self.bump();
 
         Box::new(Node::node(
             Expr::NumberLit(NumberLit {
                 binary_suffix,
                 value,
+                raw_value,
             }),
             self.sess.struct_token_loc(token, self.prev_token),
         ))
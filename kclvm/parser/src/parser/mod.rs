@@ -27,7 +27,7 @@ mod ty;
 use crate::session::ParseSession;
 
 use compiler_base_span::span::{new_byte_pos, BytePos};
-use kclvm_ast::ast::{Comment, NodeRef, PosTuple};
+use kclvm_ast::ast::{AstIndex, Comment, Expr, ExprComment, ExprCommentKind, NodeRef, PosTuple};
 use kclvm_ast::token::{CommentKind, Token, TokenKind};
 use kclvm_ast::token_stream::{Cursor, TokenStream};
 use kclvm_error::ParseErrorMessage;
@@ -45,10 +45,16 @@ pub struct Parser<'a> {
     cursor: Cursor,
     /// all comments.
     comments: Vec<NodeRef<Comment>>,
+    /// The list and config expressions with their element nodes, which are used to
+    /// attach the interior comments.
+    collections: Vec<Collection>,
     /// parse-time session
     pub sess: &'a ParseSession,
 }
 
+/// The id and position of a list or config expression and its element nodes.
+type Collection = (AstIndex, PosTuple, Vec<(AstIndex, PosTuple)>);
+
 /// The DropMarker is used to mark whether to discard the token Mark whether to discard the token.
 /// The principle is to store the index of the token in the token stream. When there is no index
 /// change during the parse process, it is discarded and an error is output
@@ -64,6 +70,7 @@ impl<'a> Parser<'a> {
             prev_token: Token::dummy(),
             cursor: TokenStream::new(non_comment_tokens).cursor(),
             comments,
+            collections: Vec::new(),
             sess,
         };
 
@@ -228,3 +235,61 @@ impl<'a> Parser<'a> {
         (non_comment_tokens, comments)
     }
 }
+
+impl<'a> Parser<'a> {
+    /// Record the list or config expression and its elements for attaching the interior comments.
+    pub(crate) fn record_collection(&mut self, expr: &NodeRef<Expr>) {
+        let elements = match &expr.node {
+            Expr::List(list_expr) => list_expr
+                .elts
+                .iter()
+                .map(|elt| (elt.id.clone(), elt.pos()))
+                .collect(),
+            Expr::Config(config_expr) => config_expr
+                .items
+                .iter()
+                .map(|item| (item.id.clone(), item.pos()))
+                .collect(),
+            _ => return,
+        };
+        self.collections
+            .push((expr.id.clone(), expr.pos(), elements));
+    }
+
+    /// Attach each interior comment of the list and config expressions to the nearest node
+    /// in the innermost enclosing expression. A comment on the same line after an element
+    /// trails the element, otherwise it leads the next element or dangles in the expression
+    /// when there are no more elements.
+    pub(crate) fn attach_expr_comments(&self) -> Vec<ExprComment> {
+        let mut expr_comments = vec![];
+        for comment in &self.comments {
+            let pos = (comment.line, comment.column);
+            let collection = self
+                .collections
+                .iter()
+                .filter(|(_, range, _)| (range.1, range.2) < pos && pos < (range.3, range.4))
+                .max_by_key(|(_, range, _)| (range.1, range.2));
+            let (id, _, elements) = match collection {
+                Some(collection) => collection,
+                None => continue,
+            };
+            let trailing = elements
+                .iter()
+                .rev()
+                .find(|(_, range)| range.3 == comment.line && (range.3, range.4) <= pos);
+            let (node_id, kind) = match trailing {
+                Some((node_id, _)) => (node_id, ExprCommentKind::Trailing),
+                None => match elements.iter().find(|(_, range)| (range.1, range.2) > pos) {
+                    Some((node_id, _)) => (node_id, ExprCommentKind::Leading),
+                    None => (id, ExprCommentKind::Dangling),
+                },
+            };
+            expr_comments.push(ExprComment {
+                node_id: node_id.clone(),
+                kind,
+                comment: comment.clone(),
+            });
+        }
+        expr_comments
+    }
+}
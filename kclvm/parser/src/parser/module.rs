@@ -1,6 +1,7 @@
 use kclvm_ast::ast::*;
 use kclvm_ast::node_ref;
 use kclvm_ast::{token::LitKind, token::TokenKind};
+use kclvm_span::symbol::kw;
 
 use super::Parser;
 
@@ -15,9 +16,31 @@ impl<'a> Parser<'a> {
             doc,
             comments: self.comments.clone(),
             body,
+            expr_comments: vec![],
         }
     }
 
+    /// Parse the leading doc string and import statements of the module,
+    /// and stop at the first statement which is not an import statement.
+    pub fn parse_imports(&mut self) -> Vec<NodeRef<ImportStmt>> {
+        self.parse_doc();
+        let mut imports = Vec::new();
+        loop {
+            self.skip_newlines();
+            let stmt = if self.token.is_keyword(kw::Import) {
+                self.parse_import_stmt()
+            } else if self.is_from_import_stmt() {
+                self.parse_from_import_stmt()
+            } else {
+                break;
+            };
+            if let Stmt::Import(import_stmt) = &stmt.node {
+                imports.push(node_ref!(import_stmt.clone(), stmt.pos()));
+            }
+        }
+        imports
+    }
+
     pub(crate) fn parse_doc(&mut self) -> Option<NodeRef<String>> {
         // doc string
         match self.token.kind {
@@ -54,6 +77,15 @@ impl<'a> Parser<'a> {
                 break;
             }
 
+            // The check block at the top level following a schema statement.
+            if self.token.is_keyword(kw::Check) {
+                if let Some(Stmt::Schema(schema_stmt)) = stmts.last_mut().map(|stmt| &mut stmt.node)
+                {
+                    self.parse_unindented_check_block(schema_stmt);
+                    continue;
+                }
+            }
+
             if let Some(stmt) = self.parse_stmt() {
                 stmts.push(stmt);
             } else {
@@ -6,10 +6,33 @@ use kclvm_ast::token::VALID_SPACES_LENGTH;
 use kclvm_ast::token::{CommentKind, DelimToken, LitKind, Token, TokenKind};
 use kclvm_ast::{ast::*, expr_as, node_ref};
 use kclvm_error::ParseErrorMessage;
-use kclvm_span::symbol::kw;
+use kclvm_span::symbol::{kw, reserved};
 
 use super::Parser;
 
+/// The indentation of the schema body when it can not be inferred from the source.
+const DEFAULT_INDENT_SPACES: usize = 4;
+
+/// Indent the non-blank lines of the source by the number of spaces, or dedent them when
+/// the number is negative.
+fn reindent(source: &str, spaces: isize) -> String {
+    let content = source.trim_end();
+    let lines: Vec<String> = content
+        .split('\n')
+        .map(|line| {
+            if line.trim().is_empty() {
+                line.to_string()
+            } else if spaces >= 0 {
+                format!("{}{}", " ".repeat(spaces as usize), line)
+            } else {
+                let width = line.len() - line.trim_start_matches(' ').len();
+                line[width.min((-spaces) as usize)..].to_string()
+            }
+        })
+        .collect();
+    format!("{}{}", lines.join("\n"), &source[content.len()..])
+}
+
 /// Parser implementation of statements, which consists of expressions and tokens.
 /// Parser uses `parse_exprlist` and `parse_expr` in [`kclvm_parser::parser::expr`]
 /// to get a expression node, and then concretize it into the specified expression node,
@@ -87,7 +110,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Syntax:
-    /// simple_stmt: (assign_stmt | unification_stmt | expr_stmt | assert_stmt | import_stmt | type_alias_stmt) NEWLINE
+    /// simple_stmt: (assign_stmt | unification_stmt | expr_stmt | assert_stmt | import_stmt | from_import_stmt | type_alias_stmt) NEWLINE
     fn parse_simple_stmt(&mut self) -> Option<NodeRef<Stmt>> {
         // skip new lines
         if matches!(self.token.kind, TokenKind::Newline) {
@@ -103,6 +126,10 @@ impl<'a> Parser<'a> {
         if self.token.is_keyword(kw::Import) {
             return Some(self.parse_import_stmt());
         }
+        // from ... import ...
+        if self.is_from_import_stmt() {
+            return Some(self.parse_from_import_stmt());
+        }
 
         // type ...
         if self.token.is_keyword(kw::Type) {
@@ -434,22 +461,10 @@ impl<'a> Parser<'a> {
 
     /// Syntax:
     /// import_stmt: IMPORT dot_name (AS NAME)?
-    fn parse_import_stmt(&mut self) -> NodeRef<Stmt> {
+    pub(crate) fn parse_import_stmt(&mut self) -> NodeRef<Stmt> {
         let token = self.token;
         self.bump_keyword(kw::Import);
-        let dot_name_token = self.token;
-
-        let mut leading_dot = Vec::new();
-        while let TokenKind::DotDotDot = self.token.kind {
-            leading_dot.push("...".to_string());
-            self.bump_token(TokenKind::DotDotDot);
-        }
-        while let TokenKind::Dot = self.token.kind {
-            leading_dot.push(".".to_string());
-            self.bump_token(TokenKind::Dot);
-        }
-        let dot_name = self.parse_identifier().node;
-        let dot_name_end_token = self.prev_token;
+        let (path_node, dot_name) = self.parse_import_path();
 
         let asname = if self.token.is_keyword(kw::As) {
             self.bump_keyword(kw::As);
@@ -466,14 +481,7 @@ impl<'a> Parser<'a> {
             None
         };
 
-        let mut path = leading_dot.join("");
-        path.push_str(dot_name.get_names().join(".").as_str());
-
-        let rawpath = path.clone();
-        let path_node = Node::node_with_pos(
-            path,
-            self.token_span_pos(dot_name_token, dot_name_end_token),
-        );
+        let rawpath = path_node.node.clone();
 
         let name = if let Some(as_name_value) = asname.clone() {
             as_name_value.node
@@ -487,7 +495,8 @@ impl<'a> Parser<'a> {
                 rawpath,
                 name,
                 asname,
-                pkg_name: String::new()
+                pkg_name: String::new(),
+                selected_names: vec![],
             }),
             self.token_span_pos(token, self.prev_token)
         );
@@ -497,6 +506,118 @@ impl<'a> Parser<'a> {
         t
     }
 
+    /// Syntax:
+    /// from_import_stmt: FROM dot_name IMPORT (import_names | LEFT_PARENTHESES import_names RIGHT_PARENTHESES)
+    /// import_names: NAME (COMMA NAME)* [COMMA]
+    ///
+    /// Note that `from` is not a keyword, and it only starts an import statement when
+    /// followed by the import path e.g., `from pkg import Name`.
+    pub(crate) fn parse_from_import_stmt(&mut self) -> NodeRef<Stmt> {
+        let token = self.token;
+        // Bump the `from` identifier.
+        self.bump();
+        let (path_node, _) = self.parse_import_path();
+        self.bump_keyword(kw::Import);
+
+        let has_paren = matches!(self.token.kind, TokenKind::OpenDelim(DelimToken::Paren));
+        if has_paren {
+            self.bump_token(TokenKind::OpenDelim(DelimToken::Paren));
+        }
+        let mut selected_names = Vec::new();
+        loop {
+            if has_paren {
+                self.skip_newlines();
+                if matches!(self.token.kind, TokenKind::CloseDelim(DelimToken::Paren)) {
+                    break;
+                }
+            }
+            let ident = self.parse_identifier().node;
+            match ident.names.len() {
+                1 => selected_names.push(ident.names[0].clone()),
+                _ => self
+                    .sess
+                    .struct_span_error("Invalid import name", self.prev_token.span),
+            }
+            if let TokenKind::Comma = self.token.kind {
+                self.bump_token(TokenKind::Comma);
+            } else {
+                break;
+            }
+            if !has_paren && matches!(self.token.kind, TokenKind::Newline) {
+                break;
+            }
+        }
+        if has_paren {
+            self.skip_newlines();
+            self.bump_token(TokenKind::CloseDelim(DelimToken::Paren));
+        }
+
+        let rawpath = path_node.node.clone();
+        let name = ImportStmt::selective_import_name(&rawpath);
+
+        let t = node_ref!(
+            Stmt::Import(ImportStmt {
+                path: path_node,
+                rawpath,
+                name,
+                asname: None,
+                pkg_name: String::new(),
+                selected_names,
+            }),
+            self.token_span_pos(token, self.prev_token)
+        );
+
+        self.skip_newlines();
+
+        t
+    }
+
+    /// Whether the current token starts the selective import statement `from pkg import Name`.
+    /// The `from` identifier followed by the import path can't be an expression statement.
+    pub(crate) fn is_from_import_stmt(&mut self) -> bool {
+        match self.token.ident() {
+            Some(ident) if ident.as_str() == "from" => {}
+            _ => return false,
+        }
+        match self.cursor.peek() {
+            Some(peek) => match peek.kind {
+                TokenKind::Ident(name) => !reserved::is_reserved_word(&name.as_str()),
+                // The relative import path e.g., `from .pkg import Name`, which is
+                // different from the attribute access `from.pkg`.
+                TokenKind::Dot | TokenKind::DotDotDot => self.token.span.hi() < peek.span.lo(),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Syntax:
+    /// dot_name: [leading_dots] identifier
+    fn parse_import_path(&mut self) -> (Node<String>, Identifier) {
+        let dot_name_token = self.token;
+
+        let mut leading_dot = Vec::new();
+        while let TokenKind::DotDotDot = self.token.kind {
+            leading_dot.push("...".to_string());
+            self.bump_token(TokenKind::DotDotDot);
+        }
+        while let TokenKind::Dot = self.token.kind {
+            leading_dot.push(".".to_string());
+            self.bump_token(TokenKind::Dot);
+        }
+        let dot_name = self.parse_identifier().node;
+        let dot_name_end_token = self.prev_token;
+
+        let mut path = leading_dot.join("");
+        path.push_str(dot_name.get_names().join(".").as_str());
+
+        let path_node = Node::node_with_pos(
+            path,
+            self.token_span_pos(dot_name_token, dot_name_end_token),
+        );
+        (path_node, dot_name)
+    }
+
     /// Syntax:
     /// type_alias_stmt: "type" NAME ASSIGN type
     fn parse_type_alias_stmt(&mut self) -> NodeRef<Stmt> {
@@ -963,6 +1084,7 @@ impl<'a> Parser<'a> {
     fn parse_schema_body(&mut self) -> SchemaStmt {
         self.validate_dedent();
         self.bump_token(TokenKind::Indent(VALID_SPACES_LENGTH));
+        let body_column = self.sess.lookup_char_pos(self.token.span.lo()).col.0;
 
         // doc string when it is not a string-like attribute statement.
         let body_doc = if let Some(peek) = self.cursor.peek() {
@@ -987,6 +1109,10 @@ impl<'a> Parser<'a> {
         // body
         let mut body_body = Vec::new();
         let mut body_index_signature = None;
+        let mut body_checks = Vec::new();
+        // The start of the check block followed by other statements and the start of
+        // these statements.
+        let mut misplaced_check = None;
 
         loop {
             let marker = self.mark();
@@ -994,10 +1120,49 @@ impl<'a> Parser<'a> {
             if matches!(
                 self.token.kind,
                 TokenKind::Dedent(VALID_SPACES_LENGTH) | TokenKind::Eof
-            ) || self.token.is_keyword(kw::Check)
-            {
+            ) {
+                self.struct_misplaced_check_block_error(misplaced_check.take());
                 break;
             }
+            // check_block
+            else if self.token.is_keyword(kw::Check) {
+                self.struct_misplaced_check_block_error(misplaced_check.take());
+                let check_lo = self.token.span.lo();
+                body_checks.extend(self.parse_schema_check_block());
+                self.validate_dedent();
+                if !matches!(
+                    self.token.kind,
+                    TokenKind::Dedent(VALID_SPACES_LENGTH) | TokenKind::Eof
+                ) {
+                    misplaced_check = Some((check_lo, self.token.span.lo()));
+                }
+                continue;
+            }
+            // check_block with the extra indentation
+            else if matches!(self.token.kind, TokenKind::Indent(VALID_SPACES_LENGTH))
+                && self
+                    .cursor
+                    .peek()
+                    .map_or(false, |peek| peek.is_keyword(kw::Check))
+            {
+                let check_loc = self.sess.lookup_char_pos(self.token.span.lo());
+                let line_lo = self.token.span.lo() - new_byte_pos(check_loc.col.0 as u32);
+                self.bump();
+                body_checks.extend(self.parse_schema_check_block());
+                self.validate_dedent();
+                self.bump_token(TokenKind::Dedent(VALID_SPACES_LENGTH));
+                let span = Span::new(line_lo, self.token.span.lo());
+                let replacement = reindent(
+                    &self.sess.span_to_snippet(span),
+                    body_column as isize - check_loc.col.0 as isize,
+                );
+                self.sess.struct_message_error_with_suggestions(
+                    ParseErrorMessage::OverIndentedCheckBlock,
+                    span,
+                    Some(vec![replacement]),
+                );
+                continue;
+            }
             // assert stmt
             else if self.token.is_keyword(kw::Assert) {
                 body_body.push(self.parse_assert_stmt());
@@ -1117,8 +1282,6 @@ impl<'a> Parser<'a> {
             self.drop(marker);
         }
 
-        // check_block
-        let body_checks = self.parse_schema_check_block();
         self.validate_dedent();
         self.bump_token(TokenKind::Dedent(VALID_SPACES_LENGTH));
         self.skip_newlines();
@@ -1374,6 +1537,50 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Report the check block from `check_lo` which is followed by other statements from
+    /// `trailing_lo` to the current token, with the suggested source moving the check block
+    /// after these statements.
+    fn struct_misplaced_check_block_error(&mut self, misplaced_check: Option<(BytePos, BytePos)>) {
+        if let Some((check_lo, trailing_lo)) = misplaced_check {
+            let end = self.token.span.lo();
+            let check = self.sess.span_to_snippet(Span::new(check_lo, trailing_lo));
+            let trailing = self.sess.span_to_snippet(Span::new(trailing_lo, end));
+            let trailing_content = trailing.trim_end();
+            let indent = " ".repeat(self.sess.lookup_char_pos(check_lo).col.0);
+            let replacement = format!(
+                "{}\n{}{}{}",
+                trailing_content,
+                indent,
+                check.trim_end(),
+                &trailing[trailing_content.len()..]
+            );
+            self.sess.struct_message_error_with_suggestions(
+                ParseErrorMessage::CheckBlockNotAtEnd,
+                Span::new(check_lo, end),
+                Some(vec![replacement]),
+            );
+        }
+    }
+
+    /// Parse the check block at the top level following the schema statement, which
+    /// is reported with the suggested source indenting it into the schema body, and
+    /// append its check expressions into the schema statement for the error recovery.
+    pub(crate) fn parse_unindented_check_block(&mut self, schema_stmt: &mut SchemaStmt) {
+        let check_lo = self.token.span.lo();
+        schema_stmt.checks.extend(self.parse_schema_check_block());
+        let span = Span::new(check_lo, self.token.span.lo());
+        let body_column = schema_stmt
+            .body
+            .first()
+            .map_or(DEFAULT_INDENT_SPACES, |stmt| stmt.column as usize);
+        let replacement = reindent(&self.sess.span_to_snippet(span), body_column as isize);
+        self.sess.struct_message_error_with_suggestions(
+            ParseErrorMessage::UnindentedCheckBlock,
+            span,
+            Some(vec![replacement]),
+        );
+    }
+
     /// Syntax:
     /// check_block: CHECK COLON NEWLINE _INDENT check_expr+ _DEDENT
     /// check_expr: simple_expr [IF simple_expr] [COMMA primary_expr] NEWLINE
@@ -1601,6 +1808,7 @@ impl<'a> Parser<'a> {
                 prev_token: Token::dummy(),
                 cursor: stream.cursor(),
                 comments: Vec::new(),
+                collections: Vec::new(),
                 sess: this.sess,
             };
 
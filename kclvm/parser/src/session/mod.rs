@@ -3,7 +3,9 @@ use compiler_base_macros::bug;
 use compiler_base_session::Session;
 use indexmap::IndexSet;
 use kclvm_ast::token::Token;
-use kclvm_error::{Diagnostic, Handler, ParseError, ParseErrorMessage};
+use kclvm_error::{
+    Diagnostic, DiagnosticCallback, FixableDiagnostic, Handler, ParseError, ParseErrorMessage,
+};
 use kclvm_span::{BytePos, Loc, Span};
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -22,6 +24,15 @@ impl ParseSession {
         Self(sess, RwLock::new(Handler::default()))
     }
 
+    /// Register the callback invoked whenever a new diagnostic is added into the session,
+    /// see [Handler::set_diagnostic_sink]. The callback is invoked while the session
+    /// handler is locked, so it must not access the session diagnostics.
+    #[inline]
+    pub fn set_diagnostic_sink(&self, callback: DiagnosticCallback) -> &Self {
+        self.1.write().set_diagnostic_sink(callback);
+        self
+    }
+
     /// Lookup char pos from span.
     #[inline]
     pub(crate) fn lookup_char_pos(&self, pos: BytePos) -> Loc {
@@ -120,4 +131,9 @@ impl ParseSession {
     pub fn classification(&self) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
         self.1.read().classification()
     }
+
+    /// Collect all diagnostics with suggested replacements for the batch auto-fix.
+    pub fn collect_fixable_diagnostics(&self) -> Vec<FixableDiagnostic> {
+        self.1.read().collect_fixable_diagnostics()
+    }
 }
@@ -0,0 +1,303 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// SourceProvider abstracts all the file accesses of the loader, which makes it possible
+/// to load KCL programs from non-filesystem sources such as embedded assets, remote
+/// storages or in-memory archives.
+pub trait SourceProvider: Debug + Send + Sync {
+    /// Read the whole content of the file at `path` into a string.
+    fn read(&self, path: &Path) -> Result<String>;
+    /// List the direct entries of the directory at `path`.
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
+    /// Whether the file or directory at `path` exists.
+    fn exists(&self, path: &Path) -> bool;
+    /// Whether `path` is an existing directory.
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+    /// Get the canonical absolute form of the existing `path` with all the intermediate
+    /// components normalized and the symbolic links resolved.
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        Ok(path.canonicalize()?)
+    }
+}
+
+pub type SourceProviderRef = Arc<dyn SourceProvider>;
+
+/// The default [SourceProvider] which reads sources from the local filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FileSystemProvider;
+
+impl SourceProvider for FileSystemProvider {
+    #[inline]
+    fn read(&self, path: &Path) -> Result<String> {
+        Ok(std::fs::read_to_string(path)?)
+    }
+
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = vec![];
+        for entry in std::fs::read_dir(path)? {
+            entries.push(entry?.path());
+        }
+        Ok(entries)
+    }
+
+    #[inline]
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+/// The file name suffixes of the archives supported by the [ArchiveProvider].
+const ARCHIVE_SUFFIXES: &[&str] = &[".tar", ".tar.gz", ".tgz", ".zip"];
+
+/// Whether the path is an archive file on the local filesystem supported by the
+/// [ArchiveProvider] e.g., `bundle.tar.gz` and `bundle.zip`.
+pub fn is_archive_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| {
+                ARCHIVE_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+            })
+}
+
+/// A [SourceProvider] which reads the sources from a tar or zip archive, the tar archive is
+/// gzip compressed when the archive file name ends with `.gz` or `.tgz` e.g., `bundle.tar.gz`.
+/// The archive entries are mounted at the archive path, so the entry `app/main.k` of
+/// `bundle.tar.gz` is read from `bundle.tar.gz/app/main.k`, and the imports of it are resolved
+/// within the archive by the `kcl.mod` files in the archive. The paths out of the archive e.g.,
+/// the vendor packages are read from the base provider, which defaults to the local filesystem.
+///
+/// The archive input paths of [crate::load_program] e.g., `bundle.tar.gz` are mounted with
+/// the provider over [crate::LoadProgramOptions::source_provider] automatically, and the caller
+/// may also mount it explicitly and pass the paths under the archive path as the inputs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_parser::{load_program, ArchiveProvider, LoadProgramOptions, ParseSessionRef};
+/// use std::sync::Arc;
+///
+/// let provider = ArchiveProvider::from_path("bundle.tar.gz").unwrap();
+/// let main = provider.root().join("main.k").display().to_string();
+/// let opts = LoadProgramOptions {
+///     source_provider: Arc::new(provider),
+///     ..Default::default()
+/// };
+/// let program = load_program(ParseSessionRef::default(), &[&main], Some(opts), None)
+///     .unwrap()
+///     .program;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchiveProvider {
+    /// The path where the archive entries are mounted.
+    root: PathBuf,
+    /// The contents of the files in the archive by their mounted paths.
+    files: HashMap<PathBuf, Vec<u8>>,
+    /// The direct entries of the directories in the archive by their mounted paths.
+    dirs: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    /// The provider of the paths out of the archive.
+    base: SourceProviderRef,
+}
+
+impl Default for ArchiveProvider {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::default(),
+            files: HashMap::default(),
+            dirs: HashMap::default(),
+            base: Arc::new(FileSystemProvider),
+        }
+    }
+}
+
+impl ArchiveProvider {
+    /// Read the archive file and mount its entries at the absolute path of the archive.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let root = path.canonicalize()?;
+        let file = File::open(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("zip") => Self::from_zip_reader(root, file),
+            Some("gz" | "tgz") => Self::from_reader(root, GzDecoder::new(file)),
+            _ => Self::from_reader(root, file),
+        }
+    }
+
+    /// Read the uncompressed tar archive from the reader and mount its entries at `root`.
+    pub fn from_reader<R: Read>(root: impl Into<PathBuf>, reader: R) -> Result<Self> {
+        let mut provider = Self::new(root.into());
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_type = entry.header().entry_type();
+            if !entry_type.is_file() && !entry_type.is_dir() {
+                continue;
+            }
+            let path = provider.mount_path(&entry.path()?)?;
+            if entry_type.is_dir() {
+                provider.insert_entry(path, None);
+            } else {
+                let mut content = vec![];
+                entry.read_to_end(&mut content)?;
+                provider.insert_entry(path, Some(content));
+            }
+        }
+        Ok(provider)
+    }
+
+    /// Read the zip archive from the reader and mount its entries at `root`.
+    pub fn from_zip_reader<R: Read + Seek>(root: impl Into<PathBuf>, reader: R) -> Result<Self> {
+        let mut provider = Self::new(root.into());
+        let mut archive = zip::ZipArchive::new(reader)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let path = provider.mount_path(Path::new(entry.name()))?;
+            if entry.is_dir() {
+                provider.insert_entry(path, None);
+            } else {
+                let mut content = vec![];
+                entry.read_to_end(&mut content)?;
+                provider.insert_entry(path, Some(content));
+            }
+        }
+        Ok(provider)
+    }
+
+    /// Read the paths out of the archive with the `base` provider instead of the local
+    /// filesystem, which allows mounting multiple archives.
+    pub fn with_base(mut self, base: SourceProviderRef) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// The path where the archive entries are mounted.
+    #[inline]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn new(root: PathBuf) -> Self {
+        let mut provider = Self {
+            root,
+            ..Default::default()
+        };
+        provider.dirs.insert(provider.root.clone(), BTreeSet::new());
+        provider
+    }
+
+    /// Get the mounted path of the archive entry path.
+    fn mount_path(&self, entry_path: &Path) -> Result<PathBuf> {
+        let mut path = self.root.clone();
+        // The entry paths may be prefixed with `./`, and the entries out of the
+        // archive e.g., `../main.k` are invalid.
+        for component in entry_path.components() {
+            match component {
+                Component::Normal(name) => path.push(name),
+                Component::CurDir => {}
+                _ => {
+                    return Err(anyhow!(
+                        "invalid archive entry path {}",
+                        entry_path.display()
+                    ))
+                }
+            }
+        }
+        Ok(path)
+    }
+
+    /// Insert the file with its content or the directory at the mounted path.
+    fn insert_entry(&mut self, path: PathBuf, content: Option<Vec<u8>>) {
+        if path == self.root {
+            return;
+        }
+        self.insert_parent_dirs(&path);
+        match content {
+            Some(content) => {
+                self.files.insert(path, content);
+            }
+            None => {
+                self.dirs.entry(path).or_default();
+            }
+        }
+    }
+
+    /// Insert the entry into the directory entries of its ancestors up to the root.
+    fn insert_parent_dirs(&mut self, path: &Path) {
+        let mut path = path.to_path_buf();
+        while let Some(parent) = path.parent().map(|parent| parent.to_path_buf()) {
+            let is_inserted = self.dirs.entry(parent.clone()).or_default().insert(path);
+            if !is_inserted || parent == self.root {
+                break;
+            }
+            path = parent;
+        }
+    }
+}
+
+impl SourceProvider for ArchiveProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        if !path.starts_with(&self.root) {
+            return self.base.read(path);
+        }
+        match self.files.get(path) {
+            Some(content) => Ok(String::from_utf8(content.clone())?),
+            None => Err(anyhow!(
+                "the file {} is not found in the archive",
+                path.display()
+            )),
+        }
+    }
+
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        if !path.starts_with(&self.root) {
+            return self.base.list_dir(path);
+        }
+        match self.dirs.get(path) {
+            Some(entries) => Ok(entries.iter().cloned().collect()),
+            None => Err(anyhow!(
+                "the directory {} is not found in the archive",
+                path.display()
+            )),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        if !path.starts_with(&self.root) {
+            return self.base.exists(path);
+        }
+        self.files.contains_key(path) || self.dirs.contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        if !path.starts_with(&self.root) {
+            return self.base.is_dir(path);
+        }
+        self.dirs.contains_key(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        if !path.starts_with(&self.root) {
+            return self.base.canonicalize(path);
+        }
+        // The mounted paths are already absolute and normalized.
+        if self.exists(path) {
+            Ok(path.to_path_buf())
+        } else {
+            Err(anyhow!(
+                "the path {} is not found in the archive",
+                path.display()
+            ))
+        }
+    }
+}
@@ -0,0 +1,5 @@
+[package]
+name = "dir_as_package"
+edition = "0.0.1"
+version = "0.0.1"
+
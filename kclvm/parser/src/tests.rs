@@ -7,6 +7,7 @@ use std::{
 use compiler_base_span::{FilePathMapping, SourceMap};
 use entry::expand_input_files;
 use kclvm_config::modfile::{get_vendor_home, KCL_PKG_PATH};
+use kclvm_error::{Diagnostic, Level};
 
 use crate::*;
 
@@ -174,6 +175,22 @@ pub fn test_parse_expr_invalid() {
     }
 }
 
+#[test]
+pub fn test_parse_type() {
+    for case in [
+        "int",
+        "  int | str ",
+        "[{str:float}]",
+        "some.pkg.Person",
+        "(int) -> str",
+    ] {
+        assert!(parse_type(case).is_some(), "{case}");
+    }
+    for case in ["", "  ", "[int", "int str", "{str:}}", "int|"] {
+        assert!(parse_type(case).is_none(), "{case}");
+    }
+}
+
 const PARSE_FILE_INVALID_TEST_CASES: &[&str] = &[
     "a: int",                   // No initial value error
     "a -",                      // Invalid binary expression error
@@ -186,6 +203,9 @@ const PARSE_FILE_INVALID_TEST_CASES: &[&str] = &[
     "func(a=1,b)",              // Call argument error
     "'${}'",                    // Empty string interpolation error
     "'${a: jso}'",              // Invalid string interpolation format spec error
+    "a = {a=2}}",               // Extra closing delimiter error
+    "a = ((2+3)",               // Unclosed delimiter error
+    "a = [1, 2",                // Unclosed delimiter error
 ];
 
 #[test]
@@ -196,6 +216,33 @@ pub fn test_parse_file_invalid() {
     }
 }
 
+#[test]
+pub fn test_parse_file_strict_mode() {
+    let code = "a = ((2+3)";
+    let result = parse_file_force_errors("test.k", Some(code.to_string()));
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("unclosed delimiter '('"));
+
+    let sess = Arc::new(ParseSession::default());
+    create_session_globals_then(|| {
+        parse_file_with_session_and_mode(
+            sess.clone(),
+            "test.k",
+            Some(code.to_string()),
+            ParseMode::ParseComments,
+        )
+    })
+    .unwrap();
+    assert!(!sess
+        .1
+        .read()
+        .diagnostics
+        .iter()
+        .any(|diag| diag.messages[0].message.starts_with("unclosed delimiter")));
+}
+
 pub fn test_vendor_home() {
     let vendor = &PathBuf::from(".")
         .join("testdata")
@@ -870,3 +917,1024 @@ fn parse_all_file_under_path() {
 
     assert_eq!(res.paths.len(), 1);
 }
+
+#[test]
+fn test_load_program_inputs_read() {
+    let testpath = PathBuf::from("./src/testdata/multimods/kcl1")
+        .canonicalize()
+        .unwrap();
+    let main = testpath.join("main.k");
+    let main = main.to_str().unwrap();
+
+    let res = load_program(ParseSessionRef::default(), &[main], None, None).unwrap();
+    assert_eq!(
+        res.inputs_read,
+        vec![PathBuf::from(main), testpath.join(KCL_MOD_FILE)]
+    );
+
+    // Sources provided in memory are not read from the disk.
+    let mut opts = LoadProgramOptions::default();
+    opts.k_code_list = vec!["a = 1".to_string()];
+    let res = load_program(ParseSessionRef::default(), &[main], Some(opts), None).unwrap();
+    assert_eq!(res.inputs_read, vec![testpath.join(KCL_MOD_FILE)]);
+}
+
+#[test]
+fn test_load_program_tolerate_missing_entry() {
+    let testpath = PathBuf::from("./src/testdata/multimods/kcl1")
+        .canonicalize()
+        .unwrap();
+    let main = testpath.join("main.k");
+    let main = main.to_str().unwrap();
+    let missing = testpath.join("missing.k");
+    let missing = missing.to_str().unwrap();
+
+    // The missing entry is an error by default.
+    assert!(load_program(ParseSessionRef::default(), &[missing], None, None).is_err());
+
+    let mut opts = LoadProgramOptions::default();
+    opts.tolerate_missing_entry = true;
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[missing],
+        Some(opts.clone()),
+        None,
+    )
+    .unwrap();
+    assert!(res.program.modules.is_empty());
+    assert!(res.paths.is_empty());
+    assert_eq!(res.errors.len(), 1);
+    assert_eq!(
+        res.errors[0].code,
+        Some(kclvm_error::DiagnosticId::Error(
+            ErrorKind::CannotFindModule
+        ))
+    );
+    assert_eq!(res.errors[0].messages[0].range.0.filename, missing);
+
+    // The existing entries are still loaded.
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[main, missing],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    assert_eq!(res.program.get_main_files(), vec![main.to_string()]);
+    assert_eq!(res.errors.len(), 1);
+}
+
+#[test]
+fn test_load_program_forward_slash_paths() {
+    let testpath = PathBuf::from("./src/testdata/multimods/kcl1")
+        .canonicalize()
+        .unwrap();
+    let main = testpath.join("main.k");
+    let main = main.to_str().unwrap();
+    let missing = testpath.join("sub").join("missing.k");
+    let missing = missing.to_str().unwrap();
+
+    let mut opts = LoadProgramOptions::default();
+    opts.tolerate_missing_entry = true;
+    opts.forward_slash_paths = true;
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[main, missing],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    let is_normalized = |path: &str| path == normalize_path(path, true);
+    assert!(res
+        .paths
+        .iter()
+        .all(|p| is_normalized(&p.to_string_lossy())));
+    for (filename, module) in &res.program.modules {
+        assert!(is_normalized(filename));
+        assert_eq!(filename, &module.read().unwrap().filename);
+    }
+    assert_eq!(res.errors.len(), 1);
+    let filename = &res.errors[0].messages[0].range.0.filename;
+    assert!(is_normalized(filename));
+    assert!(filename.ends_with("missing.k"));
+    #[cfg(target_os = "windows")]
+    {
+        assert!(!filename.contains('\\'));
+        assert!(res.program.modules.keys().all(|p| !p.contains('\\')));
+    }
+}
+
+#[test]
+fn test_load_program_forward_slash_paths_with_module_cache() {
+    let testpath = PathBuf::from("./src/testdata/multimods/kcl1")
+        .canonicalize()
+        .unwrap();
+    let main = testpath.join("main.k");
+    let main_path = main.to_str().unwrap();
+    let module_cache = KCLModuleCache::default();
+    let load = |forward_slash_paths: bool| {
+        let mut opts = LoadProgramOptions::default();
+        opts.forward_slash_paths = forward_slash_paths;
+        load_program(
+            ParseSessionRef::default(),
+            &[main_path],
+            Some(opts),
+            Some(module_cache.clone()),
+        )
+        .unwrap()
+    };
+    let res = load(false);
+    for (filename, module) in &res.program.modules {
+        assert_eq!(filename, &module.read().unwrap().filename);
+    }
+    // The cached AST parsed with another filename e.g., with the forward slashes on
+    // Windows is parsed again instead of keeping the stale filename.
+    module_cache
+        .read()
+        .unwrap()
+        .ast_cache
+        .get(&main)
+        .unwrap()
+        .write()
+        .unwrap()
+        .filename = "stale/main.k".to_string();
+    let res = load(true);
+    assert!(!res.cached_files.contains(&main));
+    for (filename, module) in &res.program.modules {
+        assert_eq!(filename, &module.read().unwrap().filename);
+    }
+    let res = load(true);
+    assert!(res.cached_files.contains(&main));
+    // The cache can be cleared by the normalized module filename.
+    let filename = PathBuf::from(normalize_path(main_path, true));
+    module_cache.write().unwrap().clear(&filename);
+    assert!(!module_cache.read().unwrap().ast_cache.contains_key(&main));
+
+    // The paths are kept as they are without the option.
+    let mut res = load(false);
+    let verbatim = r"\\?\d:\xx\main.k";
+    res.paths = vec![PathBuf::from(verbatim)];
+    res.errors.insert(Diagnostic {
+        level: Level::Error,
+        messages: vec![Message {
+            range: (
+                Position {
+                    filename: verbatim.to_string(),
+                    ..Default::default()
+                },
+                Position {
+                    filename: verbatim.to_string(),
+                    ..Default::default()
+                },
+            ),
+            style: Style::Line,
+            message: "error".to_string(),
+            note: None,
+            suggested_replacement: None,
+            ..Default::default()
+        }],
+        code: None,
+    });
+    res.normalize_paths(false);
+    assert_eq!(res.paths, vec![PathBuf::from(verbatim)]);
+    let range = &res.errors.last().unwrap().messages[0].range;
+    assert_eq!(range.0.filename, verbatim);
+    assert_eq!(range.1.filename, verbatim);
+}
+
+#[test]
+fn test_load_program_treat_dir_as_package() {
+    let testpath = PathBuf::from("./src/testdata/dir_as_package")
+        .canonicalize()
+        .unwrap();
+    let app = testpath.join("app");
+    let app = app.to_str().unwrap();
+
+    // By default, the package root is the directory of the `kcl.mod`, thus `import sub`
+    // in the `app` directory can not be found.
+    let res = load_program(ParseSessionRef::default(), &[app], None, None).unwrap();
+    assert_eq!(res.program.root, testpath.to_str().unwrap());
+    assert!(!res.errors.is_empty());
+
+    let mut opts = LoadProgramOptions::default();
+    opts.treat_dir_as_package = true;
+    let res = load_program(ParseSessionRef::default(), &[app], Some(opts), None).unwrap();
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(res.program.root, app);
+    // The test files and the subdirectories are not compiled into the main package.
+    assert_eq!(
+        res.program.get_main_files(),
+        vec![
+            testpath.join("app").join("base.k").display().to_string(),
+            testpath.join("app").join("main.k").display().to_string(),
+        ]
+    );
+    assert!(res.program.pkgs.contains_key("sub"));
+}
+
+/// A [SourceProvider] that overlays the in-memory files on the local filesystem.
+#[derive(Debug, Default)]
+struct OverlayProvider {
+    files: HashMap<PathBuf, String>,
+}
+
+impl SourceProvider for OverlayProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        match self.files.get(path) {
+            Some(content) => Ok(content.clone()),
+            None => FileSystemProvider.read(path),
+        }
+    }
+
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = FileSystemProvider.list_dir(path)?;
+        entries.extend(
+            self.files
+                .keys()
+                .filter(|file| file.parent() == Some(path))
+                .cloned(),
+        );
+        Ok(entries)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.contains_key(path) || FileSystemProvider.exists(path)
+    }
+}
+
+#[test]
+fn test_load_program_entries_with_source_provider() {
+    let testpath = PathBuf::from("./src/testdata/dir_as_package")
+        .canonicalize()
+        .unwrap();
+    let app = testpath.join("app");
+    let mut provider = OverlayProvider::default();
+    provider
+        .files
+        .insert(app.join("extra.k"), "c = 1\n".to_string());
+    provider.files.insert(
+        app.join(KCL_MOD_FILE),
+        "[package]\nname = \"app\"\n".to_string(),
+    );
+    let mut opts = LoadProgramOptions::default();
+    opts.source_provider = Arc::new(provider);
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[app.to_str().unwrap()],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    // The package root is the directory of the provided `kcl.mod`, and the provided
+    // files are compiled into the main package.
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(res.program.root, app.to_str().unwrap());
+    assert_eq!(
+        res.program.get_main_files(),
+        vec![
+            app.join("base.k").display().to_string(),
+            app.join("extra.k").display().to_string(),
+            app.join("main.k").display().to_string(),
+        ]
+    );
+    assert!(res.program.pkgs.contains_key("sub"));
+}
+
+#[test]
+fn test_load_program_warnings() {
+    let sess = ParseSessionRef::default();
+    sess.1.write().add_warning(
+        kclvm_error::WarningKind::CompilerWarning,
+        &[Message {
+            range: (Position::dummy_pos(), Position::dummy_pos()),
+            style: Style::Line,
+            message: "warning".to_string(),
+            note: None,
+            suggested_replacement: None,
+            ..Default::default()
+        }],
+    );
+    let res = load_program(sess, &["./testdata/import-01.k"], None, None).unwrap();
+    assert_eq!(res.warnings.len(), 1);
+    assert!(res.warnings.iter().all(|d| res.errors.contains(d)));
+    assert!(res.warnings.iter().all(|d| d.is_warning()));
+}
+
+#[test]
+fn test_load_program_with_diagnostic_sink() {
+    let received = Arc::new(std::sync::Mutex::new(vec![]));
+    let sess = ParseSessionRef::default();
+    let sink = received.clone();
+    sess.set_diagnostic_sink(Box::new(move |diag| {
+        sink.lock().unwrap().push(diag.clone())
+    }));
+    let mut opts = LoadProgramOptions::default();
+    opts.k_code_list = vec!["a = (1 +\nb = ]\n".to_string()];
+    let res = load_program(sess, &["./testdata/assign-01.k"], Some(opts), None).unwrap();
+    let received = received.lock().unwrap();
+    assert!(!received.is_empty());
+    // The diagnostics are streamed and accumulated as well.
+    assert_eq!(received.len(), res.errors.len());
+    assert!(received.iter().all(|d| res.errors.contains(d)));
+}
+
+#[test]
+fn test_load_program_parse_timings() {
+    let main = "./testdata/import-01.k";
+    let res = load_program(ParseSessionRef::default(), &[main], None, None).unwrap();
+    assert!(res.parse_timings.is_empty());
+
+    let mut opts = LoadProgramOptions::default();
+    opts.collect_timings = true;
+    let res = load_program(ParseSessionRef::default(), &[main], Some(opts), None).unwrap();
+    assert_eq!(res.parse_timings.len(), res.paths.len());
+    assert!(res
+        .parse_timings
+        .windows(2)
+        .all(|timings| timings[0].1 >= timings[1].1));
+}
+
+#[test]
+fn test_load_program_with_overlay_positions() {
+    let filename = PathBuf::from("./testdata/assign-01.k")
+        .canonicalize()
+        .unwrap()
+        .display()
+        .to_string();
+    // The overlay has more lines than the disk file and a syntax error at the line 6.
+    let code = "a = 1\n\n\n\nb = 2\nc = (1 + \n".to_string();
+    let sess = ParseSessionRef::default();
+    // Register the disk content into the source map of the session first.
+    parse_file_with_global_session(sess.clone(), &filename, None).unwrap();
+    let mut opts = LoadProgramOptions::default();
+    opts.load_packages = false;
+    opts.k_code_list = vec![code.clone()];
+    let res = load_program(sess, &[&filename], Some(opts), None).unwrap();
+    let module = res.program.get_main_package_first_module().unwrap();
+    assert_eq!(module.body[1].line, 5);
+    // The overlay is parsed in the same session and keeps the filename.
+    assert_eq!(module.body[1].filename, filename);
+    assert!(res.errors.iter().all(|d| d
+        .messages
+        .iter()
+        .all(|m| m.range.0.filename != filename || m.range.0.line >= 6)));
+    assert!(res
+        .errors
+        .iter()
+        .any(|d| d.messages.iter().any(|m| m.range.0.filename == filename)));
+
+    let res = parse_single_file(&filename, Some(code)).unwrap();
+    assert_eq!(res.module.body[1].line, 5);
+    assert!(res.errors.iter().all(|d| d
+        .messages
+        .iter()
+        .all(|m| m.range.0.filename != filename || m.range.0.line >= 6)));
+    assert!(!res.errors.is_empty());
+}
+
+#[test]
+fn test_file_graph_affected_by() {
+    let file = |name: &str| file_graph::PkgFile::new(PathBuf::from(name), MAIN_PKG.to_string());
+    let mut graph = file_graph::PkgFileGraph::default();
+    graph.update_file(&file("a.k"), &[file("b.k")]);
+    graph.update_file(&file("b.k"), &[file("c.k")]);
+    graph.update_file(&file("d.k"), &[file("c.k")]);
+    graph.update_file(&file("e.k"), &[]);
+
+    let mut affected = graph.affected_by(&file("c.k"));
+    affected.sort_by(|a, b| a.get_path().cmp(b.get_path()));
+    assert_eq!(
+        affected,
+        vec![file("a.k"), file("b.k"), file("c.k"), file("d.k")]
+    );
+    assert_eq!(graph.affected_by(&file("a.k")), vec![file("a.k")]);
+    assert!(graph.affected_by(&file("f.k")).is_empty());
+}
+
+/// A [SourceProvider] that records all the files read through it.
+#[derive(Debug, Default)]
+struct RecordingProvider {
+    read: std::sync::Mutex<Vec<PathBuf>>,
+}
+
+impl SourceProvider for RecordingProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        self.read.lock().unwrap().push(path.to_path_buf());
+        FileSystemProvider.read(path)
+    }
+
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        FileSystemProvider.list_dir(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        FileSystemProvider.exists(path)
+    }
+}
+
+#[test]
+fn test_load_program_with_source_provider() {
+    let provider = Arc::new(RecordingProvider::default());
+    let mut opts = LoadProgramOptions::default();
+    opts.source_provider = provider.clone();
+    let res = load_program(
+        ParseSessionRef::default(),
+        &["./testdata/import-01.k"],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    let mut read = provider.read.lock().unwrap().clone();
+    let mut paths = res.paths.clone();
+    read.sort();
+    paths.sort();
+    assert_eq!(read, paths);
+}
+
+#[test]
+fn test_load_program_from_archive() {
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        vec![],
+        flate2::Compression::default(),
+    ));
+    for (path, content) in [
+        ("kcl.mod", "[package]\nname = \"bundle\"\n"),
+        ("main.k", "import sub\n\na = sub.b\n"),
+        ("main_test.k", "test_a = lambda {\n    assert a == 1\n}\n"),
+        ("sub/sub.k", "b = 1\n"),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, content.as_bytes())
+            .unwrap();
+    }
+    let archive = builder.into_inner().unwrap().finish().unwrap();
+    let root = std::env::current_dir().unwrap().join("bundle.tar.gz");
+    let provider =
+        ArchiveProvider::from_reader(&root, flate2::read::GzDecoder::new(archive.as_slice()))
+            .unwrap();
+    assert!(provider.is_dir(&root.join("sub")));
+    assert!(!provider.exists(&root.join("sub").join("main.k")));
+
+    let mut opts = LoadProgramOptions::default();
+    opts.source_provider = Arc::new(provider);
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[root.to_str().unwrap()],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    // The imports are resolved within the archive with the `kcl.mod` in the archive.
+    assert_eq!(res.program.root, root.display().to_string());
+    assert_eq!(
+        res.program.get_main_files(),
+        vec![root.join("main.k").display().to_string()]
+    );
+    assert_eq!(
+        res.program.pkgs.get("sub"),
+        Some(&vec![root.join("sub").join("sub.k").display().to_string()])
+    );
+}
+
+#[test]
+fn test_load_program_from_archive_entry() {
+    let files = [
+        ("kcl.mod", "[package]\nname = \"bundle\"\n"),
+        ("main.k", "import sub\n\na = sub.b\n"),
+        ("sub/sub.k", "b = 1\n"),
+    ];
+    let dir = std::env::temp_dir().join(format!("kcl_archive_entry_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // The gzip compressed tar archive.
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        vec![],
+        flate2::Compression::default(),
+    ));
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, content.as_bytes())
+            .unwrap();
+    }
+    let tar_gz = dir.join("bundle.tar.gz");
+    std::fs::write(&tar_gz, builder.into_inner().unwrap().finish().unwrap()).unwrap();
+    // The zip archive.
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+    for (path, content) in files {
+        writer
+            .start_file(path, zip::write::FileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(&mut writer, content.as_bytes()).unwrap();
+    }
+    let zip = dir.join("bundle.zip");
+    std::fs::write(&zip, writer.finish().unwrap().into_inner()).unwrap();
+
+    for archive in [tar_gz, zip] {
+        assert!(is_archive_file(&archive));
+        // The archive input is mounted as the package directory without a source provider.
+        let res = load_program(
+            ParseSessionRef::default(),
+            &[archive.to_str().unwrap()],
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let root = archive.canonicalize().unwrap();
+        assert_eq!(res.program.root, root.display().to_string());
+        assert_eq!(
+            res.program.get_main_files(),
+            vec![root.join("main.k").display().to_string()]
+        );
+        assert!(res.program.pkgs.contains_key("sub"));
+        // The files in the archive are read from the archive file.
+        assert_eq!(res.inputs_read, vec![root]);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_parse_file_with_attach_comments_mode() {
+    let code = r#"a = [1, # one
+    # two
+    2
+    # end
+]
+b = {
+    c = [3]  # three
+} # b
+"#;
+    let m = create_session_globals_then(|| {
+        parse_file_with_session_and_mode(
+            ParseSessionRef::default(),
+            "main.k",
+            Some(code.to_string()),
+            ParseMode::AttachComments,
+        )
+    })
+    .unwrap();
+    let (a, b) = match (&m.body[0].node, &m.body[1].node) {
+        (ast::Stmt::Assign(a), ast::Stmt::Assign(b)) => (&a.value, &b.value),
+        _ => panic!("expect assign statements"),
+    };
+    let (elts, items) = match (&a.node, &b.node) {
+        (ast::Expr::List(list), ast::Expr::Config(config)) => (&list.elts, &config.items),
+        _ => panic!("expect the list and config expressions"),
+    };
+    let attached: Vec<(&str, &ast::AstIndex, ast::ExprCommentKind)> = m
+        .expr_comments
+        .iter()
+        .map(|c| (c.comment.node.text.as_str(), &c.node_id, c.kind))
+        .collect();
+    assert_eq!(
+        attached,
+        vec![
+            ("# one", &elts[0].id, ast::ExprCommentKind::Trailing),
+            ("# two", &elts[1].id, ast::ExprCommentKind::Leading),
+            ("# end", &a.id, ast::ExprCommentKind::Dangling),
+            ("# three", &items[0].id, ast::ExprCommentKind::Trailing),
+        ]
+    );
+    assert_eq!(m.comments.len(), 5);
+
+    let m = parse_file_force_errors("main.k", Some(code.to_string())).unwrap();
+    assert!(m.expr_comments.is_empty());
+}
+
+#[test]
+fn test_lex_tokens() {
+    let tokens = lex_tokens("main.k", Some("if a:\n    b = 1\n".to_string())).unwrap();
+    let kinds: Vec<&str> = tokens.iter().map(|t| t.kind.as_str()).collect();
+    assert_eq!(
+        kinds[..10],
+        [
+            "identifier",
+            "identifier",
+            ":",
+            "newline",
+            "indent",
+            "identifier",
+            "=",
+            "integer",
+            "newline",
+            "dedent"
+        ]
+    );
+    assert_eq!(tokens[5].text, "b");
+    assert_eq!(tokens[5].range.0.line, 2);
+    assert_eq!(tokens[5].range.0.column, Some(4));
+    assert!(lex_tokens("main.k", Some("a = \"1\n".to_string())).is_err());
+}
+
+#[test]
+fn test_parse_imports() {
+    let code = r#""""Module doc string."""
+
+import a.b
+import .c as d
+
+schema Person:
+    name: str
+
+import e
+"#;
+    let imports = parse_imports("main.k", Some(code.to_string())).unwrap();
+    assert_eq!(imports.len(), 2);
+    assert_eq!(imports[0].node.path.node, "a.b");
+    assert_eq!(imports[0].node.name, "b");
+    assert_eq!(
+        (imports[0].line, imports[0].column, imports[0].end_column),
+        (3, 0, 10)
+    );
+    assert_eq!(imports[1].node.rawpath, ".c");
+    assert_eq!(imports[1].node.asname.as_ref().unwrap().node, "d");
+    assert_eq!(imports[1].line, 4);
+
+    assert!(parse_imports("main.k", Some("import".to_string())).is_err());
+    // The lex errors after the leading import statements are ignored.
+    let code = "import a\nimport b\nc = \"1\n";
+    let imports = parse_imports("main.k", Some(code.to_string())).unwrap();
+    assert_eq!(imports.len(), 2);
+    assert_eq!(imports[1].line, 2);
+    let code = "import\nimport b\nc = \"1\n";
+    assert!(parse_imports("main.k", Some(code.to_string())).is_err());
+}
+
+#[test]
+fn test_parse_from_import() {
+    let code = r#"from a.b import Name, name
+from .c import (
+    Name,
+    other,
+)
+from = 1
+from.attr = 2
+"#;
+    let module = parse_file_force_errors("main.k", Some(code.to_string())).unwrap();
+    assert_eq!(module.body.len(), 4);
+    let import_stmt = match &module.body[0].node {
+        ast::Stmt::Import(import_stmt) => import_stmt,
+        _ => panic!("expected the import statement"),
+    };
+    assert!(import_stmt.is_selective());
+    assert_eq!(import_stmt.path.node, "a.b");
+    assert_eq!(import_stmt.name, "@a.b");
+    let names: Vec<&str> = import_stmt
+        .selected_names
+        .iter()
+        .map(|name| name.node.as_str())
+        .collect();
+    assert_eq!(names, ["Name", "name"]);
+    assert_eq!(
+        (
+            import_stmt.selected_names[1].line,
+            import_stmt.selected_names[1].column
+        ),
+        (1, 22)
+    );
+    match &module.body[1].node {
+        ast::Stmt::Import(import_stmt) => {
+            assert_eq!(import_stmt.rawpath, ".c");
+            assert_eq!(import_stmt.selected_names.len(), 2);
+        }
+        _ => panic!("expected the import statement"),
+    }
+    // `from` is not a keyword.
+    assert!(matches!(module.body[2].node, ast::Stmt::Assign(_)));
+    assert!(matches!(module.body[3].node, ast::Stmt::Assign(_)));
+
+    let imports = parse_imports("main.k", Some(code.to_string())).unwrap();
+    assert_eq!(imports.len(), 2);
+    assert!(parse_file_force_errors("main.k", Some("from a import\n".to_string())).is_err());
+}
+
+#[test]
+fn test_load_program_with_edition() {
+    let edition_err = |opts: Option<LoadProgramOptions>, path: &str| {
+        let res = load_program(ParseSessionRef::default(), &[path], opts, None).unwrap();
+        res.errors
+            .iter()
+            .filter(|d| d.code == Some(kclvm_error::DiagnosticId::Error(ErrorKind::InvalidSyntax)))
+            .count()
+    };
+    let code = "from math import log\n\na = log(10)\n".to_string();
+    let mut opts = LoadProgramOptions::default();
+    opts.k_code_list = vec![code];
+    // The selective import is supported by the current edition.
+    assert_eq!(edition_err(Some(opts.clone()), "./testdata/assign-01.k"), 0);
+    opts.edition = Some("2024".to_string());
+    assert_eq!(edition_err(Some(opts.clone()), "./testdata/assign-01.k"), 0);
+    opts.edition = Some("2023".to_string());
+    assert_eq!(edition_err(Some(opts.clone()), "./testdata/assign-01.k"), 1);
+    // Unknown editions are errors.
+    opts.edition = Some("2000".to_string());
+    let err = load_program(
+        ParseSessionRef::default(),
+        &["./testdata/assign-01.k"],
+        Some(opts),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown edition '2000', expected one of '2023', '2024'"
+    );
+    // The edition in the `[compile]` section of kcl.mod overrides the option.
+    let mut opts = LoadProgramOptions::default();
+    opts.edition = Some("2024".to_string());
+    assert_eq!(edition_err(Some(opts), "./testdata/edition/main.k"), 1);
+    // A malformed kcl.mod is reported instead of being ignored.
+    let err = load_program(
+        ParseSessionRef::default(),
+        &["./testdata/edition_invalid_mod/main.k"],
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert!(err.to_string().starts_with("failed to parse"));
+}
+
+#[test]
+fn test_normalize_import_path() {
+    let root = PathBuf::from("./src/testdata").canonicalize().unwrap();
+    let file = root.join("multimods").join("kcl1").join("main.k");
+    let (root, file) = (root.display().to_string(), file.display().to_string());
+
+    let import = normalize_import_path(&FileSystemProvider, &root, &file, "..kcl2");
+    assert_eq!(import.path, "multimods.kcl2");
+    assert!(import.is_relative);
+    assert_eq!(import.kind, ImportKind::Internal);
+
+    let import = normalize_import_path(&FileSystemProvider, &root, &file, "multimods.kcl2");
+    assert!(!import.is_relative);
+    assert_eq!(import.kind, ImportKind::Internal);
+
+    assert_eq!(
+        normalize_import_path(&FileSystemProvider, &root, &file, "math").kind,
+        ImportKind::Builtin
+    );
+    assert_eq!(
+        normalize_import_path(&FileSystemProvider, &root, &file, "kcl_plugin.hello").kind,
+        ImportKind::Plugin
+    );
+
+    let import = normalize_import_path(&FileSystemProvider, &root, &file, "k8s.api.core.v1");
+    assert_eq!(import.path, "k8s.api.core.v1");
+    assert_eq!(import.kind, ImportKind::External);
+    assert_eq!(import.external_pkg_name, Some("k8s".to_string()));
+}
+
+#[test]
+fn test_merged_package_module() {
+    let mut opts = LoadProgramOptions::default();
+    opts.k_code_list = vec![
+        "\"\"\"Package doc.\"\"\"\na = 1\nb = 2\n".to_string(),
+        "c = a + b\n".to_string(),
+    ];
+    let program = load_program(
+        ParseSessionRef::default(),
+        &["a.k", "b.k"],
+        Some(opts),
+        None,
+    )
+    .unwrap()
+    .program;
+    let module = program.merged_package_module(kclvm_ast::MAIN_PKG);
+    assert_eq!(module.doc.unwrap().node, "\"\"\"Package doc.\"\"\"");
+    assert_eq!(
+        module
+            .body
+            .iter()
+            .map(|stmt| (stmt.filename.as_str(), stmt.line))
+            .collect::<Vec<_>>(),
+        vec![("a.k", 2), ("a.k", 3), ("b.k", 1)]
+    );
+    assert!(program.merged_package_module("not_found").body.is_empty());
+}
+
+#[test]
+fn test_parse_embedded() {
+    let filename = "./testdata/embedded.md";
+    let source = std::fs::read_to_string(filename).unwrap();
+    let results = parse_embedded(filename, &source, "kcl").unwrap();
+    assert_eq!(results.len(), 3);
+    // The positions are rebased to the lines of the Markdown file.
+    assert_eq!(results[0].module.body[0].line, 4);
+    assert!(results[0].errors.is_empty());
+    assert_eq!(results[1].module.body[0].line, 13);
+    assert!(!results[1].errors.is_empty());
+    let err = results[1].errors.first().unwrap();
+    assert_eq!(err.messages[0].range.0.line, 14);
+    assert!(err.messages[0].range.0.filename.ends_with("embedded.md"));
+    // The inner fence with a shorter length does not close the block.
+    assert_eq!(results[2].module.body.len(), 1);
+    assert_eq!(results[2].module.body[0].line, 18);
+    assert!(results[2].errors.is_empty());
+}
+
+#[test]
+fn test_load_programs() {
+    let results = load_programs(
+        &[
+            "./testdata/load_programs/a.k",
+            "./testdata/load_programs/not_found.k",
+            "./testdata/load_programs/b.k",
+            "./testdata/load_programs/c.k",
+        ],
+        None,
+        None,
+    );
+    assert_eq!(
+        results.iter().map(|(e, _)| e.as_str()).collect::<Vec<_>>(),
+        vec![
+            "./testdata/load_programs/a.k",
+            "./testdata/load_programs/not_found.k",
+            "./testdata/load_programs/b.k",
+            "./testdata/load_programs/c.k",
+        ]
+    );
+    // The broken package is reported by every entry that imports it.
+    for i in [0, 2] {
+        let result = results[i].1.as_ref().unwrap();
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.messages[0].range.0.filename.ends_with("broken.k")));
+    }
+    assert!(results[1].1.is_err());
+    let result = results[3].1.as_ref().unwrap();
+    assert!(result.errors.is_empty());
+    assert!(result.program.pkgs.contains_key(kclvm_ast::MAIN_PKG));
+}
+
+/// A [SourceProvider] that resolves the paths case-insensitively like the
+/// default file systems on macOS and Windows.
+#[derive(Debug, Default)]
+struct CaseInsensitiveProvider;
+
+impl CaseInsensitiveProvider {
+    fn resolve(path: &Path) -> PathBuf {
+        let mut resolved = PathBuf::new();
+        for component in path.components() {
+            let name = component.as_os_str();
+            let exact = resolved.join(name);
+            if exact.exists() || !resolved.is_dir() {
+                resolved = exact;
+                continue;
+            }
+            resolved = std::fs::read_dir(&resolved)
+                .ok()
+                .and_then(|entries| {
+                    entries.filter_map(|e| e.ok()).map(|e| e.path()).find(|p| {
+                        p.file_name()
+                            .map_or(false, |n| n.eq_ignore_ascii_case(name))
+                    })
+                })
+                .unwrap_or(exact);
+        }
+        resolved
+    }
+}
+
+impl SourceProvider for CaseInsensitiveProvider {
+    fn read(&self, path: &Path) -> Result<String> {
+        FileSystemProvider.read(&Self::resolve(path))
+    }
+
+    fn list_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        FileSystemProvider.list_dir(&Self::resolve(path))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        FileSystemProvider.exists(&Self::resolve(path))
+    }
+}
+
+#[test]
+fn test_import_case_mismatch_warning() {
+    let mut opts = LoadProgramOptions::default();
+    opts.source_provider = Arc::new(CaseInsensitiveProvider);
+    let res = load_program(
+        ParseSessionRef::default(),
+        &["./testdata/import_case/main.k"],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    // The import is still resolved but with a portability warning.
+    assert!(res
+        .errors
+        .iter()
+        .all(|e| e.level != kclvm_error::Level::Error));
+    assert_eq!(res.warnings.len(), 1);
+    let msg = &res.warnings[0].messages[0];
+    assert_eq!(
+        msg.message,
+        "the package `import_case.MyPkg` is resolved to `import_case.mypkg` with a different case, which is not found on the case-sensitive file systems"
+    );
+    assert_eq!(msg.range.0.line, 1);
+    // There is no warning on the case-sensitive file systems where the import is not found.
+    if cfg!(target_os = "linux") {
+        let res = load_program(
+            ParseSessionRef::default(),
+            &["./testdata/import_case/main.k"],
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(res.warnings.is_empty());
+    }
+}
+
+#[test]
+fn test_module_cache_save_and_load() {
+    let dir = std::env::temp_dir().join(format!("kcl_module_cache_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("main.k");
+    std::fs::write(&file, "a = 1\n").unwrap();
+    let snapshot = dir.join("cache").join("modules.json");
+    let file_str = file.to_str().unwrap();
+
+    let module_cache = KCLModuleCache::default();
+    load_program(
+        ParseSessionRef::default(),
+        &[file_str],
+        None,
+        Some(module_cache.clone()),
+    )
+    .unwrap();
+    module_cache.read().unwrap().save(&snapshot).unwrap();
+
+    // The restored cache is reused by the next load.
+    let restored = ModuleCache::load(&snapshot).unwrap();
+    assert_eq!(restored.ast_cache.len(), 1);
+    assert!(restored.dep_cache.is_empty());
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[file_str],
+        None,
+        Some(Arc::new(RwLock::new(restored))),
+    )
+    .unwrap();
+    assert_eq!(res.cached_files.len(), 1);
+
+    // The entries of the changed files are ignored.
+    std::fs::write(&file, "a = 2\n").unwrap();
+    let restored = ModuleCache::load(&snapshot).unwrap();
+    assert!(restored.ast_cache.is_empty());
+    // The files changed after being parsed and before saving are also ignored.
+    module_cache.read().unwrap().save(&snapshot).unwrap();
+    let restored = ModuleCache::load(&snapshot).unwrap();
+    assert!(restored.ast_cache.is_empty());
+    // A missing snapshot is an empty cache.
+    assert!(ModuleCache::load(dir.join("missing.json"))
+        .unwrap()
+        .ast_cache
+        .is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_module_cache_load_with_new_package_file() {
+    let dir = std::env::temp_dir().join(format!("kcl_module_cache_pkg_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("pkg")).unwrap();
+    let file = dir.join("main.k");
+    std::fs::write(&file, "import pkg\n\nb = pkg.a\n").unwrap();
+    std::fs::write(dir.join("pkg").join("a.k"), "a = 1\n").unwrap();
+    let snapshot = dir.join("cache").join("modules.json");
+    let file_str = file.to_str().unwrap();
+
+    let module_cache = KCLModuleCache::default();
+    load_program(
+        ParseSessionRef::default(),
+        &[file_str],
+        None,
+        Some(module_cache.clone()),
+    )
+    .unwrap();
+    module_cache.read().unwrap().save(&snapshot).unwrap();
+
+    // The file added to the imported package after saving is loaded with the restored
+    // cache, because the package dependencies are not restored from the snapshot.
+    std::fs::write(dir.join("pkg").join("c.k"), "c = 1\n").unwrap();
+    let restored = ModuleCache::load(&snapshot).unwrap();
+    assert_eq!(restored.ast_cache.len(), 2);
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[file_str],
+        None,
+        Some(Arc::new(RwLock::new(restored))),
+    )
+    .unwrap();
+    assert_eq!(res.cached_files.len(), 2);
+    assert_eq!(res.program.pkgs.get("pkg").unwrap().len(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,5 @@
+[package]
+name = "edition"
+
+[compile]
+language_edition = "2023"
@@ -0,0 +1,5 @@
+[package]
+name = "edition_invalid_mod"
+
+[compile
+language_edition = "2023"
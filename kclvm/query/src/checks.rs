@@ -0,0 +1,158 @@
+//! Enumerate the `check:` constraints of the schemas, which is used to generate the
+//! validation documents or translate the simple checks to the other validation engines.
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::{ast, MAIN_PKG};
+use kclvm_ast_pretty::{print_ast_node, ASTNode};
+use kclvm_sema::{
+    resolver::{scope::ProgramScope, Options},
+    ty::SchemaType,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::util::resolve_program_copy;
+
+/// A `check:` constraint of the schema.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CheckConstraint {
+    /// The schema which defines the check e.g., `Person` or `pkg.Base` for the
+    /// check inherited from the parent schema `Base` in the package `pkg`.
+    pub schema: String,
+    /// The check expression e.g., `age >= 0`.
+    pub test: String,
+    /// The condition expression of the check e.g., `name` in `len(name) > 0 if name`.
+    pub if_cond: Option<String>,
+    /// The check failure message, which is the string value when the message is a
+    /// string literal, or the message expression otherwise.
+    pub message: Option<String>,
+    /// The source range `(filename, line, column, end_line, end_column)` of the check.
+    pub range: ast::PosTuple,
+}
+
+/// Get all the `check:` constraints of the schema including the checks inherited from
+/// the parent schemas and the mixins, in the order the checks are evaluated at runtime:
+/// the parent schema checks, the schema's own checks and then the mixin checks. The
+/// schema name is looked up in the main package, and the schema in the other package
+/// is denoted by `pkgpath.Name`. The program is resolved on a copy, so the input AST
+/// is not modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::schema_checks;
+/// use std::sync::Arc;
+///
+/// let code = r#"
+/// schema Person:
+///     age: int
+///
+///     check:
+///         age >= 0, "age must be non-negative"
+/// "#;
+/// let program = load_program(
+///     Arc::new(ParseSession::default()),
+///     &["main.k"],
+///     Some(LoadProgramOptions {
+///         k_code_list: vec![code.to_string()],
+///         ..Default::default()
+///     }),
+///     None,
+/// )
+/// .unwrap()
+/// .program;
+/// let checks = schema_checks(&program, "Person").unwrap();
+/// assert_eq!(checks.len(), 1);
+/// assert_eq!(checks[0].test, "age >= 0");
+/// assert_eq!(checks[0].message.as_deref(), Some("age must be non-negative"));
+/// ```
+pub fn schema_checks(program: &ast::Program, schema: &str) -> Result<Vec<CheckConstraint>> {
+    let (program, scope) = resolve_program_copy(
+        program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            ..Default::default()
+        },
+    );
+    let schema_ty = lookup_schema_type(&scope, schema)?;
+    let mut checks = vec![];
+    collect_schema_checks(&program, &schema_ty, &mut checks)?;
+    Ok(checks)
+}
+
+/// Look up the schema type by the name in the main package, and the schema in the
+/// other package is denoted by `pkgpath.Name`.
+pub(crate) fn lookup_schema_type(scope: &ProgramScope, schema: &str) -> Result<SchemaType> {
+    let (pkgpath, name) = match schema.rsplit_once('.') {
+        Some((pkgpath, name)) => (pkgpath, name),
+        None => (MAIN_PKG, schema),
+    };
+    scope
+        .scope_map
+        .get(pkgpath)
+        .and_then(|scope| {
+            scope
+                .borrow()
+                .elems
+                .get(name)
+                .map(|obj| obj.borrow().ty.clone())
+        })
+        .filter(|ty| ty.is_schema())
+        .map(|ty| ty.into_schema_type())
+        .ok_or_else(|| anyhow!("schema '{}' is not found", schema))
+}
+
+/// Collect the checks of the parent schema, the schema's own checks and then the checks
+/// of the mixins.
+fn collect_schema_checks(
+    program: &ast::Program,
+    schema_ty: &SchemaType,
+    checks: &mut Vec<CheckConstraint>,
+) -> Result<()> {
+    walk_schema_checks(program, schema_ty, &mut |schema_ty, check| {
+        checks.push(CheckConstraint {
+            schema: schema_ty.full_ty_str(),
+            test: print_ast_node(ASTNode::Expr(&check.node.test)),
+            if_cond: check
+                .node
+                .if_cond
+                .as_ref()
+                .map(|if_cond| print_ast_node(ASTNode::Expr(if_cond))),
+            message: check.node.msg.as_ref().map(|msg| match &msg.node {
+                ast::Expr::StringLit(string_lit) => string_lit.value.clone(),
+                _ => print_ast_node(ASTNode::Expr(msg)),
+            }),
+            range: check.pos(),
+        });
+    })
+}
+
+/// Walk the checks of the parent schema, the schema's own checks and then the checks
+/// of the mixins with the schema defining each check. The schema statement is found
+/// in the file where the schema type is defined.
+pub(crate) fn walk_schema_checks(
+    program: &ast::Program,
+    schema_ty: &SchemaType,
+    walk: &mut dyn FnMut(&SchemaType, &ast::Node<ast::CheckExpr>),
+) -> Result<()> {
+    if let Some(base) = &schema_ty.base {
+        walk_schema_checks(program, base, walk)?;
+    }
+    if let Some(module) = program.get_module(&schema_ty.filename)? {
+        for stmt in &module.body {
+            if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                if schema_stmt.name.node != schema_ty.name {
+                    continue;
+                }
+                for check in &schema_stmt.checks {
+                    walk(schema_ty, check);
+                }
+            }
+        }
+    }
+    for mixin in &schema_ty.mixins {
+        walk_schema_checks(program, mixin, walk)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,319 @@
+//! Compare a schema across two versions of a program for the structural compatibility,
+//! which is used to detect the breaking changes of the KCL packages published as the
+//! configuration contracts.
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::{ast, MAIN_PKG};
+use kclvm_sema::{
+    resolver::Options,
+    ty::{subsume, SchemaAttr, SchemaType},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::query::{get_full_schema_type_in_scope, GetSchemaOption};
+use crate::util::resolve_program_copy;
+
+/// The category of a schema change.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The configurations valid for the old schema may be invalid for the new schema.
+    Breaking,
+    /// The configurations valid for the old schema are still valid for the new schema.
+    NonBreaking,
+    /// A new optional attribute or capability is added.
+    Additive,
+}
+
+/// A change of the schema.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    /// The changed attribute, or `None` for the change of the schema itself.
+    pub attr: Option<String>,
+    /// The change description e.g., `attribute 'name' is removed`.
+    pub message: String,
+}
+
+/// The compatibility report of a schema between the old and new programs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompatReport {
+    /// The schema name e.g., `Person` or `pkg.Person`.
+    pub schema: String,
+    /// The changes in the order of the old attributes and then the new attributes.
+    pub changes: Vec<SchemaChange>,
+}
+
+impl CompatReport {
+    /// Whether the report contains any breaking change.
+    #[inline]
+    pub fn is_breaking(&self) -> bool {
+        self.changes_of(ChangeKind::Breaking).next().is_some()
+    }
+
+    /// Returns the changes of the kind.
+    pub fn changes_of(&self, kind: ChangeKind) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.kind == kind)
+    }
+
+    fn push(&mut self, kind: ChangeKind, attr: Option<&str>, message: String) {
+        self.changes.push(SchemaChange {
+            kind,
+            attr: attr.map(|attr| attr.to_string()),
+            message,
+        });
+    }
+}
+
+/// Compare the schema in the old and new programs and report the changes. The schema
+/// attributes are flattened with the attributes of the parent schemas and the mixins.
+/// The schema name is looked up in the main package, and the schema in the other
+/// package is denoted by `pkgpath.Name`. The rules are
+///
+/// + Removing an attribute, making an attribute required, adding a required attribute,
+///   narrowing an attribute type or removing the index signature is breaking.
+/// + Making an attribute optional, widening an attribute type or changing a default
+///   value is non-breaking.
+/// + Adding an optional attribute or an index signature is additive.
+///
+/// The programs are resolved on copies, so the input ASTs are not modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::schema_compat;
+/// use std::sync::Arc;
+///
+/// let load = |code: &str| {
+///     load_program(
+///         Arc::new(ParseSession::default()),
+///         &["main.k"],
+///         Some(LoadProgramOptions {
+///             k_code_list: vec![code.to_string()],
+///             ..Default::default()
+///         }),
+///         None,
+///     )
+///     .unwrap()
+///     .program
+/// };
+/// let old = load("schema Person:\n    name: str\n    age?: int\n");
+/// let new = load("schema Person:\n    name: str\n    age: int\n");
+/// let report = schema_compat(&old, &new, "Person").unwrap();
+/// assert!(report.is_breaking());
+/// assert_eq!(report.changes[0].message, "attribute 'age' becomes required");
+/// ```
+pub fn schema_compat(
+    old_program: &ast::Program,
+    new_program: &ast::Program,
+    schema: &str,
+) -> Result<CompatReport> {
+    let old_schema_ty = resolve_schema_type(old_program, schema)?
+        .ok_or_else(|| anyhow!("schema '{}' is not found in the old program", schema))?;
+    let mut report = CompatReport {
+        schema: schema.to_string(),
+        changes: vec![],
+    };
+    let new_schema_ty = match resolve_schema_type(new_program, schema)? {
+        Some(schema_ty) => schema_ty,
+        None => {
+            report.push(
+                ChangeKind::Breaking,
+                None,
+                format!("schema '{}' is removed", schema),
+            );
+            return Ok(report);
+        }
+    };
+    let old_attrs = get_attr_names(&old_schema_ty);
+    let new_attrs = get_attr_names(&new_schema_ty);
+    for name in &old_attrs {
+        let old_attr = match old_schema_ty.get_obj_of_attr(name) {
+            Some(old_attr) => old_attr,
+            None => continue,
+        };
+        let new_attr = match new_schema_ty.get_obj_of_attr(name) {
+            Some(new_attr) => new_attr,
+            None => {
+                report.push(
+                    ChangeKind::Breaking,
+                    Some(name),
+                    format!("attribute '{}' is removed", name),
+                );
+                continue;
+            }
+        };
+        compare_attr(name, old_attr, new_attr, &mut report);
+    }
+    for name in &new_attrs {
+        if old_attrs.contains(name) {
+            continue;
+        }
+        let new_attr = match new_schema_ty.get_obj_of_attr(name) {
+            Some(new_attr) => new_attr,
+            None => continue,
+        };
+        if is_required(new_attr) {
+            report.push(
+                ChangeKind::Breaking,
+                Some(name),
+                format!("required attribute '{}' is added", name),
+            );
+        } else {
+            report.push(
+                ChangeKind::Additive,
+                Some(name),
+                format!("optional attribute '{}' is added", name),
+            );
+        }
+    }
+    match (
+        &old_schema_ty.index_signature,
+        &new_schema_ty.index_signature,
+    ) {
+        (Some(old_index), None) => report.push(
+            ChangeKind::Breaking,
+            None,
+            format!("index signature '{}' is removed", old_index.ty_str()),
+        ),
+        (None, Some(new_index)) => report.push(
+            ChangeKind::Additive,
+            None,
+            format!("index signature '{}' is added", new_index.ty_str()),
+        ),
+        (Some(old_index), Some(new_index)) if old_index.ty_str() != new_index.ty_str() => {
+            let kind = if subsume(old_index.key_ty.clone(), new_index.key_ty.clone(), false)
+                && subsume(old_index.val_ty.clone(), new_index.val_ty.clone(), false)
+            {
+                ChangeKind::NonBreaking
+            } else {
+                ChangeKind::Breaking
+            };
+            report.push(
+                kind,
+                None,
+                format!(
+                    "index signature is changed from '{}' to '{}'",
+                    old_index.ty_str(),
+                    new_index.ty_str()
+                ),
+            )
+        }
+        _ => {}
+    }
+    Ok(report)
+}
+
+fn compare_attr(
+    name: &str,
+    old_attr: &SchemaAttr,
+    new_attr: &SchemaAttr,
+    report: &mut CompatReport,
+) {
+    match (is_required(old_attr), is_required(new_attr)) {
+        (false, true) => report.push(
+            ChangeKind::Breaking,
+            Some(name),
+            format!("attribute '{}' becomes required", name),
+        ),
+        (true, false) => report.push(
+            ChangeKind::NonBreaking,
+            Some(name),
+            format!("attribute '{}' becomes optional", name),
+        ),
+        _ => {}
+    }
+    let (old_ty, new_ty) = (old_attr.ty.ty_str(), new_attr.ty.ty_str());
+    if old_ty != new_ty {
+        if subsume(old_attr.ty.clone(), new_attr.ty.clone(), false) {
+            report.push(
+                ChangeKind::NonBreaking,
+                Some(name),
+                format!(
+                    "type of attribute '{}' is widened from '{}' to '{}'",
+                    name, old_ty, new_ty
+                ),
+            );
+        } else if subsume(new_attr.ty.clone(), old_attr.ty.clone(), false) {
+            report.push(
+                ChangeKind::Breaking,
+                Some(name),
+                format!(
+                    "type of attribute '{}' is narrowed from '{}' to '{}'",
+                    name, old_ty, new_ty
+                ),
+            );
+        } else {
+            report.push(
+                ChangeKind::Breaking,
+                Some(name),
+                format!(
+                    "type of attribute '{}' is changed from '{}' to '{}'",
+                    name, old_ty, new_ty
+                ),
+            );
+        }
+    }
+    if old_attr.default != new_attr.default {
+        report.push(
+            ChangeKind::NonBreaking,
+            Some(name),
+            format!(
+                "default value of attribute '{}' is changed from '{}' to '{}'",
+                name,
+                old_attr.default.as_deref().unwrap_or_default(),
+                new_attr.default.as_deref().unwrap_or_default()
+            ),
+        );
+    }
+}
+
+/// Whether the attribute must be set by the configurations.
+#[inline]
+fn is_required(attr: &SchemaAttr) -> bool {
+    !attr.is_optional && !attr.has_default
+}
+
+/// Get the attribute names of the full schema type including the attributes of the parent
+/// schemas, in which the mixin attributes have been merged into the schema attributes by
+/// the resolver.
+fn get_attr_names(schema_ty: &SchemaType) -> Vec<String> {
+    let mut names = match &schema_ty.base {
+        Some(base) => get_attr_names(base),
+        None => vec![],
+    };
+    for name in schema_ty.attrs.keys() {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names
+}
+
+/// Resolve the program on a copy and get the full schema type by name.
+fn resolve_schema_type(program: &ast::Program, schema: &str) -> Result<Option<SchemaType>> {
+    let (_, scope) = resolve_program_copy(
+        program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            resolve_val: true,
+            ..Default::default()
+        },
+    );
+    let (pkgpath, name) = match schema.rsplit_once('.') {
+        Some((pkgpath, name)) => (pkgpath, name),
+        None => (MAIN_PKG, schema),
+    };
+    match scope.scope_map.get(pkgpath) {
+        Some(scope) => Ok(get_full_schema_type_in_scope(
+            &scope.borrow(),
+            Some(name),
+            &GetSchemaOption::Definitions,
+        )?
+        .shift_remove(name)),
+        None => Ok(None),
+    }
+}
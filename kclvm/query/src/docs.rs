@@ -0,0 +1,210 @@
+//! Extract the docstrings of the modules, schemas and schema attributes into a structured
+//! model, which is used to render the package documents e.g., Markdown and HTML.
+
+use kclvm_ast::ast;
+use kclvm_ast_pretty::{print_ast_node, ASTNode};
+use kclvm_sema::resolver::doc::{parse_doc_string, parse_schema_doc_string};
+use serde::{Deserialize, Serialize};
+
+/// The documents of a program.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DocModel {
+    /// The module documents sorted by the package path and the filename.
+    pub modules: Vec<ModuleDoc>,
+}
+
+/// The documents of a module.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ModuleDoc {
+    /// The package path of the module e.g., `__main__` or `pkg.sub`.
+    pub pkgpath: String,
+    /// The module filename.
+    pub filename: String,
+    /// The module docstring without the quotes and the common indentation.
+    pub doc: Option<String>,
+    /// The source range `(filename, line, column, end_line, end_column)` of the docstring.
+    pub doc_range: Option<ast::PosTuple>,
+    /// The schema documents in the definition order.
+    pub schemas: Vec<SchemaDoc>,
+}
+
+/// The documents of a schema.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SchemaDoc {
+    /// The schema name e.g., `Person`.
+    pub name: String,
+    /// The summary in the schema docstring.
+    pub summary: String,
+    /// The parent schema e.g., `Base` or `pkg.Base`.
+    pub parent: Option<String>,
+    pub is_mixin: bool,
+    pub is_protocol: bool,
+    /// The schema attribute documents in the definition order.
+    pub attrs: Vec<AttrDoc>,
+    /// The examples in the schema docstring.
+    pub examples: Vec<String>,
+    /// The source range `(filename, line, column, end_line, end_column)` of the schema.
+    pub range: ast::PosTuple,
+}
+
+/// The documents of a schema attribute.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AttrDoc {
+    /// The attribute name e.g., `name`.
+    pub name: String,
+    /// The attribute type as written in the source e.g., `[str]`.
+    pub ty: String,
+    /// The default value expression e.g., `"Alice"`.
+    pub default: Option<String>,
+    pub is_optional: bool,
+    /// The attribute description in the `Attributes` section of the schema docstring.
+    pub doc: String,
+    /// The source range `(filename, line, column, end_line, end_column)` of the attribute.
+    pub range: ast::PosTuple,
+}
+
+/// Extract the docstrings of the modules, schemas and schema attributes of all the
+/// packages in the program, together with the attribute types and default values and
+/// their source locations. Only the syntax is used, so the program need not be resolved.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::extract_docs;
+/// use std::sync::Arc;
+///
+/// let code = r#"""Person module."""
+///
+/// schema Person:
+///     """The person.
+///
+///     Attributes
+///     ----------
+///     name : str
+///         The person name.
+///     """
+///     name: str = "Alice"
+/// "#;
+/// let program = load_program(
+///     Arc::new(ParseSession::default()),
+///     &["main.k"],
+///     Some(LoadProgramOptions {
+///         k_code_list: vec![code.to_string()],
+///         ..Default::default()
+///     }),
+///     None,
+/// )
+/// .unwrap()
+/// .program;
+/// let docs = extract_docs(&program);
+/// let module = &docs.modules[0];
+/// assert_eq!(module.doc.as_deref(), Some("Person module."));
+/// assert_eq!(module.schemas[0].summary, "The person.");
+/// assert_eq!(module.schemas[0].attrs[0].doc, "The person name.");
+/// assert_eq!(module.schemas[0].attrs[0].default.as_deref(), Some("\"Alice\""));
+/// ```
+pub fn extract_docs(program: &ast::Program) -> DocModel {
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    let mut modules = vec![];
+    for pkgpath in pkgpaths {
+        let mut pkg_modules: Vec<ModuleDoc> = program
+            .get_modules_for_pkg(pkgpath)
+            .iter()
+            .map(|module| {
+                let module = module.read().expect("Failed to acquire module lock");
+                extract_module_doc(pkgpath, &module)
+            })
+            .collect();
+        pkg_modules.sort_by(|a, b| a.filename.cmp(&b.filename));
+        modules.extend(pkg_modules);
+    }
+    DocModel { modules }
+}
+
+fn extract_module_doc(pkgpath: &str, module: &ast::Module) -> ModuleDoc {
+    ModuleDoc {
+        pkgpath: pkgpath.to_string(),
+        filename: module.filename.clone(),
+        doc: module.doc.as_ref().map(|doc| parse_doc_string(&doc.node)),
+        doc_range: module.doc.as_ref().map(|doc| doc.pos()),
+        schemas: module
+            .body
+            .iter()
+            .filter_map(|stmt| match &stmt.node {
+                ast::Stmt::Schema(schema_stmt) => Some(extract_schema_doc(schema_stmt, stmt.pos())),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+fn extract_schema_doc(schema_stmt: &ast::SchemaStmt, range: ast::PosTuple) -> SchemaDoc {
+    let parsed_doc = parse_schema_doc_string(
+        &schema_stmt
+            .doc
+            .as_ref()
+            .map(|doc| doc.node.clone())
+            .unwrap_or_default(),
+    );
+    let attr_doc = |name: &str| {
+        parsed_doc
+            .attrs
+            .iter()
+            .find(|attr| attr.name == name)
+            .map(|attr| attr.desc.join("\n").trim().to_string())
+            .unwrap_or_default()
+    };
+    let mut attrs = vec![];
+    for stmt in &schema_stmt.body {
+        match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr) => attrs.push(AttrDoc {
+                name: schema_attr.name.node.clone(),
+                ty: schema_attr.ty.node.to_string(),
+                default: schema_attr
+                    .value
+                    .as_ref()
+                    .map(|value| print_ast_node(ASTNode::Expr(value))),
+                is_optional: schema_attr.is_optional,
+                doc: attr_doc(&schema_attr.name.node),
+                range: stmt.pos(),
+            }),
+            ast::Stmt::Unification(unification_stmt) => {
+                let name = unification_stmt.target.node.get_name();
+                let value = Box::new(ast::Node::node_with_pos(
+                    ast::Expr::Schema(unification_stmt.value.node.clone()),
+                    unification_stmt.value.pos(),
+                ));
+                attrs.push(AttrDoc {
+                    ty: unification_stmt.value.node.name.node.get_name(),
+                    default: Some(print_ast_node(ASTNode::Expr(&value))),
+                    is_optional: false,
+                    doc: attr_doc(&name),
+                    name,
+                    range: stmt.pos(),
+                });
+            }
+            _ => {}
+        }
+    }
+    let mut examples: Vec<String> = parsed_doc
+        .examples
+        .values()
+        .map(|example| example.value.clone())
+        .collect();
+    examples.sort();
+    SchemaDoc {
+        name: schema_stmt.name.node.clone(),
+        summary: parsed_doc.summary,
+        parent: schema_stmt
+            .parent_name
+            .as_ref()
+            .map(|parent| parent.node.get_name()),
+        is_mixin: schema_stmt.is_mixin,
+        is_protocol: schema_stmt.is_protocol,
+        attrs,
+        examples,
+        range,
+    }
+}
@@ -0,0 +1,355 @@
+//! Export the schema types as the JSON Schema, which lets the external validators and
+//! the document tools understand the schemas. The literal union types e.g., `"a" | "b"`
+//! are exported as `enum`, and the simple bounds in the unconditional `check:` constraints
+//! e.g., `0 <= replicas <= 10` and `len(name) > 0` are exported as the numeric and length
+//! bounds of the attributes.
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+use kclvm_sema::{
+    resolver::Options,
+    ty::{DictType, SchemaType, Type, TypeKind},
+};
+use serde_json::{json, Map, Value};
+
+use crate::checks::{lookup_schema_type, walk_schema_checks};
+use crate::util::resolve_program_copy;
+
+/// The JSON Schema draft of the exported schemas.
+pub const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Export the schema as the JSON Schema. The schema name is looked up in the main
+/// package, and the schema in the other package is denoted by `pkgpath.Name`. The
+/// schemas referenced by the attributes are exported in `$defs` by their full names
+/// e.g., `pkg.Base`. The program is resolved on a copy, so the input AST is not modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::schema_json_schema;
+/// use std::sync::Arc;
+///
+/// let code = r#"
+/// schema Service:
+///     protocol: "TCP" | "UDP"
+///     port: int
+///
+///     check:
+///         0 < port <= 65535
+/// "#;
+/// let program = load_program(
+///     Arc::new(ParseSession::default()),
+///     &["main.k"],
+///     Some(LoadProgramOptions {
+///         k_code_list: vec![code.to_string()],
+///         ..Default::default()
+///     }),
+///     None,
+/// )
+/// .unwrap()
+/// .program;
+/// let schema = schema_json_schema(&program, "Service").unwrap();
+/// assert_eq!(
+///     schema["properties"]["protocol"]["enum"],
+///     serde_json::json!(["TCP", "UDP"])
+/// );
+/// assert_eq!(schema["properties"]["port"]["exclusiveMinimum"], 0);
+/// assert_eq!(schema["properties"]["port"]["maximum"], 65535);
+/// ```
+pub fn schema_json_schema(program: &ast::Program, schema: &str) -> Result<Value> {
+    let (program, scope) = resolve_program_copy(
+        program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            ..Default::default()
+        },
+    );
+    let schema_ty = lookup_schema_type(&scope, schema)?;
+    let mut exporter = JsonSchemaExporter {
+        program: &program,
+        root: schema_ty.full_ty_str(),
+        defs: IndexMap::new(),
+    };
+    let mut json_schema = exporter.schema_json_schema(&schema_ty)?;
+    if let Value::Object(object) = &mut json_schema {
+        object.insert("$schema".to_string(), json!(JSON_SCHEMA_DRAFT));
+        let defs: Map<String, Value> = exporter
+            .defs
+            .into_iter()
+            .filter_map(|(name, def)| def.map(|def| (name, def)))
+            .collect();
+        if !defs.is_empty() {
+            object.insert("$defs".to_string(), Value::Object(defs));
+        }
+    }
+    Ok(json_schema)
+}
+
+struct JsonSchemaExporter<'a> {
+    program: &'a ast::Program,
+    /// The full name of the exported schema, which is referenced by `#` in itself.
+    root: String,
+    /// The definitions of the referenced schemas by the full names, `None` denotes the
+    /// schema is being exported, which breaks the recursive references.
+    defs: IndexMap<String, Option<Value>>,
+}
+
+impl<'a> JsonSchemaExporter<'a> {
+    /// Export the schema as an object with the attributes of the parent schemas, the
+    /// schema itself and the mixins, and the bounds in the checks.
+    fn schema_json_schema(&mut self, schema_ty: &SchemaType) -> Result<Value> {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        let mut attr_tys = IndexMap::new();
+        let mut schema = Some(schema_ty);
+        let mut schemas = vec![];
+        while let Some(schema_ty) = schema {
+            schemas.push(schema_ty);
+            schema = schema_ty.base.as_deref();
+        }
+        let schemas = schemas
+            .iter()
+            .rev()
+            .flat_map(|schema_ty| std::iter::once(*schema_ty).chain(schema_ty.mixins.iter()));
+        for schema_ty in schemas {
+            for (name, attr) in &schema_ty.attrs {
+                let mut property = self.ty_json_schema(&attr.ty)?;
+                if let (Value::Object(object), Some(doc)) = (&mut property, &attr.doc) {
+                    if !doc.is_empty() {
+                        object.insert("description".to_string(), json!(doc));
+                    }
+                }
+                properties.insert(name.clone(), property);
+                attr_tys.insert(name.clone(), attr.ty.clone());
+                if !attr.is_optional && !required.contains(name) {
+                    required.push(name.clone());
+                }
+            }
+        }
+        walk_schema_checks(self.program, schema_ty, &mut |_, check| {
+            if check.node.if_cond.is_none() {
+                for bound in check_bounds(&check.node.test) {
+                    if let (Some(ty), Some(Value::Object(property))) =
+                        (attr_tys.get(&bound.attr), properties.get_mut(&bound.attr))
+                    {
+                        bound.apply(ty, property);
+                    }
+                }
+            }
+        })?;
+        let mut object = Map::new();
+        object.insert("type".to_string(), json!("object"));
+        object.insert("title".to_string(), json!(schema_ty.name));
+        if !schema_ty.doc.is_empty() {
+            object.insert("description".to_string(), json!(schema_ty.doc));
+        }
+        object.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            object.insert("required".to_string(), json!(required));
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Export the type, where the referenced schemas are exported in the definitions.
+    fn ty_json_schema(&mut self, ty: &Type) -> Result<Value> {
+        if let Some(values) = ty.literal_values() {
+            let values = values
+                .iter()
+                .map(|value| serde_json::from_str(value))
+                .collect::<serde_json::Result<Vec<Value>>>()?;
+            return Ok(json!({ "enum": values }));
+        }
+        Ok(match &ty.kind {
+            TypeKind::None => json!({"type": "null"}),
+            TypeKind::Bool => json!({"type": "boolean"}),
+            TypeKind::Int => json!({"type": "integer"}),
+            TypeKind::Float => json!({"type": "number"}),
+            TypeKind::Str => json!({"type": "string"}),
+            TypeKind::List(item_ty) => {
+                json!({"type": "array", "items": self.ty_json_schema(item_ty)?})
+            }
+            TypeKind::Dict(DictType { val_ty, .. }) => {
+                json!({"type": "object", "additionalProperties": self.ty_json_schema(val_ty)?})
+            }
+            TypeKind::Union(types) => {
+                let types = types
+                    .iter()
+                    .map(|ty| self.ty_json_schema(ty))
+                    .collect::<Result<Vec<Value>>>()?;
+                json!({ "anyOf": types })
+            }
+            TypeKind::Schema(schema_ty) => {
+                let full_name = schema_ty.full_ty_str();
+                if full_name == self.root {
+                    return Ok(json!({ "$ref": "#" }));
+                }
+                if !self.defs.contains_key(&full_name) {
+                    self.defs.insert(full_name.clone(), None);
+                    let def = self.schema_json_schema(schema_ty)?;
+                    self.defs.insert(full_name.clone(), Some(def));
+                }
+                json!({ "$ref": format!("#/$defs/{}", full_name) })
+            }
+            _ => json!({}),
+        })
+    }
+}
+
+/// A bound of the attribute in the check e.g., `replicas >= 0` or `len(name) > 0`.
+#[derive(Debug, Clone, PartialEq)]
+struct Bound {
+    attr: String,
+    /// Whether the bound is on the length of the attribute value.
+    is_len: bool,
+    op: ast::CmpOp,
+    value: f64,
+}
+
+impl Bound {
+    /// Apply the bound to the JSON Schema of the attribute when it is expressible for
+    /// the attribute type, and the tighter bound is kept when there are multiple bounds.
+    fn apply(&self, ty: &Type, property: &mut Map<String, Value>) {
+        if let Some((keyword, value, is_min)) = self.keyword(ty) {
+            let tighter = match property.get(keyword).and_then(|v| v.as_f64()) {
+                Some(bound) if is_min => value > bound,
+                Some(bound) => value < bound,
+                None => true,
+            };
+            if tighter {
+                property.insert(keyword.to_string(), number_value(value));
+            }
+        }
+    }
+
+    /// Get the JSON Schema keyword, the bound value and whether it is a lower bound.
+    fn keyword(&self, ty: &Type) -> Option<(&'static str, f64, bool)> {
+        if self.is_len {
+            // The lengths are integers, so the exclusive bounds are converted to the
+            // inclusive bounds.
+            if self.value.fract() != 0.0 {
+                return None;
+            }
+            let (min, max) = match &ty.kind {
+                TypeKind::Str => ("minLength", "maxLength"),
+                TypeKind::List(_) => ("minItems", "maxItems"),
+                TypeKind::Dict(_) => ("minProperties", "maxProperties"),
+                _ => return None,
+            };
+            match self.op {
+                ast::CmpOp::Gt => Some((min, (self.value + 1.0).max(0.0), true)),
+                ast::CmpOp::GtE => Some((min, self.value.max(0.0), true)),
+                ast::CmpOp::Lt if self.value > 0.0 => Some((max, self.value - 1.0, false)),
+                ast::CmpOp::LtE if self.value >= 0.0 => Some((max, self.value, false)),
+                _ => None,
+            }
+        } else if ty.is_number() {
+            match self.op {
+                ast::CmpOp::Gt => Some(("exclusiveMinimum", self.value, true)),
+                ast::CmpOp::GtE => Some(("minimum", self.value, true)),
+                ast::CmpOp::Lt => Some(("exclusiveMaximum", self.value, false)),
+                ast::CmpOp::LtE => Some(("maximum", self.value, false)),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Get the bounds in the check expression, which is a comparison chain between the
+/// attribute or its length and the number literals, or a conjunction of them.
+fn check_bounds(test: &ast::Node<ast::Expr>) -> Vec<Bound> {
+    match &test.node {
+        ast::Expr::Binary(binary_expr) if binary_expr.op == ast::BinOp::And => {
+            let mut bounds = check_bounds(&binary_expr.left);
+            bounds.extend(check_bounds(&binary_expr.right));
+            bounds
+        }
+        ast::Expr::Paren(paren_expr) => check_bounds(&paren_expr.expr),
+        ast::Expr::Compare(compare) => {
+            let operands: Vec<&ast::Node<ast::Expr>> = std::iter::once(&compare.left)
+                .chain(compare.comparators.iter())
+                .map(|operand| operand.as_ref())
+                .collect();
+            let mut bounds = vec![];
+            for (i, op) in compare.ops.iter().enumerate() {
+                let (left, right) = (operands[i], operands[i + 1]);
+                if let (Some((attr, is_len)), Some(value)) =
+                    (bound_operand(left), number_literal(right))
+                {
+                    bounds.push(Bound {
+                        attr,
+                        is_len,
+                        op: op.clone(),
+                        value,
+                    });
+                } else if let (Some(value), Some((attr, is_len))) =
+                    (number_literal(left), bound_operand(right))
+                {
+                    let op = match op {
+                        ast::CmpOp::Lt => ast::CmpOp::Gt,
+                        ast::CmpOp::LtE => ast::CmpOp::GtE,
+                        ast::CmpOp::Gt => ast::CmpOp::Lt,
+                        ast::CmpOp::GtE => ast::CmpOp::LtE,
+                        _ => continue,
+                    };
+                    bounds.push(Bound {
+                        attr,
+                        is_len,
+                        op,
+                        value,
+                    });
+                }
+            }
+            bounds
+        }
+        _ => vec![],
+    }
+}
+
+/// Get the attribute name and whether the operand is the length of the attribute
+/// e.g., `name` and `len(name)`.
+fn bound_operand(expr: &ast::Node<ast::Expr>) -> Option<(String, bool)> {
+    match &expr.node {
+        ast::Expr::Identifier(identifier) if identifier.names.len() == 1 => {
+            Some((identifier.get_name(), false))
+        }
+        ast::Expr::Call(call_expr) if call_expr.args.len() == 1 => match &call_expr.func.node {
+            ast::Expr::Identifier(func) if func.get_name() == "len" => {
+                bound_operand(&call_expr.args[0])
+                    .filter(|(_, is_len)| !is_len)
+                    .map(|(attr, _)| (attr, true))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the value of the number literal without the unit suffix e.g., `1` and `-1.5`.
+fn number_literal(expr: &ast::Node<ast::Expr>) -> Option<f64> {
+    match &expr.node {
+        ast::Expr::NumberLit(number_lit) if number_lit.binary_suffix.is_none() => {
+            Some(match number_lit.value {
+                ast::NumberLitValue::Int(v) => v as f64,
+                ast::NumberLitValue::Float(v) => v,
+            })
+        }
+        ast::Expr::Unary(unary_expr) if unary_expr.op == ast::UnaryOp::USub => {
+            number_literal(&unary_expr.operand).map(|v| -v)
+        }
+        _ => None,
+    }
+}
+
+/// Get the JSON number, which is an integer when the value is integral.
+fn number_value(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
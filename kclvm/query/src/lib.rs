@@ -3,11 +3,18 @@
 //! function to modify the file. The main principle is to parse the AST according to the
 //! input file name, and according to the ast::OverrideSpec transforms the nodes in the
 //! AST, recursively modifying or deleting the values of the nodes in the AST.
+pub mod checks;
+pub mod compat;
+pub mod docs;
+pub mod json_schema;
 pub mod node;
 pub mod r#override;
 pub mod path;
 pub mod query;
 pub mod selector;
+pub mod type_check;
+pub mod usages;
+pub mod vars;
 
 #[cfg(test)]
 mod tests;
@@ -18,9 +25,19 @@ use kclvm_ast_pretty::print_ast_module;
 use kclvm_error::diagnostic::Errors;
 use kclvm_parser::parse_single_file;
 
+pub use checks::{schema_checks, CheckConstraint};
+pub use compat::{schema_compat, ChangeKind, CompatReport, SchemaChange};
+pub use docs::{extract_docs, AttrDoc, DocModel, ModuleDoc, SchemaDoc};
+pub use json_schema::schema_json_schema;
 use kclvm_sema::pre_process::fix_config_expr_nest_attr;
 pub use query::{get_schema_type, GetSchemaOption};
-pub use r#override::{apply_override_on_module, apply_overrides};
+pub use r#override::{
+    apply_override_on_module, apply_overrides, apply_overrides_with_opts, load_override_specs_file,
+    OverrideTypeChecker,
+};
+pub use type_check::{check_expr_type, TypeError};
+pub use usages::{find_attribute_usages, AttrUsage, AttrUsageKind};
+pub use vars::{top_level_vars, TopLevelVar};
 
 /// Override and rewrite a file with override specifications. Please note that this is an external user API,
 /// and it can directly modify the KCL file in place.
@@ -34,6 +51,7 @@ pub use r#override::{apply_override_on_module, apply_overrides};
 ///     List of specs that need to be overridden.
 ///     Each spec string satisfies the form: <pkgpath>:<field_path>=<filed_value> or <pkgpath>:<field_path>-
 ///     When the pkgpath is '__main__', `<pkgpath>:` can be omitted.
+///     The form <field_path>.<index>+=<filed_value> inserts the value into the list before the index.
 ///
 /// `import_paths`: &\[[String]\]
 ///     List of import paths that are need to be added.
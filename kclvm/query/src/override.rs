@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 use anyhow::{anyhow, Result};
 
@@ -9,13 +10,19 @@ use kclvm_ast::walk_list_mut;
 use kclvm_ast::walker::MutSelfMutWalker;
 use kclvm_ast::MAIN_PKG;
 use kclvm_ast::{ast, path::get_target_path};
-use kclvm_ast_pretty::print_ast_module;
+use kclvm_ast_pretty::{print_ast_module, print_ast_node, ASTNode};
 use kclvm_parser::parse_expr;
 use kclvm_sema::pre_process::{fix_config_expr_nest_attr, transform_multi_assign};
+use kclvm_sema::resolver::{scope::ProgramScope, Options};
+use kclvm_sema::ty::{DictType, Type, TypeKind, TypeRef};
 
+use crate::type_check::{check_expr_type, TypeError};
 use crate::{node::AstNodeMover, path::parse_attribute_path};
 
-use super::util::invalid_spec_error;
+use super::util::{
+    invalid_spec_error, list_insert_index_out_of_range_error, override_type_error,
+    resolve_program_copy,
+};
 
 /// Import statement column offset always start with 1.
 /// todo: The (1-based) column offset needs to be constrained by specifications.
@@ -44,6 +51,25 @@ pub fn apply_overrides(
     import_paths: &[String],
     print_ast: bool,
 ) -> Result<()> {
+    apply_overrides_with_opts(prog, overrides, import_paths, print_ast, false)
+}
+
+/// Apply overrides on the AST program like [apply_overrides]. When `check_types` is true,
+/// all the override values are validated against the types of the override targets
+/// before any override is applied, see [OverrideTypeChecker].
+pub fn apply_overrides_with_opts(
+    prog: &mut ast::Program,
+    overrides: &[String],
+    import_paths: &[String],
+    print_ast: bool,
+    check_types: bool,
+) -> Result<()> {
+    if check_types && !overrides.is_empty() {
+        let type_checker = OverrideTypeChecker::new(prog);
+        for o in overrides {
+            parse_override_spec_with_type_check(o, &type_checker)?;
+        }
+    }
     for o in overrides {
         if let Some(modules) = prog.pkgs.get(MAIN_PKG) {
             for m in modules.iter() {
@@ -61,6 +87,164 @@ pub fn apply_overrides(
     Ok(())
 }
 
+/// OverrideTypeChecker validates the override values against the resolved types of the
+/// override targets in the main package e.g., rejects `config.replicas="3"` when the
+/// schema attribute `replicas` is typed `int`. The program is resolved once on a copy,
+/// so the input AST is not modified.
+///
+/// # Examples
+///
+/// ```no_check
+/// use kclvm_parser::load_program;
+/// use kclvm_query::r#override::{parse_override_spec_with_type_check, OverrideTypeChecker};
+///
+/// let prog = load_program(&["config.k"], None, None).unwrap();
+/// let checker = OverrideTypeChecker::new(&prog);
+/// parse_override_spec_with_type_check("config.replicas=3", &checker).unwrap();
+/// ```
+pub struct OverrideTypeChecker<'a> {
+    program: &'a ast::Program,
+    scope: ProgramScope,
+    /// The package paths imported by the main package to the import names, which are
+    /// used to refer to the schemas in the other packages.
+    imports: HashMap<String, String>,
+}
+
+impl<'a> OverrideTypeChecker<'a> {
+    pub fn new(program: &'a ast::Program) -> Self {
+        let (_, scope) = resolve_program_copy(
+            program,
+            Options {
+                lint_check: false,
+                merge_program: false,
+                ..Default::default()
+            },
+        );
+        let mut imports = HashMap::new();
+        for module in program.get_modules_for_pkg(MAIN_PKG) {
+            let module = module.read().expect("Failed to acquire module lock");
+            for stmt in &module.body {
+                if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                    if import_stmt.selected_names.is_empty() {
+                        imports
+                            .entry(import_stmt.path.node.clone())
+                            .or_insert_with(|| import_stmt.name.clone());
+                    }
+                }
+            }
+        }
+        Self {
+            program,
+            scope,
+            imports,
+        }
+    }
+
+    /// Check the override value against the type of the override target with the resolver,
+    /// so the non-literal values e.g., `[1, 2]`, `{a = 1}` and `Config {}` are checked as
+    /// well. The targets whose types can't be determined or written in the main package
+    /// are not checked.
+    pub fn check(&self, o: &ast::OverrideSpec) -> Result<(), TypeError> {
+        if let ast::OverrideAction::Delete = o.action {
+            return Ok(());
+        }
+        let mut parts =
+            parse_attribute_path(&o.field_path).map_err(|err| TypeError::new(err.to_string()))?;
+        // `a.b.0 += 1` inserts the item into the list `a.b`.
+        let is_item_insert = matches!(o.operation, ast::ConfigEntryOperation::Insert)
+            && parts.len() > 1
+            && parts
+                .last()
+                .map_or(false, |part| part.parse::<usize>().is_ok());
+        if is_item_insert {
+            parts.pop();
+        }
+        let expected_ty = match self.scope.main_scope() {
+            Some(main_scope) => match parts.split_first() {
+                Some((target_id, attrs)) => match main_scope.borrow().elems.get(target_id) {
+                    Some(obj) => get_attr_type_with_path(obj.borrow().ty.clone(), attrs),
+                    None => None,
+                },
+                None => None,
+            },
+            None => None,
+        };
+        let expected_ty = match expected_ty {
+            Some(expected_ty) if is_item_insert && expected_ty.is_list() => {
+                expected_ty.list_item_ty()
+            }
+            Some(expected_ty) if !is_item_insert => expected_ty,
+            _ => return Ok(()),
+        };
+        let expected_type = match self.type_annotation(&expected_ty) {
+            Some(expected_type) => expected_type,
+            None => return Ok(()),
+        };
+        // The value is checked in the form it is applied, e.g., `a=1+1` overrides `a` with the string.
+        let value = match build_expr_from_string(&o.field_value) {
+            Some(value) => print_ast_node(ASTNode::Expr(&value)),
+            None => return Ok(()),
+        };
+        check_expr_type(&value, &expected_type, Some(self.program))
+    }
+
+    /// Get the type annotation of the type in the main package, where the schemas in the
+    /// other packages are referred by the import names, return `None` when the type can't
+    /// be written in the main package.
+    fn type_annotation(&self, ty: &Type) -> Option<String> {
+        match &ty.kind {
+            TypeKind::Any | TypeKind::Bool | TypeKind::Int | TypeKind::Float | TypeKind::Str => {
+                Some(ty.ty_str())
+            }
+            TypeKind::BoolLit(v) => Some(if *v { "True" } else { "False" }.to_string()),
+            TypeKind::IntLit(v) => Some(v.to_string()),
+            TypeKind::FloatLit(v) => Some(v.to_string()),
+            TypeKind::StrLit(v) => Some(format!("{:?}", v)),
+            TypeKind::List(item_ty) => Some(format!("[{}]", self.type_annotation(item_ty)?)),
+            TypeKind::Dict(DictType { key_ty, val_ty, .. }) => Some(format!(
+                "{{{}:{}}}",
+                self.type_annotation(key_ty)?,
+                self.type_annotation(val_ty)?
+            )),
+            TypeKind::Union(types) => types
+                .iter()
+                .map(|ty| self.type_annotation(ty))
+                .collect::<Option<Vec<String>>>()
+                .map(|types| types.join(" | ")),
+            TypeKind::Schema(schema_ty) => {
+                if schema_ty.pkgpath.is_empty() || schema_ty.pkgpath == MAIN_PKG {
+                    Some(schema_ty.name.clone())
+                } else {
+                    self.imports
+                        .get(&schema_ty.pkgpath)
+                        .map(|name| format!("{}.{}", name, schema_ty.name))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Get the type of the schema attribute path `attrs` on the type `ty`, return `None`
+/// when the path is not a schema attribute path or the type can't be determined.
+fn get_attr_type_with_path(ty: TypeRef, attrs: &[String]) -> Option<TypeRef> {
+    if attrs.is_empty() {
+        return None;
+    }
+    let mut ty = ty;
+    for attr in attrs {
+        if !ty.is_schema() {
+            return None;
+        }
+        ty = ty.into_schema_type().get_type_of_attr(attr)?;
+    }
+    if ty.is_any() {
+        None
+    } else {
+        Some(ty)
+    }
+}
+
 /// Build a expression from string.
 pub fn build_expr_from_string(value: &str) -> Option<ast::NodeRef<ast::Expr>> {
     let expr: Option<ast::NodeRef<ast::Expr>> = parse_expr(value);
@@ -130,6 +314,15 @@ pub fn apply_override_on_module(
     // b = Config {}
     // ```
     transform_multi_assign(m);
+    // Insert the value into the list at the specific index when the spec is of the
+    // form `a.b.<index>+=value`, e.g., `c.items.0+=x` inserts `x` before the index 0.
+    if let ast::ConfigEntryOperation::Insert = o.operation {
+        if ss.len() > 1 {
+            if let Ok(index) = ss[ss.len() - 1].parse::<usize>() {
+                return insert_list_value_at_index(m, &ss[..ss.len() - 1], index, value);
+            }
+        }
+    }
     let mut transformer = OverrideTransformer {
         target_id: target_id.to_string(),
         field_paths: ss[1..].to_vec(),
@@ -139,11 +332,112 @@ pub fn apply_override_on_module(
         has_override: false,
         action: o.action,
         operation: o.operation,
+        replaced_ranges: vec![],
     };
     transformer.walk_module(m);
+    // Keep the comments of the overridden nodes along with them.
+    relocate_comments_in_ranges(m, &transformer.replaced_ranges);
     Ok(transformer.has_override)
 }
 
+/// Move the comments located inside the replaced node line ranges to the start line
+/// of these ranges. The new override value nodes do not hold the source positions of
+/// the original ones, thus these comments would otherwise be printed after the overridden
+/// nodes instead of being retained with them.
+fn relocate_comments_in_ranges(m: &mut ast::Module, ranges: &[(u64, u64)]) {
+    if ranges.is_empty() {
+        return;
+    }
+    for comment in m.comments.iter_mut() {
+        if let Some((start_line, _)) = ranges
+            .iter()
+            .find(|(start_line, end_line)| comment.line > *start_line && comment.line <= *end_line)
+        {
+            comment.line = *start_line;
+            comment.end_line = *start_line;
+        }
+    }
+    // The printer requires the comments are sorted by the line.
+    m.comments.sort_by_key(|comment| comment.line);
+}
+
+/// Insert the override value into the list found by the attribute path `parts`
+/// before the `index`. When the value is a list, all of its elements are inserted.
+fn insert_list_value_at_index(
+    m: &mut ast::Module,
+    parts: &[String],
+    index: usize,
+    value: &str,
+) -> Result<bool> {
+    let (target_id, field_paths) = match parts.split_first() {
+        Some(parts) => parts,
+        None => return Ok(false),
+    };
+    let values = match build_expr_from_string(value) {
+        Some(expr) => match expr.node {
+            ast::Expr::List(list_expr) => list_expr.elts,
+            _ => vec![expr],
+        },
+        None => return Err(anyhow!("Invalid override value {}", value)),
+    };
+    for stmt in m.body.iter_mut() {
+        let list_expr = match &mut stmt.node {
+            ast::Stmt::Assign(assign_stmt)
+                if assign_stmt.targets.len() == 1
+                    && get_target_path(&assign_stmt.targets[0].node) == *target_id =>
+            {
+                get_list_expr_mut_with_path(&mut assign_stmt.value.node, field_paths)
+            }
+            ast::Stmt::Unification(unification_stmt)
+                if unification_stmt
+                    .target
+                    .node
+                    .names
+                    .first()
+                    .map_or(false, |name| name.node == *target_id) =>
+            {
+                get_list_expr_mut_with_path(
+                    &mut unification_stmt.value.node.config.node,
+                    field_paths,
+                )
+            }
+            _ => None,
+        };
+        if let Some(list_expr) = list_expr {
+            if index > list_expr.elts.len() {
+                return Err(list_insert_index_out_of_range_error(
+                    &parts.join("."),
+                    index,
+                    list_expr.elts.len(),
+                ));
+            }
+            for (i, value) in values.into_iter().enumerate() {
+                list_expr.elts.insert(index + i, value);
+            }
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Get the list expression mut ref from the config expression with the attribute path.
+fn get_list_expr_mut_with_path<'a>(
+    expr: &'a mut ast::Expr,
+    parts: &[String],
+) -> Option<&'a mut ast::ListExpr> {
+    match parts.split_first() {
+        Some((part, parts)) => try_get_config_expr_mut(expr)?
+            .items
+            .iter_mut()
+            .filter(|item| get_key_path(&item.node.key) == *part)
+            .find_map(|item| get_list_expr_mut_with_path(&mut item.node.value.node, parts)),
+        None => match expr {
+            ast::Expr::List(list_expr) => Some(list_expr),
+            _ => None,
+        },
+    }
+}
+
 /// Parse override spec string to override structure.
 ///
 /// parse_override_spec("alice.age=10") -> ast::OverrideSpec {
@@ -185,6 +479,43 @@ pub fn parse_override_spec(spec: &str) -> Result<ast::OverrideSpec> {
     }
 }
 
+/// Parse override spec string to override structure like [parse_override_spec], and
+/// validate the override value against the type of the override target, see
+/// [OverrideTypeChecker].
+pub fn parse_override_spec_with_type_check(
+    spec: &str,
+    type_checker: &OverrideTypeChecker,
+) -> Result<ast::OverrideSpec> {
+    let o = parse_override_spec(spec)?;
+    type_checker
+        .check(&o)
+        .map_err(|err| override_type_error(spec, &err))?;
+    Ok(o)
+}
+
+/// Load override spec strings from a file, one spec per line. Blank lines and lines
+/// starting with `#` are ignored. Every spec is validated with [parse_override_spec]
+/// and the file and line of the first malformed spec is reported.
+pub fn load_override_specs_file<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        anyhow!(
+            "Failed to read the overrides file {}: {err}",
+            path.display()
+        )
+    })?;
+    let mut specs = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let spec = line.trim();
+        if spec.is_empty() || spec.starts_with('#') {
+            continue;
+        }
+        parse_override_spec(spec).map_err(|err| anyhow!("{}:{}: {err}", path.display(), i + 1))?;
+        specs.push(spec.to_string());
+    }
+    Ok(specs)
+}
+
 /// split_override_spec_op split the override_spec and do not split the override_op in list
 /// expr, dict expr and string e.g., "a.b=1" -> (a.b, 1, =), "a["a=1"]=1" -> (a["a=1"], =, 1)
 pub fn split_override_spec_op(spec: &str) -> Option<(String, String, ast::ConfigEntryOperation)> {
@@ -252,6 +583,10 @@ fn apply_import_paths_on_module(m: &mut ast::Module, import_paths: &[String]) ->
     let mut exist_import_set: HashSet<String> = HashSet::new();
     for stmt in &m.body {
         if let ast::Stmt::Import(import_stmt) = &stmt.node {
+            // The selective import does not expose the package name.
+            if import_stmt.is_selective() {
+                continue;
+            }
             if let Some(asname) = &import_stmt.asname {
                 exist_import_set.insert(format!("{} as {}", import_stmt.path.node, asname.node));
             } else {
@@ -277,6 +612,7 @@ fn apply_import_paths_on_module(m: &mut ast::Module, import_paths: &[String]) ->
             name: name.to_string(),
             asname: None,
             pkg_name: String::new(),
+            selected_names: vec![],
         };
         let import_stmt = Box::new(ast::Node::new(
             ast::Stmt::Import(import_node),
@@ -317,12 +653,17 @@ macro_rules! override_top_level_stmt {
                                     config_expr,
                                     merged_config_expr,
                                     &$self.action,
+                                    &mut $self.replaced_ranges,
                                 );
                             }
                         }
                         ast::Expr::Config(config_expr) => {
-                            $self.has_override =
-                                merge_config_expr(config_expr, merged_config_expr, &$self.action);
+                            $self.has_override = merge_config_expr(
+                                config_expr,
+                                merged_config_expr,
+                                &$self.action,
+                                &mut $self.replaced_ranges,
+                            );
                         }
                         _ => {}
                     }
@@ -342,12 +683,14 @@ macro_rules! override_top_level_stmt {
                                     config_expr,
                                     merged_config_expr,
                                     &$self.action,
+                                    &mut $self.replaced_ranges,
                                 );
                             }
                         }
                     }
                 } else {
                     // Override the node value.
+                    $self.replaced_ranges.push((item.line, item.end_line));
                     $stmt.value = value;
                     $self.has_override = true;
                 }
@@ -364,6 +707,7 @@ macro_rules! override_top_level_stmt {
             }
             ast::ConfigEntryOperation::Override => {
                 // Override the node value.
+                $self.replaced_ranges.push((item.line, item.end_line));
                 $stmt.value = value;
                 $self.has_override = true;
             }
@@ -381,6 +725,8 @@ struct OverrideTransformer {
     pub has_override: bool,
     pub action: ast::OverrideAction,
     pub operation: ast::ConfigEntryOperation,
+    /// The line ranges of the nodes replaced by the override value.
+    pub replaced_ranges: Vec<(u64, u64)>,
 }
 
 impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
@@ -436,6 +782,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
                                                     config_expr,
                                                     merged_config_expr,
                                                     &self.action,
+                                                    &mut self.replaced_ranges,
                                                 );
                                             }
                                         } else if let ast::Expr::Schema(merged_schema_expr) =
@@ -455,6 +802,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
                                                         config_expr,
                                                         merged_config_expr,
                                                         &self.action,
+                                                        &mut self.replaced_ranges,
                                                     );
                                                 }
                                             }
@@ -462,6 +810,8 @@ impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
                                             // Unification is only support to override the schema expression.
                                             if let ast::Expr::Schema(schema_expr) = value.node {
                                                 if self.field_paths.len() == 0 {
+                                                    self.replaced_ranges
+                                                        .push((item.line, item.end_line));
                                                     unification_stmt.value = Box::new(
                                                         ast::Node::dummy_node(schema_expr),
                                                     );
@@ -475,6 +825,8 @@ impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
                                         // Unification is only support to override the schema expression.
                                         if let ast::Expr::Schema(schema_expr) = value.node {
                                             if self.field_paths.len() == 0 {
+                                                self.replaced_ranges
+                                                    .push((item.line, item.end_line));
                                                 unification_stmt.value =
                                                     Box::new(ast::Node::dummy_node(schema_expr));
                                                 self.has_override = true;
@@ -720,7 +1072,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for OverrideTransformer {
 impl OverrideTransformer {
     /// Lookup schema config all fields and replace if it is matched with the override spec,
     /// return whether is found a replaced one.
-    fn lookup_config_and_replace(&self, config_expr: &mut ast::ConfigExpr) -> bool {
+    fn lookup_config_and_replace(&mut self, config_expr: &mut ast::ConfigExpr) -> bool {
         // Split a path into multiple parts. `a.b.c` -> ["a", "b", "c"]
         let parts = self
             .field_paths
@@ -733,6 +1085,7 @@ impl OverrideTransformer {
             &self.action,
             &self.operation,
             &self.override_value,
+            &mut self.replaced_ranges,
         )
     }
 
@@ -750,6 +1103,7 @@ fn merge_config_expr(
     config_expr: &mut ast::ConfigExpr,
     merged_config_expr: &ast::ConfigExpr,
     action: &ast::OverrideAction,
+    replaced_ranges: &mut Vec<(u64, u64)>,
 ) -> bool {
     let mut changed = false;
     for item in &merged_config_expr.items {
@@ -765,6 +1119,7 @@ fn merge_config_expr(
                 action,
                 &item.node.operation,
                 &Some(item.node.value.clone()),
+                replaced_ranges,
             ) {
                 changed = true;
             }
@@ -781,6 +1136,7 @@ fn replace_config_with_path_parts(
     action: &ast::OverrideAction,
     operation: &ast::ConfigEntryOperation,
     value: &Option<ast::NodeRef<ast::Expr>>,
+    replaced_ranges: &mut Vec<(u64, u64)>,
 ) -> bool {
     // Do not replace empty path parts and out of index parts on the config expression.
     if parts.is_empty() {
@@ -822,6 +1178,7 @@ fn replace_config_with_path_parts(
                                                         config_expr,
                                                         merged_config_expr,
                                                         action,
+                                                        replaced_ranges,
                                                     );
                                                 }
                                             }
@@ -830,6 +1187,7 @@ fn replace_config_with_path_parts(
                                                     config_expr,
                                                     merged_config_expr,
                                                     action,
+                                                    replaced_ranges,
                                                 );
                                             }
                                             _ => {}
@@ -854,12 +1212,14 @@ fn replace_config_with_path_parts(
                                                         config_expr,
                                                         merged_config_expr,
                                                         action,
+                                                        replaced_ranges,
                                                     );
                                                 }
                                             }
                                         }
                                     } else {
                                         // Override the node value.
+                                        replaced_ranges.push((item.line, item.end_line));
                                         item.node.value = value;
                                         changed = true;
                                     }
@@ -878,6 +1238,7 @@ fn replace_config_with_path_parts(
                                 }
                                 ast::ConfigEntryOperation::Override => {
                                     // Override the node value.
+                                    replaced_ranges.push((item.line, item.end_line));
                                     item.node.value = value;
                                     changed = true;
                                 }
@@ -905,6 +1266,7 @@ fn replace_config_with_path_parts(
                     action,
                     operation,
                     value,
+                    replaced_ranges,
                 );
             }
         }
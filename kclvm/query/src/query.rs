@@ -164,17 +164,28 @@ pub fn get_full_schema_type(
     schema_name: Option<&str>,
     opts: CompilationOptions,
 ) -> Result<IndexMap<String, SchemaType>> {
-    let mut result = IndexMap::new();
     let scope = resolve_file(&opts)?;
-    for (name, o) in &scope.borrow().elems {
+    let scope = scope.borrow();
+    get_full_schema_type_in_scope(&scope, schema_name, &opts.get_schema_opts)
+}
+
+/// Get the full schema type list in the scope, in which the parent schema types are
+/// included in the schema types.
+pub(crate) fn get_full_schema_type_in_scope(
+    scope: &Scope,
+    schema_name: Option<&str>,
+    opt: &GetSchemaOption,
+) -> Result<IndexMap<String, SchemaType>> {
+    let mut result = IndexMap::new();
+    for (name, o) in &scope.elems {
         if o.borrow().ty.is_schema() {
             let mut schema_ty = o.borrow().ty.into_schema_type();
             if let Some(base) = &schema_ty.base {
                 schema_ty.base = Some(Box::new(get_full_schema_type_recursive(*base.clone())?));
             }
-            if opts.get_schema_opts == GetSchemaOption::All
-                || (opts.get_schema_opts == GetSchemaOption::Definitions && !schema_ty.is_instance)
-                || (opts.get_schema_opts == GetSchemaOption::Instances && schema_ty.is_instance)
+            if *opt == GetSchemaOption::All
+                || (*opt == GetSchemaOption::Definitions && !schema_ty.is_instance)
+                || (*opt == GetSchemaOption::Instances && schema_ty.is_instance)
             {
                 // Schema name filter
                 match schema_name {
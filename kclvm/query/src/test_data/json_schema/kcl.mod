@@ -0,0 +1,4 @@
+[package]
+name = "json_schema"
+edition = "0.0.1"
+version = "0.0.1"
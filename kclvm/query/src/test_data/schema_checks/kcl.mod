@@ -0,0 +1,4 @@
+[package]
+name = "schema_checks"
+edition = "0.0.1"
+version = "0.0.1"
@@ -0,0 +1,4 @@
+[package]
+name = "top_level_vars"
+edition = "0.0.1"
+version = "0.0.1"
@@ -1,11 +1,17 @@
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, sync::Arc};
 
 use super::{r#override::apply_override_on_module, *};
 use crate::{
-    path::parse_attribute_path, r#override::parse_override_spec, selector::list_variables,
+    path::parse_attribute_path,
+    r#override::{
+        load_override_specs_file, parse_override_spec, parse_override_spec_with_type_check,
+    },
+    selector::list_variables,
 };
+use kclvm_ast::ast;
+use kclvm_ast_pretty::{print_ast_node, ASTNode};
 use kclvm_error::{DiagnosticId, ErrorKind, Level};
-use kclvm_parser::parse_file_force_errors;
+use kclvm_parser::{load_program, parse_file_force_errors, LoadProgramOptions, ParseSession};
 use kclvm_utils::path::PathPrefix;
 use pretty_assertions::assert_eq;
 use selector::ListOptions;
@@ -245,6 +251,26 @@ fn test_parse_override_spec_invalid() {
     }
 }
 
+#[test]
+fn test_load_override_specs_file() {
+    let specs = load_override_specs_file(get_test_dir("overrides/valid.txt".to_string())).unwrap();
+    assert_eq!(
+        specs,
+        vec![
+            "appConfig.image=\"kcl/kcl:v1\"".to_string(),
+            "appConfig.replicas=2".to_string(),
+            "appConfig.labels-".to_string(),
+        ]
+    );
+    let path = get_test_dir("overrides/invalid.txt".to_string());
+    let err = load_override_specs_file(&path).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with(&format!("{}:3: Invalid spec format 'a-1'", path.display())),
+        "{err}"
+    );
+}
+
 #[test]
 fn test_parse_property_path() {
     assert_eq!(parse_attribute_path("a.b.c").unwrap(), vec!["a", "b", "c"]);
@@ -839,6 +865,147 @@ fn test_override_file_insert() {
     fs::copy(simple_bk_path.clone(), simple_path.clone()).unwrap();
 }
 
+#[test]
+fn test_override_file_with_comments() {
+    let specs = vec![r#"config.image={tag="v2"}"#.to_string()];
+    let simple_path = get_test_dir("test_override_file_comments/main.k".to_string());
+    let simple_bk_path = get_test_dir("test_override_file_comments/main.bk.k".to_string());
+    let expect_path = get_test_dir("test_override_file_comments/expect.k".to_string());
+    fs::copy(simple_bk_path.clone(), simple_path.clone()).unwrap();
+
+    assert_eq!(
+        override_file(&simple_path.display().to_string(), &specs, &[])
+            .unwrap()
+            .result,
+        true
+    );
+
+    let simple_content = fs::read_to_string(simple_path.clone()).unwrap();
+    let expect_content = fs::read_to_string(expect_path.clone()).unwrap();
+
+    let simple_content = simple_content.replace("\r\n", "\n");
+    let expect_content = expect_content.replace("\r\n", "\n");
+
+    assert_eq!(simple_content, expect_content);
+
+    fs::copy(simple_bk_path.clone(), simple_path.clone()).unwrap();
+}
+
+#[test]
+fn test_parse_override_spec_with_types() {
+    let code = r#"
+schema Sub:
+    name: str
+
+schema Config:
+    replicas: int
+    name: str
+    mode: "dev" | "prod" = "dev"
+    ports: [int] = []
+    labels: {str:str} = {}
+    sub?: Sub
+
+config = Config {
+    replicas = 1
+    name = "config"
+}
+"#;
+    let mut program = load_program(
+        Arc::new(ParseSession::default()),
+        &["test.k"],
+        Some(LoadProgramOptions {
+            k_code_list: vec![code.to_string()],
+            ..Default::default()
+        }),
+        None,
+    )
+    .unwrap()
+    .program;
+    let checker = OverrideTypeChecker::new(&program);
+    let valid_specs = [
+        "config.replicas=3",
+        r#"config.name="override_name""#,
+        r#"config.mode="prod""#,
+        "config.ports=[80, 443]",
+        "config.ports+=[8080]",
+        "config.ports.0+=8080",
+        r#"config.labels={app = "nginx"}"#,
+        r#"config.sub=Sub {name = "sub"}"#,
+        r#"config.sub={name = "sub"}"#,
+        "config.replicas-",
+        "config.unknown=1",
+        "other=True",
+    ];
+    for spec in valid_specs {
+        assert!(
+            parse_override_spec_with_type_check(spec, &checker).is_ok(),
+            "{spec} test failed"
+        );
+    }
+    let invalid_specs = [
+        r#"config.replicas="3""#,
+        r#"config.mode="test""#,
+        r#"config.ports=["80"]"#,
+        r#"config.ports.0+="8080""#,
+        "config.labels={app = 1}",
+        "config.sub={name = 1}",
+        "config.name=[1]",
+    ];
+    for spec in invalid_specs {
+        let err = parse_override_spec_with_type_check(spec, &checker).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("Invalid override spec '{spec}', ")),
+            "{spec} test failed: {err}"
+        );
+    }
+    // Without the type checker, only the spec syntax is checked.
+    assert!(parse_override_spec(r#"config.replicas="3""#).is_ok());
+    // No override is applied when any override value is invalid.
+    let overrides = vec![
+        "config.name=\"a\"".to_string(),
+        r#"config.replicas="3""#.to_string(),
+    ];
+    assert!(apply_overrides_with_opts(&mut program, &overrides, &[], false, true).is_err());
+    let module = program.get_main_package_first_module().unwrap();
+    assert!(print_ast_module(&module).contains(r#"name = "config""#));
+}
+
+#[test]
+fn test_override_list_insert_index() {
+    let code = r#"
+config = {
+    items = [1, 2]
+}
+"#;
+    let mut module = parse_file_force_errors("test.k", Some(code.to_string())).unwrap();
+    let specs = vec!["config.items.0+=0", "config.items.3+=[3, 4]"];
+    for spec in specs {
+        assert!(apply_override_on_module(&mut module, spec, &[]).unwrap());
+    }
+    let items = match &module.body[0].node {
+        ast::Stmt::Assign(assign_stmt) => match &assign_stmt.value.node {
+            ast::Expr::Config(config_expr) => match &config_expr.items[0].node.value.node {
+                ast::Expr::List(list_expr) => list_expr
+                    .elts
+                    .iter()
+                    .map(|elt| print_ast_node(ASTNode::Expr(elt)))
+                    .collect::<Vec<String>>(),
+                _ => panic!("expect a list expression"),
+            },
+            _ => panic!("expect a config expression"),
+        },
+        _ => panic!("expect an assign statement"),
+    };
+    assert_eq!(items, vec!["0", "1", "2", "3", "4"]);
+
+    let err = apply_override_on_module(&mut module, "config.items.6+=6", &[]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Insert index 6 is out of range for the list 'config.items' with length 5"
+    );
+}
+
 #[test]
 fn test_list_variable_with_invalid_kcl() {
     let file = PathBuf::from("./src/test_data/test_list_variables/invalid.k")
@@ -1097,3 +1264,373 @@ fn test_list_merged_variables() {
         }
     }
 }
+
+#[test]
+fn test_schema_checks() {
+    let path = get_test_dir("schema_checks/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let checks = schema_checks(&program, "Server").unwrap();
+    let summary: Vec<_> = checks
+        .iter()
+        .map(|check| {
+            (
+                check.schema.as_str(),
+                check.test.as_str(),
+                check.if_cond.as_deref(),
+                check.message.as_deref(),
+                check.range.1,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "pkg.Base",
+                "len(name) > 0",
+                None,
+                Some("name must not be empty"),
+                5
+            ),
+            (
+                "Server",
+                "replicas >= 0",
+                None,
+                Some("\"replicas must be non-negative, got ${replicas}\""),
+                15
+            ),
+            ("Server", "\"app\" in labels", Some("labels"), None, 16),
+            ("PortMixin", "1 <= port <= 65535", None, None, 7),
+        ]
+    );
+    assert!(checks[0].range.0.ends_with("base.k"));
+    // The schema in the other package is denoted by the package path.
+    assert_eq!(schema_checks(&program, "pkg.Base").unwrap().len(), 1);
+    assert_eq!(
+        schema_checks(&program, "NotFound").unwrap_err().to_string(),
+        "schema 'NotFound' is not found"
+    );
+}
+
+#[test]
+fn test_schema_json_schema() {
+    let path = get_test_dir("json_schema/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let schema = schema_json_schema(&program, "Server").unwrap();
+    assert_eq!(schema["$schema"], json_schema::JSON_SCHEMA_DRAFT);
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["title"], "Server");
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["name"],
+        serde_json::json!({"type": "string", "minLength": 1, "maxLength": 63})
+    );
+    assert_eq!(
+        properties["port"],
+        serde_json::json!({"type": "integer", "minimum": 1, "maximum": 65535})
+    );
+    assert_eq!(
+        properties["protocol"],
+        serde_json::json!({"enum": ["TCP", "UDP"]})
+    );
+    assert_eq!(
+        properties["replicas"],
+        serde_json::json!({"type": "integer", "minimum": 0, "exclusiveMaximum": 10})
+    );
+    assert_eq!(
+        properties["ratio"],
+        serde_json::json!({"type": "number", "exclusiveMinimum": -0.5})
+    );
+    // The conditional checks are not expressible.
+    assert_eq!(
+        properties["labels"],
+        serde_json::json!({"type": "object", "additionalProperties": {"type": "string"}})
+    );
+    assert_eq!(properties["backup"], serde_json::json!({"$ref": "#"}));
+    assert_eq!(
+        properties["owner"],
+        serde_json::json!({"$ref": "#/$defs/pkg.Base"})
+    );
+    assert_eq!(
+        schema["$defs"]["pkg.Base"]["properties"]["name"]["maxLength"],
+        63
+    );
+    let mut required: Vec<&str> = schema["required"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|name| name.as_str().unwrap())
+        .collect();
+    required.sort();
+    assert_eq!(required, vec!["name", "port", "protocol", "replicas"]);
+    assert_eq!(
+        schema_json_schema(&program, "NotFound")
+            .unwrap_err()
+            .to_string(),
+        "schema 'NotFound' is not found"
+    );
+}
+
+#[test]
+fn test_find_attribute_usages() {
+    let path = get_test_dir("attribute_usages/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let summary = |usages: Vec<AttrUsage>| -> Vec<(AttrUsageKind, String, u64, u64)> {
+        usages
+            .into_iter()
+            .map(|usage| {
+                let filename = PathBuf::from(&usage.range.0)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string();
+                (usage.kind, filename, usage.range.1, usage.range.2)
+            })
+            .collect()
+    };
+    // The usages through the sub schemas are included, and the attribute with the
+    // same name of the other schema `Service` is excluded.
+    let usages = find_attribute_usages(&program, "pkg.Deployment", "replicas").unwrap();
+    assert_eq!(
+        summary(usages),
+        vec![
+            (AttrUsageKind::Set, "main.k".to_string(), 4, 4),
+            (AttrUsageKind::Set, "main.k".to_string(), 14, 4),
+            (AttrUsageKind::Set, "main.k".to_string(), 16, 25),
+            (AttrUsageKind::Set, "main.k".to_string(), 17, 27),
+            (AttrUsageKind::Read, "main.k".to_string(), 19, 12),
+            (AttrUsageKind::Read, "main.k".to_string(), 19, 26),
+            (AttrUsageKind::Set, "base.k".to_string(), 3, 4),
+            (AttrUsageKind::Read, "base.k".to_string(), 6, 8),
+        ]
+    );
+    // The redeclaration in the sub schema and the default value in the parent schema
+    // are both set sites in the schema chain.
+    let usages = find_attribute_usages(&program, "StatefulDeployment", "replicas").unwrap();
+    assert_eq!(
+        summary(usages),
+        vec![
+            (AttrUsageKind::Set, "main.k".to_string(), 4, 4),
+            (AttrUsageKind::Set, "main.k".to_string(), 16, 25),
+            (AttrUsageKind::Read, "main.k".to_string(), 19, 26),
+            (AttrUsageKind::Set, "base.k".to_string(), 3, 4),
+        ]
+    );
+    assert_eq!(
+        find_attribute_usages(&program, "pkg.Deployment", "not_found")
+            .unwrap_err()
+            .to_string(),
+        "attribute 'not_found' is not found in the schema 'pkg.Deployment'"
+    );
+    assert_eq!(
+        find_attribute_usages(&program, "NotFound", "replicas")
+            .unwrap_err()
+            .to_string(),
+        "schema 'NotFound' is not found"
+    );
+}
+
+#[test]
+fn test_schema_compat() {
+    let load = |name: &str| {
+        let path = get_test_dir(format!("schema_compat/{}", name));
+        load_program(
+            Arc::new(ParseSession::default()),
+            &[&path.display().to_string()],
+            None,
+            None,
+        )
+        .unwrap()
+        .program
+    };
+    let (old, new) = (load("old.k"), load("new.k"));
+    let report = schema_compat(&old, &new, "Person").unwrap();
+    assert!(report.is_breaking());
+    let changes: Vec<(ChangeKind, Option<&str>)> = report
+        .changes
+        .iter()
+        .map(|change| (change.kind, change.attr.as_deref()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (ChangeKind::Breaking, Some("age")),
+            (ChangeKind::Breaking, Some("nickname")),
+            (ChangeKind::Breaking, Some("port")),
+            (ChangeKind::NonBreaking, Some("replicas")),
+            (ChangeKind::NonBreaking, Some("replicas")),
+            (ChangeKind::Breaking, Some("kind")),
+            (ChangeKind::Breaking, Some("owner")),
+            (ChangeKind::Additive, Some("labels")),
+            (ChangeKind::Additive, None),
+        ]
+    );
+    assert_eq!(report.changes[1].message, "attribute 'nickname' is removed");
+    assert!(report.changes[2].message.contains("is narrowed"));
+    assert!(report.changes[3].message.contains("is widened"));
+    assert_eq!(
+        report.changes[4].message,
+        "default value of attribute 'replicas' is changed from '1' to '2'"
+    );
+    assert_eq!(report.changes_of(ChangeKind::Additive).count(), 2);
+    // The inherited schema is compatible with itself.
+    let report = schema_compat(&old, &old, "Base").unwrap();
+    assert!(report.changes.is_empty());
+    // The removed schema is breaking, and the unknown schema is an error.
+    let report = schema_compat(&old, &Default::default(), "Person").unwrap();
+    assert_eq!(report.changes[0].message, "schema 'Person' is removed");
+    assert!(schema_compat(&old, &new, "NotFound").is_err());
+}
+
+#[test]
+fn test_extract_docs() {
+    let path = get_test_dir("docs/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let docs = extract_docs(&program);
+    assert_eq!(docs.modules.len(), 2);
+    let main = &docs.modules[0];
+    assert_eq!(main.pkgpath, "__main__");
+    assert_eq!(
+        main.doc.as_deref(),
+        Some("The main module.\n\nIt defines the application.")
+    );
+    assert_eq!(main.doc_range.as_ref().unwrap().1, 1);
+    assert_eq!(main.schemas.len(), 1);
+    let app = &main.schemas[0];
+    assert_eq!(app.name, "App");
+    assert_eq!(app.summary, "The application.");
+    assert_eq!(app.parent.as_deref(), Some("pkg.Base"));
+    assert_eq!(app.range.1, 7);
+    let attrs: Vec<(&str, &str, Option<&str>, bool, &str, u64)> = app
+        .attrs
+        .iter()
+        .map(|attr| {
+            (
+                attr.name.as_str(),
+                attr.ty.as_str(),
+                attr.default.as_deref(),
+                attr.is_optional,
+                attr.doc.as_str(),
+                attr.range.1,
+            )
+        })
+        .collect();
+    assert_eq!(
+        attrs,
+        vec![
+            (
+                "replicas",
+                "int",
+                Some("1"),
+                true,
+                "The replica count,\ndefaults to 1.",
+                18
+            ),
+            ("labels", "{str:str}", None, false, "The labels.", 19),
+            (
+                "base",
+                "pkg.Base",
+                Some("pkg.Base {name = \"app\"}"),
+                false,
+                "",
+                20
+            ),
+        ]
+    );
+    assert_eq!(docs.modules[1].pkgpath, "pkg");
+    assert_eq!(docs.modules[1].doc, None);
+    assert_eq!(docs.modules[1].schemas[0].attrs[0].name, "name");
+}
+
+#[test]
+fn test_check_expr_type() {
+    let path = get_test_dir("schema_checks/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let program = Some(&program);
+    assert!(check_expr_type("1", "int", program).is_ok());
+    assert!(check_expr_type("1", "int|str", program).is_ok());
+    assert!(check_expr_type("1", "str", program)
+        .unwrap_err()
+        .message
+        .contains("expected str"));
+    assert!(check_expr_type("[1, \"a\"]", "[int]", program).is_err());
+    // The schema in the main package and the imported package.
+    assert!(check_expr_type("{replicas = 1}", "Server", program).is_ok());
+    assert!(check_expr_type("{replicas = \"1\"}", "Server", program).is_err());
+    assert!(check_expr_type("pkg.Base {name = \"a\"}", "pkg.Base", program).is_ok());
+    assert_eq!(
+        check_expr_type("1", "[int", program).unwrap_err().message,
+        "invalid type '[int'"
+    );
+    assert_eq!(
+        check_expr_type("1 +", "int", program).unwrap_err().message,
+        "invalid expression '1 +'"
+    );
+    assert_eq!(
+        check_expr_type("1 2", "int", program).unwrap_err().message,
+        "invalid expression '1 2'"
+    );
+    // The input program is not modified.
+    assert_eq!(program.unwrap().get_main_files().len(), 1);
+}
+
+#[test]
+fn test_top_level_vars() {
+    let path = get_test_dir("top_level_vars/main.k".to_string());
+    let program = load_program(
+        Arc::new(ParseSession::default()),
+        &[&path.display().to_string()],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let vars: Vec<(String, String, bool)> = top_level_vars(&program)
+        .into_iter()
+        .map(|var| (var.name, var.ty, var.exported))
+        .collect();
+    assert_eq!(
+        vars,
+        vec![
+            ("server".to_string(), "Server".to_string(), true),
+            ("_names".to_string(), "[str]".to_string(), false),
+            ("base".to_string(), "pkg.Base".to_string(), true),
+            ("replicas".to_string(), "int".to_string(), true),
+        ]
+    );
+}
@@ -0,0 +1,168 @@
+//! Type check a single expression against an expected type, which is used to validate
+//! the user-entered values e.g., the `-O` override values and the form inputs against
+//! the declared types of the schema attributes before applying them.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use kclvm_ast::{ast, walker::MutSelfWalker, MAIN_PKG};
+use kclvm_error::Level;
+use kclvm_parser::{parse_expr, parse_type};
+use kclvm_sema::resolver::Options;
+
+use crate::util::resolve_program_copy;
+
+/// The virtual file of the expression in the main package.
+const CHECK_EXPR_FILENAME: &str = "__kcl_check_expr__.k";
+/// The variable name the expression is assigned to with the expected type annotation.
+const CHECK_EXPR_NAME: &str = "__kcl_check_expr__";
+
+/// The error of the expression type check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    /// The error message e.g., `expected int, got str(1)`.
+    pub message: String,
+}
+
+impl TypeError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+/// Check whether the expression `expr_src` e.g., `{replicas = 3}` is compatible with the
+/// expected type `expected_type` e.g., `apps.Deployment` with the resolver. The names in
+/// the expression and the type are looked up in the main package of the program context,
+/// and the modules imported by the main package e.g., `apps` in `import apps` can be used
+/// as well. Without the program context, only the builtin types and functions can be used.
+/// The program is resolved on a copy, so the input AST is not modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_query::type_check::check_expr_type;
+///
+/// assert!(check_expr_type("[1, 2]", "[int]", None).is_ok());
+/// assert!(check_expr_type("{a = 1}", "{str:int}", None).is_ok());
+/// assert!(check_expr_type("\"1\"", "int", None).is_err());
+/// ```
+pub fn check_expr_type(
+    expr_src: &str,
+    expected_type: &str,
+    program: Option<&ast::Program>,
+) -> Result<(), TypeError> {
+    let ty = parse_type(expected_type)
+        .ok_or_else(|| TypeError::new(format!("invalid type '{}'", expected_type)))?;
+    let value = parse_complete_expr(expr_src.trim())
+        .ok_or_else(|| TypeError::new(format!("invalid expression '{}'", expr_src)))?;
+    // Assign the expression to the variable annotated with the expected type, so the
+    // resolver checks the expression against the type with the type subsumption. The
+    // imports of the main package are added, so the expression and the type can refer
+    // to the imported modules.
+    let mut program = program.cloned().unwrap_or_default();
+    let program_files: HashSet<String> = program.modules.keys().cloned().collect();
+    let mut body: Vec<ast::NodeRef<ast::Stmt>> = vec![];
+    let mut imports = HashSet::new();
+    for module in program.get_modules_for_pkg(MAIN_PKG) {
+        let module = module.read().expect("Failed to acquire module lock");
+        for stmt in &module.body {
+            if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                let asname = import_stmt
+                    .asname
+                    .as_ref()
+                    .map(|asname| asname.node.clone());
+                if imports.insert((import_stmt.path.node.clone(), asname)) {
+                    body.push(stmt.clone());
+                }
+            }
+        }
+    }
+    let pos = (CHECK_EXPR_FILENAME.to_string(), 1, 0, 1, 0);
+    let target = ast::Target {
+        name: ast::Node::node_with_pos(CHECK_EXPR_NAME.to_string(), pos.clone()),
+        paths: vec![],
+        pkgpath: "".to_string(),
+    };
+    body.push(Box::new(ast::Node::node_with_pos(
+        ast::Stmt::Assign(ast::AssignStmt {
+            targets: vec![Box::new(ast::Node::node_with_pos(target, pos.clone()))],
+            value,
+            ty: Some(ty),
+        }),
+        pos,
+    )));
+    let module = ast::Module {
+        filename: CHECK_EXPR_FILENAME.to_string(),
+        body,
+        ..Default::default()
+    };
+    program
+        .pkgs
+        .entry(MAIN_PKG.to_string())
+        .or_default()
+        .push(CHECK_EXPR_FILENAME.to_string());
+    program.modules.insert(
+        CHECK_EXPR_FILENAME.to_string(),
+        Arc::new(RwLock::new(module)),
+    );
+    let (_, scope) = resolve_program_copy(
+        &program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            ..Default::default()
+        },
+    );
+    // Only the errors in the expression are reported.
+    let message = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .flat_map(|diag| diag.messages.iter())
+        .find(|msg| !program_files.contains(&msg.range.0.filename))
+        .map(|msg| msg.message.clone());
+    match message {
+        Some(message) => Err(TypeError::new(message)),
+        None => Ok(()),
+    }
+}
+
+/// Parse the source string to a single expression, return `None` when the source has
+/// syntax errors or extra tokens after the expression.
+fn parse_complete_expr(src: &str) -> Option<ast::NodeRef<ast::Expr>> {
+    let expr = parse_expr(src)?;
+    let mut finder = MissingExprFinder::default();
+    finder.walk_expr(&expr.node);
+    // The expression must end at the end of the source.
+    let end_line = src.lines().count() as u64;
+    let end_column = src.lines().last().map_or(0, |line| line.chars().count()) as u64;
+    if finder.found || expr.end_line != end_line || expr.end_column != end_column {
+        None
+    } else {
+        Some(expr)
+    }
+}
+
+/// Find the missing expressions which are the placeholders of the syntax errors.
+#[derive(Debug, Default)]
+struct MissingExprFinder {
+    found: bool,
+}
+
+impl MutSelfWalker for MissingExprFinder {
+    fn walk_missing_expr(&mut self, _missing_expr: &ast::MissingExpr) {
+        self.found = true;
+    }
+}
@@ -0,0 +1,279 @@
+//! Find the usages of a schema attribute across the program, which is used to analyze
+//! the impact before changing a widely-used schema attribute.
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::{ast, walker::MutSelfWalker};
+use kclvm_error::Position;
+use kclvm_sema::{
+    advanced_resolver::AdvancedResolver,
+    core::{
+        global_state::GlobalState,
+        symbol::{SchemaSymbol, Symbol, SymbolKind, SymbolRef},
+    },
+    namer::Namer,
+    resolver::Options,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::util::resolve_program_copy;
+
+/// The kind of the schema attribute usage.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttrUsageKind {
+    /// The attribute is assigned e.g., `replicas = 3` in the config `Deployment {replicas = 3}`
+    /// or the assignment `deploy.replicas = 3`.
+    Set,
+    /// The attribute is read e.g., `deploy.replicas` or `replicas` in the schema check block.
+    Read,
+}
+
+/// A usage of the schema attribute.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttrUsage {
+    pub kind: AttrUsageKind,
+    /// The source range `(filename, line, column, end_line, end_column)` of the attribute name.
+    pub range: ast::PosTuple,
+}
+
+/// Find all the usages of the schema attribute `attr` in the program, including the usages
+/// through the sub schemas e.g., a sub schema instance setting the inherited attribute. The
+/// attribute default values and the attribute assignments in the bodies of the schema chain,
+/// i.e., the schema, its parent schemas and its sub schemas, are all reported as set sites. The
+/// schema name is looked up in the main package, and the schema in the other package is denoted
+/// by `pkgpath.Name`. The usages are sorted by the source position, and the program is resolved
+/// on a copy, so the input AST is not modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::usages::{find_attribute_usages, AttrUsageKind};
+/// use std::sync::Arc;
+///
+/// let code = r#"
+/// schema Deployment:
+///     replicas: int
+///
+/// deploy = Deployment {replicas = 3}
+/// replicas = deploy.replicas
+/// "#;
+/// let program = load_program(
+///     Arc::new(ParseSession::default()),
+///     &["main.k"],
+///     Some(LoadProgramOptions {
+///         k_code_list: vec![code.to_string()],
+///         ..Default::default()
+///     }),
+///     None,
+/// )
+/// .unwrap()
+/// .program;
+/// let usages = find_attribute_usages(&program, "Deployment", "replicas").unwrap();
+/// let kinds: Vec<AttrUsageKind> = usages.iter().map(|usage| usage.kind).collect();
+/// assert_eq!(kinds, vec![AttrUsageKind::Set, AttrUsageKind::Read]);
+/// ```
+pub fn find_attribute_usages(
+    program: &ast::Program,
+    schema: &str,
+    attr: &str,
+) -> Result<Vec<AttrUsage>> {
+    let (program, scope) = resolve_program_copy(
+        program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            type_erasure: false,
+            ..Default::default()
+        },
+    );
+    let mut gs = GlobalState::default();
+    Namer::find_symbols(&program, &mut gs);
+    AdvancedResolver::resolve_program(&program, &mut gs, scope.node_ty_map)?;
+
+    let symbols = gs.get_symbols();
+    let schema_ref = symbols
+        .get_symbol_by_qualified_name(schema)
+        .filter(|symbol_ref| matches!(symbol_ref.get_kind(), SymbolKind::Schema))
+        .ok_or_else(|| anyhow!("schema '{}' is not found", schema))?;
+    let attr_ref = symbols
+        .get_symbol(schema_ref)
+        .and_then(|schema_symbol| schema_symbol.get_attribute(attr, symbols, None))
+        .ok_or_else(|| {
+            anyhow!(
+                "attribute '{}' is not found in the schema '{}'",
+                attr,
+                schema
+            )
+        })?;
+    // The attribute symbols of the schema and its sub schemas, the sub schema may
+    // inherit the attribute symbol from the schema or redeclare its own one.
+    let mut attr_refs: HashSet<SymbolRef> = HashSet::from([attr_ref]);
+    // The schema chain of the schema, its parent schemas and its sub schemas, whose
+    // attribute defaults and assignments in the schema bodies are all set sites.
+    let mut parents = vec![];
+    if let Some(schema_symbol) = symbols.get_schema_symbol(schema_ref) {
+        schema_symbol.get_parents(symbols, &mut parents);
+    }
+    let mut chain: Vec<&SchemaSymbol> = std::iter::once(schema_ref)
+        .chain(parents)
+        .filter_map(|schema_ref| symbols.get_schema_symbol(schema_ref))
+        .collect();
+    for (_, schema_symbol) in symbols.get_all_schemas().iter() {
+        let mut parents = vec![];
+        schema_symbol.get_parents(symbols, &mut parents);
+        if parents.contains(&schema_ref) {
+            if let Some(sub_attr_ref) = schema_symbol.get_attribute(attr, symbols, None) {
+                attr_refs.insert(sub_attr_ref);
+            }
+            chain.push(schema_symbol);
+        }
+    }
+
+    let mut usages = vec![];
+    for schema_symbol in chain {
+        let (start, _) = schema_symbol.get_range();
+        let module = match program.get_module(&start.filename)? {
+            Some(module) => module,
+            None => continue,
+        };
+        for stmt in &module.body {
+            if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                if schema_stmt.name.node == schema_symbol.get_name() {
+                    collect_attr_set_sites(&schema_stmt.body, attr, &mut usages);
+                }
+            }
+        }
+    }
+
+    let mut collector = SetSiteCollector::default();
+    for module in program.modules.values() {
+        let module = module
+            .read()
+            .map_err(|e| anyhow!("Failed to acquire module lock: {e}"))?;
+        collector.walk_module(&module);
+    }
+
+    for (node_key, symbol_ref) in symbols.get_node_symbol_map() {
+        if !matches!(symbol_ref.get_kind(), SymbolKind::Unresolved) {
+            continue;
+        }
+        let symbol = match symbols.get_symbol(*symbol_ref) {
+            Some(symbol) => symbol,
+            None => continue,
+        };
+        if !symbol
+            .get_definition()
+            .map_or(false, |def| attr_refs.contains(&def))
+        {
+            continue;
+        }
+        let kind = if collector.ids.contains(&node_key.id) {
+            AttrUsageKind::Set
+        } else {
+            AttrUsageKind::Read
+        };
+        let (start, end) = symbol.get_range();
+        usages.push(AttrUsage {
+            kind,
+            range: pos_tuple(&start, &end),
+        });
+    }
+    usages.sort_by(|a, b| {
+        (&a.range.0, a.range.1, a.range.2).cmp(&(&b.range.0, b.range.1, b.range.2))
+    });
+    usages.dedup();
+    Ok(usages)
+}
+
+/// Collect the attribute defaults and the attribute assignments in the schema body
+/// e.g., `replicas: int = 1` and `replicas = 3`.
+fn collect_attr_set_sites(
+    stmts: &[ast::NodeRef<ast::Stmt>],
+    attr: &str,
+    usages: &mut Vec<AttrUsage>,
+) {
+    for stmt in stmts {
+        match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr)
+                if schema_attr.name.node == attr && schema_attr.value.is_some() =>
+            {
+                usages.push(AttrUsage {
+                    kind: AttrUsageKind::Set,
+                    range: schema_attr.name.pos(),
+                });
+            }
+            ast::Stmt::Assign(assign_stmt) => {
+                for target in &assign_stmt.targets {
+                    if target.node.paths.is_empty() && target.node.name.node == attr {
+                        usages.push(AttrUsage {
+                            kind: AttrUsageKind::Set,
+                            range: target.node.name.pos(),
+                        });
+                    }
+                }
+            }
+            ast::Stmt::AugAssign(aug_assign_stmt)
+                if aug_assign_stmt.target.node.paths.is_empty()
+                    && aug_assign_stmt.target.node.name.node == attr =>
+            {
+                usages.push(AttrUsage {
+                    kind: AttrUsageKind::Set,
+                    range: aug_assign_stmt.target.node.name.pos(),
+                });
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_attr_set_sites(&if_stmt.body, attr, usages);
+                collect_attr_set_sites(&if_stmt.orelse, attr, usages);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collect the AST ids of the names which are assigned, i.e., the assignment
+/// targets and the config entry keys.
+#[derive(Debug, Default)]
+struct SetSiteCollector {
+    ids: HashSet<ast::AstIndex>,
+}
+
+impl MutSelfWalker for SetSiteCollector {
+    fn walk_config_expr(&mut self, config_expr: &ast::ConfigExpr) {
+        for config_entry in &config_expr.items {
+            if let Some(key) = &config_entry.node.key {
+                if let ast::Expr::Identifier(identifier) = &key.node {
+                    self.ids
+                        .extend(identifier.names.iter().map(|name| name.id.clone()));
+                }
+                self.walk_expr(&key.node);
+            }
+            self.walk_expr(&config_entry.node.value.node);
+        }
+    }
+
+    fn walk_target(&mut self, target: &ast::Target) {
+        self.ids.insert(target.name.id.clone());
+        for path in target.paths.iter() {
+            match path {
+                ast::MemberOrIndex::Member(member) => {
+                    self.ids.insert(member.id.clone());
+                }
+                ast::MemberOrIndex::Index(index) => self.walk_expr(&index.node),
+            }
+        }
+    }
+}
+
+#[inline]
+fn pos_tuple(start: &Position, end: &Position) -> ast::PosTuple {
+    (
+        start.filename.clone(),
+        start.line,
+        start.column.unwrap_or_default(),
+        end.line,
+        end.column.unwrap_or_default(),
+    )
+}
@@ -1,4 +1,10 @@
+use std::sync::{Arc, RwLock};
+
 use anyhow::{anyhow, Result};
+use kclvm_ast::ast;
+use kclvm_sema::resolver::{resolve_program_with_opts, scope::ProgramScope, Options};
+
+use crate::type_check::TypeError;
 
 /// Get field package path and identifier name from the path.
 /// (TODO: Needs to be a package related to the language specification
@@ -28,6 +34,27 @@ pub(crate) fn invalid_spec_error(spec: &str) -> anyhow::Error {
     anyhow!("Invalid spec format '{}', expected <field_path>=filed_value>, <field_path>:filed_value>, <field_path>+=filed_value> or <field_path>-", spec)
 }
 
+/// Get the override spec type error message.
+#[inline]
+pub(crate) fn override_type_error(spec: &str, err: &TypeError) -> anyhow::Error {
+    anyhow!("Invalid override spec '{}', {}", spec, err)
+}
+
+/// Get the list insert index out of range error message.
+#[inline]
+pub(crate) fn list_insert_index_out_of_range_error(
+    path: &str,
+    index: usize,
+    len: usize,
+) -> anyhow::Error {
+    anyhow!(
+        "Insert index {} is out of range for the list '{}' with length {}",
+        index,
+        path,
+        len
+    )
+}
+
 /// Get the invalid symbol selector spec error message.
 #[inline]
 pub(crate) fn invalid_symbol_selector_spec_error(spec: &str) -> anyhow::Error {
@@ -36,3 +63,25 @@ pub(crate) fn invalid_symbol_selector_spec_error(spec: &str) -> anyhow::Error {
         spec
     )
 }
+
+/// Resolve the program on a deep copy of the modules, because the resolver will
+/// transform the AST, and return the resolved copy with its program scope.
+pub(crate) fn resolve_program_copy(
+    program: &ast::Program,
+    opts: Options,
+) -> (ast::Program, ProgramScope) {
+    let mut program = ast::Program {
+        modules: program
+            .modules
+            .iter()
+            .map(|(filename, module)| {
+                let module = module.read().expect("Failed to acquire module lock");
+                (filename.clone(), Arc::new(RwLock::new(module.clone())))
+            })
+            .collect(),
+        modules_not_imported: Default::default(),
+        ..program.clone()
+    };
+    let scope = resolve_program_with_opts(&mut program, opts, None);
+    (program, scope)
+}
@@ -0,0 +1,98 @@
+//! List the top-level variables of the main package with their inferred types, which is
+//! used e.g., for the autocomplete in a REPL or the summary of what a file defines.
+
+use kclvm_ast::{ast, MAIN_PKG};
+use kclvm_sema::resolver::{scope::ScopeObjectKind, Options};
+use serde::{Deserialize, Serialize};
+
+use crate::util::resolve_program_copy;
+
+/// A top-level variable of the main package.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TopLevelVar {
+    /// The variable name e.g., `deploy`.
+    pub name: String,
+    /// The inferred type of the variable e.g., `Deployment` or `[int]`.
+    pub ty: String,
+    /// Whether the variable is exported, i.e., its name is not prefixed with an underscore.
+    pub exported: bool,
+}
+
+/// List the top-level variables of the main package with their types inferred by the resolver,
+/// in the order of their first definitions in the source. The schemas, type aliases and imported
+/// modules are not included, and the program is resolved on a copy, so the input AST is not
+/// modified.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+/// use kclvm_query::vars::top_level_vars;
+/// use std::sync::Arc;
+///
+/// let code = r#"
+/// a = 1
+/// _b = [a]
+/// "#;
+/// let program = load_program(
+///     Arc::new(ParseSession::default()),
+///     &["main.k"],
+///     Some(LoadProgramOptions {
+///         k_code_list: vec![code.to_string()],
+///         ..Default::default()
+///     }),
+///     None,
+/// )
+/// .unwrap()
+/// .program;
+/// let vars: Vec<(String, String, bool)> = top_level_vars(&program)
+///     .into_iter()
+///     .map(|var| (var.name, var.ty, var.exported))
+///     .collect();
+/// assert_eq!(
+///     vars,
+///     vec![
+///         ("a".to_string(), "int".to_string(), true),
+///         ("_b".to_string(), "[int]".to_string(), false),
+///     ]
+/// );
+/// ```
+pub fn top_level_vars(program: &ast::Program) -> Vec<TopLevelVar> {
+    let (_, scope) = resolve_program_copy(
+        program,
+        Options {
+            lint_check: false,
+            merge_program: false,
+            ..Default::default()
+        },
+    );
+    let main_scope = match scope.scope_map.get(MAIN_PKG) {
+        Some(main_scope) => main_scope.borrow(),
+        None => return vec![],
+    };
+    let mut vars: Vec<(ast::PosTuple, TopLevelVar)> = main_scope
+        .elems
+        .iter()
+        .filter_map(|(name, obj)| {
+            let obj = obj.borrow();
+            matches!(obj.kind, ScopeObjectKind::Variable).then(|| {
+                (
+                    (
+                        obj.start.filename.clone(),
+                        obj.start.line,
+                        obj.start.column.unwrap_or_default(),
+                        obj.end.line,
+                        obj.end.column.unwrap_or_default(),
+                    ),
+                    TopLevelVar {
+                        name: name.to_string(),
+                        ty: obj.ty.full_ty_str(),
+                        exported: !name.starts_with('_'),
+                    },
+                )
+            })
+        })
+        .collect();
+    vars.sort_by(|(a, _), (b, _)| a.cmp(b));
+    vars.into_iter().map(|(_, var)| var).collect()
+}
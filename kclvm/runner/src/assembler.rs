@@ -279,6 +279,16 @@ impl KclvmAssembler {
     /// `gen_libs` will create multiple threads and call the method provided by [KclvmLibAssembler] in each thread
     /// to generate the dynamic link library in parallel.
     pub(crate) fn gen_libs(self, args: &ExecProgramArgs) -> Result<Vec<String>> {
+        Ok(self.gen_libs_with_cache_stats(args)?.0)
+    }
+
+    /// Generate the dynamic link libraries like [KclvmAssembler::gen_libs], and also return
+    /// whether the library of each package is reused from the build cache. The library of
+    /// the main package is never cached.
+    pub(crate) fn gen_libs_with_cache_stats(
+        self,
+        args: &ExecProgramArgs,
+    ) -> Result<(Vec<String>, IndexMap<String, bool>)> {
         self.clean_path_for_genlibs(
             DEFAULT_IR_FILE,
             &self.single_file_assembler.get_code_file_suffix(),
@@ -312,6 +322,7 @@ impl KclvmAssembler {
             );
         }
         let mut lib_paths = vec![];
+        let mut lib_cache_stats = IndexMap::default();
         for (pkgpath, (compile_prog, import_names, cache_dir)) in compile_progs {
             // Clone a single file assembler for one thread.
             let assembler = self.single_file_assembler.clone();
@@ -344,15 +355,16 @@ impl KclvmAssembler {
                 // specify a standard entry for these multi-files and cannot
                 // be shared, so the cache of the main package is not read and
                 // written.
-                let file_path = if is_main_pkg {
+                let (file_path, lib_cached) = if is_main_pkg {
                     // generate dynamic link library for single file kcl program
-                    assembler.assemble(
+                    let file_path = assembler.assemble(
                         &compile_prog,
                         import_names,
                         &code_file,
                         &code_file_path,
                         args,
-                    )?
+                    )?;
+                    (file_path, false)
                 } else {
                     // Read the lib path cache
                     let file_relative_path: Option<String> = load_pkg_cache(
@@ -378,7 +390,7 @@ impl KclvmAssembler {
                         None => None,
                     };
                     match file_abs_path {
-                        Some(path) => path,
+                        Some(path) => (path, true),
                         None => {
                             // Generate the object file for single file kcl program.
                             let file_path = assembler.assemble(
@@ -397,17 +409,18 @@ impl KclvmAssembler {
                                 CacheOption::default(),
                                 &self.external_pkgs,
                             );
-                            file_path
+                            (file_path, false)
                         }
                     }
                 };
                 file_lock.unlock()?;
                 lib_paths.push(file_path);
+                lib_cache_stats.insert(pkgpath, lib_cached);
             };
         }
         self.single_file_assembler
             .clean_lock_file(&self.entry_file)?;
-        Ok(lib_paths)
+        Ok((lib_paths, lib_cache_stats))
     }
 }
 
@@ -0,0 +1,303 @@
+//! Structural diff of the outputs of two KCL programs.
+//!
+//! The outputs are compared document by document, so the multi-document
+//! output e.g., the YAML stream separated by `---` is supported.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Result;
+use kclvm_parser::ParseSession;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{exec_program, ExecProgramArgs, MapErrorResult};
+
+/// A change of one output path between two program outputs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConfigChange {
+    /// The path only exists in the new output.
+    Added {
+        document: usize,
+        path: String,
+        value: Value,
+    },
+    /// The path only exists in the old output.
+    Removed {
+        document: usize,
+        path: String,
+        value: Value,
+    },
+    /// The path exists in both outputs with different values.
+    Changed {
+        document: usize,
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl ConfigChange {
+    /// Returns the index of the output document of the change.
+    pub fn document(&self) -> usize {
+        match self {
+            ConfigChange::Added { document, .. }
+            | ConfigChange::Removed { document, .. }
+            | ConfigChange::Changed { document, .. } => *document,
+        }
+    }
+
+    /// Returns the output path of the change e.g., `app.spec.replicas`, which
+    /// is empty when the whole document is changed.
+    pub fn path(&self) -> &str {
+        match self {
+            ConfigChange::Added { path, .. }
+            | ConfigChange::Removed { path, .. }
+            | ConfigChange::Changed { path, .. } => path,
+        }
+    }
+}
+
+/// The structural diff between the outputs of two programs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// The number of the output documents of the old and the new programs.
+    pub documents: (usize, usize),
+    /// The changes in the document and path order.
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+    /// Compute the diff between the old and the new output documents.
+    pub fn new(old: &[Value], new: &[Value]) -> Self {
+        let mut diff = ConfigDiff {
+            documents: (old.len(), new.len()),
+            changes: vec![],
+        };
+        for document in 0..old.len().max(new.len()) {
+            diff.diff_value(
+                document,
+                String::new(),
+                old.get(document),
+                new.get(document),
+            );
+        }
+        diff
+    }
+
+    /// Whether the two outputs are structurally equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn diff_value(
+        &mut self,
+        document: usize,
+        path: String,
+        old: Option<&Value>,
+        new: Option<&Value>,
+    ) {
+        match (old, new) {
+            (Some(Value::Object(old)), Some(Value::Object(new))) => {
+                for (key, value) in old {
+                    self.diff_value(document, join_key(&path, key), Some(value), new.get(key));
+                }
+                for (key, value) in new {
+                    if !old.contains_key(key) {
+                        self.diff_value(document, join_key(&path, key), None, Some(value));
+                    }
+                }
+            }
+            (Some(Value::Array(old)), Some(Value::Array(new))) => {
+                for i in 0..old.len().max(new.len()) {
+                    self.diff_value(document, format!("{path}[{i}]"), old.get(i), new.get(i));
+                }
+            }
+            (Some(old), Some(new)) => {
+                if old != new {
+                    self.changes.push(ConfigChange::Changed {
+                        document,
+                        path,
+                        old: old.clone(),
+                        new: new.clone(),
+                    });
+                }
+            }
+            (Some(value), None) => self.changes.push(ConfigChange::Removed {
+                document,
+                path,
+                value: value.clone(),
+            }),
+            (None, Some(value)) => self.changes.push(ConfigChange::Added {
+                document,
+                path,
+                value: value.clone(),
+            }),
+            (None, None) => {}
+        }
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    /// Render the diff in the human-readable format, one change per line e.g.,
+    ///
+    /// ```text
+    /// + app.labels.env: "prod"
+    /// - app.debug: true
+    /// ~ app.replicas: 1 -> 3
+    /// ```
+    ///
+    /// The paths are prefixed with the document index e.g., `[1] app.replicas`
+    /// when any of the outputs has multiple documents.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let multi_documents = self.documents.0 > 1 || self.documents.1 > 1;
+        for change in &self.changes {
+            let path = if change.path().is_empty() {
+                "<root>"
+            } else {
+                change.path()
+            };
+            let path = if multi_documents {
+                format!("[{}] {path}", change.document())
+            } else {
+                path.to_string()
+            };
+            match change {
+                ConfigChange::Added { value, .. } => writeln!(f, "+ {path}: {value}")?,
+                ConfigChange::Removed { value, .. } => writeln!(f, "- {path}: {value}")?,
+                ConfigChange::Changed { old, new, .. } => writeln!(f, "~ {path}: {old} -> {new}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Execute two programs e.g., the same program with different overrides and
+/// return the structural diff of their outputs.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{diff_programs, ExecProgramArgs};
+///
+/// let mut args_a = ExecProgramArgs::default();
+/// args_a.k_filename_list = vec!["main.k".to_string()];
+/// args_a.k_code_list = vec!["app = {replicas = 1, debug = True}".to_string()];
+/// let mut args_b = args_a.clone();
+/// args_b.k_code_list = vec!["app = {replicas = 3, debug = True}".to_string()];
+///
+/// let diff = diff_programs(&args_a, &args_b).unwrap();
+/// assert_eq!(diff.to_string(), "~ app.replicas: 1 -> 3\n");
+/// ```
+pub fn diff_programs(args_a: &ExecProgramArgs, args_b: &ExecProgramArgs) -> Result<ConfigDiff> {
+    let old = exec_documents(args_a)?;
+    let new = exec_documents(args_b)?;
+    Ok(ConfigDiff::new(&old, &new))
+}
+
+/// The impact of a batch of override specs on the program output.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OverrideImpact {
+    /// The override specs applied together e.g., `["app.replicas=3"]`.
+    pub specs: Vec<String>,
+    /// The output diff between the program without and with the specs.
+    pub diff: ConfigDiff,
+}
+
+impl OverrideImpact {
+    /// Whether the override specs change the program output.
+    #[inline]
+    pub fn is_changed(&self) -> bool {
+        !self.diff.is_empty()
+    }
+
+    /// Returns the changed output paths in the document and path order.
+    pub fn paths(&self) -> Vec<&str> {
+        self.diff.changes.iter().map(|c| c.path()).collect()
+    }
+}
+
+/// Compute which override batches would change the output of the program.
+///
+/// Each batch is a group of override specs applied together on top of
+/// `args`, including the overrides already in `args.overrides`, and is
+/// compared with the output of `args` itself. Use single-spec batches to
+/// inspect every override individually, or group the specs to reduce the
+/// cost when only the total impact of a group matters.
+///
+/// # Cost
+///
+/// The program is executed `batches.len() + 1` times: once for the baseline
+/// and once for each batch. Each execution loads, resolves and evaluates the
+/// whole program from scratch, so the batch count should be kept small for
+/// large programs.
+///
+/// The overrides are never written back to the source files, i.e.,
+/// `args.print_override_ast` and `args.debug` are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{override_impact, ExecProgramArgs};
+///
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["main.k".to_string()];
+/// args.k_code_list = vec!["app = {replicas = 1}".to_string()];
+///
+/// let impacts = override_impact(
+///     &args,
+///     &[vec!["app.replicas=3".to_string()], vec!["app.replicas=1".to_string()]],
+/// )
+/// .unwrap();
+/// assert_eq!(impacts[0].paths(), vec!["app.replicas"]);
+/// assert!(!impacts[1].is_changed());
+/// ```
+pub fn override_impact(
+    args: &ExecProgramArgs,
+    batches: &[Vec<String>],
+) -> Result<Vec<OverrideImpact>> {
+    let mut args = args.clone();
+    args.print_override_ast = false;
+    args.debug = 0;
+    let baseline = exec_documents(&args)?;
+    let mut impacts = Vec::with_capacity(batches.len());
+    for specs in batches {
+        let mut args = args.clone();
+        args.overrides.extend(specs.iter().cloned());
+        let output = exec_documents(&args)?;
+        impacts.push(OverrideImpact {
+            specs: specs.clone(),
+            diff: ConfigDiff::new(&baseline, &output),
+        });
+    }
+    Ok(impacts)
+}
+
+/// Execute the program and return its output documents.
+fn exec_documents(args: &ExecProgramArgs) -> Result<Vec<Value>> {
+    let result = exec_program(Arc::new(ParseSession::default()), args)?.map_err_to_result()?;
+    Ok(serde_json::Deserializer::from_str(&result.json_result)
+        .into_iter::<Value>()
+        .collect::<Result<Vec<Value>, _>>()?)
+}
+
+/// Join the config key to the path, the keys which are not identifiers are quoted
+/// e.g., `metadata.labels["app.kubernetes.io/name"]`.
+fn join_key(path: &str, key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if !is_identifier {
+        format!("{path}[{key:?}]")
+    } else if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
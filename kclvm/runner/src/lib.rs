@@ -1,33 +1,48 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     ffi::OsStr,
-    path::Path,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, bail, Result};
 use assembler::KclvmLibAssembler;
+pub use diff::{diff_programs, override_impact, ConfigChange, ConfigDiff, OverrideImpact};
+use indexmap::IndexMap;
 use kclvm_ast::{
-    ast::{Module, Program},
+    ast::{self, Module, Program},
     MAIN_PKG,
 };
 use kclvm_config::cache::KCL_CACHE_PATH_ENV_VAR;
-use kclvm_parser::{load_program, KCLModuleCache, ParseSessionRef};
-use kclvm_query::apply_overrides;
+use kclvm_error::{ErrorFormat, Level};
+use kclvm_parser::{load_program, KCLModuleCache, ParseSession, ParseSessionRef};
+use kclvm_query::{apply_overrides, r#override::parse_override_spec};
 use kclvm_sema::resolver::{
     resolve_program, resolve_program_with_opts, scope::ProgramScope, Options,
 };
 use kclvm_utils::fslock::open_lock_file;
+use kclvm_version::get_version_info;
 use linker::Command;
-pub use runner::{Artifact, ExecProgramArgs, ExecProgramResult, MapErrorResult};
-use runner::{FastRunner, RunnerOptions};
+use md5::{Digest, Md5};
+pub use provenance::{annotate_provenance, CONFIG_HASH_ANNOTATION, SOURCE_ANNOTATION};
+use runner::{get_pkg_strict_range_check, FastRunner, RunnerOptions};
+pub use runner::{
+    Artifact, ArtifactError, ExecProgramArgs, ExecProgramResult, MapErrorResult, PackageCacheStats,
+    PhaseTimings,
+};
 #[cfg(feature = "llvm")]
 use runner::{LibRunner, ProgramRunner};
+pub use split::{split_output, write_split_outputs, SPLIT_OUTPUT_EXTENSION};
 use tempfile::tempdir;
 
 pub mod assembler;
+pub mod diff;
 pub mod linker;
+pub mod provenance;
+pub mod repl;
 pub mod runner;
+pub mod split;
 
 #[cfg(test)]
 pub mod tests;
@@ -80,6 +95,7 @@ pub const KCL_FAST_EVAL_ENV_VAR: &str = "KCL_FAST_EVAL";
 /// let result = exec_program(sess, &args).unwrap();
 /// ```
 pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+    let start = Instant::now();
     // parse args from json string
     let opts = args.get_load_program_options();
     let kcl_paths_str = args
@@ -88,20 +104,421 @@ pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Exe
         .map(|s| s.as_str())
         .collect::<Vec<&str>>();
     let module_cache = KCLModuleCache::default();
-    let mut program = load_program(
+    let load_result = load_program(
         sess.clone(),
         kcl_paths_str.as_slice(),
         Some(opts),
         Some(module_cache),
-    )?
-    .program;
+    )?;
+    let ast_cache_stats = get_ast_cache_stats(&load_result.program, &load_result.cached_files);
+    execute_loaded_program(sess, load_result.program, args, ast_cache_stats, start)
+}
+
+/// Execute the KCL program with args like [exec_program], but load the program with
+/// a shared module cache, so repeated runs only re-parse the files cleared from the
+/// cache e.g., the files changed since the last run. The cached ASTs are not modified
+/// by the overrides or the execution.
+pub fn exec_program_with_module_cache(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    module_cache: KCLModuleCache,
+) -> Result<ExecProgramResult> {
+    let start = Instant::now();
+    let opts = args.get_load_program_options();
+    let kcl_paths_str = args
+        .k_filename_list
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>();
+    let load_result = load_program(
+        sess.clone(),
+        kcl_paths_str.as_slice(),
+        Some(opts),
+        Some(module_cache),
+    )?;
+    let ast_cache_stats = get_ast_cache_stats(&load_result.program, &load_result.cached_files);
+    let mut program = load_result.program;
+    // Detach the modules from the cache before applying the overrides.
+    for modules in [&mut program.modules, &mut program.modules_not_imported] {
+        for module in modules.values_mut() {
+            let detached = module
+                .read()
+                .map_err(|e| anyhow!("Failed to acquire module lock: {e}"))?
+                .clone();
+            *module = Arc::new(RwLock::new(detached));
+        }
+    }
+    execute_loaded_program(sess, program, args, ast_cache_stats, start)
+}
+
+/// Apply the overrides and the inputs to the loaded program, execute it and report
+/// the load time and the AST cache outcome since the `start` of the load.
+fn execute_loaded_program(
+    sess: ParseSessionRef,
+    mut program: Program,
+    args: &ExecProgramArgs,
+    ast_cache_stats: IndexMap<String, bool>,
+    start: Instant,
+) -> Result<ExecProgramResult> {
     apply_overrides(
         &mut program,
         &args.overrides,
         &[],
         args.print_override_ast || args.debug > 0,
     )?;
-    execute(sess, program, args)
+    apply_frozen_inputs(&mut program, &args.frozen_inputs)?;
+    apply_inputs(&mut program, &args.inputs)?;
+    let load_time = start.elapsed();
+    let mut result = execute(sess, program, args)?;
+    if args.profile_cache {
+        merge_ast_cache_stats(&mut result, ast_cache_stats);
+    }
+    if let Some(timings) = &mut result.timings {
+        timings.load = load_time;
+    }
+    Ok(result)
+}
+
+/// Get whether the ASTs of all the files of each package are reused from the module cache.
+fn get_ast_cache_stats(program: &Program, cached_files: &[PathBuf]) -> IndexMap<String, bool> {
+    let cached_files: HashSet<&Path> = cached_files.iter().map(|p| p.as_path()).collect();
+    let mut stats: IndexMap<String, bool> = program
+        .pkgs
+        .iter()
+        .map(|(pkgpath, filenames)| {
+            let cached = !filenames.is_empty()
+                && filenames
+                    .iter()
+                    .all(|filename| cached_files.contains(Path::new(filename)));
+            (pkgpath.clone(), cached)
+        })
+        .collect();
+    stats.sort_keys();
+    stats
+}
+
+/// Merge the AST cache outcome of each package into [ExecProgramResult::cache_stats].
+fn merge_ast_cache_stats(result: &mut ExecProgramResult, ast_cache_stats: IndexMap<String, bool>) {
+    for (pkgpath, cached) in ast_cache_stats {
+        result.cache_stats.entry(pkgpath).or_default().ast_cached = cached;
+    }
+    result.cache_stats.sort_keys();
+}
+
+/// Inject the frozen inputs into the main package as top-level variables, which can be
+/// read by the program but not redefined and are not emitted in the output. An error is
+/// returned when the program assigns to any of the frozen input names including in the
+/// top-level `if` bodies e.g., `cannot override the frozen input 'env'`.
+pub fn apply_frozen_inputs(
+    program: &mut Program,
+    frozen_inputs: &HashMap<String, serde_json::Value>,
+) -> Result<()> {
+    if frozen_inputs.is_empty() {
+        return Ok(());
+    }
+    for (name, stmt) in get_main_top_level_assigns(program)? {
+        if frozen_inputs.contains_key(&name) {
+            bail!(
+                "cannot override the frozen input '{}' at {}:{}:{}",
+                name,
+                stmt.0,
+                stmt.1,
+                stmt.2 + 1
+            );
+        }
+    }
+    inject_top_level_values(program, frozen_inputs.iter().collect(), "frozen input")
+}
+
+/// Inject the inputs into the main package as top-level variables whose types are inferred
+/// from the values, which the program can read like any other top-level variable but are
+/// not emitted in the output. The program's own top-level assignment to an input name
+/// including in the top-level `if` bodies takes precedence, so the input is only a default
+/// value of the variable, while the frozen inputs can not be redefined.
+pub fn apply_inputs(
+    program: &mut Program,
+    inputs: &HashMap<String, serde_json::Value>,
+) -> Result<()> {
+    if inputs.is_empty() {
+        return Ok(());
+    }
+    let assigned: HashSet<String> = get_main_top_level_assigns(program)?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    inject_top_level_values(
+        program,
+        inputs
+            .iter()
+            .filter(|(name, _)| !assigned.contains(*name))
+            .collect(),
+        "input",
+    )
+}
+
+/// Get the names of the inputs injected by [apply_inputs] i.e., the ones not assigned by
+/// the program itself, which are not emitted in the output.
+pub(crate) fn get_injected_input_names(
+    program: &Program,
+    inputs: &HashMap<String, serde_json::Value>,
+) -> Result<Vec<String>> {
+    if inputs.is_empty() {
+        return Ok(vec![]);
+    }
+    // The injected assignments are dummy nodes without the filename.
+    let assigned: HashSet<String> = get_main_top_level_assigns(program)?
+        .into_iter()
+        .filter(|(_, (filename, _, _))| !filename.is_empty())
+        .map(|(name, _)| name)
+        .collect();
+    Ok(inputs
+        .keys()
+        .filter(|name| !assigned.contains(*name))
+        .cloned()
+        .collect())
+}
+
+/// Get the names assigned by the top-level statements of the main package including the
+/// ones in the top-level `if` bodies, together with the `(filename, line, column)` of the
+/// statements.
+fn get_main_top_level_assigns(program: &Program) -> Result<Vec<(String, (String, u64, u64))>> {
+    let mut assigns = vec![];
+    let main_modules = program.pkgs.get(MAIN_PKG).cloned().unwrap_or_default();
+    for filename in &main_modules {
+        let module = program
+            .get_module(filename)?
+            .ok_or(anyhow!("module {filename:?} not found in program"))?;
+        collect_top_level_assigns(&module.body, &mut assigns);
+    }
+    Ok(assigns)
+}
+
+fn collect_top_level_assigns(
+    stmts: &[ast::NodeRef<ast::Stmt>],
+    assigns: &mut Vec<(String, (String, u64, u64))>,
+) {
+    for stmt in stmts {
+        let names = match &stmt.node {
+            ast::Stmt::Assign(assign_stmt) => assign_stmt
+                .targets
+                .iter()
+                .map(|target| target.node.get_name().to_string())
+                .collect(),
+            ast::Stmt::AugAssign(aug_assign_stmt) => {
+                vec![aug_assign_stmt.target.node.get_name().to_string()]
+            }
+            ast::Stmt::Unification(unification_stmt) => {
+                vec![unification_stmt.target.node.get_name()]
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_top_level_assigns(&if_stmt.body, assigns);
+                collect_top_level_assigns(&if_stmt.orelse, assigns);
+                vec![]
+            }
+            _ => vec![],
+        };
+        for name in names {
+            assigns.push((name, (stmt.filename.clone(), stmt.line, stmt.column)));
+        }
+    }
+}
+
+/// Insert the top-level assignments of the values after the import statements of the
+/// first main module in a stable order.
+fn inject_top_level_values(
+    program: &mut Program,
+    mut values: Vec<(&String, &serde_json::Value)>,
+    kind: &str,
+) -> Result<()> {
+    let filename = match program.pkgs.get(MAIN_PKG).and_then(|files| files.first()) {
+        Some(filename) => filename.clone(),
+        None => return Ok(()),
+    };
+    let mut module = program
+        .get_module_mut(&filename)?
+        .ok_or(anyhow!("module {filename:?} not found in program"))?;
+    let mut index = module
+        .body
+        .iter()
+        .position(|stmt| !matches!(stmt.node, ast::Stmt::Import(_)))
+        .unwrap_or(module.body.len());
+    values.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, value) in values {
+        let value = json_value_to_kcl_expr(value)
+            .ok_or(anyhow!("invalid value for the {kind} '{name}': {value}"))?;
+        let assign_stmt = ast::AssignStmt {
+            targets: vec![Box::new(ast::Node::dummy_node(ast::Target {
+                name: ast::Node::dummy_node(name.to_string()),
+                paths: vec![],
+                pkgpath: "".to_string(),
+            }))],
+            ty: None,
+            value,
+        };
+        module.body.insert(
+            index,
+            Box::new(ast::Node::dummy_node(ast::Stmt::Assign(assign_stmt))),
+        );
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Convert a JSON value to the equivalent KCL literal expression. The strings are built
+/// as string literal nodes instead of being parsed, so the `${...}` in the string values
+/// and the keys stays literal and is never evaluated as the string interpolation.
+fn json_value_to_kcl_expr(value: &serde_json::Value) -> Option<ast::NodeRef<ast::Expr>> {
+    let expr = match value {
+        serde_json::Value::Null => ast::Expr::NameConstantLit(ast::NameConstantLit {
+            value: ast::NameConstant::None,
+        }),
+        serde_json::Value::Bool(value) => ast::Expr::NameConstantLit(ast::NameConstantLit {
+            value: if *value {
+                ast::NameConstant::True
+            } else {
+                ast::NameConstant::False
+            },
+        }),
+        serde_json::Value::Number(number) => {
+            let value = match number.as_i64() {
+                Some(value) => ast::NumberLitValue::Int(value),
+                None => ast::NumberLitValue::Float(number.as_f64()?),
+            };
+            ast::Expr::NumberLit(ast::NumberLit {
+                binary_suffix: None,
+                value,
+                raw_value: None,
+            })
+        }
+        serde_json::Value::String(value) => {
+            ast::Expr::StringLit(ast::StringLit::from(value.to_string()))
+        }
+        serde_json::Value::Array(items) => ast::Expr::List(ast::ListExpr {
+            elts: items
+                .iter()
+                .map(json_value_to_kcl_expr)
+                .collect::<Option<Vec<_>>>()?,
+            ctx: ast::ExprContext::Load,
+        }),
+        serde_json::Value::Object(items) => ast::Expr::Config(ast::ConfigExpr {
+            items: items
+                .iter()
+                .map(|(k, v)| {
+                    Some(Box::new(ast::Node::dummy_node(ast::ConfigEntry {
+                        key: Some(Box::new(ast::Node::dummy_node(ast::Expr::StringLit(
+                            ast::StringLit::from(k.to_string()),
+                        )))),
+                        value: json_value_to_kcl_expr(v)?,
+                        operation: ast::ConfigEntryOperation::Union,
+                    })))
+                })
+                .collect::<Option<Vec<_>>>()?,
+        }),
+    };
+    Some(Box::new(ast::Node::dummy_node(expr)))
+}
+
+/// Compute a content-addressed cache key of the compilation described by `args`.
+///
+/// The key hashes the compiler version, the contents of all the files read to load
+/// the program (including `kcl.mod` and `kcl.mod.lock`, which pin the dependency
+/// versions) and the normalized arguments. Top-level arguments are ordered by name
+/// and override specs on the same field path are deduplicated with the last-wins
+/// semantics, so reordering them does not change the key unless it changes the result.
+/// The input maps are hashed in the key order and the paths under the work directory
+/// are hashed relative to it, so the key can be shared across the checkouts.
+pub fn compilation_cache_key(args: &ExecProgramArgs) -> Result<String> {
+    let opts = args.get_load_program_options();
+    let kcl_paths_str = args
+        .k_filename_list
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>();
+    let mut inputs_read = load_program(
+        Arc::new(ParseSession::default()),
+        kcl_paths_str.as_slice(),
+        Some(opts),
+        None,
+    )?
+    .inputs_read;
+    inputs_read.sort();
+    inputs_read.dedup();
+
+    let work_dir = match &args.work_dir {
+        Some(work_dir) => PathBuf::from(work_dir),
+        None => std::env::current_dir()?,
+    };
+    let work_dir = work_dir.canonicalize().unwrap_or(work_dir);
+    let relative_path = |path: &Path| -> String {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        path.strip_prefix(&work_dir)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let mut md5 = Md5::new();
+    md5.input(get_version_info().as_bytes());
+    for input in &inputs_read {
+        md5.input(relative_path(input).as_bytes());
+        md5.input(std::fs::read(input)?.as_slice());
+    }
+
+    let mut args = args.clone();
+    // Only the last value of the same argument takes effect.
+    let mut arguments: IndexMap<String, ast::Argument> = IndexMap::default();
+    for arg in args.args.drain(..) {
+        arguments.insert(arg.name.clone(), arg);
+    }
+    arguments.sort_keys();
+    args.args = arguments.into_values().collect();
+    // Only the last override spec of the same field path takes effect, except that the
+    // insert specs e.g., `a.b+=[1]` are cumulative and all of them after the last one
+    // take effect in order.
+    let mut overrides: IndexMap<String, Vec<String>> = IndexMap::default();
+    for spec in args.overrides.drain(..) {
+        let override_spec = parse_override_spec(&spec)?;
+        let mut specs = overrides
+            .shift_remove(&override_spec.field_path)
+            .unwrap_or_default();
+        if !matches!(override_spec.operation, ast::ConfigEntryOperation::Insert) {
+            specs.clear();
+        }
+        specs.push(spec);
+        overrides.insert(override_spec.field_path, specs);
+    }
+    args.overrides = overrides.into_values().flatten().collect();
+    args.external_pkgs
+        .sort_by(|a, b| a.pkg_name.cmp(&b.pkg_name));
+    args.k_filename_list = args
+        .k_filename_list
+        .iter()
+        .map(|filename| relative_path(&work_dir.join(filename)))
+        .collect();
+    args.work_dir = None;
+    // Sort the object keys, which makes the serialization of the `inputs` and
+    // `frozen_inputs` hash maps independent of their iteration order.
+    let args = sort_json_keys(serde_json::to_value(&args)?);
+    md5.input(serde_json::to_string(&args)?.as_bytes());
+
+    Ok(md5.result().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Recursively rebuild the JSON objects with the keys in the sorted order.
+fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_json_keys).collect())
+        }
+        serde_json::Value::Object(items) => {
+            let items: BTreeMap<String, serde_json::Value> = items
+                .into_iter()
+                .map(|(k, v)| (k, sort_json_keys(v)))
+                .collect();
+            serde_json::Value::Object(items.into_iter().collect())
+        }
+        value => value,
+    }
 }
 
 /// Execute the KCL artifact with args.
@@ -117,7 +534,24 @@ pub fn exec_artifact<P: AsRef<OsStr>>(
     {
         let _ = path;
         let _ = args;
-        Err(anyhow::anyhow!("error: llvm feature is not enabled. Note: Set KCL_FAST_EVAL=1 or rebuild the crate with the llvm feature."))
+        Err(ArtifactError::LlvmDisabled.into())
+    }
+}
+
+/// Execute the KCL artifact with args, or evaluate the program of the input files in
+/// `args` with the fast evaluator when the artifact can not be run because the crate
+/// is built without the `llvm` feature ([ArtifactError::LlvmDisabled]).
+pub fn run_artifact_or_eval<P: AsRef<OsStr>>(
+    path: P,
+    args: &ExecProgramArgs,
+) -> Result<ExecProgramResult> {
+    match exec_artifact(path, args) {
+        Err(err) if err.downcast_ref::<ArtifactError>() == Some(&ArtifactError::LlvmDisabled) => {
+            let mut args = args.clone();
+            args.fast_eval = true;
+            exec_program(Arc::new(ParseSession::default()), &args)
+        }
+        result => result,
     }
 }
 
@@ -176,26 +610,73 @@ pub fn execute(
     mut program: Program,
     args: &ExecProgramArgs,
 ) -> Result<ExecProgramResult> {
+    // The error format of the arguments applies to the compile diagnostics too.
+    if args.error_format == ErrorFormat::Short {
+        sess.1.write().error_format = ErrorFormat::Short;
+    }
+    if let Some(pkgpath) = &args.eval_pkg {
+        check_eval_pkg(&program, pkgpath)?;
+    }
     // If the user only wants to compile the kcl program, the following code will only resolve ast.
+    let start = Instant::now();
     if args.compile_only {
         let mut resolve_opts = Options::default();
         resolve_opts.merge_program = false;
         // Resolve ast
         let scope = resolve_program_with_opts(&mut program, resolve_opts, None);
         emit_compile_diag_to_string(sess, &scope, args.compile_only)?;
-        return Ok(ExecProgramResult::default());
+        return Ok(ExecProgramResult {
+            timings: args.profile.then(|| PhaseTimings {
+                resolve: start.elapsed(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
     }
     // Resolve ast
     let scope = resolve_program(&mut program);
+    let resolve_time = start.elapsed();
     // Emit parse and resolve errors if exists.
     emit_compile_diag_to_string(sess, &scope, false)?;
-    Ok(
+    let start = Instant::now();
+    let (mut result, codegen_time) =
         // Use the fast evaluator to run the kcl program.
-        if args.fast_eval || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok() {
-            FastRunner::new(Some(RunnerOptions {
-                plugin_agent_ptr: args.plugin_agent,
+        // Collecting all the check failures, the evaluation limits, the attribute
+        // coverage, the assertions, the sandbox mode, reading the files, the output
+        // transforms, the partial application of lambdas, selecting the output
+        // variables, hiding the inputs from the output, the source map, the provenance
+        // annotations, splitting the output, evaluating the other packages, the
+        // per-package strict range check and the strict number output are only supported
+        // by the evaluator.
+        if args.fast_eval
+            || args.emit_source_map
+            || args.annotate_provenance
+            || args.split_output_by.is_some()
+            || args.eval_pkg.is_some()
+            || args.strict_number
+            || !get_pkg_strict_range_check(&program, args.strict_range_check).is_empty()
+            || args.collect_all_check_failures
+            || args.record_attr_coverage
+            || args.record_assertions
+            || args.max_depth.is_some()
+            || args.max_output_bytes.is_some()
+            || args.max_collection_len.is_some()
+            || args.max_string_len.is_some()
+            || args.sandbox
+            || args.allow_file_read
+            || !scope.partial_calls.is_empty()
+            || !args.output_transforms.is_empty()
+            || !args.include_vars.is_empty()
+            || !args.frozen_inputs.is_empty()
+            || !args.inputs.is_empty()
+            || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok()
+        {
+            let result = FastRunner::new(Some(RunnerOptions {
+                plugin_agent_ptr: args.get_plugin_agent(),
+                partial_calls: scope.partial_calls.iter().cloned().collect(),
             }))
-            .run(&program, args)?
+            .run(&program, args)?;
+            (result, None)
         } else {
             // Compile the kcl program to native lib and run it.
             #[cfg(feature = "llvm")]
@@ -209,40 +690,117 @@ pub fn execute(
                 let temp_entry_file = temp_file(temp_dir_path)?;
 
                 // Generate libs
-                let lib_paths = assembler::KclvmAssembler::new(
+                let (lib_paths, lib_cache_stats) = assembler::KclvmAssembler::new(
                     program,
                     scope,
                     temp_entry_file.clone(),
                     KclvmLibAssembler::LLVM,
                     args.get_package_maps_from_external_pkg(),
                 )
-                .gen_libs(args)?;
+                .gen_libs_with_cache_stats(args)?;
 
                 // Link libs into one library
                 let lib_suffix = Command::get_lib_suffix();
                 let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
                 let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)?;
+                let codegen_time = start.elapsed();
 
                 // Run the library
                 let runner = LibRunner::new(Some(RunnerOptions {
-                    plugin_agent_ptr: args.plugin_agent,
+                    plugin_agent_ptr: args.get_plugin_agent(),
+                    ..Default::default()
                 }));
-                let result = runner.run(&lib_path, args)?;
+                let mut result = runner.run(&lib_path, args)?;
+                if args.profile_cache {
+                    for (pkgpath, cached) in lib_cache_stats {
+                        result.cache_stats.entry(pkgpath).or_default().lib_cached = Some(cached);
+                    }
+                }
 
                 remove_file(&lib_path)?;
                 clean_tmp_files(&temp_entry_file, &lib_suffix)?;
-                result
+                (result, Some(codegen_time))
             }
             // If we don't enable llvm feature, the default running path is through the evaluator.
             #[cfg(not(feature = "llvm"))]
             {
-                FastRunner::new(Some(RunnerOptions {
-                    plugin_agent_ptr: args.plugin_agent,
+                let result = FastRunner::new(Some(RunnerOptions {
+                    plugin_agent_ptr: args.get_plugin_agent(),
+                    ..Default::default()
                 }))
-                .run(&program, args)?
+                .run(&program, args)?;
+                (result, None)
             }
-        },
-    )
+        };
+    if args.profile_memory {
+        result.process_peak_memory_bytes = process_peak_memory_bytes();
+    }
+    if args.profile {
+        result.timings = Some(PhaseTimings {
+            load: Duration::ZERO,
+            resolve: resolve_time,
+            codegen: codegen_time,
+            eval: start
+                .elapsed()
+                .saturating_sub(codegen_time.unwrap_or_default()),
+        });
+    }
+    Ok(result)
+}
+
+/// Check the package `pkgpath` to evaluate and output instead of the main package is in the
+/// program. The package is evaluated under its own package path by the evaluator, thus the
+/// full names of its schemas are unchanged. An error is returned when the package is not
+/// found in the program.
+fn check_eval_pkg(program: &Program, pkgpath: &str) -> Result<()> {
+    if pkgpath == MAIN_PKG {
+        return Ok(());
+    }
+    match program.pkgs.get(pkgpath) {
+        Some(_) => Ok(()),
+        None => {
+            let mut pkgpaths: Vec<&String> = program
+                .pkgs
+                .keys()
+                .filter(|pkgpath| *pkgpath != MAIN_PKG)
+                .collect();
+            pkgpaths.sort();
+            bail!(
+                "the package '{}' to evaluate is not found in the program, the packages are: [{}]",
+                pkgpath,
+                pkgpaths
+                    .iter()
+                    .map(|pkgpath| format!("'{}'", pkgpath))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+/// Get the peak resident memory in bytes of the current process lifetime with `getrusage`,
+/// which costs one system call. Note the peak covers the whole process including the
+/// parsing, resolving and the earlier executions, so it is an upper bound of the usage
+/// of this execution instead of a delta.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn process_peak_memory_bytes() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let max_rss = unsafe { usage.assume_init() }.ru_maxrss as u64;
+    // The `ru_maxrss` is in kilobytes on Linux and in bytes on macOS.
+    if cfg!(target_os = "linux") {
+        Some(max_rss * 1024)
+    } else {
+        Some(max_rss)
+    }
+}
+
+/// The peak resident memory is not supported on the other platforms.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_peak_memory_bytes() -> Option<u64> {
+    None
 }
 
 /// `execute_module` can directly execute the ast `Module`.
@@ -398,6 +956,9 @@ fn emit_compile_diag_to_string(
     scope: &ProgramScope,
     include_warnings: bool,
 ) -> Result<()> {
+    if sess.1.read().error_format == ErrorFormat::Short {
+        return emit_compile_diag_to_short_string(sess, scope, include_warnings);
+    }
     let mut res_str = sess.1.write().emit_to_string()?;
     let sema_err = scope.emit_diagnostics_to_string(sess.0.clone(), include_warnings);
     if let Err(err) = &sema_err {
@@ -413,3 +974,25 @@ fn emit_compile_diag_to_string(
         .then(|| Ok(()))
         .unwrap_or_else(|| bail!(res_str))
 }
+
+/// Emit the parsing and resolving diagnostics in the [ErrorFormat::Short] format of the
+/// session handler, one line per diagnostic.
+fn emit_compile_diag_to_short_string(
+    sess: ParseSessionRef,
+    scope: &ProgramScope,
+    include_warnings: bool,
+) -> Result<()> {
+    let mut handler = sess.1.read().clone();
+    let has_parse_diags = !handler.diagnostics.is_empty();
+    for diag in &scope.handler.diagnostics {
+        if matches!(diag.level, Level::Error | Level::Suggestions)
+            || (include_warnings && matches!(diag.level, Level::Warning))
+        {
+            handler.add_diagnostic(diag.clone());
+        }
+    }
+    if has_parse_diags || handler.has_errors() {
+        bail!(handler.emit_to_string()?)
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! Annotate the Kubernetes objects in the output with their provenance.
+//!
+//! Each top-level output document which looks like a Kubernetes object, i.e., has the
+//! `apiVersion`, `kind` and `metadata` fields, is annotated with the hash of its config
+//! and the KCL source files, which makes the applied resources traceable to the KCL
+//! source in the GitOps tooling. The other documents pass through unchanged.
+
+use anyhow::{bail, Result};
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::ast;
+use kclvm_runtime::val_plan::PlanOptions;
+use kclvm_runtime::{Context, JsonEncodeOptions, ValueRef, YamlEncodeOptions, JSON_STREAM_SEP};
+use md5::{Digest, Md5};
+use serde_json::Value;
+
+use crate::ExecProgramResult;
+
+/// The annotation of the MD5 hash of the document config before being annotated.
+pub const CONFIG_HASH_ANNOTATION: &str = "kcl-lang.io/config-hash";
+/// The annotation of the comma-separated KCL source files of the document.
+pub const SOURCE_ANNOTATION: &str = "kcl-lang.io/source";
+
+/// Annotate the Kubernetes objects in the JSON and YAML output of the result with
+/// [CONFIG_HASH_ANNOTATION] and [SOURCE_ANNOTATION] in `metadata.annotations`. The
+/// output is split and re-encoded with the plan options it was planned with, e.g., the
+/// YAML separator set by `manifests.yaml_stream`, and `document_sources` returns the
+/// source files of each document. An error is returned when the existing
+/// `metadata.annotations` of an object is not a dict.
+pub fn annotate_provenance(
+    result: &mut ExecProgramResult,
+    plan_opts: &PlanOptions,
+    document_sources: impl Fn(&Value) -> Vec<String>,
+) -> Result<()> {
+    let json_documents: Vec<&str> = if result.json_result.is_empty() {
+        vec![]
+    } else {
+        result.json_result.split(JSON_STREAM_SEP).collect()
+    };
+    // The YAML documents are separated by the separator lines at the column 0, which
+    // can not occur in the indented block scalars.
+    let yaml_sep = format!("\n{}\n", plan_opts.sep.as_deref().unwrap_or("---"));
+    let yaml_documents: Vec<&str> = if result.yaml_result.is_empty() {
+        vec![]
+    } else {
+        result.yaml_result.split(&yaml_sep).collect()
+    };
+    let with_yaml = !yaml_documents.is_empty();
+    if with_yaml && json_documents.len() != yaml_documents.len() {
+        bail!(
+            "the output has {} JSON documents but {} YAML documents",
+            json_documents.len(),
+            yaml_documents.len()
+        );
+    }
+    let json_opts = JsonEncodeOptions {
+        sort_keys: plan_opts.sort_keys,
+        strict_number: plan_opts.strict_number,
+        ..Default::default()
+    };
+    let yaml_opts = YamlEncodeOptions {
+        sort_keys: plan_opts.sort_keys,
+        strict_number: plan_opts.strict_number,
+        ..Default::default()
+    };
+    let mut ctx = Context::new();
+    let mut json_results = vec![];
+    let mut yaml_results = vec![];
+    for (index, json) in json_documents.iter().enumerate() {
+        let document: Value = serde_json::from_str(json)?;
+        if !is_k8s_object(&document) {
+            json_results.push(json.to_string());
+            if with_yaml {
+                yaml_results.push(yaml_documents[index].to_string());
+            }
+            continue;
+        }
+        let value = ValueRef::from_json(&mut ctx, json)?;
+        let mut metadata = value.dict_get_value("metadata").unwrap();
+        let mut annotations = match metadata.dict_get_value("annotations") {
+            Some(annotations) if annotations.is_config() => annotations,
+            Some(annotations) if !annotations.is_none_or_undefined() => bail!(
+                "the field 'metadata.annotations' of the output document {} must be a dict, got {}",
+                index,
+                annotations.type_str()
+            ),
+            _ => {
+                let annotations = ValueRef::dict(None);
+                metadata.dict_update_key_value("annotations", annotations.clone());
+                annotations
+            }
+        };
+        let hash: String = Md5::digest(json.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let source = document_sources(&document).join(",");
+        annotations.dict_update_key_value(CONFIG_HASH_ANNOTATION, ValueRef::str(&hash));
+        annotations.dict_update_key_value(SOURCE_ANNOTATION, ValueRef::str(&source));
+        json_results.push(value.to_json_string_with_options(&json_opts));
+        if with_yaml {
+            yaml_results.push(
+                value
+                    .to_yaml_string_with_options(&yaml_opts)
+                    .trim_end_matches('\n')
+                    .to_string(),
+            );
+        }
+    }
+    result.json_result = json_results.join(JSON_STREAM_SEP);
+    result.yaml_result = yaml_results.join(yaml_sep.as_str());
+    Ok(())
+}
+
+/// Get the source files of the output document from the source map, which are the
+/// files of the assignments to the top-level variables in the document. A variable is
+/// in the document when the document is the top-level output containing it, or is the
+/// output value of it in `variables` e.g., an item of `manifests.yaml_stream`. The
+/// `main_files` are returned when no assignment is found e.g., for the literal values.
+pub(crate) fn document_sources(
+    document: &Value,
+    source_map: &IndexMap<String, ast::PosTuple>,
+    variables: &IndexMap<String, Value>,
+    main_files: &[String],
+) -> Vec<String> {
+    let mut sources = IndexSet::new();
+    for (path, (filename, ..)) in source_map {
+        let name = path.split('.').next().unwrap_or_default();
+        if document.get(name).is_some() || variables.get(name) == Some(document) {
+            sources.insert(filename.clone());
+        }
+    }
+    if sources.is_empty() {
+        main_files.to_vec()
+    } else {
+        sources.into_iter().collect()
+    }
+}
+
+/// Whether the JSON value looks like a Kubernetes object with the `apiVersion`,
+/// `kind` and `metadata` fields.
+fn is_k8s_object(value: &Value) -> bool {
+    match value.as_object() {
+        Some(object) => {
+            object.contains_key("apiVersion")
+                && object.contains_key("kind")
+                && object.get("metadata").map_or(false, Value::is_object)
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,219 @@
+//! An incremental evaluation session for the REPL.
+//!
+//! The session keeps the top-level statements submitted one input at a time, and each
+//! input is type checked and evaluated together with the accumulated statements, so the
+//! input can refer to the bindings defined by the previous inputs. The statements are only
+//! accepted when the input is evaluated successfully, and the accumulated statements are
+//! left unchanged otherwise.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::ast;
+use kclvm_parser::{parse_file_force_errors, ParseSession};
+
+use crate::{exec_program, ExecProgramArgs};
+
+/// The virtual file of the accumulated statements and the input in the main package.
+const REPL_FILENAME: &str = "repl.k";
+/// The variable the expression input is assigned to for reading its value.
+const REPL_VALUE_NAME: &str = "__kcl_repl_value__";
+
+/// An accepted top-level statement.
+#[derive(Debug, Clone)]
+struct ReplStmt {
+    /// The names bound by the statement e.g., the variable, schema and import names, including
+    /// the names assigned in the if blocks and updated by the augmented assignments, thus the
+    /// statement is removed when any of the names is redefined.
+    names: Vec<String>,
+    /// The source code of the statement.
+    code: String,
+}
+
+/// The output of an input evaluated in the [ReplSession].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplOutput {
+    /// The YAML value of the input when the input is a single expression e.g., `a + 1`,
+    /// and `None` when the input consists of statements e.g., `a = 1`.
+    pub value: Option<String>,
+    /// The messages printed during the evaluation e.g., with `print()`.
+    pub log_message: String,
+}
+
+/// A REPL session which evaluates the inputs one at a time against the top-level bindings
+/// accumulated by the previous inputs.
+///
+/// A binding is redefined by the input defining the same name, which replaces the statement
+/// of the previous definition, and the statements depending on it are evaluated with the new
+/// definition. The input is rejected with the diagnostics when it, or any accumulated statement
+/// affected by its redefinitions, fails to type check or evaluate.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::repl::ReplSession;
+///
+/// let mut session = ReplSession::default();
+/// session.eval("a = 1").unwrap();
+/// session.eval("b = a + 1").unwrap();
+/// assert_eq!(session.eval("b").unwrap().value, Some("2".to_string()));
+/// // Redefine `a`, and `b` is evaluated with the new definition.
+/// session.eval("a = 2").unwrap();
+/// assert_eq!(session.eval("b").unwrap().value, Some("3".to_string()));
+/// // The input failing to type check is rejected.
+/// assert!(session.eval("c: str = a").is_err());
+/// assert!(session.eval("c").is_err());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ReplSession {
+    /// The arguments e.g., the work directory and the external packages to evaluate the inputs.
+    args: ExecProgramArgs,
+    /// The accepted top-level statements in the input order.
+    stmts: Vec<ReplStmt>,
+}
+
+impl ReplSession {
+    /// Create a session evaluating the inputs with the arguments e.g., the work directory and
+    /// the external packages. The file names, code and path selectors of the arguments are
+    /// ignored.
+    pub fn new(args: ExecProgramArgs) -> Self {
+        Self {
+            args,
+            stmts: vec![],
+        }
+    }
+
+    /// Evaluate the input against the accumulated bindings. When the input is a single
+    /// expression, its value is returned and no binding is accepted. Otherwise, the statements
+    /// of the input are accepted as the bindings for the following inputs except the expression
+    /// statements, which are evaluated only once.
+    pub fn eval(&mut self, input: &str) -> Result<ReplOutput> {
+        let module = parse_file_force_errors(REPL_FILENAME, Some(input.to_string()))?;
+        let lines: Vec<&str> = input.lines().collect();
+        let is_expr = match module.body.as_slice() {
+            [stmt] => {
+                matches!(&stmt.node, ast::Stmt::Expr(expr_stmt) if expr_stmt.exprs.len() == 1)
+            }
+            _ => false,
+        };
+        // The end line of the block statements e.g., the schema statement may be after the
+        // trailing empty line.
+        let get_code = |stmt: &ast::NodeRef<ast::Stmt>| -> String {
+            let end_line = (stmt.end_line as usize).min(lines.len());
+            lines[stmt.line as usize - 1..end_line]
+                .join("\n")
+                .trim_end()
+                .to_string()
+        };
+        let mut new_stmts: Vec<ReplStmt> = vec![];
+        let mut new_names: HashSet<String> = HashSet::new();
+        let mut exprs = vec![];
+        for stmt in &module.body {
+            if let ast::Stmt::Expr(_) = &stmt.node {
+                exprs.push(get_code(stmt));
+            } else {
+                let mut defined = vec![];
+                let mut updated = vec![];
+                collect_bound_names(stmt, &mut defined, &mut updated);
+                new_names.extend(defined.iter().cloned());
+                new_stmts.push(ReplStmt {
+                    names: defined.into_iter().chain(updated).collect(),
+                    code: get_code(stmt),
+                });
+            }
+        }
+        // The statements binding the redefined names are replaced by the new ones, and the
+        // augmented assignments e.g., `a += 1` update the previous definitions instead.
+        let mut stmts: Vec<ReplStmt> = self
+            .stmts
+            .iter()
+            .filter(|stmt| !stmt.names.iter().any(|name| new_names.contains(name)))
+            .cloned()
+            .collect();
+        stmts.extend(new_stmts);
+
+        let mut code: Vec<String> = stmts.iter().map(|stmt| stmt.code.clone()).collect();
+        if is_expr {
+            code.push(format!("{} = {}", REPL_VALUE_NAME, exprs[0]));
+        } else {
+            code.extend(exprs);
+        }
+        let mut args = self.args.clone();
+        args.k_filename_list = vec![REPL_FILENAME.to_string()];
+        args.k_code_list = vec![code.join("\n")];
+        args.fast_eval = true;
+        if is_expr {
+            args.path_selector = vec![REPL_VALUE_NAME.to_string()];
+            args.show_hidden = true;
+        } else {
+            args.path_selector = vec![];
+        }
+        let result = exec_program(Arc::new(ParseSession::default()), &args)?;
+        if !result.err_message.is_empty() {
+            return Err(anyhow!(result.err_message));
+        }
+        self.stmts = stmts;
+        Ok(ReplOutput {
+            value: is_expr.then(|| result.yaml_result.trim_end_matches('\n').to_string()),
+            log_message: result.log_message,
+        })
+    }
+
+    /// Get the source code of the accumulated top-level statements.
+    pub fn source(&self) -> String {
+        self.stmts
+            .iter()
+            .map(|stmt| stmt.code.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    /// Clear the accumulated top-level statements.
+    pub fn reset(&mut self) {
+        self.stmts.clear();
+    }
+}
+
+/// Collect the top-level names defined by the statement into `defined` e.g., the variable,
+/// schema and import names, and the names updated by the augmented assignments into `updated`.
+/// The names assigned in the if blocks are top-level names as well.
+fn collect_bound_names(
+    stmt: &ast::NodeRef<ast::Stmt>,
+    defined: &mut Vec<String>,
+    updated: &mut Vec<String>,
+) {
+    match &stmt.node {
+        ast::Stmt::Assign(assign_stmt) => defined.extend(
+            assign_stmt
+                .targets
+                .iter()
+                .filter(|target| target.node.paths.is_empty())
+                .map(|target| target.node.get_name().to_string()),
+        ),
+        ast::Stmt::AugAssign(aug_assign_stmt) => {
+            updated.push(aug_assign_stmt.target.node.get_name().to_string())
+        }
+        ast::Stmt::Unification(unification_stmt) => {
+            defined.push(unification_stmt.target.node.get_name())
+        }
+        ast::Stmt::TypeAlias(type_alias_stmt) => {
+            defined.push(type_alias_stmt.type_name.node.get_name())
+        }
+        ast::Stmt::Schema(schema_stmt) => defined.push(schema_stmt.name.node.clone()),
+        ast::Stmt::Rule(rule_stmt) => defined.push(rule_stmt.name.node.clone()),
+        ast::Stmt::Import(import_stmt) if import_stmt.is_selective() => defined.extend(
+            import_stmt
+                .selected_names
+                .iter()
+                .map(|name| name.node.clone()),
+        ),
+        ast::Stmt::Import(import_stmt) => defined.push(import_stmt.name.clone()),
+        ast::Stmt::If(if_stmt) => {
+            for stmt in if_stmt.body.iter().chain(&if_stmt.orelse) {
+                collect_bound_names(stmt, defined, updated);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,25 +1,34 @@
 use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
 use kclvm_evaluator::Evaluator;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 use std::{cell::RefCell, rc::Rc};
 
-use kclvm_ast::ast;
+use kclvm_ast::ast::{self, AstIndex};
 use kclvm_config::{
-    modfile::get_vendor_home,
-    settings::{SettingsFile, SettingsPathBuf},
+    modfile::{get_pkg_root, get_vendor_home, load_mod_file},
+    settings::{build_settings_pathbuf, SettingsFile, SettingsPathBuf},
 };
-use kclvm_error::{Diagnostic, Handler};
+use kclvm_error::{Diagnostic, ErrorFormat, Handler};
 #[cfg(not(target_arch = "wasm32"))]
 use kclvm_runtime::kclvm_plugin_init;
+use kclvm_runtime::val_plan::PlanOptions;
 #[cfg(feature = "llvm")]
 use kclvm_runtime::FFIRunOptions;
-use kclvm_runtime::{Context, PanicInfo, RuntimePanicRecord};
+use kclvm_runtime::{
+    AssertionRecord, Context, OutputTransform, PanicInfo, RuntimePanicRecord, JSON_STREAM_SEP,
+};
 #[cfg(target_arch = "wasm32")]
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::os::raw::c_char;
 
+use crate::get_injected_input_names;
+use crate::provenance::{annotate_provenance, document_sources};
+use crate::split::split_output;
+
 const RESULT_SIZE: usize = 2048 * 2048;
 const KCL_DEBUG_ERROR_ENV_VAR: &str = "KCL_DEBUG_ERROR";
 
@@ -46,6 +55,8 @@ pub struct ExecProgramArgs {
     pub overrides: Vec<String>,
     /// -S path_selector
     pub path_selector: Vec<String>,
+    /// Only emit the named top-level variables, which is applied before the path selector.
+    pub include_vars: Vec<String>,
     pub disable_yaml_result: bool,
     /// Whether to apply overrides on the source code.
     pub print_override_ast: bool,
@@ -63,11 +74,94 @@ pub struct ExecProgramArgs {
     pub show_hidden: bool,
     /// Whether including schema type in JSON/YAML result
     pub include_schema_type_path: bool,
+    /// Whether to emit the integral unit values e.g., `1Ki` as integers in JSON/YAML result.
+    pub strict_number: bool,
     /// Whether to compile only.
     pub compile_only: bool,
+    /// Whether to emit the source map linking output paths back to the KCL source.
+    pub emit_source_map: bool,
+    /// Whether to continue past failed schema `check:` conditions and report
+    /// all of the failures together instead of stopping at the first one.
+    pub collect_all_check_failures: bool,
+    /// The maximum depth of the function, schema and rule calls, which is unlimited by default.
+    pub max_depth: Option<usize>,
+    /// The maximum bytes of the JSON or YAML output, which is unlimited by default.
+    pub max_output_bytes: Option<usize>,
+    /// The maximum length of the list and dict values, which is unlimited by default.
+    pub max_collection_len: Option<usize>,
+    /// The maximum length of the string values, which is unlimited by default.
+    pub max_string_len: Option<usize>,
+    /// The top-level values which can be read by the program but not redefined, and
+    /// are not emitted in the output. The execution fails when the program tries to
+    /// override any of them.
+    pub frozen_inputs: HashMap<String, serde_json::Value>,
+    /// The top-level values e.g., the data of a values file, which are bound to the names
+    /// with the types inferred from the values and are not emitted in the output. The
+    /// program's own top-level assignment of the same name takes precedence over the input.
+    pub inputs: HashMap<String, serde_json::Value>,
+    /// The field path e.g., `metadata.name` of each top-level output document, by whose
+    /// value the output is split into one file per document in
+    /// [ExecProgramResult::split_outputs] instead of one combined stream.
+    pub split_output_by: Option<String>,
+    /// Whether to annotate each output document which looks like a Kubernetes object,
+    /// i.e., has the `apiVersion`, `kind` and `metadata` fields, with the hash of its
+    /// config and the KCL source files in `metadata.annotations`.
+    pub annotate_provenance: bool,
+    /// The package path e.g., `pkg.a` of the package in the program to evaluate and output
+    /// instead of the main package. The packages it imports are still resolved and evaluated.
+    pub eval_pkg: Option<String>,
+    /// Whether to allow the program to read the files under the workspace e.g.,
+    /// `file.read("data.txt")` and `file.read_json("data.json")`.
+    pub allow_file_read: bool,
+    /// Whether to evaluate the program without any side effects, e.g., for the untrusted
+    /// programs. It overrides the individual options such as `allow_file_read` to off and
+    /// the following operations result in an error instead:
+    ///
+    /// - importing and calling the plugins e.g., `kcl_plugin.hello`;
+    /// - the file system and environment access of the `file` module, i.e., `read`, `glob`,
+    ///   `exists`, `abs`, `mkdir`, `delete`, `cp`, `mv`, `size`, `write`, `append`,
+    ///   `read_env`, `read_json` and `read_yaml`;
+    /// - writing the files with `json.dump_to_file`, `yaml.dump_to_file` and
+    ///   `yaml.dump_all_to_file`;
+    /// - hashing the files with `crypto.filesha256`, `crypto.filesha512` and
+    ///   `crypto.fileblake3`;
+    /// - the nondeterministic values of `datetime.today`, `datetime.now`,
+    ///   `datetime.ticks`, `datetime.date` and `crypto.uuid`;
+    /// - the host paths of `file.modpath`, `file.workdir` and `file.current`.
+    pub sandbox: bool,
+    /// Whether to record the schema attributes assigned by the schema configs
+    /// in [ExecProgramResult::attr_coverage].
+    pub record_attr_coverage: bool,
+    /// Whether to record every evaluated assertion in [ExecProgramResult::assertions] and
+    /// report all of the failed assertions together instead of stopping at the first one.
+    pub record_assertions: bool,
+    /// Whether to report the peak resident memory of the process in
+    /// [ExecProgramResult::process_peak_memory_bytes].
+    pub profile_memory: bool,
+    /// Whether to report whether each package is reused from the module cache and
+    /// the build cache in [ExecProgramResult::cache_stats].
+    pub profile_cache: bool,
+    /// Whether to report the wall-clock time of each execution phase in
+    /// [ExecProgramResult::timings].
+    pub profile: bool,
+    /// Whether to use the forward slashes as the path separators in the module filenames,
+    /// the compile and runtime errors and the source map on Windows, which makes the
+    /// outputs reproducible across the platforms.
+    pub forward_slash_paths: bool,
+    /// Whether to compile all the KCL files except the tests at the top level of an input
+    /// directory together as the main package without looking up the `kcl.mod`.
+    pub treat_dir_as_package: bool,
+    /// The format of the compile and runtime errors, e.g., [ErrorFormat::Short] renders
+    /// each error on one line.
+    #[serde(skip)]
+    pub error_format: ErrorFormat,
     /// plugin_agent is the address of plugin.
     #[serde(skip)]
     pub plugin_agent: u64,
+    /// Output transforms applied to the output value in the registration order
+    /// after the evaluation and before the serialization.
+    #[serde(skip)]
+    pub output_transforms: Vec<OutputTransform>,
     /// fast_eval denotes directly executing at the AST level to obtain
     /// the result without any form of compilation.
     #[serde(skip)]
@@ -75,6 +169,33 @@ pub struct ExecProgramArgs {
 }
 
 impl ExecProgramArgs {
+    /// Register a transform of the output value, which is applied after the evaluation
+    /// and before the serialization. Multiple transforms compose in the registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_runner::{exec_program, ExecProgramArgs};
+    /// use kclvm_runtime::ValueRef;
+    /// use kclvm_parser::ParseSession;
+    /// use std::sync::Arc;
+    ///
+    /// fn add_env(mut value: ValueRef) -> ValueRef {
+    ///     value.dict_update_key_value("env", ValueRef::str("prod"));
+    ///     value
+    /// }
+    ///
+    /// let mut args = ExecProgramArgs::default();
+    /// args.k_filename_list = vec!["main.k".to_string()];
+    /// args.k_code_list = vec!["name = \"app\"".to_string()];
+    /// args.register_output_transform(add_env);
+    /// let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    /// assert_eq!(result.yaml_result, "name: app\nenv: prod");
+    /// ```
+    pub fn register_output_transform(&mut self, transform: OutputTransform) {
+        self.output_transforms.push(transform);
+    }
+
     /// [`get_package_maps_from_external_pkg`] gets the package name to package path mapping.
     pub fn get_package_maps_from_external_pkg(&self) -> HashMap<String, String> {
         let mut package_maps = HashMap::new();
@@ -103,6 +224,57 @@ pub struct ExecProgramResult {
     pub yaml_result: String,
     pub log_message: String,
     pub err_message: String,
+    /// Output path e.g., `deployment.spec.replicas` to the source range
+    /// `(filename, line, column, end_line, end_column)` of the assignment
+    /// that set it, only set when `emit_source_map` is enabled.
+    pub source_map: IndexMap<String, ast::PosTuple>,
+    /// The peak resident memory in bytes of the whole process lifetime at the end of the
+    /// execution, only set when `profile_memory` is enabled on the supported platforms
+    /// (Linux and macOS). It is not the memory used by this execution alone: it includes
+    /// the parsing and resolving, and the earlier executions in the same process e.g.,
+    /// a language server or an API server, so it never decreases between executions.
+    pub process_peak_memory_bytes: Option<u64>,
+    /// The assignment count of each schema attribute, the first key is the schema
+    /// runtime type e.g., `pkg.Person`, only set when `record_attr_coverage` is enabled.
+    pub attr_coverage: IndexMap<String, IndexMap<String, usize>>,
+    /// The source range and the outcome of every evaluated assertion in the evaluation
+    /// order, only set when `record_assertions` is enabled.
+    pub assertions: Vec<AssertionRecord>,
+    /// The cache outcome of each package keyed by the package path, only set
+    /// when `profile_cache` is enabled.
+    pub cache_stats: IndexMap<String, PackageCacheStats>,
+    /// The wall-clock time of each execution phase, only set when `profile` is enabled.
+    pub timings: Option<PhaseTimings>,
+    /// The YAML of each top-level output document keyed by the file name e.g.,
+    /// `app.yaml`, only set when `split_output_by` is set.
+    pub split_outputs: IndexMap<String, String>,
+}
+
+/// The wall-clock time spent in each phase of one run, which helps to find out whether
+/// a program is e.g., resolve-heavy or evaluation-heavy.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct PhaseTimings {
+    /// Loading and parsing the program including applying the overrides, which is zero
+    /// when the program is parsed by the caller e.g., [crate::execute].
+    pub load: Duration,
+    /// Resolving the program.
+    pub resolve: Duration,
+    /// Compiling and linking the native lib, which is `None` when the program is run
+    /// by the evaluator.
+    pub codegen: Option<Duration>,
+    /// Evaluating the program and serializing the output.
+    pub eval: Duration,
+}
+
+/// The cache outcome of a package in one run, which helps to find out why a build is slow.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct PackageCacheStats {
+    /// Whether the ASTs of all the package files are reused from the module cache
+    /// instead of being parsed again.
+    pub ast_cached: bool,
+    /// Whether the native lib of the package is reused from the build cache, which is
+    /// `None` when the package is not compiled to a native lib e.g., run by the evaluator.
+    pub lib_cached: Option<bool>,
 }
 
 pub trait MapErrorResult {
@@ -148,11 +320,28 @@ impl ExecProgramArgs {
         serde_json::from_str::<ExecProgramArgs>(s).expect(s)
     }
 
+    /// Load the [ExecProgramArgs] from a settings file e.g., `kcl.yaml`, including the
+    /// input files, options, overrides, path selectors and package maps. The work
+    /// directory is set to the parent of the settings file when it declares input files.
+    pub fn from_settings_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_string_lossy().to_string();
+        build_settings_pathbuf(&[], Some(vec![path.as_str()]), None)?.try_into()
+    }
+
     /// Serialize the [ExecProgramArgs] structure as a String of JSON.
     pub fn to_json(&self) -> String {
         serde_json::ser::to_string(self).unwrap()
     }
 
+    /// Get the address of the plugin agent, which is always 0 in the sandbox mode.
+    pub fn get_plugin_agent(&self) -> u64 {
+        if self.sandbox {
+            0
+        } else {
+            self.plugin_agent
+        }
+    }
+
     /// Get the input file list.
     pub fn get_files(&self) -> Vec<&str> {
         self.k_filename_list.iter().map(|s| s.as_str()).collect()
@@ -165,7 +354,9 @@ impl ExecProgramArgs {
             vendor_dirs: vec![get_vendor_home()],
             package_maps: self.get_package_maps_from_external_pkg(),
             k_code_list: self.k_code_list.clone(),
-            load_plugins: self.plugin_agent > 0,
+            load_plugins: self.get_plugin_agent() > 0,
+            forward_slash_paths: self.forward_slash_paths,
+            treat_dir_as_package: self.treat_dir_as_package,
             ..Default::default()
         }
     }
@@ -186,6 +377,7 @@ impl TryFrom<SettingsFile> for ExecProgramArgs {
             args.debug = cli_configs.debug.unwrap_or_default() as i32;
             args.sort_keys = cli_configs.sort_keys.unwrap_or_default();
             args.show_hidden = cli_configs.show_hidden.unwrap_or_default();
+            args.strict_number = cli_configs.strict_number.unwrap_or_default();
             args.fast_eval = cli_configs.fast_eval.unwrap_or_default();
             args.include_schema_type_path =
                 cli_configs.include_schema_type_path.unwrap_or_default();
@@ -222,6 +414,10 @@ impl TryFrom<SettingsPathBuf> for ExecProgramArgs {
 #[derive(Debug, Default)]
 pub struct RunnerOptions {
     pub plugin_agent_ptr: u64,
+    /// The AST ids of the calls partially applying lambdas, see [ProgramScope::partial_calls].
+    ///
+    /// [ProgramScope::partial_calls]: kclvm_sema::resolver::scope::ProgramScope::partial_calls
+    pub partial_calls: HashSet<AstIndex>,
 }
 
 #[cfg(feature = "llvm")]
@@ -230,6 +426,27 @@ pub struct Artifact(libloading::Library, String);
 #[cfg(not(feature = "llvm"))]
 pub struct Artifact(String);
 
+/// The errors of running the [Artifact], which callers can branch on e.g., falling back
+/// to the evaluator when the LLVM backend is not available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactError {
+    /// The crate is built without the `llvm` feature, so the artifact can not be run.
+    LlvmDisabled,
+}
+
+impl std::fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ArtifactError::LlvmDisabled => write!(
+                f,
+                "error: llvm feature is not enabled. Note: Set KCL_FAST_EVAL=1 or rebuild the crate with the llvm feature."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArtifactError {}
+
 pub trait ProgramRunner {
     /// Run with the arguments [ExecProgramArgs] and return the program execute result that
     /// contains the planning result and the evaluation errors if any.
@@ -240,13 +457,13 @@ impl ProgramRunner for Artifact {
     fn run(&self, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         #[cfg(feature = "llvm")]
         unsafe {
-            LibRunner::lib_kclvm_plugin_init(&self.0, args.plugin_agent)?;
+            LibRunner::lib_kclvm_plugin_init(&self.0, args.get_plugin_agent())?;
             LibRunner::lib_kcl_run(&self.0, args)
         }
         #[cfg(not(feature = "llvm"))]
         {
             let _ = args;
-            Err(anyhow::anyhow!("error: llvm feature is not enabled. Note: Set KCL_FAST_EVAL=1 or rebuild the crate with the llvm feature."))
+            Err(ArtifactError::LlvmDisabled.into())
         }
     }
 }
@@ -444,10 +661,11 @@ impl LibRunner {
             json_result: json_buffer.to_string()?,
             log_message: log_buffer.to_string()?,
             err_message: err_buffer.to_string()?,
+            ..Default::default()
         };
         // Wrap runtime JSON Panic error string into diagnostic style string.
         if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
-            result.err_message = match Handler::default()
+            result.err_message = match new_err_handler(args)
                 .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(
                     result.err_message.as_str(),
                 )))
@@ -503,8 +721,17 @@ impl FastRunner {
 
     /// Run kcl library with exec arguments.
     pub fn run(&self, program: &ast::Program, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
-        let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)));
-        let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+        let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)?));
+        let mut evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+        evaluator.partial_calls = self.opts.partial_calls.clone();
+        if let Some(pkgpath) = &args.eval_pkg {
+            evaluator.output_pkgpath = pkgpath.clone();
+        }
+        // The provenance annotations need the source map to find the source files
+        // of the output documents.
+        if args.emit_source_map || args.annotate_provenance {
+            evaluator.source_map_recorder = Some(Default::default());
+        }
         #[cfg(target_arch = "wasm32")]
         // Ensure the panic hook is set (this will only happen once) for the WASM target,
         // because it is single threaded.
@@ -544,7 +771,8 @@ impl FastRunner {
                     kclvm_plugin_init(plugin_method);
                 }
             }
-            evaluator.run()
+            let result = evaluator.run();
+            result.map(|(json, yaml)| (json, yaml, evaluator.source_map()))
         });
         #[cfg(not(target_arch = "wasm32"))]
         std::panic::set_hook(prev_hook);
@@ -559,9 +787,24 @@ impl FastRunner {
         let is_err = evaluator_result.is_err();
         match evaluator_result {
             Ok(r) => match r {
-                Ok((json, yaml)) => {
+                Ok((json, yaml, source_map)) => {
                     result.json_result = json;
                     result.yaml_result = yaml;
+                    result.source_map = source_map
+                        .into_iter()
+                        .map(|(path, (start, end))| {
+                            (
+                                path,
+                                (
+                                    start.filename,
+                                    start.line,
+                                    start.column.unwrap_or_default(),
+                                    end.line,
+                                    end.column.unwrap_or_default(),
+                                ),
+                            )
+                        })
+                        .collect();
                 }
                 Err(err) => {
                     result.err_message = err.to_string();
@@ -577,9 +820,30 @@ impl FastRunner {
                 };
             }
         }
-        // Wrap runtime JSON Panic error string into diagnostic style string.
-        if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
-            result.err_message = match Handler::default()
+        result.attr_coverage = std::mem::take(&mut ctx.borrow_mut().attr_coverage);
+        result.assertions = std::mem::take(&mut ctx.borrow_mut().assertions);
+        // Report all the collected schema check failures together.
+        let check_failures = std::mem::take(&mut ctx.borrow_mut().check_failures);
+        if result.err_message.is_empty() && !check_failures.is_empty() {
+            result.json_result.clear();
+            result.yaml_result.clear();
+            result.source_map.clear();
+            result.err_message = if std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
+                let mut handler = new_err_handler(args);
+                for failure in check_failures {
+                    handler.add_diagnostic(failure.into());
+                }
+                match handler.emit_to_string() {
+                    Ok(msg) => msg,
+                    Err(err) => err.to_string(),
+                }
+            } else {
+                serde_json::to_string(&check_failures)?
+            };
+        } else if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err()
+        {
+            // Wrap runtime JSON Panic error string into diagnostic style string.
+            result.err_message = match new_err_handler(args)
                 .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(
                     result.err_message.as_str(),
                 )))
@@ -589,6 +853,22 @@ impl FastRunner {
                 Err(err) => err.to_string(),
             };
         }
+        if args.annotate_provenance && result.err_message.is_empty() {
+            let plan_opts = ctx.borrow().plan_opts.clone();
+            let variables = plan_variables(&evaluator, &plan_opts, &result.source_map);
+            let source_map = std::mem::take(&mut result.source_map);
+            annotate_provenance(&mut result, &plan_opts, |document| {
+                document_sources(document, &source_map, &variables, &args.k_filename_list)
+            })?;
+            if args.emit_source_map {
+                result.source_map = source_map;
+            }
+        }
+        if let Some(field_path) = &args.split_output_by {
+            if result.err_message.is_empty() {
+                result.split_outputs = split_output(&result, field_path, &ctx.borrow().plan_opts)?;
+            }
+        }
         // Free all value references at runtime. This is because the runtime context marks
         // all KCL objects and holds their copies, so it is necessary to actively GC them.
         ctx.borrow().gc();
@@ -596,21 +876,112 @@ impl FastRunner {
     }
 }
 
-pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Context {
+/// Plan the values of the top-level variables in the source map with the plan options
+/// except the variable and path filters, which are the output documents of them when
+/// they are output e.g., by `manifests.yaml_stream`.
+fn plan_variables(
+    evaluator: &Evaluator,
+    plan_opts: &PlanOptions,
+    source_map: &IndexMap<String, ast::PosTuple>,
+) -> IndexMap<String, serde_json::Value> {
+    let mut ctx = Context::new();
+    ctx.plan_opts = PlanOptions {
+        include_vars: vec![],
+        exclude_vars: vec![],
+        query_paths: vec![],
+        ..plan_opts.clone()
+    };
+    let mut variables = IndexMap::new();
+    for path in source_map.keys() {
+        let name = path.split('.').next().unwrap_or_default();
+        if variables.contains_key(name) {
+            continue;
+        }
+        let value = evaluator.get_variable_in_pkgpath(name, &evaluator.output_pkgpath);
+        if !value.is_list_or_config() {
+            continue;
+        }
+        let (json, _) = value.plan(&ctx);
+        if let Some(document) = json
+            .split(JSON_STREAM_SEP)
+            .next()
+            .and_then(|json| serde_json::from_str(json).ok())
+        {
+            variables.insert(name.to_string(), document);
+        }
+    }
+    variables
+}
+
+/// New a handler to emit the runtime errors in the error format of the arguments.
+fn new_err_handler(args: &ExecProgramArgs) -> Handler {
+    let mut handler = Handler::default();
+    handler.error_format = args.error_format;
+    handler
+}
+
+pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Result<Context> {
     let mut ctx = Context::new();
     ctx.cfg.strict_range_check = args.strict_range_check;
     ctx.cfg.debug_mode = args.debug != 0;
+    ctx.cfg.collect_all_check_failures = args.collect_all_check_failures;
+    ctx.cfg.max_depth = args.max_depth;
+    ctx.cfg.max_output_bytes = args.max_output_bytes;
+    ctx.cfg.max_collection_len = args.max_collection_len;
+    ctx.cfg.max_string_len = args.max_string_len;
+    ctx.cfg.allow_file_read = args.allow_file_read && !args.sandbox;
+    ctx.cfg.sandbox = args.sandbox;
+    ctx.cfg.record_attr_coverage = args.record_attr_coverage;
+    ctx.cfg.record_assertions = args.record_assertions;
+    ctx.cfg.pkg_strict_range_check = get_pkg_strict_range_check(program, args.strict_range_check);
+    ctx.set_pkg_strict_range_check(kclvm_ast::MAIN_PKG);
     ctx.plan_opts.disable_none = args.disable_none;
     ctx.plan_opts.show_hidden = args.show_hidden;
     ctx.plan_opts.sort_keys = args.sort_keys;
     ctx.plan_opts.include_schema_type_path = args.include_schema_type_path;
     ctx.plan_opts.query_paths = args.path_selector.clone();
+    ctx.plan_opts.include_vars = args.include_vars.clone();
+    ctx.plan_opts.exclude_vars = args.frozen_inputs.keys().cloned().collect();
+    ctx.plan_opts
+        .exclude_vars
+        .extend(get_injected_input_names(program, &args.inputs)?);
+    ctx.plan_opts.strict_number = args.strict_number;
+    ctx.output_transforms = args.output_transforms.clone();
     for arg in &args.args {
         ctx.builtin_option_init(&arg.name, &arg.value);
     }
     ctx.set_kcl_workdir(&args.work_dir.clone().unwrap_or_default());
     ctx.set_kcl_module_path(&program.root);
-    ctx
+    Ok(ctx)
+}
+
+/// Get the `strict_range_check` option of each package in the program from the `[compile]`
+/// section of the package `kcl.mod`. It returns an empty map when no package overrides the
+/// global option.
+pub(crate) fn get_pkg_strict_range_check(
+    program: &ast::Program,
+    global: bool,
+) -> HashMap<String, bool> {
+    let overrides: HashMap<String, Option<bool>> = program
+        .pkgs
+        .iter()
+        .map(|(pkgpath, modules)| {
+            let strict_range_check = modules
+                .first()
+                .and_then(|file| get_pkg_root(file))
+                .and_then(|root| load_mod_file(root).ok())
+                .and_then(|mod_file| mod_file.compile)
+                .and_then(|compile| compile.strict_range_check);
+            (pkgpath.clone(), strict_range_check)
+        })
+        .collect();
+    if overrides.values().all(|v| v.is_none()) {
+        return HashMap::new();
+    }
+    overrides
+        .into_iter()
+        .map(|(pkgpath, strict_range_check)| (pkgpath, strict_range_check.unwrap_or(global)))
+        .collect()
 }
 
 #[repr(C)]
@@ -0,0 +1,112 @@
+//! Split the multi-document output into one file per document.
+//!
+//! Each top-level output document e.g., an item of `manifests.yaml_stream` is named
+//! by the value of a field path like `metadata.name`, which automates writing the
+//! Kubernetes manifests into separate files.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use indexmap::IndexMap;
+use kclvm_runtime::val_plan::PlanOptions;
+use serde_json::Value;
+
+use crate::ExecProgramResult;
+
+/// The file extension of the split output files.
+pub const SPLIT_OUTPUT_EXTENSION: &str = "yaml";
+
+/// Split the YAML output of the result into the documents keyed by the file name
+/// `<value>.yaml`, in which the value is read from the field path e.g., `metadata.name`
+/// of each document. The YAML output is split by the separator in the plan options it
+/// was planned with e.g., the one set by `manifests.yaml_stream`. An error is returned
+/// when the field of a document is missing or not a string or number, or when two
+/// documents have the same file name.
+pub fn split_output(
+    result: &ExecProgramResult,
+    field_path: &str,
+    plan_opts: &PlanOptions,
+) -> Result<IndexMap<String, String>> {
+    let documents = serde_json::Deserializer::from_str(&result.json_result)
+        .into_iter::<Value>()
+        .collect::<Result<Vec<Value>, _>>()?;
+    // The YAML documents are separated by the separator lines at the column 0, which
+    // can not occur in the indented block scalars.
+    let yaml_sep = format!("\n{}\n", plan_opts.sep.as_deref().unwrap_or("---"));
+    let yaml_documents: Vec<&str> = if result.yaml_result.is_empty() {
+        vec![]
+    } else {
+        result.yaml_result.split(&yaml_sep).collect()
+    };
+    if documents.len() != yaml_documents.len() {
+        bail!(
+            "the output has {} JSON documents but {} YAML documents",
+            documents.len(),
+            yaml_documents.len()
+        );
+    }
+    let mut outputs = IndexMap::new();
+    for (index, (document, yaml)) in documents.iter().zip(yaml_documents).enumerate() {
+        let name = match get_field(document, field_path) {
+            Some(Value::String(name)) => name.to_string(),
+            Some(Value::Number(number)) => number.to_string(),
+            Some(value) => bail!(
+                "the field '{}' of the output document {} must be a string, got {}",
+                field_path,
+                index,
+                value
+            ),
+            None => bail!(
+                "the field '{}' is not found in the output document {}",
+                field_path,
+                index
+            ),
+        };
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            bail!(
+                "invalid output file name '{}' of the output document {}",
+                name,
+                index
+            );
+        }
+        let filename = format!("{name}.{SPLIT_OUTPUT_EXTENSION}");
+        if outputs.contains_key(&filename) {
+            bail!(
+                "duplicate output file name '{}' of the output document {}",
+                filename,
+                index
+            );
+        }
+        outputs.insert(filename, yaml.to_string());
+    }
+    Ok(outputs)
+}
+
+/// Write the split outputs of the result into the directory, which is created when it
+/// does not exist, and return the written file paths in the document order.
+pub fn write_split_outputs<P: AsRef<Path>>(
+    result: &ExecProgramResult,
+    dir: P,
+) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| {
+        anyhow!(
+            "failed to create the output directory {}: {e}",
+            dir.display()
+        )
+    })?;
+    let mut paths = vec![];
+    for (filename, yaml) in &result.split_outputs {
+        let path = dir.join(filename);
+        std::fs::write(&path, format!("{yaml}\n"))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Get the value of the field path e.g., `metadata.name` in the JSON value.
+fn get_field<'a>(value: &'a Value, field_path: &str) -> Option<&'a Value> {
+    field_path
+        .split('.')
+        .try_fold(value, |value, key| value.as_object()?.get(key))
+}
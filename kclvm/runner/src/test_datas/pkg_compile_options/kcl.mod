@@ -0,0 +1,4 @@
+[package]
+name = "pkg_compile_options"
+edition = "0.0.1"
+version = "0.0.1"
@@ -0,0 +1,7 @@
+[package]
+name = "sub"
+edition = "0.0.1"
+version = "0.0.1"
+
+[compile]
+strict_range_check = false
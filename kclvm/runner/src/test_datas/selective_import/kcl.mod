@@ -0,0 +1,4 @@
+[package]
+name = "selective_import"
+edition = "0.0.1"
+version = "0.0.1"
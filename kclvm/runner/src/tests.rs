@@ -6,18 +6,28 @@ use crate::assembler::KclvmAssembler;
 use crate::assembler::KclvmLibAssembler;
 #[cfg(feature = "llvm")]
 use crate::assembler::LibAssembler;
-use crate::exec_program;
 #[cfg(feature = "llvm")]
 use crate::temp_file;
-use crate::{execute, runner::ExecProgramArgs};
+use crate::{
+    compilation_cache_key, diff_programs, exec_program, exec_program_with_module_cache,
+    override_impact, write_split_outputs, ConfigChange, PackageCacheStats, CONFIG_HASH_ANNOTATION,
+    SOURCE_ANNOTATION,
+};
+use crate::{
+    execute,
+    repl::{ReplOutput, ReplSession},
+    runner::ExecProgramArgs,
+};
 #[cfg(feature = "llvm")]
 use anyhow::Context;
 use anyhow::Result;
-use kclvm_ast::ast::{Module, Program};
+use kclvm_ast::ast::{self, Module, Program};
 #[cfg(feature = "llvm")]
 use kclvm_compiler::codegen::OBJECT_FILE_SUFFIX;
 use kclvm_config::settings::load_file;
+use kclvm_error::ErrorFormat;
 use kclvm_parser::load_program;
+use kclvm_parser::KCLModuleCache;
 use kclvm_parser::ParseSession;
 #[cfg(feature = "llvm")]
 use kclvm_sema::resolver::resolve_program;
@@ -28,6 +38,7 @@ use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Duration;
 use std::{
     collections::HashMap,
     fs::{self, File},
@@ -479,6 +490,28 @@ fn test_from_setting_file_program_arg() {
     }
 }
 
+#[test]
+fn test_from_settings_file() {
+    let settings_file = Path::new(&test_case_path())
+        .join("settings_file")
+        .join("settings.yaml");
+    let exec_prog_args = ExecProgramArgs::from_settings_file(&settings_file).unwrap();
+    assert_eq!(
+        exec_prog_args.k_filename_list,
+        vec!["../main.k", "./before/base.k", "./main.k", "./sub/sub.k"]
+    );
+    assert_eq!(exec_prog_args.args.len(), 2);
+    assert_eq!(exec_prog_args.args[0].name, "app-name");
+    assert_eq!(exec_prog_args.args[0].value, "\"kclvm\"");
+    assert_eq!(
+        exec_prog_args.work_dir,
+        settings_file
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+    );
+    assert!(ExecProgramArgs::from_settings_file("not_exist.yaml").is_err());
+}
+
 fn test_exec_file() {
     let result = std::panic::catch_unwind(|| {
         for file in get_files(exec_data_path(), false, true, ".k") {
@@ -735,3 +768,1099 @@ fn test_kcl_issue_1799() {
         )
     );
 }
+
+#[test]
+fn test_exec_with_source_map() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"schema Spec:
+    replicas: int
+
+deployment = {
+    spec = Spec {
+        replicas = 3
+    }
+}
+_hidden = 1
+"#
+        .to_string(),
+    );
+    args.emit_source_map = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let source_map = &res.source_map;
+    assert_eq!(source_map["deployment"].1, 4);
+    assert_eq!(source_map["deployment.spec"].1, 5);
+    assert_eq!(source_map["deployment.spec.replicas"].1, 6);
+    assert_eq!(source_map["deployment.spec.replicas"].2, 8);
+    assert!(!source_map.contains_key("_hidden"));
+
+    args.emit_source_map = false;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.source_map.is_empty());
+}
+
+#[test]
+fn test_exec_with_source_map_of_executed_assignments() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"schema Spec:
+    replicas: int = 1
+    name: str
+
+if True:
+    a = 1
+else:
+    a = 2
+
+spec = Spec {
+    name = "app"
+}
+containers = [
+    {name = "c0"}
+    {name = "c1"}
+]
+"#
+        .to_string(),
+    );
+    args.emit_source_map = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let source_map = &res.source_map;
+    // Only the taken branch of the if statement is recorded.
+    assert_eq!(source_map["a"].1, 6);
+    // The schema attribute default value is recorded when the config does not set it.
+    assert_eq!(source_map["spec.replicas"].1, 2);
+    assert_eq!(source_map["spec.name"].1, 11);
+    assert_eq!(source_map["containers.0"].1, 14);
+    assert_eq!(source_map["containers.1.name"].1, 15);
+}
+
+#[test]
+fn test_exec_with_include_vars() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"helper = {replicas = 3}
+app = {name = "app", replicas = helper.replicas}
+other = 1
+"#
+        .to_string(),
+    );
+    args.include_vars = vec!["app".to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.yaml_result, "app:\n  name: app\n  replicas: 3");
+
+    // The path selector is applied after the variable selection.
+    args.path_selector = vec!["app".to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.yaml_result, "name: app\nreplicas: 3");
+}
+
+#[test]
+fn test_exec_with_strict_number() {
+    let mut args = ExecProgramArgs::default();
+    args.fast_eval = true;
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("memory = 1Ki\nratio = 1.0\ncount = 1\n".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.yaml_result, "memory: 1024.0\nratio: 1.0\ncount: 1");
+
+    args.strict_number = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.yaml_result, "memory: 1024\nratio: 1.0\ncount: 1");
+    assert_eq!(
+        res.json_result,
+        "{\"memory\": 1024, \"ratio\": 1.0, \"count\": 1}"
+    );
+}
+
+#[test]
+fn test_exec_with_collect_all_check_failures() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"schema App:
+    name: str
+    replicas: int
+
+    check:
+        len(name) > 3, "name is too short"
+        replicas > 0, "replicas must be positive"
+        replicas < 10, "replicas is too large"
+
+app = App {name = "a", replicas = 0}
+"#
+        .to_string(),
+    );
+    // Stop at the first failed check by default.
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.contains("name is too short"));
+    assert!(!res.err_message.contains("replicas must be positive"));
+
+    args.collect_all_check_failures = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.yaml_result.is_empty());
+    assert!(res.err_message.contains("name is too short"));
+    assert!(res.err_message.contains("replicas must be positive"));
+    assert!(!res.err_message.contains("replicas is too large"));
+}
+
+#[test]
+fn test_exec_with_record_assertions() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"replicas = 0
+assert replicas >= 0, "replicas must not be negative"
+assert replicas > 0, "replicas must be positive"
+assert replicas < 10
+"#
+        .to_string(),
+    );
+    // Stop at the first failed assertion by default.
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.contains("replicas must be positive"));
+    assert!(res.assertions.is_empty());
+
+    args.record_assertions = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.yaml_result.is_empty());
+    assert!(res.err_message.contains("replicas must be positive"));
+    let outcomes: Vec<(u64, bool, &str)> = res
+        .assertions
+        .iter()
+        .map(|a| (a.line, a.passed, a.message.as_str()))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            (2, true, "replicas must not be negative"),
+            (3, false, "replicas must be positive"),
+            (4, true, ""),
+        ]
+    );
+}
+
+#[test]
+fn test_exec_with_limits() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"f1 = lambda n: int -> int { n + 1 }
+f2 = lambda n: int -> int { f1(n) + 1 }
+f3 = lambda n: int -> int { f2(n) + 1 }
+a = f3(7)
+"#
+        .to_string(),
+    );
+    args.max_depth = Some(3);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "a: 10");
+
+    args.max_depth = Some(2);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.contains("maximum call depth 2 exceeded"));
+
+    args.max_depth = None;
+    args.max_output_bytes = Some(4);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res
+        .err_message
+        .contains("the output size exceeds the limit of 4 bytes"));
+}
+
+#[test]
+fn test_exec_with_size_limits() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("a = [0] * 3\nb = \"ab\" * 2\nc = [i for i in range(3)]\n".to_string());
+    args.max_collection_len = Some(3);
+    args.max_string_len = Some(4);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+
+    args.max_collection_len = Some(2);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res
+        .err_message
+        .contains("the collection length 3 exceeds the limit of 2"));
+    assert!(res.err_message.contains("main.k:1"), "{}", res.err_message);
+
+    // The limit applies to the dict insertions and unions too.
+    let mut dict_args = ExecProgramArgs::default();
+    dict_args.k_filename_list.push("main.k".to_string());
+    dict_args.max_collection_len = Some(2);
+    for code in [
+        "a = {\"x\": 1, \"y\": 2, \"z\": 3}\n",
+        "a = {k: 1 for k in [\"x\", \"y\", \"z\"]}\n",
+        "a = {\"x\": 1, \"y\": 2} | {\"z\": 3}\n",
+    ] {
+        dict_args.k_code_list = vec![code.to_string()];
+        let res = exec_program(Arc::new(ParseSession::default()), &dict_args).unwrap();
+        assert!(
+            res.err_message
+                .contains("the collection length 3 exceeds the limit of 2"),
+            "{}",
+            res.err_message
+        );
+    }
+
+    args.max_collection_len = None;
+    args.max_string_len = Some(3);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res
+        .err_message
+        .contains("the string length 4 exceeds the limit of 3"));
+    assert!(res.err_message.contains("main.k:2"), "{}", res.err_message);
+
+    // The limit applies to the string interpolation and the string builtin functions.
+    let mut str_args = ExecProgramArgs::default();
+    str_args.k_filename_list.push("main.k".to_string());
+    str_args.max_string_len = Some(4);
+    for code in [
+        "a = \"ab\"\nb = \"${a}${a}\"\nc = \"${b}${b}\"\n",
+        "a = \"-\".join([\"ab\", \"cd\"])\n",
+        "a = \"abab\".replace(\"a\", \"aa\")\n",
+        "a = \"{}{}\".format(\"abc\", \"abc\")\n",
+    ] {
+        str_args.k_code_list = vec![code.to_string()];
+        let res = exec_program(Arc::new(ParseSession::default()), &str_args).unwrap();
+        assert!(
+            res.err_message.contains("exceeds the limit of 4"),
+            "{}",
+            res.err_message
+        );
+    }
+}
+
+#[test]
+fn test_exec_with_frozen_inputs() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("app = {name = \"app\", replicas = env.replicas}\n".to_string());
+    args.frozen_inputs
+        .insert("env".to_string(), serde_json::json!({"replicas": 3}));
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    // The frozen input is not emitted in the output.
+    assert_eq!(res.yaml_result, "app:\n  name: app\n  replicas: 3");
+
+    // The program can not redefine the frozen input.
+    for code in [
+        "env = {replicas = 1}\n",
+        "if True:\n    env = {replicas = 1}\n",
+        "if False:\n    a = 1\nelse:\n    env = {replicas = 1}\n",
+    ] {
+        args.k_code_list = vec![code.to_string()];
+        let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot override the frozen input 'env'"));
+    }
+
+    // The string interpolation in the frozen input values and keys is not evaluated.
+    args.k_code_list = vec!["label = env\n".to_string()];
+    args.frozen_inputs.insert(
+        "env".to_string(),
+        serde_json::json!({"${1+1}": "${1+1}", "items": ["${label}"]}),
+    );
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let result: serde_json::Value = serde_json::from_str(&res.json_result).unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!({"label": {"${1+1}": "${1+1}", "items": ["${label}"]}})
+    );
+}
+
+#[test]
+fn test_exec_with_inputs() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("replicas = data.replicas\nname = \"app\"\n".to_string());
+    args.inputs
+        .insert("data".to_string(), serde_json::json!({"replicas": 3}));
+    args.inputs
+        .insert("name".to_string(), serde_json::json!("input"));
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    // The top-level assignment in the program takes precedence over the input,
+    // and the injected input is not emitted in the output.
+    assert_eq!(res.yaml_result, "replicas: 3\nname: app");
+
+    // The assignment in the top-level if body also takes precedence over the input.
+    args.k_code_list = vec!["if True:\n    name = \"app\"\nlabel = name\n".to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "name: app\nlabel: app");
+
+    // The string interpolation in the input values and keys is not evaluated.
+    args.k_code_list = vec!["label = data\n".to_string()];
+    args.inputs.insert(
+        "data".to_string(),
+        serde_json::json!({"${1+1}": "${1+1}", "items": ["${name}"]}),
+    );
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let result: serde_json::Value = serde_json::from_str(&res.json_result).unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!({"label": {"${1+1}": "${1+1}", "items": ["${name}"]}})
+    );
+}
+
+#[test]
+fn test_compilation_cache_key() {
+    let args_with = |overrides: &[&str], options: &[(&str, &str)], code: &str| {
+        let mut args = ExecProgramArgs::default();
+        args.k_filename_list.push("main.k".to_string());
+        args.k_code_list.push(code.to_string());
+        args.overrides = overrides.iter().map(|s| s.to_string()).collect();
+        args.args = options
+            .iter()
+            .map(|(name, value)| ast::Argument {
+                name: name.to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+        args
+    };
+    let code = "a = {b = 1, c = 2}\n";
+    let key = compilation_cache_key(&args_with(
+        &["a.b=2", "a.c=3"],
+        &[("x", "1"), ("y", "2")],
+        code,
+    ))
+    .unwrap();
+    // The argument order and the overridden specs do not change the key.
+    assert_eq!(
+        key,
+        compilation_cache_key(&args_with(
+            &["a.c=1", "a.b=2", "a.c=3"],
+            &[("y", "2"), ("x", "1")],
+            code,
+        ))
+        .unwrap()
+    );
+    assert_ne!(
+        key,
+        compilation_cache_key(&args_with(
+            &["a.b=2", "a.c=4"],
+            &[("x", "1"), ("y", "2")],
+            code
+        ))
+        .unwrap()
+    );
+    assert_ne!(
+        key,
+        compilation_cache_key(&args_with(
+            &["a.b=2", "a.c=3"],
+            &[("x", "1"), ("y", "2")],
+            "a = {b = 1}\n",
+        ))
+        .unwrap()
+    );
+    // The insert specs of the same field path are cumulative.
+    let code = "a = {b = []}\n";
+    assert_ne!(
+        compilation_cache_key(&args_with(&["a.b+=[1]", "a.b+=[2]"], &[], code)).unwrap(),
+        compilation_cache_key(&args_with(&["a.b+=[2]"], &[], code)).unwrap()
+    );
+    assert_eq!(
+        compilation_cache_key(&args_with(&["a.b+=[1]", "a.b=[0]", "a.b+=[2]"], &[], code)).unwrap(),
+        compilation_cache_key(&args_with(&["a.b=[0]", "a.b+=[2]"], &[], code)).unwrap()
+    );
+
+    // The insertion order of the inputs does not change the key.
+    let names = (0..32)
+        .map(|i| format!("input{i}"))
+        .collect::<Vec<String>>();
+    let mut args = args_with(&[], &[], code);
+    let mut other_args = args.clone();
+    for name in &names {
+        args.inputs
+            .insert(name.to_string(), serde_json::json!(name));
+    }
+    for name in names.iter().rev() {
+        other_args
+            .inputs
+            .insert(name.to_string(), serde_json::json!(name));
+    }
+    assert_eq!(
+        compilation_cache_key(&args).unwrap(),
+        compilation_cache_key(&other_args).unwrap()
+    );
+
+    // The files in the different checkouts of the work directory have the same key.
+    let checkout_args = |work_dir: &str| {
+        let mut args = args_with(&[], &[], code);
+        args.work_dir = Some(work_dir.to_string());
+        args.k_filename_list = vec![format!("{work_dir}/main.k")];
+        args
+    };
+    assert_eq!(
+        compilation_cache_key(&checkout_args("/checkout1")).unwrap(),
+        compilation_cache_key(&checkout_args("/checkout2")).unwrap()
+    );
+}
+
+#[test]
+fn test_exec_with_profile_memory() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("a = [i for i in range(1000)]\n".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.process_peak_memory_bytes, None);
+
+    args.profile_memory = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    if cfg!(any(target_os = "linux", target_os = "macos")) {
+        assert!(res.process_peak_memory_bytes.unwrap() > 0);
+    } else {
+        assert_eq!(res.process_peak_memory_bytes, None);
+    }
+}
+
+#[test]
+fn test_exec_with_profile() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("a = [i for i in range(1000)]\n".to_string());
+    args.fast_eval = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.timings, None);
+
+    args.profile = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let timings = res.timings.unwrap();
+    assert!(timings.load > Duration::ZERO);
+    assert!(timings.resolve > Duration::ZERO);
+    assert!(timings.eval > Duration::ZERO);
+    // The evaluator does not compile the program to a native lib.
+    assert_eq!(timings.codegen, None);
+}
+
+#[test]
+fn test_exec_with_profile_cache() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/multi_file_compilation/import_regular_module/main.k".to_string());
+    args.fast_eval = true;
+    let module_cache = KCLModuleCache::default();
+    let res = exec_program_with_module_cache(
+        Arc::new(ParseSession::default()),
+        &args,
+        module_cache.clone(),
+    )
+    .unwrap();
+    assert!(res.cache_stats.is_empty());
+
+    args.profile_cache = true;
+    let res = exec_program_with_module_cache(
+        Arc::new(ParseSession::default()),
+        &args,
+        module_cache.clone(),
+    )
+    .unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    // The imported package is reused from the module cache filled by the last run,
+    // while the main package files are always parsed again.
+    let stats = |ast_cached| PackageCacheStats {
+        ast_cached,
+        lib_cached: None,
+    };
+    assert_eq!(
+        res.cache_stats.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("__main__".to_string(), stats(false)),
+            ("mymodule".to_string(), stats(true)),
+        ]
+    );
+
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(res.cache_stats["mymodule"], stats(false));
+}
+
+#[test]
+fn test_exec_with_allow_file_read() {
+    let main_file = std::path::Path::new("./src/test_datas/file_read/main.k")
+        .canonicalize()
+        .unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(main_file.display().to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res
+        .err_message
+        .contains("please enable the 'allow_file_read' option"));
+
+    args.allow_file_read = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(
+        res.yaml_result,
+        "json_data:\n  replicas: 3\n  labels:\n    app: nginx\nyaml_data:\n  replicas: 2"
+    );
+
+    // The file contents can not be read by file.read without the option, while the
+    // file paths can still be checked.
+    args.allow_file_read = false;
+    args.work_dir = Some(main_file.parent().unwrap().display().to_string());
+    args.k_filename_list = vec!["main.k".to_string()];
+    args.k_code_list =
+        vec!["import file\nimport json\n\na = json.decode(file.read(\"data.json\"))\n".to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(
+        res.err_message
+            .contains("read() is not allowed to read the file"),
+        "{}",
+        res.err_message
+    );
+    args.k_code_list = vec![format!(
+        "import file\n\na = file.exists(\"{}\")\n",
+        main_file.parent().unwrap().join("data.json").display()
+    )];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "a: true");
+    args.allow_file_read = true;
+    args.k_code_list = vec![format!(
+        "import file\nimport json\n\na = json.decode(file.read(\"{}\")).replicas\n",
+        main_file.parent().unwrap().join("data.json").display()
+    )];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "a: 3");
+
+    // The files outside the workspace can not be read.
+    for name in ["read", "read_json"] {
+        args.k_code_list = vec![format!(
+            "import file\n\na = file.{name}(\"{}/../Cargo.toml\")\n",
+            env!("CARGO_MANIFEST_DIR")
+        )];
+        let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(
+            res.err_message.contains("outside the workspace"),
+            "{}",
+            res.err_message
+        );
+    }
+}
+
+#[test]
+fn test_exec_with_sandbox() {
+    let main_file = std::path::Path::new("./src/test_datas/file_read/main.k")
+        .canonicalize()
+        .unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(main_file.display().to_string());
+    // The sandbox mode overrides the allow flags to off.
+    args.allow_file_read = true;
+    args.sandbox = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(
+        res.err_message
+            .contains("file.read_json() is not allowed in the sandbox mode"),
+        "{}",
+        res.err_message
+    );
+
+    let mut args = ExecProgramArgs::default();
+    args.sandbox = true;
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("import file\n\na = file.read_env(\"HOME\")\n".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(
+        res.err_message
+            .contains("file.read_env() is not allowed in the sandbox mode"),
+        "{}",
+        res.err_message
+    );
+
+    // The nondeterministic values and the host paths are not allowed.
+    for (import, func) in [
+        ("datetime", "datetime.now"),
+        ("datetime", "datetime.today"),
+        ("datetime", "datetime.ticks"),
+        ("crypto", "crypto.uuid"),
+        ("file", "file.modpath"),
+        ("file", "file.workdir"),
+        ("file", "file.current"),
+    ] {
+        args.k_code_list = vec![format!("import {import}\n\na = {func}()\n")];
+        let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(
+            res.err_message
+                .contains(&format!("{func}() is not allowed in the sandbox mode")),
+            "{}",
+            res.err_message
+        );
+    }
+
+    // The pure evaluation is not affected.
+    args.k_code_list = vec!["a = 1\n".to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "a: 1");
+}
+
+#[test]
+#[cfg(not(feature = "llvm"))]
+fn test_run_artifact_or_eval() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push("a = 1\n".to_string());
+    let err = crate::exec_artifact("main.so", &args).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<crate::ArtifactError>(),
+        Some(&crate::ArtifactError::LlvmDisabled)
+    );
+    let res = crate::run_artifact_or_eval("main.so", &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "a: 1");
+}
+
+#[test]
+fn test_exec_with_eval_pkg() {
+    let main_file = std::path::Path::new("./src/test_datas/eval_pkg/main.k")
+        .canonicalize()
+        .unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(main_file.display().to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "name: app");
+
+    // Only the package is output with its dependencies evaluated.
+    args.eval_pkg = Some("app".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(
+        res.yaml_result,
+        "name: app\nlabels:\n  team: infra\n  app: app"
+    );
+
+    // The package is evaluated under its own package path, which keeps the full names of
+    // its schemas, and the source map is recorded for its variables.
+    args.eval_pkg = Some("svc".to_string());
+    args.include_schema_type_path = true;
+    args.emit_source_map = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert!(
+        res.yaml_result.contains("  _type: svc.Service"),
+        "{}",
+        res.yaml_result
+    );
+    assert!(res.source_map["service"].0.ends_with("svc.k"));
+
+    args.eval_pkg = Some("not_found".to_string());
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the package 'not_found' to evaluate is not found in the program, the packages are: ['app', 'lib', 'svc']"
+    );
+}
+
+#[test]
+fn test_exec_with_selective_import() {
+    let main_file = std::path::Path::new("./src/test_datas/selective_import/main.k")
+        .canonicalize()
+        .unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(main_file.display().to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(
+        res.yaml_result,
+        "app:\n  name: app\n  labels:\n    app: app\n    team: infra\nmessage: hello kcl"
+    );
+}
+
+#[test]
+fn test_exec_with_short_error_format() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push("a: int = \"1\"\nb = c\n".to_string());
+    let sess = Arc::new(ParseSession::default());
+    sess.1.write().error_format = ErrorFormat::Short;
+    let err = exec_program(sess, &args).unwrap_err().to_string();
+    let lines: Vec<&str> = err.lines().collect();
+    assert_eq!(lines.len(), 2, "{err}");
+    assert!(lines[0].contains("main.k:1:"), "{err}");
+    assert!(lines[0].ends_with(" [E2G22]"), "{err}");
+    assert!(
+        lines[1].contains("main.k:2:5: error: name 'c' is not defined"),
+        "{err}"
+    );
+    assert!(lines[1].ends_with(" [E2L23]"), "{err}");
+
+    // The runtime errors are rendered in the error format of the arguments.
+    args.k_code_list = vec!["a = 1\nassert a > 1, \"a must be greater than 1\"\n".to_string()];
+    args.error_format = ErrorFormat::Short;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    let lines: Vec<&str> = res.err_message.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", res.err_message);
+    assert!(
+        lines[0].contains("main.k:2:") && lines[0].contains(": error: "),
+        "{}",
+        res.err_message
+    );
+    assert!(
+        lines[0].contains("a must be greater than 1"),
+        "{}",
+        res.err_message
+    );
+}
+
+#[test]
+fn test_exec_with_pkg_compile_options() {
+    let main_file = std::path::Path::new("./src/test_datas/pkg_compile_options/main.k")
+        .canonicalize()
+        .unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(main_file.display().to_string());
+    args.strict_range_check = true;
+    // The package `sub` disables the strict range check in its `kcl.mod`.
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(res.yaml_result, "b: 2147483648");
+
+    // The main package still uses the global option.
+    args.k_code_list
+        .push("import sub\n\na = 2147483647 + 1\nb = sub.b\n".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(
+        res.err_message.contains("A 32 bit integer overflow"),
+        "{}",
+        res.err_message
+    );
+}
+
+#[test]
+fn test_exec_with_split_output_by() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"import manifests
+
+manifests.yaml_stream([
+    {kind = "Deployment", metadata.name = "app"}
+    {kind = "Service", metadata.name = "app-svc"}
+])
+"#
+        .to_string(),
+    );
+    args.split_output_by = Some("metadata.name".to_string());
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(
+        res.split_outputs.keys().collect::<Vec<&String>>(),
+        vec!["app.yaml", "app-svc.yaml"]
+    );
+    assert_eq!(
+        res.split_outputs["app-svc.yaml"],
+        "kind: Service\nmetadata:\n  name: app-svc"
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let paths = write_split_outputs(&res, dir.path()).unwrap();
+    assert_eq!(
+        paths,
+        vec![dir.path().join("app.yaml"), dir.path().join("app-svc.yaml")]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&paths[0]).unwrap(),
+        "kind: Deployment\nmetadata:\n  name: app\n"
+    );
+
+    // The output is split by the separator of the stream.
+    args.k_code_list = vec![r#"import manifests
+
+manifests.yaml_stream([
+    {kind = "Deployment", metadata.name = "app"}
+    {kind = "Service", metadata.name = "app-svc"}
+], opts = {sep = "--- # next"})
+"#
+    .to_string()];
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    assert_eq!(
+        res.split_outputs
+            .into_iter()
+            .collect::<Vec<(String, String)>>(),
+        vec![
+            (
+                "app.yaml".to_string(),
+                "kind: Deployment\nmetadata:\n  name: app".to_string()
+            ),
+            (
+                "app-svc.yaml".to_string(),
+                "kind: Service\nmetadata:\n  name: app-svc".to_string()
+            ),
+        ]
+    );
+
+    // The documents must have distinct names.
+    args.split_output_by = Some("kind".to_string());
+    args.k_code_list = vec![
+        "import manifests\n\nmanifests.yaml_stream([{kind = \"A\"}, {kind = \"A\"}])\n".to_string(),
+    ];
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("duplicate output file name 'A.yaml' of the output document 1"));
+}
+
+#[test]
+fn test_exec_with_annotate_provenance() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push(
+        r#"import manifests
+
+manifests.yaml_stream([
+    {apiVersion = "v1", kind = "Service", metadata.name = "app-svc"}
+    {name = "not-k8s"}
+    {apiVersion = "v1", kind = "ConfigMap", metadata = {name = "app", annotations.owner = "team"}}
+])
+"#
+        .to_string(),
+    );
+    args.annotate_provenance = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    let documents: Vec<&str> = res.yaml_result.split("\n---\n").collect();
+    assert_eq!(documents.len(), 3);
+    assert!(documents[0].contains("  annotations:\n    kcl-lang.io/config-hash: "));
+    assert!(documents[0].ends_with("    kcl-lang.io/source: main.k"));
+    assert_eq!(documents[1], "name: not-k8s");
+    assert!(documents[2].contains("    owner: team\n    kcl-lang.io/config-hash: "));
+    let json: Vec<serde_json::Value> = res
+        .json_result
+        .split('\n')
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect();
+    assert_eq!(json[1], serde_json::json!({"name": "not-k8s"}));
+    let hash = json[0]["metadata"]["annotations"][CONFIG_HASH_ANNOTATION]
+        .as_str()
+        .unwrap();
+    assert_eq!(hash.len(), 32);
+    assert_eq!(
+        json[2]["metadata"]["annotations"][SOURCE_ANNOTATION],
+        "main.k"
+    );
+}
+
+#[test]
+fn test_exec_with_annotate_provenance_sources() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list = vec!["main.k".to_string(), "app.k".to_string()];
+    args.k_code_list = vec![
+        "svc = {apiVersion = \"v1\", kind = \"Service\", metadata.name = \"svc\"}\n".to_string(),
+        r#"import manifests
+
+cm = {apiVersion = "v1", kind = "ConfigMap", metadata.name = "cm"}
+manifests.yaml_stream([svc, cm], opts = {sep = "--- # next"})
+"#
+        .to_string(),
+    ];
+    args.annotate_provenance = true;
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    // The documents are split with the custom separator.
+    let documents: Vec<&str> = res.yaml_result.split("\n--- # next\n").collect();
+    assert_eq!(documents.len(), 2, "{}", res.yaml_result);
+    // Each document is annotated with the file setting it.
+    assert!(documents[0].ends_with("    kcl-lang.io/source: main.k"));
+    assert!(documents[1].ends_with("    kcl-lang.io/source: app.k"));
+    // The source map is only output when it is enabled.
+    assert!(res.source_map.is_empty());
+}
+
+#[test]
+fn test_repl_session() {
+    let mut session = ReplSession::default();
+    assert_eq!(session.eval("a = 1").unwrap(), ReplOutput::default());
+    session
+        .eval("schema Person:\n    name: str\n    age: int = a\n")
+        .unwrap();
+    let output = session
+        .eval("alice = Person {name = \"alice\"}\nprint(alice.age)")
+        .unwrap();
+    assert_eq!(output.value, None);
+    assert_eq!(output.log_message, "1\n");
+    assert_eq!(
+        session.eval("alice").unwrap().value,
+        Some("name: alice\nage: 1".to_string())
+    );
+    // The redefinition replaces the previous definition.
+    session.eval("a = 2\nb = [a]").unwrap();
+    assert_eq!(
+        session.eval("alice.age").unwrap().value,
+        Some("2".to_string())
+    );
+    assert_eq!(session.eval("b").unwrap().value, Some("- 2".to_string()));
+    // The inputs failing to type check or evaluate are rejected.
+    assert!(session.eval("a = \"2\"").is_err());
+    assert!(session.eval("c = 1 / 0").is_err());
+    assert!(session.eval("c").is_err());
+    assert_eq!(
+        session.source(),
+        "schema Person:\n    name: str\n    age: int = a\nalice = Person {name = \"alice\"}\na = 2\nb = [a]"
+    );
+    session.reset();
+    assert!(session.eval("a").is_err());
+}
+
+#[test]
+fn test_repl_session_bindings() {
+    let mut session = ReplSession::default();
+    // The augmented assignment updates the previous definition.
+    session.eval("_a = 1").unwrap();
+    session.eval("_a += 1").unwrap();
+    assert_eq!(session.eval("_a").unwrap().value, Some("2".to_string()));
+    // The redefinition replaces both the definition and the update.
+    session.eval("_a = 5").unwrap();
+    assert_eq!(session.eval("_a").unwrap().value, Some("5".to_string()));
+    assert_eq!(session.source(), "_a = 5");
+    // The names assigned in the if blocks are redefined as well.
+    session
+        .eval("if True:\n    c = 1\nelse:\n    d = 1\n")
+        .unwrap();
+    assert_eq!(session.eval("c").unwrap().value, Some("1".to_string()));
+    session.eval("c = 2").unwrap();
+    assert_eq!(session.eval("c").unwrap().value, Some("2".to_string()));
+    assert_eq!(session.source(), "_a = 5\nc = 2");
+}
+
+#[test]
+fn test_diff_programs() {
+    let mut args_a = ExecProgramArgs::default();
+    args_a.k_filename_list.push("main.k".to_string());
+    args_a.k_code_list.push(
+        r#"import manifests
+
+manifests.yaml_stream([
+    {name = "a", replicas = 1, labels = {"app.io/name" = "a"}}
+    {name = "b", ports = [80, 443]}
+])
+"#
+        .to_string(),
+    );
+    let mut args_b = args_a.clone();
+    args_b.k_code_list = vec![r#"import manifests
+
+manifests.yaml_stream([
+    {name = "a", replicas = 3, labels = {"app.io/name" = "a"}}
+    {name = "b", ports = [80]}
+    {name = "c"}
+])
+"#
+    .to_string()];
+    let diff = diff_programs(&args_a, &args_b).unwrap();
+    assert_eq!(diff.documents, (2, 3));
+    assert_eq!(
+        diff.changes,
+        vec![
+            ConfigChange::Changed {
+                document: 0,
+                path: "replicas".to_string(),
+                old: serde_json::json!(1),
+                new: serde_json::json!(3),
+            },
+            ConfigChange::Removed {
+                document: 1,
+                path: "ports[1]".to_string(),
+                value: serde_json::json!(443),
+            },
+            ConfigChange::Added {
+                document: 2,
+                path: "".to_string(),
+                value: serde_json::json!({"name": "c"}),
+            },
+        ]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "~ [0] replicas: 1 -> 3\n- [1] ports[1]: 443\n+ [2] <root>: {\"name\":\"c\"}\n"
+    );
+    assert!(diff_programs(&args_a, &args_a).unwrap().is_empty());
+}
+
+#[test]
+fn test_override_impact() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list
+        .push("app = {name = \"app\", replicas = 1}\n".to_string());
+    args.overrides.push("app.name=\"web\"".to_string());
+    let impacts = override_impact(
+        &args,
+        &[
+            vec!["app.replicas=3".to_string()],
+            vec!["app.name=\"web\"".to_string()],
+            vec!["app.replicas=1".to_string(), "app.env=\"dev\"".to_string()],
+        ],
+    )
+    .unwrap();
+    assert_eq!(impacts.len(), 3);
+    assert!(impacts[0].is_changed());
+    assert_eq!(impacts[0].paths(), vec!["app.replicas"]);
+    // The override is already applied in the baseline.
+    assert!(!impacts[1].is_changed());
+    assert_eq!(impacts[2].specs.len(), 2);
+    assert_eq!(impacts[2].paths(), vec!["app.env"]);
+}
+
+#[test]
+fn test_override_impact_keeps_source_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("main.k");
+    let code = "app = {name = \"app\", replicas = 1}\n";
+    fs::write(&file, code).unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push(file.to_string_lossy().to_string());
+    args.overrides.push("app.name=\"web\"".to_string());
+    args.print_override_ast = true;
+    args.debug = 1;
+    let impacts = override_impact(&args, &[vec!["app.replicas=3".to_string()]]).unwrap();
+    assert_eq!(impacts[0].paths(), vec!["app.replicas"]);
+    assert_eq!(fs::read_to_string(&file).unwrap(), code);
+}
+
+#[test]
+fn test_exec_with_output_transforms() {
+    fn set_dev_env(mut value: kclvm_runtime::ValueRef) -> kclvm_runtime::ValueRef {
+        value.dict_update_key_value("env", kclvm_runtime::ValueRef::str("dev"));
+        value
+    }
+    fn prod_env(mut value: kclvm_runtime::ValueRef) -> kclvm_runtime::ValueRef {
+        if value.dict_get_value("env").is_some() {
+            value.dict_update_key_value("env", kclvm_runtime::ValueRef::str("prod"));
+        }
+        value
+    }
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push("main.k".to_string());
+    args.k_code_list.push("name = \"app\"\n".to_string());
+    args.register_output_transform(set_dev_env);
+    args.register_output_transform(prod_env);
+    let res = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(res.err_message.is_empty(), "{}", res.err_message);
+    // The transforms compose in the registration order.
+    assert_eq!(res.yaml_result, "name: app\nenv: prod");
+    assert_eq!(res.json_result, "{\"name\": \"app\", \"env\": \"prod\"}");
+}
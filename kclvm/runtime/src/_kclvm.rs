@@ -36,6 +36,7 @@ pub enum ApiFunc {
     kclvm_builtin_bool,
     kclvm_builtin_dict,
     kclvm_builtin_float,
+    kclvm_builtin_hash,
     kclvm_builtin_hex,
     kclvm_builtin_int,
     kclvm_builtin_isnullish,
@@ -87,6 +88,7 @@ pub enum ApiFunc {
     kclvm_builtin_str_title,
     kclvm_builtin_str_upper,
     kclvm_builtin_sum,
+    kclvm_builtin_template,
     kclvm_builtin_typeof,
     kclvm_builtin_zip,
     kclvm_config_attr_map,
@@ -155,6 +157,8 @@ pub enum ApiFunc {
     kclvm_file_mv,
     kclvm_file_read,
     kclvm_file_read_env,
+    kclvm_file_read_json,
+    kclvm_file_read_yaml,
     kclvm_file_size,
     kclvm_file_workdir,
     kclvm_file_write,
@@ -15,6 +15,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_bool" => crate::kclvm_builtin_bool as *const () as u64,
         "kclvm_builtin_dict" => crate::kclvm_builtin_dict as *const () as u64,
         "kclvm_builtin_float" => crate::kclvm_builtin_float as *const () as u64,
+        "kclvm_builtin_hash" => crate::kclvm_builtin_hash as *const () as u64,
         "kclvm_builtin_hex" => crate::kclvm_builtin_hex as *const () as u64,
         "kclvm_builtin_int" => crate::kclvm_builtin_int as *const () as u64,
         "kclvm_builtin_isnullish" => crate::kclvm_builtin_isnullish as *const () as u64,
@@ -70,6 +71,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_str_title" => crate::kclvm_builtin_str_title as *const () as u64,
         "kclvm_builtin_str_upper" => crate::kclvm_builtin_str_upper as *const () as u64,
         "kclvm_builtin_sum" => crate::kclvm_builtin_sum as *const () as u64,
+        "kclvm_builtin_template" => crate::kclvm_builtin_template as *const () as u64,
         "kclvm_builtin_typeof" => crate::kclvm_builtin_typeof as *const () as u64,
         "kclvm_builtin_zip" => crate::kclvm_builtin_zip as *const () as u64,
         "kclvm_config_attr_map" => crate::kclvm_config_attr_map as *const () as u64,
@@ -160,6 +162,8 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_file_mv" => crate::kclvm_file_mv as *const () as u64,
         "kclvm_file_read" => crate::kclvm_file_read as *const () as u64,
         "kclvm_file_read_env" => crate::kclvm_file_read_env as *const () as u64,
+        "kclvm_file_read_json" => crate::kclvm_file_read_json as *const () as u64,
+        "kclvm_file_read_yaml" => crate::kclvm_file_read_yaml as *const () as u64,
         "kclvm_file_size" => crate::kclvm_file_size as *const () as u64,
         "kclvm_file_workdir" => crate::kclvm_file_workdir as *const () as u64,
         "kclvm_file_write" => crate::kclvm_file_write as *const () as u64,
@@ -307,11 +307,47 @@ pub struct PanicInfo {
     pub is_warning: bool,
 }
 
+/// The source range and the outcome of an evaluated assert statement.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct AssertionRecord {
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+    pub end_line: u64,
+    pub end_column: u64,
+    /// Whether the assertion passed.
+    pub passed: bool,
+    /// The assertion error message, which is empty when it is not set in the assert statement.
+    pub message: String,
+}
+
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
 pub struct ContextConfig {
     pub debug_mode: bool,
     pub strict_range_check: bool,
     pub disable_schema_check: bool,
+    /// Whether to continue past failed `check:` conditions and collect
+    /// all of the failures instead of stopping at the first one.
+    pub collect_all_check_failures: bool,
+    /// The maximum depth of the function, schema and rule calls, which is unlimited by default.
+    pub max_depth: Option<usize>,
+    /// The maximum bytes of the JSON or YAML output, which is unlimited by default.
+    pub max_output_bytes: Option<usize>,
+    /// The maximum length of the list and dict values, which is unlimited by default.
+    pub max_collection_len: Option<usize>,
+    /// The maximum length of the string values, which is unlimited by default.
+    pub max_string_len: Option<usize>,
+    /// Whether to allow reading the data files under the workspace e.g., `file.read_json`.
+    pub allow_file_read: bool,
+    /// Whether to forbid all the side effects, see `ExecProgramArgs::sandbox`.
+    pub sandbox: bool,
+    /// Whether to record the schema attributes assigned by the schema configs.
+    pub record_attr_coverage: bool,
+    /// Whether to record every evaluated assertion and continue past the failed ones.
+    pub record_assertions: bool,
+    /// The `strict_range_check` option of each package keyed by the package path, which
+    /// is empty when no package overrides the global option.
+    pub pkg_strict_range_check: HashMap<String, bool>,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -330,6 +366,10 @@ impl Default for ContextBuffer {
     }
 }
 
+/// Output transform functions applied to the output value before it is planned
+/// into the JSON and YAML strings.
+pub type OutputTransform = fn(ValueRef) -> ValueRef;
+
 /// Plugin functions
 pub type PluginFunction =
     Arc<dyn Fn(&Context, &ValueRef, &ValueRef) -> anyhow::Result<ValueRef> + Send + Sync>;
@@ -367,10 +407,19 @@ pub struct Context {
     pub yaml_result: String,
     /// Panic information at runtime
     pub panic_info: PanicInfo,
+    /// Collected schema check failures when `collect_all_check_failures` is enabled.
+    pub check_failures: Vec<PanicInfo>,
+    /// The assignment count of each schema attribute when `record_attr_coverage` is enabled,
+    /// the first key is the schema runtime type and the second key is the attribute name.
+    pub attr_coverage: IndexMap<String, IndexMap<String, usize>>,
+    /// The evaluated assertions in the evaluation order when `record_assertions` is enabled.
+    pub assertions: Vec<AssertionRecord>,
     /// Planning options
     pub plan_opts: PlanOptions,
     /// Builtin plugin functions, the key of the map is the form <module_name>.<module_func> e.g., `hello.say_hello`
     pub plugin_functions: IndexMap<String, PluginFunction>,
+    /// Output transforms applied to the output value in order before it is planned.
+    pub output_transforms: Vec<OutputTransform>,
 }
 
 impl UnwindSafe for Context {}
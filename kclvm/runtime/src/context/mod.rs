@@ -89,6 +89,43 @@ impl crate::Context {
         self.panic_info.kcl_pkgpath = pkgpath.to_string();
     }
 
+    /// Switch the `strict_range_check` option to the one of the package `pkgpath` if set.
+    pub fn set_pkg_strict_range_check(&mut self, pkgpath: &str) {
+        let pkgpath = pkgpath.trim_start_matches(crate::PKG_PATH_PREFIX);
+        if let Some(strict_range_check) = self.cfg.pkg_strict_range_check.get(pkgpath) {
+            self.cfg.strict_range_check = *strict_range_check;
+        }
+    }
+
+    /// Raise a runtime error when the string length exceeds the `max_string_len` limit,
+    /// which is checked by all the string-producing operations and builtin functions.
+    pub fn check_string_len(&mut self, len: usize) {
+        if let Some(max_string_len) = self.cfg.max_string_len {
+            if len > max_string_len {
+                self.set_err_type(&crate::RuntimeErrorType::EvaluationError);
+                panic!("the string length {len} exceeds the limit of {max_string_len}");
+            }
+        }
+    }
+
+    /// Raise a runtime error when the collection length exceeds the `max_collection_len` limit.
+    pub fn check_collection_len(&mut self, len: usize) {
+        if let Some(max_collection_len) = self.cfg.max_collection_len {
+            if len > max_collection_len {
+                self.set_err_type(&crate::RuntimeErrorType::EvaluationError);
+                panic!("the collection length {len} exceeds the limit of {max_collection_len}");
+            }
+        }
+    }
+
+    /// Raise a runtime error when the side-effecting function `name` e.g., `file.read`
+    /// is called in the sandbox mode.
+    pub fn check_sandbox(&self, name: &str) {
+        if self.cfg.sandbox {
+            panic!("{name}() is not allowed in the sandbox mode");
+        }
+    }
+
     pub fn set_kcl_module_path(&mut self, module_path: &str) {
         self.module_path = module_path.to_string();
     }
@@ -227,6 +227,7 @@ pub extern "C-unwind" fn kclvm_crypto_uuid(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("crypto.uuid");
     return ValueRef::str(Uuid::new_v4().to_string().as_ref()).into_raw(ctx);
 }
 
@@ -240,6 +241,7 @@ pub extern "C-unwind" fn kclvm_crypto_filesha256(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("crypto.filesha256");
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         // Open the file
@@ -280,6 +282,7 @@ pub extern "C-unwind" fn kclvm_crypto_filesha512(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("crypto.filesha512");
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         let mut file = File::open(&filepath)
@@ -318,6 +321,7 @@ pub extern "C-unwind" fn kclvm_crypto_fileblake3(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("crypto.fileblake3");
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         let mut file = File::open(&filepath)
@@ -15,8 +15,9 @@ pub extern "C-unwind" fn kclvm_datetime_today(
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("datetime.today");
+    let s = Local::now();
     ValueRef::str(&(s.format("%Y-%m-%d %H:%M:%S").to_string() + "." + &s.timestamp().to_string()))
         .into_raw(ctx)
 }
@@ -31,8 +32,9 @@ pub extern "C-unwind" fn kclvm_datetime_now(
     args: *const kclvm_value_ref_t,
     kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("datetime.now");
+    let s = Local::now();
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let format = get_call_arg_str(args, kwargs, 0, Some("format"))
@@ -50,6 +52,7 @@ pub extern "C-unwind" fn kclvm_datetime_ticks(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("datetime.ticks");
     let x = Local::now().timestamp();
     ValueRef::float(x as f64).into_raw(ctx)
 }
@@ -63,8 +66,9 @@ pub extern "C-unwind" fn kclvm_datetime_date(
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
-    let s = Local::now();
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("datetime.date");
+    let s = Local::now();
     ValueRef::str(&s.format("%Y-%m-%d %H:%M:%S").to_string()).into_raw(ctx)
 }
 
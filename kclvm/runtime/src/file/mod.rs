@@ -19,8 +19,7 @@ pub extern "C-unwind" fn kclvm_file_read(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(x) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
-        let contents = fs::read_to_string(&x)
-            .unwrap_or_else(|e| panic!("failed to access the file '{}': {}", x, e));
+        let contents = read_data_file(ctx, "read", &x);
 
         let s = ValueRef::str(contents.as_ref());
         return s.into_raw(ctx);
@@ -39,6 +38,7 @@ pub extern "C-unwind" fn kclvm_file_glob(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.glob");
 
     let pattern = get_call_arg_str(args, kwargs, 0, Some("pattern"))
         .expect("glob() takes exactly one argument (0 given)");
@@ -62,6 +62,7 @@ pub extern "C-unwind" fn kclvm_file_modpath(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.modpath");
     let s = ValueRef::str(ctx.module_path.as_ref());
     s.into_raw(ctx)
 }
@@ -74,6 +75,7 @@ pub extern "C-unwind" fn kclvm_file_workdir(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.workdir");
     let s = ValueRef::str(ctx.workdir.as_ref());
     s.into_raw(ctx)
 }
@@ -87,6 +89,7 @@ pub extern "C-unwind" fn kclvm_file_current(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.current");
     let s = ValueRef::str(ctx.panic_info.kcl_file.as_ref());
     s.into_raw(ctx)
 }
@@ -104,6 +107,7 @@ pub extern "C-unwind" fn kclvm_file_exists(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.exists");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         let exist = Path::new(&path).exists();
@@ -125,6 +129,7 @@ pub extern "C-unwind" fn kclvm_file_abs(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.abs");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Ok(abs_path) = Path::new(&path).canonicalize() {
@@ -147,6 +152,7 @@ pub extern "C-unwind" fn kclvm_file_mkdir(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.mkdir");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("directory")) {
         let exists = get_call_arg_bool(args, kwargs, 1, Some("exists")).unwrap_or_default();
@@ -173,6 +179,7 @@ pub extern "C-unwind" fn kclvm_file_delete(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.delete");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Err(e) = fs::remove_file(&path) {
@@ -204,6 +211,7 @@ pub extern "C-unwind" fn kclvm_file_cp(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.cp");
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
@@ -241,6 +249,7 @@ pub extern "C-unwind" fn kclvm_file_mv(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.mv");
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
@@ -266,6 +275,7 @@ pub extern "C-unwind" fn kclvm_file_size(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.size");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         let metadata = fs::metadata(&path);
@@ -294,6 +304,7 @@ pub extern "C-unwind" fn kclvm_file_write(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.write");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
@@ -324,6 +335,7 @@ pub extern "C-unwind" fn kclvm_file_append(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.append");
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
@@ -357,6 +369,7 @@ pub extern "C-unwind" fn kclvm_file_read_env(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_sandbox("file.read_env");
 
     if let Some(key) = get_call_arg_str(args, kwargs, 0, Some("key")) {
         match std::env::var(key) {
@@ -367,3 +380,88 @@ pub extern "C-unwind" fn kclvm_file_read_env(
         panic!("read_env() requires 'key' argument");
     }
 }
+
+/// Read and parse a JSON file to a value. It is only allowed when the `allow_file_read`
+/// option is enabled, and the relative path is resolved from the module root.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_file_read_json(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        let contents = read_data_file(ctx, "read_json", &path);
+        match ValueRef::from_json(ctx, &contents) {
+            Ok(value) => value.into_raw(ctx),
+            Err(err) => panic!("failed to parse the JSON file '{}': {}", path, err),
+        }
+    } else {
+        panic!("read_json() requires 'filepath' argument");
+    }
+}
+
+/// Read and parse a YAML file to a value. It is only allowed when the `allow_file_read`
+/// option is enabled, and the relative path is resolved from the module root.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C-unwind" fn kclvm_file_read_yaml(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        let contents = read_data_file(ctx, "read_yaml", &path);
+        match ValueRef::from_yaml(ctx, &contents) {
+            Ok(value) => value.into_raw(ctx),
+            Err(err) => panic!("failed to parse the YAML file '{}': {}", path, err),
+        }
+    } else {
+        panic!("read_yaml() requires 'filepath' argument");
+    }
+}
+
+/// Read a data file for the function `name`. The file must be under the module root
+/// or the work directory to keep the evaluation hermetic.
+fn read_data_file(ctx: &Context, name: &str, path: &str) -> String {
+    ctx.check_sandbox(&format!("file.{name}"));
+    if !ctx.cfg.allow_file_read {
+        panic!(
+            "{}() is not allowed to read the file '{}', please enable the 'allow_file_read' option",
+            name, path
+        );
+    }
+    let roots: Vec<&String> = [&ctx.module_path, &ctx.workdir]
+        .into_iter()
+        .filter(|root| !root.is_empty())
+        .collect();
+    let filepath = match roots.first() {
+        Some(root) if Path::new(path).is_relative() => Path::new(root).join(path),
+        _ => Path::new(path).to_path_buf(),
+    };
+    let filepath = filepath
+        .canonicalize()
+        .unwrap_or_else(|e| panic!("failed to access the file '{}': {}", path, e));
+    let allowed = roots.iter().any(|root| {
+        Path::new(root)
+            .canonicalize()
+            .map(|root| filepath.starts_with(root))
+            .unwrap_or_default()
+    });
+    if !allowed {
+        panic!(
+            "{}() can not read the file '{}' outside the workspace",
+            name, path
+        );
+    }
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("failed to access the file '{}': {}", path, e))
+}
@@ -82,6 +82,7 @@ pub extern "C-unwind" fn kclvm_manifests_yaml_stream(
                         .get_by_key("sep")
                         .unwrap_or_else(|| ValueRef::str("---"))
                         .as_str(),
+                    ..Default::default()
                 }
             } else {
                 panic!(
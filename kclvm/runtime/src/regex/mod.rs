@@ -53,6 +53,7 @@ pub extern "C-unwind" fn kclvm_regex_replace(
                 let count = get_call_arg_int(args, kwargs, 3, Some("count")).unwrap_or_else(|| 0);
                 let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
                 let s = re.replacen(string.as_ref(), count as usize, replace.as_ref() as &str);
+                ctx.check_string_len(s.len());
                 return ValueRef::str(&s).into_raw(ctx);
             }
             panic!("replace() missing the required positional argument: 'replace'");
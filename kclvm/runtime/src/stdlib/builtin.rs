@@ -628,6 +628,66 @@ pub fn type_of(x: &ValueRef, full_name: &ValueRef) -> ValueRef {
     return ValueRef::str(x.type_str().as_str());
 }
 
+/// Return the SHA-256 hex digest of the canonical serialization of the value,
+/// which is stable for the same content regardless of the key order.
+pub fn hash(x: &ValueRef) -> ValueRef {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write;
+
+    let mut hasher = Sha256::new();
+    hasher.update(x.to_canonical_json_string().as_bytes());
+    let mut hex = String::with_capacity(2 * Sha256::output_size());
+    for byte in hasher.finalize() {
+        let _ = write!(&mut hex, "{byte:02x}");
+    }
+    ValueRef::str(&hex)
+}
+
+/// Substitute the `${key}` placeholders in the template with the values of the
+/// dict, in which `$$` is an escaped literal `$` and a `$` not followed by `{` or
+/// `$` is kept as it is. The string values are substituted as they are and other
+/// values are converted like `str()`. A missing key is an error unless the default
+/// value is given. In KCL code, the template should be a raw string e.g.,
+/// `r"${name}"` or escape the placeholders e.g., `"\${name}"`, otherwise the
+/// placeholders are evaluated as the string interpolation.
+pub fn template(tmpl: &str, vars: &ValueRef, default: Option<&ValueRef>) -> ValueRef {
+    let mut result = String::with_capacity(tmpl.len());
+    let mut rest = tmpl;
+    while let Some(index) = rest.find('$') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = match after.find('}') {
+                Some(end) => end,
+                None => panic!("template() unterminated placeholder in '{tmpl}'"),
+            };
+            let key = &after[..end];
+            if key.is_empty() {
+                panic!("template() empty placeholder name in '{tmpl}'");
+            }
+            let value = match vars.dict_get_value(key) {
+                Some(value) => value,
+                None => match default {
+                    Some(default) => default.clone(),
+                    None => panic!("template() missing the value of the placeholder '{key}'"),
+                },
+            };
+            match &*value.rc.borrow() {
+                Value::str_value(s) => result.push_str(s),
+                _ => result.push_str(&value.to_string()),
+            }
+            rest = &after[end + 1..];
+        } else {
+            result.push('$');
+        }
+    }
+    result.push_str(rest);
+    ValueRef::str(&result)
+}
+
 #[cfg(test)]
 mod test_builtin {
 
@@ -640,6 +700,43 @@ mod test_builtin {
         });
     }
 
+    #[test]
+    fn test_hash() {
+        let mut ctx = Context::new();
+        let x = ValueRef::from_json(&mut ctx, r#"{"a": 1, "b": [1.0, "c"]}"#).unwrap();
+        let y = ValueRef::from_json(&mut ctx, r#"{"b": [1, "c"], "a": 1.0}"#).unwrap();
+        let z = ValueRef::from_json(&mut ctx, r#"{"a": 2, "b": [1, "c"]}"#).unwrap();
+        let hash_x = builtin::hash(&x).as_str();
+        assert_eq!(hash_x.len(), 64);
+        assert_eq!(hash_x, builtin::hash(&y).as_str());
+        assert_ne!(hash_x, builtin::hash(&z).as_str());
+    }
+
+    #[test]
+    fn test_template() {
+        let vars = ValueRef::dict(Some(&[
+            ("name", &ValueRef::str("app")),
+            ("replicas", &ValueRef::int(3)),
+        ]));
+        assert_eq!(
+            builtin::template("${name}-${replicas} costs $$5 $x", &vars, None).as_str(),
+            "app-3 costs $5 $x"
+        );
+        assert_eq!(
+            builtin::template("${name}-${env}", &vars, Some(&ValueRef::str("dev"))).as_str(),
+            "app-dev"
+        );
+        assert_panic(
+            "template() missing the value of the placeholder 'env'",
+            || {
+                let _ = builtin::template("${env}", &ValueRef::dict(None), None);
+            },
+        );
+        assert_panic("template() unterminated placeholder in '${name'", || {
+            let _ = builtin::template("${name", &ValueRef::dict(None), None);
+        });
+    }
+
     #[test]
     fn test_pow() {
         assert_eq!(
@@ -660,16 +660,26 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_range(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
-    match get_call_arg(args, kwargs, 0, Some("start")) {
+    let (start, stop, step) = match get_call_arg(args, kwargs, 0, Some("start")) {
         Some(arg0) => match get_call_arg(args, kwargs, 1, Some("stop")) {
             Some(arg1) => match get_call_arg(args, kwargs, 2, Some("step")) {
-                Some(arg2) => builtin::range(&arg0, &arg1, &arg2).into_raw(ctx_ref),
-                _ => builtin::range(&arg0, &arg1, &ValueRef::int(1)).into_raw(ctx_ref),
+                Some(arg2) => (arg0, arg1, arg2),
+                _ => (arg0, arg1, ValueRef::int(1)),
             },
-            _ => builtin::range(&ValueRef::int(0), &arg0, &ValueRef::int(1)).into_raw(ctx_ref),
+            _ => (ValueRef::int(0), arg0, ValueRef::int(1)),
         },
-        _ => kclvm_value_Undefined(ctx),
+        _ => return kclvm_value_Undefined(ctx),
+    };
+    if let (Value::int_value(start), Value::int_value(stop), Value::int_value(step)) =
+        (&*start.rc.borrow(), &*stop.rc.borrow(), &*step.rc.borrow())
+    {
+        if *step != 0 {
+            let len = (*stop as i128 - *start as i128 + *step as i128 - step.signum() as i128)
+                / *step as i128;
+            ctx_ref.check_collection_len(len.max(0) as usize);
+        }
     }
+    builtin::range(&start, &stop, &step).into_raw(ctx_ref)
 }
 
 /// Return `True` if the input value is `None` or `Undefined`, and `False` otherwise.
@@ -689,3 +699,53 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_isnullish(
     }
     panic!("is_nullable() takes exactly one argument (0 given)");
 }
+
+/// Return the SHA-256 hex digest of the canonical serialization of the value.
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_hash(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
+        return builtin::hash(&arg0).into_raw(ctx);
+    }
+    panic!("hash() takes exactly one argument (0 given)");
+}
+
+/// Substitute the `${key}` placeholders in the template with the values of the dict.
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C-unwind" fn kclvm_builtin_template(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *mut kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    let tmpl = match get_call_arg(args, kwargs, 0, Some("tmpl")) {
+        Some(tmpl) if tmpl.is_str() => tmpl.as_str(),
+        Some(tmpl) => panic!(
+            "template() argument 'tmpl' must be str, not {}",
+            tmpl.type_str()
+        ),
+        None => panic!("template() missing 2 required positional arguments: 'tmpl' and 'vars'"),
+    };
+    let vars = match get_call_arg(args, kwargs, 1, Some("vars")) {
+        Some(vars) if vars.is_dict() => vars,
+        Some(vars) => panic!(
+            "template() argument 'vars' must be dict, not {}",
+            vars.type_str()
+        ),
+        None => panic!("template() missing 1 required positional argument: 'vars'"),
+    };
+    let default = get_call_arg(args, kwargs, 2, Some("default"));
+    builtin::template(&tmpl, &vars, default.as_ref()).into_raw(ctx)
+}
@@ -51,6 +51,7 @@ pub unsafe extern "C-unwind" fn kclvm_plugin_invoke(
 ) -> *const kclvm_value_ref_t {
     let ctx_ref = mut_ptr_as_ref(ctx);
     let method_ref = c2str(method);
+    ctx_ref.check_sandbox(method_ref);
     let plugin_short_method = match method_ref.strip_prefix(PLUGIN_MODULE_PREFIX) {
         Some(s) => s,
         None => method_ref,
@@ -2599,7 +2599,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_lower(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_lower().into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_lower();
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_lower");
     }
@@ -2614,7 +2617,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_upper(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_upper().into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_upper();
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_upper");
     }
@@ -2629,7 +2635,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_capitalize(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_capitalize().into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_capitalize();
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_capitalize");
     }
@@ -2726,7 +2735,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_format(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     if let Some(val) = args.pop_arg_first() {
-        val.str_format(args, kwargs).into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_format(args, kwargs);
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_format");
     }
@@ -2869,7 +2881,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_join(
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
         let iter = args.arg_i(0).unwrap();
-        val.str_join(&iter).into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_join(&iter);
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_join");
     }
@@ -2919,8 +2934,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_replace(
         let old = args.arg_i(0).expect("expect 1 argument, found 0");
         let new = args.arg_i(1).expect("expect 2 arguments, found 1");
         let count = args.arg_i(2);
-        val.str_replace(&old, &new, count.as_ref())
-            .into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_replace(&old, &new, count.as_ref());
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_replace");
     }
@@ -3131,7 +3148,10 @@ pub unsafe extern "C-unwind" fn kclvm_builtin_str_title(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     if let Some(val) = args.pop_arg_first() {
-        val.str_title().into_raw(mut_ptr_as_ref(ctx))
+        let ctx = mut_ptr_as_ref(ctx);
+        let value = val.str_title();
+        ctx.check_string_len(value.str_len());
+        value.into_raw(ctx)
     } else {
         panic!("invalid self value in str_title");
     }
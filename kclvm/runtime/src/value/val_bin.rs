@@ -38,12 +38,14 @@ impl ValueRef {
             }
 
             (Value::str_value(a), Value::str_value(b)) => {
+                ctx.check_string_len(a.len() + b.len());
                 Self::str(format!("{}{}", *a, *b).as_ref())
             }
             (Value::list_value(a), _) => {
                 if x.is_list() {
-                    let mut list = a.clone();
                     let b = x.as_list_ref();
+                    ctx.check_collection_len(a.values.len() + b.values.len());
+                    let mut list = a.clone();
                     for x in b.values.iter() {
                         list.values.push(x.clone());
                     }
@@ -129,9 +131,13 @@ impl ValueRef {
                 Self::float(*a * *b as f64)
             }
 
-            (Value::str_value(a), Value::int_value(b)) => Self::str(a.repeat(*b as usize).as_ref()),
-            (Value::int_value(b), Value::str_value(a)) => Self::str(a.repeat(*b as usize).as_ref()),
+            (Value::str_value(a), Value::int_value(b))
+            | (Value::int_value(b), Value::str_value(a)) => {
+                ctx.check_string_len(a.len().saturating_mul((*b).max(0) as usize));
+                Self::str(a.repeat(*b as usize).as_ref())
+            }
             (Value::list_value(a), Value::int_value(b)) => {
+                ctx.check_collection_len(a.values.len().saturating_mul((*b).max(0) as usize));
                 let mut list = ListValue::default();
                 for _ in 0..(*b as usize) {
                     for x in a.values.iter() {
@@ -141,6 +147,7 @@ impl ValueRef {
                 Self::from(Value::list_value(Box::new(list)))
             }
             (Value::int_value(b), Value::list_value(a)) => {
+                ctx.check_collection_len(a.values.len().saturating_mul((*b).max(0) as usize));
                 let mut list = ListValue::default();
                 for _ in 0..(*b as usize) {
                     for x in a.values.iter() {
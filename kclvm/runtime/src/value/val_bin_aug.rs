@@ -40,11 +40,13 @@ impl ValueRef {
                 true
             }
             (Value::str_value(a), Value::str_value(b)) => {
+                ctx.check_string_len(a.len() + b.len());
                 *a = format!("{}{}", *a, *b);
                 true
             }
             (Value::list_value(a), _) => match &*x.rc.borrow() {
                 Value::list_value(ref b) => {
+                    ctx.check_collection_len(a.values.len() + b.values.len());
                     for x in b.values.iter() {
                         a.values.push(x.clone());
                     }
@@ -143,11 +145,15 @@ impl ValueRef {
                 true
             }
             (Value::str_value(a), Value::int_value(b)) => {
+                ctx.check_string_len(a.len().saturating_mul((*b).max(0) as usize));
                 *a = a.repeat(*b as usize);
                 true
             }
             (Value::list_value(list), _) => match &*x.rc.borrow() {
                 Value::int_value(ref b) => {
+                    ctx.check_collection_len(
+                        list.values.len().saturating_mul((*b).max(0) as usize),
+                    );
                     let n = list.values.len();
                     for _ in 1..(*b as usize) {
                         for i in 0..n {
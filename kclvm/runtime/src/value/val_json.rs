@@ -18,12 +18,69 @@ macro_rules! tri {
     };
 }
 
+pub(crate) const LIMIT_EXCEEDED_MSG: &str = "the output exceeds the limit";
+
+/// LimitedWriter counts the bytes written to the inner writer and fails once the
+/// total exceeds the limit, thus an output exceeding the limit is never fully
+/// encoded in the memory.
+pub(crate) struct LimitedWriter<W> {
+    inner: W,
+    written: usize,
+    limit: Option<usize>,
+    exceeded: bool,
+}
+
+impl<W> LimitedWriter<W> {
+    pub(crate) fn new(inner: W, limit: Option<usize>) -> Self {
+        Self {
+            inner,
+            written: 0,
+            limit,
+            exceeded: false,
+        }
+    }
+
+    /// Whether the writing failed because the limit was exceeded.
+    #[inline]
+    pub(crate) fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+
+    #[inline]
+    pub(crate) fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: std::io::Write> std::io::Write for LimitedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(limit) = self.limit {
+            if self.written + buf.len() > limit {
+                self.exceeded = true;
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    LIMIT_EXCEEDED_MSG,
+                ));
+            }
+        }
+        let n = self.inner.write(buf)?;
+        self.written += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct JsonEncodeOptions {
     pub sort_keys: bool,
     pub indent: i64,
     pub ignore_private: bool,
     pub ignore_none: bool,
+    /// Emit the integral unit values e.g., `1Ki` as integers instead of floats.
+    pub strict_number: bool,
 }
 
 struct JsonFormatter {
@@ -186,6 +243,33 @@ impl<'de> Deserialize<'de> for JsonValue {
     }
 }
 
+impl JsonValue {
+    /// Normalize all the integral float numbers into integers.
+    fn canonicalize(self) -> JsonValue {
+        match self {
+            JsonValue::Number(n) => match n.as_f64() {
+                Some(f)
+                    if !n.is_i64()
+                        && !n.is_u64()
+                        && f.fract() == 0.0
+                        && f >= i64::MIN as f64
+                        && f <= i64::MAX as f64 =>
+                {
+                    JsonValue::Number(serde_json::Number::from(f as i64))
+                }
+                _ => JsonValue::Number(n),
+            },
+            JsonValue::Array(v) => {
+                JsonValue::Array(v.into_iter().map(|v| v.canonicalize()).collect())
+            }
+            JsonValue::Object(m) => {
+                JsonValue::Object(m.into_iter().map(|(k, v)| (k, v.canonicalize())).collect())
+            }
+            v => v,
+        }
+    }
+}
+
 impl Serialize for JsonValue {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -426,10 +510,44 @@ impl ValueRef {
     }
 
     pub fn to_json_string_with_options(&self, opts: &JsonEncodeOptions) -> String {
+        self.to_json_string_with_limit(opts, None).expect(LIMIT_EXCEEDED_MSG)
+    }
+
+    /// Encode the value to the JSON string with the options, the serialization stops
+    /// as soon as the encoded bytes exceed the limit and `None` is returned.
+    pub fn to_json_string_with_limit(
+        &self,
+        opts: &JsonEncodeOptions,
+        limit: Option<usize>,
+    ) -> Option<String> {
         let json = self.build_json(opts);
         let formatter = JsonFormatter::with_indent(opts.indent);
+        let mut writer = LimitedWriter::new(Vec::with_capacity(128), limit);
+        let result = {
+            let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+            json.serialize(&mut serializer)
+        };
+        if let Err(err) = result {
+            if writer.exceeded() {
+                return None;
+            }
+            panic!("{}", err)
+        }
+        Some(writer.into_inner().to_str().unwrap().to_string())
+    }
+
+    /// Return the canonical JSON string of the value with sorted keys and normalized
+    /// numbers e.g., `1.0` is encoded as `1`, thus the same content always results
+    /// in the same string.
+    pub fn to_canonical_json_string(&self) -> String {
+        let json = self
+            .build_json(&JsonEncodeOptions {
+                sort_keys: true,
+                ..Default::default()
+            })
+            .canonicalize();
         let mut writer = Vec::with_capacity(128);
-        let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+        let mut serializer = serde_json::Serializer::new(&mut writer);
         json.serialize(&mut serializer).unwrap();
         writer.to_str().unwrap().to_string()
     }
@@ -457,6 +575,14 @@ impl ValueRef {
             },
             // The number_multiplier is still a number, if we want to get the string form, we can
             // use the `str` function e.g. `str(1Mi)`
+            crate::Value::unit_value(ref v, ..)
+                if opts.strict_number
+                    && v.fract() == 0.0
+                    && *v >= i64::MIN as f64
+                    && *v < i64::MAX as f64 =>
+            {
+                JsonValue::Number(serde_json::Number::from(*v as i64))
+            }
             crate::Value::unit_value(ref v, ..) => match serde_json::Number::from_f64(*v) {
                 Some(n) => JsonValue::Number(n),
                 None => JsonValue::Null,
@@ -555,6 +681,26 @@ impl ValueRef {
 mod test_value_json {
     use crate::*;
 
+    #[test]
+    fn test_value_to_json_string_with_limit() {
+        let value = ValueRef::dict(Some(&[("a", &ValueRef::list_int(&[1, 2, 3]))]));
+        let opts = JsonEncodeOptions::default();
+        let json = value.to_json_string_with_options(&opts);
+        assert_eq!(
+            value.to_json_string_with_limit(&opts, Some(json.len())),
+            Some(json.clone())
+        );
+        assert_eq!(value.to_json_string_with_limit(&opts, Some(json.len() - 1)), None);
+        assert_eq!(value.to_json_string_with_limit(&opts, None), Some(json));
+        let yaml_opts = YamlEncodeOptions::default();
+        let yaml = value.to_yaml_string_with_options(&yaml_opts);
+        assert_eq!(
+            value.to_yaml_string_with_limit(&yaml_opts, Some(yaml.len())),
+            Some(yaml.clone())
+        );
+        assert_eq!(value.to_yaml_string_with_limit(&yaml_opts, Some(4)), None);
+    }
+
     #[test]
     fn test_value_from_correct_json() {
         let mut ctx = Context::new();
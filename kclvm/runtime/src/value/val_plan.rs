@@ -22,8 +22,15 @@ pub struct PlanOptions {
     pub disable_empty_list: bool,
     /// Filter planned value with the path selector.
     pub query_paths: Vec<String>,
+    /// Only emit the named top-level variables, which is applied before the path selector.
+    pub include_vars: Vec<String>,
+    /// Never emit the named top-level variables e.g., the injected inputs.
+    pub exclude_vars: Vec<String>,
     /// YAML plan separator string, default is `---`.
     pub sep: Option<String>,
+    /// Emit the integral unit values e.g., `1Ki` as integers instead of floats, while
+    /// the floats always keep the fractional form e.g., `1.0`.
+    pub strict_number: bool,
 }
 
 /// Filter list or config results with context options.
@@ -201,23 +208,59 @@ pub fn type_of(v: &ValueRef, full_name: bool) -> String {
     builtin::type_of(v, &ValueRef::bool(full_name)).as_str()
 }
 
+/// Raise a runtime error when the output size exceeds the `max_output_bytes` limit.
+fn check_output_size(ctx: &Context, size: usize) {
+    if let Some(max_output_bytes) = ctx.cfg.max_output_bytes {
+        if size > max_output_bytes {
+            output_size_exceeded(ctx);
+        }
+    }
+}
+
+fn output_size_exceeded(ctx: &Context) -> ! {
+    panic!(
+        "the output size exceeds the limit of {} bytes",
+        ctx.cfg.max_output_bytes.unwrap_or_default()
+    );
+}
+
+/// Get the bytes left in the `max_output_bytes` limit after the used bytes, which
+/// bounds the serialization of the next output document.
+fn remaining_output_bytes(ctx: &Context, used: usize) -> Option<usize> {
+    ctx.cfg
+        .max_output_bytes
+        .map(|max_output_bytes| max_output_bytes.saturating_sub(used))
+}
+
 impl ValueRef {
     /// Plan the value to JSON and YAML strings.
     pub fn plan(&self, ctx: &Context) -> (String, String) {
         // Encoding options
         let json_opts = JsonEncodeOptions {
             sort_keys: ctx.plan_opts.sort_keys,
+            strict_number: ctx.plan_opts.strict_number,
             ..Default::default()
         };
         let yaml_opts = YamlEncodeOptions {
             sort_keys: ctx.plan_opts.sort_keys,
+            strict_number: ctx.plan_opts.strict_number,
             ..Default::default()
         };
+        // Apply the output transforms in the registration order
+        let value = ctx
+            .output_transforms
+            .iter()
+            .fold(self.clone(), |value, transform| transform(value));
+        // Filter values with top-level variable names
+        let value = value
+            .filter_by_vars(&ctx.plan_opts.include_vars)
+            .exclude_vars(&ctx.plan_opts.exclude_vars);
         // Filter values with query paths
         let value = if ctx.plan_opts.query_paths.is_empty() {
-            self.clone()
+            value
         } else {
-            self.filter_by_path(&ctx.plan_opts.query_paths)
+            value
+                .filter_by_path(&ctx.plan_opts.query_paths)
                 .unwrap_or_else(|e| panic!("{e}"))
         };
         if value.is_list_or_config() {
@@ -228,28 +271,82 @@ impl ValueRef {
                 .clone()
                 .unwrap_or_else(|| "---".to_string());
             // Plan YAML result
+            let mut yaml_size = 0;
             let yaml_result = results
                 .iter()
                 .map(|r| {
-                    r.to_yaml_string_with_options(&yaml_opts)
+                    // The trailing newline is stripped, thus not counted in the limit.
+                    let limit = remaining_output_bytes(ctx, yaml_size).map(|limit| limit + 1);
+                    let result = r
+                        .to_yaml_string_with_limit(&yaml_opts, limit)
+                        .unwrap_or_else(|| output_size_exceeded(ctx))
                         .strip_suffix('\n')
                         .unwrap()
-                        .to_string()
+                        .to_string();
+                    yaml_size += result.len() + sep.len() + 2;
+                    check_output_size(ctx, yaml_size);
+                    result
                 })
                 .collect::<Vec<String>>()
                 .join(&format!("\n{}\n", sep));
             // Plan JSON result
+            let mut json_size = 0;
             let json_result = results
                 .iter()
-                .map(|r| r.to_json_string_with_options(&json_opts))
+                .map(|r| {
+                    let result = r
+                        .to_json_string_with_limit(
+                            &json_opts,
+                            remaining_output_bytes(ctx, json_size),
+                        )
+                        .unwrap_or_else(|| output_size_exceeded(ctx));
+                    json_size += result.len() + JSON_STREAM_SEP.len();
+                    check_output_size(ctx, json_size);
+                    result
+                })
                 .collect::<Vec<String>>()
                 .join(JSON_STREAM_SEP);
             (json_result, yaml_result)
         } else {
-            (
-                value.to_json_string_with_options(&json_opts),
-                value.to_yaml_string_with_options(&yaml_opts),
-            )
+            let limit = ctx.cfg.max_output_bytes;
+            let json_result = value
+                .to_json_string_with_limit(&json_opts, limit)
+                .unwrap_or_else(|| output_size_exceeded(ctx));
+            let yaml_result = value
+                .to_yaml_string_with_limit(&yaml_opts, limit)
+                .unwrap_or_else(|| output_size_exceeded(ctx));
+            (json_result, yaml_result)
+        }
+    }
+
+    /// Keep only the top-level keys in the variable names. When the names are
+    /// empty or the value is not a config, return the value itself.
+    pub fn filter_by_vars(&self, vars: &[String]) -> ValueRef {
+        if self.is_config() && !vars.is_empty() {
+            let mut result = ValueRef::dict(None);
+            for (key, value) in &self.as_dict_ref().values {
+                if vars.contains(key) {
+                    result.dict_update_key_value(key, value.clone());
+                }
+            }
+            result
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Remove the top-level variables with the given names.
+    pub fn exclude_vars(&self, vars: &[String]) -> ValueRef {
+        if self.is_config() && !vars.is_empty() {
+            let mut result = ValueRef::dict(None);
+            for (key, value) in &self.as_dict_ref().values {
+                if !vars.contains(key) {
+                    result.dict_update_key_value(key, value.clone());
+                }
+            }
+            result
+        } else {
+            self.clone()
         }
     }
 
@@ -371,6 +468,24 @@ mod test_value_plan {
         );
     }
 
+    #[test]
+    fn test_filter_by_vars() {
+        let dict = ValueRef::dict_int(&[("k1", 1), ("k2", 2), ("k3", 3)]);
+        assert_eq!(dict.filter_by_vars(&[]), dict);
+        assert_eq!(
+            dict.filter_by_vars(&["k3".to_string(), "k1".to_string()]),
+            ValueRef::dict_int(&[("k1", 1), ("k3", 3)]),
+        );
+        assert_eq!(
+            dict.filter_by_vars(&["err_var".to_string()]),
+            ValueRef::dict(None),
+        );
+        assert_eq!(
+            ValueRef::int(1).filter_by_vars(&["k1".to_string()]),
+            ValueRef::int(1)
+        );
+    }
+
     #[test]
     fn test_value_plan_with_options() {
         let mut ctx = Context::new();
@@ -442,5 +557,40 @@ mod test_value_plan {
         let (json_string, yaml_string) = config.plan(&ctx);
         assert_eq!(json_string, "{}");
         assert_eq!(yaml_string, "{}");
+
+        ctx.plan_opts.query_paths = vec![];
+        ctx.plan_opts.include_vars = vec!["data_with_pkg".to_string()];
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            json_string,
+            "{\"data_with_pkg\": {\"_type\": \"pkg.Data\"}}"
+        );
+        assert_eq!(yaml_string, "data_with_pkg:\n  _type: pkg.Data");
+
+        ctx.plan_opts.include_vars = vec![];
+        ctx.plan_opts.exclude_vars = vec!["data".to_string(), "data_with_pkg".to_string()];
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(json_string, "{\"_hidden\": 1}");
+        assert_eq!(yaml_string, "_hidden: 1");
+    }
+
+    #[test]
+    fn test_value_plan_with_strict_number() {
+        let mut ctx = Context::new();
+        let mut config = ValueRef::dict(None);
+        config.dict_update_key_value("int", ValueRef::int(1));
+        config.dict_update_key_value("float", ValueRef::float(1.0));
+        config.dict_update_key_value("unit", ValueRef::unit(1024.0, 1, "Ki"));
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            json_string,
+            "{\"int\": 1, \"float\": 1.0, \"unit\": 1024.0}"
+        );
+        assert_eq!(yaml_string, "int: 1\nfloat: 1.0\nunit: 1024.0");
+
+        ctx.plan_opts.strict_number = true;
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(json_string, "{\"int\": 1, \"float\": 1.0, \"unit\": 1024}");
+        assert_eq!(yaml_string, "int: 1\nfloat: 1.0\nunit: 1024");
     }
 }
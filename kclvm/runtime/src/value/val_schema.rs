@@ -37,6 +37,12 @@ pub fn schema_config_meta(filename: &str, line: u64, column: u64) -> ValueRef {
 
 pub fn schema_assert(ctx: &mut Context, value: &ValueRef, msg: &str, config_meta: &ValueRef) {
     if !value.is_truthy() {
+        // Record the failure with its location and continue the evaluation.
+        let prev_panic_info = if ctx.cfg.collect_all_check_failures {
+            Some(ctx.panic_info.clone())
+        } else {
+            None
+        };
         ctx.set_err_type(&RuntimeErrorType::SchemaCheckFailure);
         if let Some(config_meta_file) = config_meta.get_by_key(CONFIG_META_FILENAME) {
             let config_meta_line = config_meta.get_by_key(CONFIG_META_LINE).unwrap();
@@ -59,6 +65,13 @@ pub fn schema_assert(ctx: &mut Context, value: &ValueRef, msg: &str, config_meta
         );
         ctx.set_kcl_location_info(Some(arg_msg.as_str()), None, None, None);
 
+        if let Some(prev_panic_info) = prev_panic_info {
+            let mut failure = std::mem::replace(&mut ctx.panic_info, prev_panic_info);
+            failure.message = msg.to_string();
+            ctx.check_failures.push(failure);
+            return;
+        }
+
         panic!("{}", msg);
     }
 }
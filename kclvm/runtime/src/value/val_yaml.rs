@@ -7,6 +7,8 @@ use crate::*;
 
 use serde::{Deserialize, Serialize};
 
+use super::val_json::{LimitedWriter, LIMIT_EXCEEDED_MSG};
+
 /// YAML encode options.
 /// - sort_keys: Sort the encode result by keys (defaults to false).
 /// - ignore_private: Whether to ignore the attribute whose name starts with
@@ -29,6 +31,8 @@ pub struct YamlEncodeOptions {
     pub ignore_private: bool,
     pub ignore_none: bool,
     pub sep: String,
+    /// Emit the integral unit values e.g., `1Ki` as integers instead of floats.
+    pub strict_number: bool,
 }
 
 impl Default for YamlEncodeOptions {
@@ -38,6 +42,7 @@ impl Default for YamlEncodeOptions {
             ignore_private: false,
             ignore_none: false,
             sep: "---".to_string(),
+            strict_number: false,
         }
     }
 }
@@ -104,23 +109,38 @@ impl ValueRef {
     }
 
     pub fn to_yaml_string_with_options(&self, opts: &YamlEncodeOptions) -> String {
+        self.to_yaml_string_with_limit(opts, None).expect(LIMIT_EXCEEDED_MSG)
+    }
+
+    /// Encode the value to the YAML string with the options, the serialization stops
+    /// as soon as the encoded bytes exceed the limit and `None` is returned.
+    pub fn to_yaml_string_with_limit(
+        &self,
+        opts: &YamlEncodeOptions,
+        limit: Option<usize>,
+    ) -> Option<String> {
         // convert Value to json in order to reuse
-        // "crate::val_json::JsonValue" to customize the serialized results
+        // "crate::val_json::JsonValue" to customize the serialized results,
+        // the compact JSON is never longer than the YAML, so the same limit applies.
         let json_opts = JsonEncodeOptions {
             sort_keys: opts.sort_keys,
             indent: 0,
             ignore_private: opts.ignore_private,
             ignore_none: opts.ignore_none,
+            strict_number: opts.strict_number,
         };
-        let json = self.to_json_string_with_options(&json_opts);
+        let json = self.to_json_string_with_limit(&json_opts, limit)?;
         let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
-        match serde_yaml::to_string(&yaml_value) {
-            Ok(s) => {
-                let s = s.strip_prefix("---\n").unwrap_or_else(|| s.as_ref());
-                s.to_string()
+        let mut writer = LimitedWriter::new(Vec::with_capacity(128), limit);
+        if let Err(err) = serde_yaml::to_writer(&mut writer, &yaml_value) {
+            if writer.exceeded() {
+                return None;
             }
-            Err(err) => panic!("{}", err),
+            panic!("{}", err)
         }
+        let s = String::from_utf8(writer.into_inner()).unwrap();
+        let s = s.strip_prefix("---\n").unwrap_or_else(|| s.as_ref());
+        Some(s.to_string())
     }
 }
 
@@ -256,6 +276,7 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    strict_number: false,
                 },
             ),
             (
@@ -266,6 +287,7 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    strict_number: false,
                 },
             ),
             (
@@ -276,6 +298,7 @@ mod test_value_yaml {
                     ignore_private: true,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    strict_number: false,
                 },
             ),
             (
@@ -286,6 +309,7 @@ mod test_value_yaml {
                     ignore_private: true,
                     ignore_none: true,
                     sep: "---".to_string(),
+                    strict_number: false,
                 },
             ),
             (
@@ -299,6 +323,7 @@ mod test_value_yaml {
                     ignore_private: false,
                     ignore_none: false,
                     sep: "---".to_string(),
+                    strict_number: false,
                 },
             ),
         ];
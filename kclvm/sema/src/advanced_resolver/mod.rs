@@ -1506,11 +1506,109 @@ mod tests {
                 column: Some(*col),
             };
             let scope_ref = gs.look_up_scope(&pos).unwrap();
+            assert_eq!(gs.get_scopes().innermost_scope_at(&pos).unwrap(), scope_ref);
             let all_defs = gs.get_all_defs_in_scope(scope_ref, &pos).unwrap();
             assert_eq!(all_defs.len(), *def_num)
         }
     }
 
+    #[test]
+    fn test_get_all_defs_of_kind() {
+        let sess = Arc::new(ParseSession::default());
+
+        let path = "src/advanced_resolver/test_data/schema_symbols.k"
+            .to_string()
+            .replace("/", &std::path::MAIN_SEPARATOR.to_string());
+        let mut program = load_program(sess.clone(), &[&path], None, None)
+            .unwrap()
+            .program;
+        let mut gs = GlobalState::default();
+        Namer::find_symbols(&program, &mut gs);
+        let node_ty_map = resolver::resolve_program(&mut program).node_ty_map;
+        AdvancedResolver::resolve_program(&program, &mut gs, node_ty_map).unwrap();
+
+        let scopes = gs.get_scopes();
+        let main_pkg_root_scope = scopes.get_root_scope(MAIN_PKG.to_string()).unwrap();
+        let root = scopes.get_scope(&main_pkg_root_scope).unwrap();
+        let module_info = gs.get_packages().get_module_info(root.get_filename());
+        let all_defs = root.get_all_defs(scopes, gs.get_symbols(), module_info, false, true);
+        let schema_defs = root.get_all_defs_of_kind(
+            SymbolKind::Schema,
+            scopes,
+            gs.get_symbols(),
+            module_info,
+            false,
+            true,
+        );
+        assert!(!schema_defs.is_empty());
+        assert!(schema_defs.len() < all_defs.len());
+        for (name, def_ref) in schema_defs.iter() {
+            assert_eq!(def_ref.get_kind(), SymbolKind::Schema);
+            assert_eq!(all_defs.get(name), Some(def_ref));
+        }
+    }
+
+    #[test]
+    fn test_resolve_type_name() {
+        let sess = Arc::new(ParseSession::default());
+
+        let path = "src/advanced_resolver/test_data/type_alias.k"
+            .to_string()
+            .replace("/", &std::path::MAIN_SEPARATOR.to_string());
+        let mut program = load_program(sess.clone(), &[&path], None, None)
+            .unwrap()
+            .program;
+        let mut gs = GlobalState::default();
+        Namer::find_symbols(&program, &mut gs);
+        let node_ty_map = resolver::resolve_program(&mut program).node_ty_map;
+        AdvancedResolver::resolve_program(&program, &mut gs, node_ty_map).unwrap();
+
+        let resolve = |names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            gs.resolve_type_name(&names, MAIN_PKG)
+                .and_then(|symbol_ref| gs.get_symbols().get_fully_qualified_name(symbol_ref))
+        };
+        assert_eq!(resolve(&["Main"]), Some("__main__.Main".to_string()));
+        assert_eq!(
+            resolve(&["a", "Person"]),
+            Some("import_test.a.Person".to_string())
+        );
+        assert_eq!(
+            resolve(&["g", "UnionType"]),
+            Some("import_test.f.UnionType".to_string())
+        );
+        assert_eq!(
+            resolve(&["import_test", "a", "Name"]),
+            Some("import_test.a.Name".to_string())
+        );
+        assert_eq!(
+            resolve(&["PersonAlias"]),
+            Some("import_test.a.Person".to_string())
+        );
+        assert_eq!(resolve(&["Name"]), Some("__main__.Name".to_string()));
+        assert_eq!(resolve(&["a", "Missing"]), None);
+        assert_eq!(resolve(&["f", "UnionType"]), None);
+        assert_eq!(resolve(&[]), None);
+
+        let qualified_name = |names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            gs.resolve_type_name(&names, MAIN_PKG)
+                .and_then(|symbol_ref| gs.get_symbols().qualified_name(symbol_ref))
+        };
+        assert_eq!(qualified_name(&["Main"]), Some("Main".to_string()));
+        assert_eq!(
+            qualified_name(&["a", "Person"]),
+            Some("import_test.a.Person".to_string())
+        );
+        for name in ["Main", "import_test.a.Person"] {
+            let symbol_ref = gs.get_symbols().get_symbol_by_qualified_name(name);
+            assert_eq!(
+                symbol_ref.and_then(|symbol_ref| gs.get_symbols().qualified_name(symbol_ref)),
+                Some(name.to_string())
+            );
+        }
+    }
+
     #[test]
     fn test_schema_def_scope() {
         let sess = Arc::new(ParseSession::default());
@@ -1539,6 +1637,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dump_scopes() {
+        let sess = Arc::new(ParseSession::default());
+
+        let path = "src/advanced_resolver/test_data/schema_def_scope.k"
+            .to_string()
+            .replace("/", &std::path::MAIN_SEPARATOR.to_string());
+        let mut program = load_program(sess.clone(), &[&path], None, None)
+            .unwrap()
+            .program;
+        let mut gs = GlobalState::default();
+        Namer::find_symbols(&program, &mut gs);
+        let node_ty_map = resolver::resolve_program(&mut program).node_ty_map;
+        AdvancedResolver::resolve_program(&program, &mut gs, node_ty_map).unwrap();
+        let dumped = gs.dump_scopes().unwrap();
+        let dumped: serde_json::Value = serde_json::from_str(&dumped).unwrap();
+        let main_scope = dumped.get(MAIN_PKG).unwrap();
+        assert_eq!(main_scope["scope_kind"], "Root");
+        assert_eq!(main_scope["pkgpath"], MAIN_PKG);
+    }
+
     #[test]
     fn test_schema_circle_dep() {
         let sess = Arc::new(ParseSession::default());
@@ -222,6 +222,45 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for AdvancedResolver<'_> {
         self.gs
             .get_scopes_mut()
             .add_ref_to_scope(cur_scope, unresolved_ref);
+        // The selected names of the selective import e.g., `Name` in `from pkg import Name`
+        // refer to the package members.
+        for name in &import_stmt.selected_names {
+            let member_symbol =
+                match self
+                    .gs
+                    .get_symbols()
+                    .get_symbol_by_fully_qualified_name(&format!(
+                        "{}.{}",
+                        import_stmt.path.node, name.node
+                    )) {
+                    Some(symbol) => symbol,
+                    None => continue,
+                };
+            let (start_pos, end_pos) = name.get_span_pos();
+            let unresolved = UnresolvedSymbol::new(
+                name.node.clone(),
+                start_pos,
+                end_pos,
+                None,
+                self.ctx.is_type_expr,
+            );
+            let name_unresolved_ref = self.gs.get_symbols_mut().alloc_unresolved_symbol(
+                unresolved,
+                self.ctx.get_node_key(&name.id),
+                self.ctx.current_pkgpath.clone().unwrap(),
+            );
+            self.gs
+                .get_symbols_mut()
+                .set_def_and_ref(member_symbol, name_unresolved_ref);
+            self.gs
+                .get_symbols_mut()
+                .symbols_info
+                .node_symbol_map
+                .insert(self.ctx.get_node_key(&name.id), name_unresolved_ref);
+            self.gs
+                .get_scopes_mut()
+                .add_ref_to_scope(cur_scope, name_unresolved_ref);
+        }
         Ok(Some(unresolved_ref))
     }
 
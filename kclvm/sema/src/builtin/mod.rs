@@ -145,6 +145,22 @@ end:   string appended after the last value, default a newline."#,
         false,
         None,
     )
+    hash => Type::function(
+        None,
+        Arc::new(Type::STR),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        "Return the SHA-256 hex digest of the canonical serialization of the value, which is computed with sorted keys and normalized numbers.",
+        false,
+        None,
+    )
     len => Type::function(
         None,
         Arc::new(Type::INT),
@@ -378,6 +394,40 @@ non-numeric types."#,
         false,
         None,
     )
+    template => Type::function(
+        None,
+        Arc::new(Type::STR),
+        &[
+            Parameter {
+                name: "tmpl".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "vars".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "default".to_string(),
+                ty: Type::any_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the template string with each `${key}` placeholder substituted by
+the value of the key in the vars dict, in which `$$` is an escaped literal `$`.
+A missing key is an error unless the default value is given. The template should
+be a raw string e.g., `r"${name}"` or escape the placeholders e.g., `"\${name}"`,
+otherwise the placeholders are evaluated as the string interpolation."#,
+        false,
+        None,
+    )
     pow => Type::function(
         None,
         Type::number(),
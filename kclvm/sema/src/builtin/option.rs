@@ -1,13 +1,68 @@
+use std::sync::Arc;
+
+use crate::ty::{
+    Type, TypeRef, BOOL_TYPE_STR, DICT_TYPE_STR, FLOAT_TYPE_STR, INT_TYPE_STR, LIST_TYPE_STR,
+    STR_TYPE_STR,
+};
+
+/// The type strings accepted by the `type` argument of the `option` function, to which
+/// the option value is converted.
+pub const OPTION_TYPES: &[&str] = &[
+    BOOL_TYPE_STR,
+    INT_TYPE_STR,
+    FLOAT_TYPE_STR,
+    STR_TYPE_STR,
+    LIST_TYPE_STR,
+    DICT_TYPE_STR,
+];
+
 /// OptionHelp denotes all the option function calling usage.
-#[derive(PartialEq, Eq, Clone, Default, Debug)]
+#[derive(PartialEq, Clone, Default, Debug)]
 pub struct OptionHelp {
     pub name: String,
     pub ty: String,
+    /// The option value type parsed from `ty`, which is `None` when the type is not set
+    /// or invalid.
+    pub ty_ref: Option<TypeRef>,
     pub required: bool,
     pub default_value: String,
     pub help: String,
 }
 
+/// Parse the `type` argument of the `option` function e.g., `int` to the option value type.
+/// An empty type string denotes the `any` type.
+///
+/// ```
+/// use kclvm_sema::builtin::option::parse_option_type;
+///
+/// assert_eq!(parse_option_type("int").unwrap().ty_str(), "int");
+/// assert_eq!(parse_option_type("list").unwrap().ty_str(), "[any]");
+/// assert!(parse_option_type("[int]").is_err());
+/// ```
+pub fn parse_option_type(ty_str: &str) -> Result<TypeRef, String> {
+    let ty = match ty_str {
+        "" => Type::ANY,
+        BOOL_TYPE_STR => Type::BOOL,
+        INT_TYPE_STR => Type::INT,
+        FLOAT_TYPE_STR => Type::FLOAT,
+        STR_TYPE_STR => Type::STR,
+        LIST_TYPE_STR => Type::list(Arc::new(Type::ANY)),
+        DICT_TYPE_STR => Type::dict(Arc::new(Type::ANY), Arc::new(Type::ANY)),
+        _ => {
+            return Err(format!(
+                "invalid option type '{}', expected one of {}",
+                ty_str,
+                OPTION_TYPES
+                    .iter()
+                    .map(|ty| format!("'{}'", ty))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+        }
+    };
+    Ok(Arc::new(ty))
+}
+
 /// Print option helps to string
 pub fn print_option_help(option_helps: &[OptionHelp]) -> String {
     let mut msg: String = "".to_string();
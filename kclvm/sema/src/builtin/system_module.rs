@@ -1929,7 +1929,7 @@ register_file_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Read the file content from path"#,
+        r#"Read the file content from path under the workspace. The `allow_file_read` option must be enabled."#,
         false,
         None,
     )
@@ -2168,6 +2168,38 @@ register_file_member! {
         false,
         None,
     )
+    read_json => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Read and parse the JSON file from the path relative to the module root. The file must be in the workspace and the `allow_file_read` option must be enabled."#,
+        false,
+        None,
+    )
+    read_yaml => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Read and parse the YAML file from the path relative to the module root. The file must be in the workspace and the `allow_file_read` option must be enabled."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -373,6 +373,75 @@ impl GlobalState {
         None
     }
 
+    /// Dump the whole scope and symbol tree of all packages into a JSON string,
+    /// which maps the package path to the dumped root scope of the package.
+    ///
+    /// # Returns
+    ///
+    /// result: [Option<String>]
+    ///     the pretty JSON string of the scope tree
+    pub fn dump_scopes(&self) -> Option<String> {
+        let mut output = serde_json::Map::new();
+        for (pkgpath, root_ref) in self.scopes.get_root_scope_map() {
+            let root = self.scopes.get_scope(root_ref)?;
+            let dumped = root.dump(&self.scopes, &self.symbols)?;
+            output.insert(pkgpath.clone(), serde_json::from_str(&dumped).ok()?);
+        }
+        serde_json::to_string_pretty(&output).ok()
+    }
+
+    /// resolve a possibly qualified type name e.g., `Person`, `a.Person` or
+    /// `some.pkg.Person` to its defining symbol, which honors the imports and the
+    /// import aliases of the modules in the package. The type alias is followed to
+    /// the symbol of its aliased type when the aliased type is a schema.
+    ///
+    /// # Parameters
+    ///
+    /// `names`: [&[String]]
+    ///     the names of the type identifier e.g., `["a", "Person"]`
+    ///
+    /// `from_pkg`: [&str]
+    ///     the package path where the type name is referenced
+    ///
+    /// # Returns
+    ///
+    /// result: [Option<SymbolRef>]
+    ///     the schema or type alias symbol, and `None` when the name is unresolved
+    pub fn resolve_type_name(&self, names: &[String], from_pkg: &str) -> Option<SymbolRef> {
+        let (first, rest) = names.split_first()?;
+        let fqn = if rest.is_empty() {
+            format!("{}.{}", from_pkg, first)
+        } else {
+            // The first name is an imported package name or the alias of it.
+            let pkgpath = self
+                .packages
+                .module_info
+                .values()
+                .filter(|module_info| module_info.pkgpath == from_pkg)
+                .find_map(|module_info| module_info.get_import_info(first))
+                .map(|import_info| import_info.fully_qualified_name.clone());
+            match pkgpath {
+                Some(pkgpath) => format!("{}.{}", pkgpath, rest.join(".")),
+                None => names.join("."),
+            }
+        };
+        let symbol_ref = self.symbols.get_symbol_by_fully_qualified_name(&fqn)?;
+        match symbol_ref.get_kind() {
+            SymbolKind::Schema => Some(symbol_ref),
+            SymbolKind::TypeAlias => {
+                let aliased = self
+                    .symbols
+                    .type_aliases
+                    .get(symbol_ref.get_id())
+                    .and_then(|alias| alias.sema_info.ty.as_ref())
+                    .and_then(|ty| self.symbols.get_type_symbol(ty, None))
+                    .filter(|aliased| aliased.get_kind() == SymbolKind::Schema);
+                Some(aliased.unwrap_or(symbol_ref))
+            }
+            _ => None,
+        }
+    }
+
     pub fn get_scope_symbols(&self, scope: ScopeRef) -> Option<Vec<SymbolRef>> {
         let scope = self.get_scopes().get_scope(&scope)?;
         let filename = scope.get_filename();
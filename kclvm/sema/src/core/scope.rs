@@ -5,7 +5,7 @@ use kclvm_ast::pos::ContainsPos;
 use kclvm_error::{diagnostic::Range, Position};
 use serde::Serialize;
 
-use crate::core::symbol::SymbolRef;
+use crate::core::symbol::{SymbolKind, SymbolRef};
 
 use super::{package::ModuleInfo, symbol::SymbolData};
 
@@ -41,6 +41,28 @@ pub trait Scope {
         get_def_from_owner: bool,
     ) -> HashMap<String, SymbolRef>;
 
+    /// Get all defs of the specific symbol kind within current scope and parent scope
+    fn get_all_defs_of_kind(
+        &self,
+        kind: SymbolKind,
+        scope_data: &ScopeData,
+        symbol_data: &Self::SymbolData,
+        module_info: Option<&ModuleInfo>,
+        maybe_in_key: bool,
+        get_def_from_owner: bool,
+    ) -> HashMap<String, SymbolRef> {
+        self.get_all_defs(
+            scope_data,
+            symbol_data,
+            module_info,
+            maybe_in_key,
+            get_def_from_owner,
+        )
+        .into_iter()
+        .filter(|(_, def_ref)| def_ref.get_kind() == kind)
+        .collect()
+    }
+
     /// Get all defs within current scope
     fn get_defs_within_scope(
         &self,
@@ -182,6 +204,32 @@ impl ScopeData {
         self.root_map.get(&name).copied()
     }
 
+    /// Get the innermost scope which contains the position `pos`. It finds the
+    /// root scope whose files contain the position first and then descends into
+    /// the children scopes with [Scope::contains_pos] until no child matches.
+    pub fn innermost_scope_at(&self, pos: &Position) -> Option<ScopeRef> {
+        let mut current = self
+            .root_map
+            .values()
+            .find(|root_ref| {
+                self.get_scope(root_ref)
+                    .map_or(false, |root| root.contains_pos(pos))
+            })
+            .copied()?;
+        'descend: loop {
+            let scope = self.get_scope(&current)?;
+            for child_ref in scope.get_children() {
+                if let Some(child) = self.get_scope(&child_ref) {
+                    if child.contains_pos(pos) {
+                        current = child_ref;
+                        continue 'descend;
+                    }
+                }
+            }
+            return Some(current);
+        }
+    }
+
     pub fn add_def_to_scope(&mut self, scope: ScopeRef, name: String, symbol: SymbolRef) {
         match scope.get_kind() {
             ScopeKind::Local => {
@@ -535,6 +535,28 @@ impl SymbolData {
         }
     }
 
+    /// Get the qualified name of the symbol which walks from the symbol to its owning package
+    /// e.g., `pkg.Schema` for the schema `Schema` in the package `pkg` and `Schema` for the
+    /// schema in the main package, which is consistent with the schema type string used in the
+    /// diagnostics and the docs. Returns `None` for the unresolved symbol.
+    pub fn qualified_name(&self, symbol_ref: SymbolRef) -> Option<String> {
+        let fqn = self.get_fully_qualified_name(symbol_ref)?;
+        match fqn
+            .strip_prefix(kclvm_ast::MAIN_PKG)
+            .and_then(|name| name.strip_prefix('.'))
+        {
+            Some(name) => Some(name.to_string()),
+            None => Some(fqn),
+        }
+    }
+
+    /// Get the symbol by the qualified name e.g., `pkg.Schema` or `Schema` for the schema in the
+    /// main package, which is the inverse of [SymbolData::qualified_name].
+    pub fn get_symbol_by_qualified_name(&self, name: &str) -> Option<SymbolRef> {
+        self.get_symbol_by_fully_qualified_name(&format!("{}.{}", kclvm_ast::MAIN_PKG, name))
+            .or_else(|| self.get_symbol_by_fully_qualified_name(name))
+    }
+
     pub fn build_fully_qualified_name_map(&mut self) {
         for (id, _) in self.packages.iter() {
             let symbol_ref = SymbolRef {
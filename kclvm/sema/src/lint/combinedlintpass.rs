@@ -1,24 +1,32 @@
-use crate::lint::lint::{LintArray, LintContext};
+use crate::lint::lint::{Lint, LintArray, LintContext};
 use crate::lint::lintpass::LintPass;
 use crate::lint::lints_def::ImportPosition;
+use crate::lint::lints_def::IncompatibleComparison;
 use crate::lint::lints_def::ReImport;
+use crate::lint::lints_def::RedundantAttribute;
 use crate::lint::lints_def::UnusedImport;
 use crate::lint_methods;
 use crate::resolver::scope::Scope;
+use crate::ty::TypeRef;
+use indexmap::IndexSet;
 use kclvm_ast::ast;
+use kclvm_error::diagnostic::Range;
 use kclvm_error::Handler;
 
-/// Call the `check_*` method of each lintpass in CombinedLintLass.check_*.
+/// Call the `check_*` method of each enabled lintpass in CombinedLintLass.check_*.
 /// ```ignore
 ///     fn check_ident(&mut self, handler: &mut Handler, ctx: &mut LintContext, id: &ast::Identifier, ){
-///         self.LintPassA.check_ident(handler, ctx, id);
-///         self.LintPassB.check_ident(handler, ctx, id);
+///         if !self.disabled_passes.contains("LintPassA") {
+///             self.LintPassA.check_ident(handler, ctx, id);
+///         }
 ///         ...
 ///     }
 /// ```
 macro_rules! expand_combined_lint_pass_method {
     ([$($passes:ident),*], $self: ident, $name: ident, $params:tt) => ({
-        $($self.$passes.$name $params;)*
+        $(if !$self.disabled_passes.contains(stringify!($passes)) {
+            $self.$passes.$name $params;
+        })*
     })
 }
 
@@ -44,6 +52,8 @@ macro_rules! expand_combined_lint_pass_methods {
 ///     LintPassA: LintPassA;
 ///     LintPassB: LintPassB;
 ///     ...
+///     disabled_passes: IndexSet<&'static str>,
+///     disabled_lints: IndexSet<&'static str>,
 /// }
 ///
 /// impl CombinedLintPass{
@@ -52,8 +62,25 @@ macro_rules! expand_combined_lint_pass_methods {
 ///            LintPassA: LintPassA,
 ///            LintPassB: LintPassB,
 ///            ...
+///            disabled_passes: IndexSet::default(),
+///            disabled_lints: IndexSet::default(),
 ///        }
 ///     }
+///     pub fn disable(&mut self, lint: &str) -> bool {
+///         let mut found = false;
+///         for l in LintPassA::get_lints().iter().filter(|l| l.matches(lint)) {
+///             self.disabled_lints.insert(l.code);
+///             found = true;
+///         }
+///         if LintPassA::get_lints().iter().all(|l| self.disabled_lints.contains(l.code)) {
+///             self.disabled_passes.insert("LintPassA");
+///         }
+///         ...
+///         found
+///     }
+///     pub fn is_enabled(&self, lint: &Lint) -> bool {
+///         !self.disabled_lints.contains(lint.code)
+///     }
 ///     pub fn get_lints() -> LintArray {
 ///         let mut lints = Vec::new();
 ///         lints.extend_from_slice(&LintPassA::get_lints());
@@ -82,15 +109,40 @@ macro_rules! declare_combined_lint_pass {
         #[allow(non_snake_case)]
         $v struct $name {
             $($passes: $passes,)*
+            disabled_passes: IndexSet<&'static str>,
+            disabled_lints: IndexSet<&'static str>,
         }
 
         impl $name {
             $v fn new() -> Self {
                 Self {
                     $($passes: $constructor,)*
+                    disabled_passes: IndexSet::default(),
+                    disabled_lints: IndexSet::default(),
                 }
             }
 
+            /// Disable the lint with the name e.g., `UNUSED_IMPORT` or the code e.g., `W0411`,
+            /// and return whether the lint exists. The lintpass is skipped only when all the
+            /// lints it reports are disabled.
+            $v fn disable(&mut self, lint: &str) -> bool {
+                let mut found = false;
+                $(for l in $passes::get_lints().iter().filter(|l| l.matches(lint)) {
+                    self.disabled_lints.insert(l.code);
+                    found = true;
+                }
+                if $passes::get_lints().iter().all(|l| self.disabled_lints.contains(l.code)) {
+                    self.disabled_passes.insert(stringify!($passes));
+                })*
+                found
+            }
+
+            /// Whether the lint is not disabled.
+            #[inline]
+            $v fn is_enabled(&self, lint: &Lint) -> bool {
+                !self.disabled_lints.contains(lint.code)
+            }
+
             $v fn get_lints() -> LintArray {
                 let mut lints = Vec::new();
                 $(lints.extend_from_slice(&$passes::get_lints());)*
@@ -112,6 +164,8 @@ macro_rules! default_lint_passes {
                 ImportPosition: ImportPosition,
                 UnusedImport: UnusedImport,
                 ReImport: ReImport,
+                IncompatibleComparison: IncompatibleComparison,
+                RedundantAttribute: RedundantAttribute,
             ]
         );
     };
@@ -0,0 +1,178 @@
+//! Lint directives are the comments which disable the lints inline by their names
+//! or codes separated by commas or whitespaces, e.g.,
+//!
+//! ```kcl
+//! # kcl-lint-disable-file W0417
+//! # kcl-lint-disable unused_import
+//! import math
+//! ```
+//!
+//! - `kcl-lint-disable`: disables the lints on the next statement, or on the statement
+//!   when it is a trailing comment on the same line of the statement.
+//! - `kcl-lint-disable-file`: disables the lints on the rest of the file.
+//!
+//! A directive without any lint names or codes is reported as a warning.
+
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_error::{Diagnostic, DiagnosticId, Handler, Message, Style, WarningKind};
+
+use super::{CombinedLintPass, Lint};
+
+/// The directive which disables the lints on the next statement.
+pub const LINT_DISABLE: &str = "kcl-lint-disable";
+/// The directive which disables the lints on the rest of the file.
+pub const LINT_DISABLE_FILE: &str = "kcl-lint-disable-file";
+
+/// A lint directive disables the lints in the line range of a file.
+#[derive(Clone)]
+pub struct LintDirective {
+    pub filename: String,
+    /// The lints disabled by the directive.
+    pub lints: Vec<&'static Lint>,
+    /// The first line where the lints are disabled.
+    pub start_line: u64,
+    /// The last line where the lints are disabled, which is inclusive.
+    pub end_line: u64,
+}
+
+impl LintDirective {
+    /// Whether the lint diagnostic is disabled by the directive.
+    pub fn suppresses(&self, diag: &Diagnostic) -> bool {
+        let kind = match &diag.code {
+            Some(DiagnosticId::Warning(kind)) => kind,
+            _ => return false,
+        };
+        self.lints.iter().any(|lint| lint.kind == *kind)
+            && diag.messages.first().map_or(false, |msg| {
+                let pos = &msg.range.0;
+                pos.filename == self.filename
+                    && pos.line >= self.start_line
+                    && pos.line <= self.end_line
+            })
+    }
+}
+
+/// Parse the lint directives from the module comments, the unknown lints in the
+/// directives are reported to the handler as warnings.
+pub fn parse_lint_directives(module: &ast::Module, handler: &mut Handler) -> Vec<LintDirective> {
+    let all_lints = CombinedLintPass::get_lints();
+    let mut stmt_ranges = vec![];
+    collect_stmt_ranges(&module.body, &mut stmt_ranges);
+    let mut directives = vec![];
+    for comment in &module.comments {
+        let text = comment.node.text.trim_start_matches('#').trim();
+        let (directive, names) =
+            text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()));
+        if directive != LINT_DISABLE && directive != LINT_DISABLE_FILE {
+            continue;
+        }
+        let mut lints = vec![];
+        for name in names
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+        {
+            match all_lints.iter().find(|lint| lint.matches(name)) {
+                Some(lint) => lints.push(*lint),
+                None => {
+                    handler.add_warning(
+                        WarningKind::UnknownLintWarning,
+                        &[Message {
+                            range: comment.get_span_pos(),
+                            style: Style::Line,
+                            message: format!("unknown lint '{}' in the lint directive", name),
+                            note: Some(
+                                "Run 'kcl lint --list' to list all the available lints".to_string(),
+                            ),
+                            suggested_replacement: None,
+                            ..Default::default()
+                        }],
+                    );
+                }
+            }
+        }
+        if lints.is_empty() && names.trim().is_empty() {
+            handler.add_warning(
+                WarningKind::UnknownLintWarning,
+                &[Message {
+                    range: comment.get_span_pos(),
+                    style: Style::Line,
+                    message: format!(
+                        "the lint directive '{}' disables no lints, expected the lint names or codes",
+                        directive
+                    ),
+                    note: Some(
+                        "Run 'kcl lint --list' to list all the available lints".to_string(),
+                    ),
+                    suggested_replacement: None,
+                    ..Default::default()
+                }],
+            );
+            continue;
+        }
+        let lines = if directive == LINT_DISABLE_FILE {
+            Some((comment.line, u64::MAX))
+        } else {
+            same_line_stmt_lines(&stmt_ranges, comment)
+                .or_else(|| next_stmt_lines(&stmt_ranges, comment.line))
+        };
+        if let Some((start_line, end_line)) = lines {
+            directives.push(LintDirective {
+                filename: module.filename.clone(),
+                lints,
+                start_line,
+                end_line,
+            });
+        }
+    }
+    directives
+}
+
+/// The `(line, column, end_line, end_column)` range of a statement.
+type StmtRange = (u64, u64, u64, u64);
+
+/// Collect the ranges of the statements including the nested ones.
+fn collect_stmt_ranges(stmts: &[ast::NodeRef<ast::Stmt>], ranges: &mut Vec<StmtRange>) {
+    for stmt in stmts {
+        ranges.push((stmt.line, stmt.column, stmt.end_line, stmt.end_column));
+        match &stmt.node {
+            ast::Stmt::Schema(schema_stmt) => collect_stmt_ranges(&schema_stmt.body, ranges),
+            ast::Stmt::If(if_stmt) => {
+                collect_stmt_ranges(&if_stmt.body, ranges);
+                collect_stmt_ranges(&if_stmt.orelse, ranges);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Get the line range of the innermost statement which the trailing comment follows on the
+/// same line, that is, the statement starts before the comment on the line or ends before it.
+fn same_line_stmt_lines(
+    stmt_ranges: &[StmtRange],
+    comment: &ast::NodeRef<ast::Comment>,
+) -> Option<(u64, u64)> {
+    stmt_ranges
+        .iter()
+        .filter(|(line, column, end_line, end_column)| {
+            (*line == comment.line && *column < comment.column)
+                || (*end_line == comment.line && *end_column <= comment.column)
+        })
+        .map(|(line, _, end_line, _)| (*line, *end_line))
+        .max_by_key(|(line, end_line)| (*line, *end_line))
+}
+
+/// Get the line range of the outermost statement which starts next to the line.
+fn next_stmt_lines(stmt_ranges: &[StmtRange], line: u64) -> Option<(u64, u64)> {
+    let start_line = stmt_ranges
+        .iter()
+        .map(|(start, ..)| *start)
+        .filter(|start| *start > line)
+        .min()?;
+    let end_line = stmt_ranges
+        .iter()
+        .filter(|(start, ..)| *start == start_line)
+        .map(|(_, _, end, _)| *end)
+        .max()?;
+    Some((start_line, end_line))
+}
@@ -1,4 +1,4 @@
-use kclvm_error::{Level, Position};
+use kclvm_error::{Level, Position, WarningKind};
 
 /// Record the information at `LintContext` when traversing the AST for analysis across AST nodes, e.g., record
 /// used importstmt(used_import_names) when traversing `ast::Identifier` and `ast::SchemaAttr`, and detect unused
@@ -30,6 +30,17 @@ pub struct Lint {
 
     // Suggest methods to fix this problem
     pub note: Option<&'static str>,
+
+    /// The kind of the warnings reported by the lint.
+    pub kind: WarningKind,
+}
+
+impl Lint {
+    /// Whether the lint matches the name e.g., `unused_import` or the code e.g., `W0411`.
+    pub fn matches(&self, lint: &str) -> bool {
+        self.code.eq_ignore_ascii_case(lint)
+            || self.name.trim_matches('"').eq_ignore_ascii_case(lint)
+    }
 }
 
 pub type LintArray = Vec<&'static Lint>;
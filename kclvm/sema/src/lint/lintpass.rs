@@ -1,6 +1,8 @@
 use crate::lint::lint::LintContext;
 use crate::resolver::scope::Scope;
+use crate::ty::TypeRef;
 use kclvm_ast::ast;
+use kclvm_error::diagnostic::Range;
 use kclvm_error::Handler;
 
 #[macro_export]
@@ -16,6 +18,14 @@ macro_rules! lint_methods {
             fn check_scope(_scope: &Scope);
 
             fn check_module(_module: &ast::Module);
+
+            /*
+            * Type
+            */
+
+            // The operand types of the comparison are inferred by the resolver, which calls the
+            // method when checking the comparison.
+            fn check_compare_types(_left: &TypeRef, _right: &TypeRef, _op: &ast::CmpOp, _range: &Range);
             /*
             * Stmt
             */
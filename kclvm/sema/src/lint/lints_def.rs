@@ -1,10 +1,12 @@
 use crate::lint::lint::{Lint, LintArray, LintContext};
 use crate::lint::lintpass::LintPass;
 use crate::resolver::scope::Scope;
+use crate::ty::{TypeKind, TypeRef};
 use crate::{declare_lint_pass, resolver::scope::ScopeObjectKind};
 use indexmap::IndexSet;
 use kclvm_ast::ast;
 use kclvm_ast::pos::GetPos;
+use kclvm_error::diagnostic::Range;
 use kclvm_error::{Handler, Level, Message, Style, WarningKind};
 
 /// The 'import_position' lint detects import statements that are not declared at the top of file.
@@ -26,6 +28,7 @@ pub static IMPORT_POSITION: &Lint = &Lint {
     desc: "Check for importstmt that are not defined at the top of file",
     code: "W0413",
     note: Some("Consider moving tihs statement to the top of the file"),
+    kind: WarningKind::ImportPositionWarning,
 };
 
 declare_lint_pass!(ImportPosition => [IMPORT_POSITION]);
@@ -63,6 +66,7 @@ impl LintPass for ImportPosition {
                                 "Consider moving tihs statement to the top of the file".to_string(),
                             ),
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                 }
@@ -91,6 +95,7 @@ pub static UNUSED_IMPORT: &Lint = &Lint {
     desc: "Check for unused importstmt",
     code: "W0411",
     note: Some("Consider removing this statement"),
+    kind: WarningKind::UnusedImportWarning,
 };
 
 declare_lint_pass!(UnusedImport => [UNUSED_IMPORT]);
@@ -103,14 +108,32 @@ impl LintPass for UnusedImport {
             if let ScopeObjectKind::Module(m) = &scope_obj.kind {
                 for (stmt, has_used) in &m.import_stmts {
                     if !has_used {
+                        // The selective import e.g., `from pkg import Name` is reported with
+                        // the selected names instead of its internal import name.
+                        let message = match &stmt.node {
+                            ast::Stmt::Import(import_stmt) if import_stmt.is_selective() => {
+                                format!(
+                                    "'{}' imported from module '{}' but unused",
+                                    import_stmt
+                                        .selected_names
+                                        .iter()
+                                        .map(|name| name.node.as_str())
+                                        .collect::<Vec<&str>>()
+                                        .join("', '"),
+                                    import_stmt.rawpath
+                                )
+                            }
+                            _ => format!("Module '{}' imported but unused", scope_obj.name),
+                        };
                         handler.add_warning(
                             WarningKind::UnusedImportWarning,
                             &[Message {
                                 range: stmt.get_span_pos(),
                                 style: Style::Line,
-                                message: format!("Module '{}' imported but unused", scope_obj.name),
+                                message,
                                 note: Some("Consider removing this statement".to_string()),
                                 suggested_replacement: None,
+                                ..Default::default()
                             }],
                         );
                     }
@@ -140,6 +163,7 @@ pub static REIMPORT: &Lint = &Lint {
     desc: "Check for deplicate importstmt",
     code: "W0404",
     note: Some("Consider removing this statement"),
+    kind: WarningKind::ReimportWarning,
 };
 
 declare_lint_pass!(ReImport => [REIMPORT]);
@@ -154,6 +178,10 @@ impl LintPass for ReImport {
         let mut import_names = IndexSet::<String>::new();
         for stmt in &module.body {
             if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                // The selective imports e.g., `from pkg import Name` don't bind the package name.
+                if import_stmt.is_selective() {
+                    continue;
+                }
                 if import_names.contains(&import_stmt.path.node) {
                     handler.add_warning(
                         WarningKind::ReimportWarning,
@@ -166,6 +194,7 @@ impl LintPass for ReImport {
                             ),
                             note: Some("Consider removing this statement".to_string()),
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                 } else {
@@ -175,3 +204,109 @@ impl LintPass for ReImport {
         }
     }
 }
+
+/// The 'incompatible_comparison' lint detects the equality comparisons between the
+/// values of incompatible types, which are always `False` for `==` and `True` for `!=`.
+///
+/// ### Example
+///
+/// ```kcl
+/// replicas: int = 1
+/// enabled = replicas == "1"
+/// ```
+/// ### Explanation
+///
+/// Comparing a string with a number or bool is almost always a mistake in the config
+/// e.g., a quoted number. Only the equality operators `==` and `!=` are checked, because
+/// the ordering operators e.g., `<` and the arithmetic operators e.g., `+` between a string
+/// and a number are already reported as type errors by the resolver.
+pub static INCOMPATIBLE_COMPARISON: &Lint = &Lint {
+    name: stringify!("INCOMPATIBLE_COMPARISON"),
+    level: Level::Warning,
+    desc: "Check for equality comparisons between incompatible types",
+    code: "W0416",
+    note: Some("Consider converting one of the operands e.g., int(x) or str(x)"),
+    kind: WarningKind::IncompatibleComparisonWarning,
+};
+
+declare_lint_pass!(IncompatibleComparison => [INCOMPATIBLE_COMPARISON]);
+
+impl LintPass for IncompatibleComparison {
+    fn check_compare_types(
+        &mut self,
+        handler: &mut Handler,
+        _ctx: &mut LintContext,
+        left: &TypeRef,
+        right: &TypeRef,
+        op: &ast::CmpOp,
+        range: &Range,
+    ) {
+        if !matches!(op, ast::CmpOp::Eq | ast::CmpOp::NotEq) {
+            return;
+        }
+        let is_number_bool =
+            |ty: &TypeRef| is_kind_or_union_of(ty, |ty| ty.is_number() || ty.is_bool());
+        let is_str = |ty: &TypeRef| is_kind_or_union_of(ty, |ty| ty.is_str());
+        if (is_number_bool(left) && is_str(right)) || (is_str(left) && is_number_bool(right)) {
+            handler.add_warning(
+                WarningKind::IncompatibleComparisonWarning,
+                &[Message {
+                    range: range.clone(),
+                    style: Style::LineAndColumn,
+                    message: format!(
+                        "comparison '{}' between incompatible types '{}' and '{}' is always {}",
+                        op.symbol(),
+                        left.ty_str(),
+                        right.ty_str(),
+                        if matches!(op, ast::CmpOp::Eq) {
+                            "False"
+                        } else {
+                            "True"
+                        }
+                    ),
+                    note: INCOMPATIBLE_COMPARISON.note.map(|note| note.to_string()),
+                    suggested_replacement: None,
+                    ..Default::default()
+                }],
+            );
+        }
+    }
+}
+
+/// Whether the type or all the types of the union type match the predicate.
+fn is_kind_or_union_of(ty: &TypeRef, pred: impl Fn(&TypeRef) -> bool + Copy) -> bool {
+    match &ty.kind {
+        TypeKind::Union(types) => types.iter().all(|ty| is_kind_or_union_of(ty, pred)),
+        _ => pred(ty),
+    }
+}
+
+/// The 'redundant_attribute' lint detects the schema attributes redeclared with the same
+/// type, optionality and default value as the attributes inherited from the parent schema.
+/// ### Example
+///
+/// ```kcl
+/// schema Base:
+///     replicas: int = 1
+///
+/// schema App(Base):
+///     replicas: int = 1
+///
+/// ```
+/// ### Explanation
+///
+/// The redeclaration has no effect and can be removed. The attribute types are resolved by
+/// the resolver, which reports this lint when building the schema type, so the lint pass
+/// itself has no checks.
+pub static REDUNDANT_ATTRIBUTE: &Lint = &Lint {
+    name: stringify!("REDUNDANT_ATTRIBUTE"),
+    level: Level::Warning,
+    desc: "Check for schema attributes redeclared identically to the parent schema",
+    code: "W0417",
+    note: Some("Consider removing this attribute"),
+    kind: WarningKind::RedundantAttributeWarning,
+};
+
+declare_lint_pass!(RedundantAttribute => [REDUNDANT_ATTRIBUTE]);
+
+impl LintPass for RedundantAttribute {}
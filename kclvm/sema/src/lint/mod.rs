@@ -78,19 +78,28 @@ use crate::resolver::{scope::Scope, Resolver};
 use kclvm_ast::pos::GetPos;
 use kclvm_error::{Handler, Position};
 mod combinedlintpass;
+mod directive;
 mod lint;
 mod lintpass;
 mod lints_def;
 use kclvm_ast::ast;
 use kclvm_ast::walker::MutSelfWalker;
 
-pub use self::{combinedlintpass::CombinedLintPass, lint::LintContext, lintpass::LintPass};
+pub use self::{
+    combinedlintpass::CombinedLintPass,
+    directive::{parse_lint_directives, LintDirective, LINT_DISABLE, LINT_DISABLE_FILE},
+    lint::{Lint, LintArray, LintContext},
+    lintpass::LintPass,
+    lints_def::{INCOMPATIBLE_COMPARISON, REDUNDANT_ATTRIBUTE},
+};
 
 /// The struct `Linter` is used to traverse the AST and call the `check_*` method defined in `CombinedLintPass`.
 pub struct Linter<T: LintPass> {
     pub pass: T,
     pub handler: Handler,
     pub ctx: LintContext,
+    /// The lint directives in the comments of the checked modules.
+    pub directives: Vec<LintDirective>,
 }
 
 impl LintContext {
@@ -109,8 +118,17 @@ impl Linter<CombinedLintPass> {
             pass: CombinedLintPass::new(),
             handler: Handler::default(),
             ctx: LintContext::dummy_ctx(),
+            directives: vec![],
         }
     }
+    /// New a linter with the lints disabled by their names or codes.
+    pub fn new_with_disabled_lints(disabled_lints: &[String]) -> Self {
+        let mut linter = Self::new();
+        for lint in disabled_lints {
+            linter.pass.disable(lint);
+        }
+        linter
+    }
     pub fn walk_scope(&mut self, scope: &Scope) {
         self.pass
             .check_scope(&mut self.handler, &mut self.ctx, scope);
@@ -122,6 +140,8 @@ impl Resolver<'_> {
     pub fn lint_check_module(&mut self, module: &ast::Module) {
         self.linter.ctx.filename = module.filename.clone();
         self.linter.walk_module(module);
+        let directives = parse_lint_directives(module, &mut self.linter.handler);
+        self.linter.directives.extend(directives);
     }
     /// Recursively iterate the scope and its child scope, run lint checks, generating diagnostics and save them in `lint.handler`
     pub fn lint_check_scope(&mut self, scope: &Scope) {
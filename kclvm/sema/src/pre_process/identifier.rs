@@ -160,6 +160,341 @@ fn remove_raw_ident_prefix(name: &str) -> String {
     }
 }
 
+#[derive(Default)]
+struct SelectiveImportTransformer {
+    /// The selected names mapping to the internal import names e.g., `Name -> @pkg`,
+    /// which are located at the import path.
+    pub selected_names: IndexMap<String, ast::Node<String>>,
+    /// The global names defined in the module, which shadow the selected names.
+    pub global_names: IndexSet<String>,
+    /// The local names e.g., schema attributes, arguments and comprehension variables
+    /// of the nested scopes, which shadow the selected names.
+    pub local_names: Vec<IndexSet<String>>,
+    /// The attribute names the schemas in the module inherit from their parent schemas
+    /// and mixins, which shadow the selected names in the schema bodies.
+    pub inherited_names: IndexMap<String, IndexSet<String>>,
+}
+
+impl SelectiveImportTransformer {
+    fn is_shadowed(&self, name: &str) -> bool {
+        self.global_names.contains(name) || self.local_names.iter().any(|n| n.contains(name))
+    }
+}
+
+/// Collect the attribute names defined or assigned in the schema body including the
+/// nested if statements.
+fn collect_schema_body_names(stmts: &[ast::NodeRef<ast::Stmt>], names: &mut IndexSet<String>) {
+    for stmt in stmts {
+        match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr) => {
+                names.insert(schema_attr.name.node.clone());
+            }
+            ast::Stmt::Assign(assign_stmt) => {
+                names.extend(
+                    assign_stmt
+                        .targets
+                        .iter()
+                        .map(|target| target.node.name.node.clone()),
+                );
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_schema_body_names(&if_stmt.body, names);
+                collect_schema_body_names(&if_stmt.orelse, names);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collect the global names defined in the module including the names assigned in the
+/// top-level if statements.
+fn collect_global_names(stmts: &[ast::NodeRef<ast::Stmt>], names: &mut IndexSet<String>) {
+    for stmt in stmts {
+        match &stmt.node {
+            ast::Stmt::Assign(assign_stmt) => {
+                for target in &assign_stmt.targets {
+                    if target.node.paths.is_empty() {
+                        names.insert(target.node.name.node.clone());
+                    }
+                }
+            }
+            ast::Stmt::AugAssign(aug_assign_stmt) => {
+                names.insert(aug_assign_stmt.target.node.name.node.clone());
+            }
+            ast::Stmt::Unification(unification_stmt) => {
+                names.insert(unification_stmt.target.node.get_name());
+            }
+            ast::Stmt::Schema(schema_stmt) => {
+                names.insert(schema_stmt.name.node.clone());
+            }
+            ast::Stmt::Rule(rule_stmt) => {
+                names.insert(rule_stmt.name.node.clone());
+            }
+            ast::Stmt::TypeAlias(type_alias_stmt) => {
+                names.insert(type_alias_stmt.type_name.node.get_name());
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_global_names(&if_stmt.body, names);
+                collect_global_names(&if_stmt.orelse, names);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The attribute names and the base schemas of a schema, which are used to find the
+/// attribute names a schema inherits from its parent schema and mixins.
+#[derive(Debug, Default, Clone)]
+pub struct SchemaAttrNames {
+    /// The attribute names defined or assigned in the schema body.
+    pub attrs: IndexSet<String>,
+    /// The full names of the parent schema and the mixins e.g., `pkg.Base`.
+    pub bases: Vec<String>,
+}
+
+/// Collect the attribute names and the base schemas of all the schemas in the program,
+/// keyed by the full schema names e.g., `pkg.Person`.
+pub fn collect_schema_attr_names(program: &ast::Program) -> IndexMap<String, SchemaAttrNames> {
+    let mut schemas = IndexMap::default();
+    for (pkgpath, modules) in &program.pkgs {
+        for module in modules {
+            let module = match program.get_module(module) {
+                Ok(Some(module)) => module,
+                _ => continue,
+            };
+            // The package paths of the import names and the full names of the selected names.
+            let mut import_paths: IndexMap<String, String> = IndexMap::default();
+            let mut selected_names: IndexMap<String, String> = IndexMap::default();
+            for stmt in &module.body {
+                if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                    let path = &import_stmt.path.node;
+                    if import_stmt.is_selective() {
+                        for name in &import_stmt.selected_names {
+                            selected_names
+                                .insert(name.node.clone(), format!("{path}.{}", name.node));
+                        }
+                    } else {
+                        import_paths.insert(import_stmt.name.clone(), path.clone());
+                    }
+                }
+            }
+            let full_name = |identifier: &ast::Identifier| -> Option<String> {
+                let name = identifier.names.last()?;
+                if !identifier.pkgpath.is_empty() {
+                    return Some(format!("{}.{}", identifier.pkgpath, name.node));
+                }
+                match identifier.names.as_slice() {
+                    [name] => Some(
+                        selected_names
+                            .get(&name.node)
+                            .cloned()
+                            .unwrap_or_else(|| format!("{pkgpath}.{}", name.node)),
+                    ),
+                    [import_name, name] => import_paths
+                        .get(&import_name.node)
+                        .map(|path| format!("{path}.{}", name.node)),
+                    _ => None,
+                }
+            };
+            for stmt in &module.body {
+                if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                    let mut attrs = IndexSet::default();
+                    collect_schema_body_names(&schema_stmt.body, &mut attrs);
+                    let bases = schema_stmt
+                        .parent_name
+                        .iter()
+                        .chain(schema_stmt.mixins.iter())
+                        .filter_map(|base| full_name(&base.node))
+                        .collect();
+                    schemas.insert(
+                        format!("{pkgpath}.{}", schema_stmt.name.node),
+                        SchemaAttrNames { attrs, bases },
+                    );
+                }
+            }
+        }
+    }
+    schemas
+}
+
+/// Get the attribute names the schema inherits from its parent schemas and mixins.
+fn inherited_attr_names(
+    schema: &str,
+    schemas: &IndexMap<String, SchemaAttrNames>,
+    visited: &mut IndexSet<String>,
+) -> IndexSet<String> {
+    let mut names = IndexSet::default();
+    if let Some(schema) = schemas.get(schema) {
+        for base in &schema.bases {
+            // Circular inheritance is reported by the resolver.
+            if !visited.insert(base.clone()) {
+                continue;
+            }
+            if let Some(base_schema) = schemas.get(base) {
+                names.extend(base_schema.attrs.iter().cloned());
+            }
+            names.extend(inherited_attr_names(base, schemas, visited));
+        }
+    }
+    names
+}
+
+#[inline]
+fn argument_names(args: &Option<ast::NodeRef<ast::Arguments>>) -> IndexSet<String> {
+    args.as_ref().map_or(IndexSet::default(), |args| {
+        args.node
+            .args
+            .iter()
+            .map(|arg| arg.node.get_name())
+            .collect()
+    })
+}
+
+#[inline]
+fn comp_clause_names(generators: &[ast::NodeRef<ast::CompClause>]) -> IndexSet<String> {
+    generators
+        .iter()
+        .flat_map(|gen| gen.node.targets.iter())
+        .map(|target| target.node.get_name())
+        .collect()
+}
+
+impl<'ctx> MutSelfMutWalker<'ctx> for SelectiveImportTransformer {
+    fn walk_import_stmt(&mut self, _: &'ctx mut ast::ImportStmt) {}
+    fn walk_schema_stmt(&mut self, schema_stmt: &'ctx mut ast::SchemaStmt) {
+        let mut names = argument_names(&schema_stmt.args);
+        collect_schema_body_names(&schema_stmt.body, &mut names);
+        if let Some(inherited_names) = self.inherited_names.get(&schema_stmt.name.node) {
+            names.extend(inherited_names.iter().cloned());
+        }
+        walk_if_mut!(self, walk_identifier, schema_stmt.parent_name);
+        walk_if_mut!(self, walk_identifier, schema_stmt.for_host_name);
+        walk_list_mut!(self, walk_identifier, schema_stmt.mixins);
+        walk_list_mut!(self, walk_call_expr, schema_stmt.decorators);
+        walk_if_mut!(self, walk_arguments, schema_stmt.args);
+        self.local_names.push(names);
+        if let Some(schema_index_signature) = schema_stmt.index_signature.as_deref_mut() {
+            self.walk_type(&mut schema_index_signature.node.key_ty.node);
+            self.walk_type(&mut schema_index_signature.node.value_ty.node);
+            let value = &mut schema_index_signature.node.value;
+            walk_if_mut!(self, walk_expr, value);
+        }
+        walk_list_mut!(self, walk_stmt, schema_stmt.body);
+        walk_list_mut!(self, walk_check_expr, schema_stmt.checks);
+        self.local_names.pop();
+    }
+    fn walk_rule_stmt(&mut self, rule_stmt: &'ctx mut ast::RuleStmt) {
+        let names = argument_names(&rule_stmt.args);
+        walk_list_mut!(self, walk_identifier, rule_stmt.parent_rules);
+        walk_list_mut!(self, walk_call_expr, rule_stmt.decorators);
+        walk_if_mut!(self, walk_identifier, rule_stmt.for_host_name);
+        walk_if_mut!(self, walk_arguments, rule_stmt.args);
+        self.local_names.push(names);
+        walk_list_mut!(self, walk_check_expr, rule_stmt.checks);
+        self.local_names.pop();
+    }
+    fn walk_lambda_expr(&mut self, lambda_expr: &'ctx mut ast::LambdaExpr) {
+        let names = argument_names(&lambda_expr.args);
+        walk_if_mut!(self, walk_arguments, lambda_expr.args);
+        walk_if_mut!(self, walk_type, lambda_expr.return_ty);
+        self.local_names.push(names);
+        walk_list_mut!(self, walk_stmt, lambda_expr.body);
+        self.local_names.pop();
+    }
+    fn walk_list_comp(&mut self, list_comp: &'ctx mut ast::ListComp) {
+        let names = comp_clause_names(&list_comp.generators);
+        self.local_names.push(names);
+        self.walk_expr(&mut list_comp.elt.node);
+        walk_list_mut!(self, walk_comp_clause, list_comp.generators);
+        self.local_names.pop();
+    }
+    fn walk_dict_comp(&mut self, dict_comp: &'ctx mut ast::DictComp) {
+        let names = comp_clause_names(&dict_comp.generators);
+        self.local_names.push(names);
+        if let Some(key) = dict_comp.entry.key.as_deref_mut() {
+            self.walk_expr(&mut key.node);
+        }
+        self.walk_expr(&mut dict_comp.entry.value.node);
+        walk_list_mut!(self, walk_comp_clause, dict_comp.generators);
+        self.local_names.pop();
+    }
+    fn walk_comp_clause(&mut self, comp_clause: &'ctx mut ast::CompClause) {
+        self.walk_expr(&mut comp_clause.iter.node);
+        walk_list_mut!(self, walk_expr, comp_clause.ifs);
+    }
+    fn walk_quant_expr(&mut self, quant_expr: &'ctx mut ast::QuantExpr) {
+        self.walk_expr(&mut quant_expr.target.node);
+        let names = quant_expr
+            .variables
+            .iter()
+            .map(|variable| variable.node.get_name())
+            .collect();
+        self.local_names.push(names);
+        self.walk_expr(&mut quant_expr.test.node);
+        walk_if_mut!(self, walk_expr, quant_expr.if_cond);
+        self.local_names.pop();
+    }
+    fn walk_selector_expr(&mut self, selector_expr: &'ctx mut ast::SelectorExpr) {
+        // The attribute name e.g., `Name` in `x.Name` is not a selected name.
+        self.walk_expr(&mut selector_expr.value.node);
+    }
+    fn walk_keyword(&mut self, keyword: &'ctx mut ast::Keyword) {
+        walk_if_mut!(self, walk_expr, keyword.value);
+    }
+    fn walk_arguments(&mut self, arguments: &'ctx mut ast::Arguments) {
+        for default in arguments.defaults.iter_mut() {
+            if let Some(d) = default.as_deref_mut() {
+                self.walk_expr(&mut d.node)
+            }
+        }
+        for ty in arguments.ty_list.iter_mut() {
+            if let Some(ty) = ty.as_deref_mut() {
+                self.walk_type(&mut ty.node);
+            }
+        }
+    }
+    fn walk_config_expr(&mut self, config_expr: &'ctx mut ast::ConfigExpr) {
+        for config_entry in config_expr.items.iter_mut() {
+            // The identifier config keys are the attribute names e.g., `Name` in `{Name = 1}`.
+            if let Some(key) = config_entry.node.key.as_deref_mut() {
+                if !matches!(key.node, ast::Expr::Identifier(_)) {
+                    self.walk_expr(&mut key.node);
+                }
+            }
+            self.walk_expr(&mut config_entry.node.value.node);
+        }
+    }
+    fn walk_config_if_entry_expr(
+        &mut self,
+        config_if_entry_expr: &'ctx mut ast::ConfigIfEntryExpr,
+    ) {
+        self.walk_expr(&mut config_if_entry_expr.if_cond.node);
+        for config_entry in config_if_entry_expr.items.iter_mut() {
+            if let Some(key) = config_entry.node.key.as_deref_mut() {
+                if !matches!(key.node, ast::Expr::Identifier(_)) {
+                    self.walk_expr(&mut key.node);
+                }
+            }
+            self.walk_expr(&mut config_entry.node.value.node);
+        }
+        walk_if_mut!(self, walk_expr, config_if_entry_expr.orelse);
+    }
+    fn walk_identifier(&mut self, identifier: &'ctx mut ast::Identifier) {
+        if !identifier.pkgpath.is_empty() || identifier.names.is_empty() {
+            return;
+        }
+        let name = &identifier.names[0];
+        if self.is_shadowed(&name.node) {
+            return;
+        }
+        if let Some(import_name) = self.selected_names.get(&name.node) {
+            // Qualify the selected name with the internal import name, e.g., `Name -> @pkg.Name`.
+            let import_name = ast::Node::node_with_pos(import_name.node.clone(), import_name.pos());
+            identifier.names.insert(0, import_name);
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct RawIdentifierTransformer;
 
@@ -220,6 +555,57 @@ impl<'ctx> MutSelfMutWalker<'ctx> for RawIdentifierTransformer {
     }
 }
 
+/// from path.to.pkg import Name
+///
+/// x = Name -> x = @path.to.pkg.Name
+///
+/// The selective import statements are bound to the internal import names, so the selected
+/// names are qualified by them, which must be fixed before the qualified identifiers.
+pub fn fix_selective_import_identifier<'ctx>(
+    module: &'ctx mut ast::Module,
+    pkgpath: &str,
+    schemas: &IndexMap<String, SchemaAttrNames>,
+) {
+    let mut selected_names = IndexMap::default();
+    for stmt in module.body.iter_mut() {
+        if let ast::Stmt::Import(import_stmt) = &mut stmt.node {
+            if import_stmt.is_selective() {
+                // The import path has been fixed by the loader e.g., the relative path.
+                import_stmt.name = ast::ImportStmt::selective_import_name(&import_stmt.path.node);
+                let import_name =
+                    ast::Node::node_with_pos(import_stmt.name.clone(), import_stmt.path.pos());
+                for name in &import_stmt.selected_names {
+                    selected_names.insert(name.node.clone(), import_name.clone());
+                }
+            }
+        }
+    }
+    if selected_names.is_empty() {
+        return;
+    }
+    let mut global_names = IndexSet::default();
+    collect_global_names(&module.body, &mut global_names);
+    let inherited_names = module
+        .body
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            ast::Stmt::Schema(schema_stmt) => {
+                let schema = format!("{pkgpath}.{}", schema_stmt.name.node);
+                let names = inherited_attr_names(&schema, schemas, &mut IndexSet::default());
+                Some((schema_stmt.name.node.clone(), names))
+            }
+            _ => None,
+        })
+        .collect();
+    let mut walker = SelectiveImportTransformer {
+        selected_names,
+        global_names,
+        inherited_names,
+        ..Default::default()
+    };
+    walker.walk_module(module);
+}
+
 /// import path.to.pkg as pkgname
 ///
 /// x = pkgname.Name
@@ -41,6 +41,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for LitTypeDefaultValueTransformer {
                             ast::Expr::NumberLit(ast::NumberLit {
                                 binary_suffix: val.suffix.clone(),
                                 value: ast::NumberLitValue::Int(val.value),
+                                raw_value: None,
                             }),
                             filename,
                             line,
@@ -56,6 +57,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for LitTypeDefaultValueTransformer {
                             ast::Expr::NumberLit(ast::NumberLit {
                                 binary_suffix: None,
                                 value: ast::NumberLitValue::Float(*val),
+                                raw_value: None,
                             }),
                             filename,
                             line,
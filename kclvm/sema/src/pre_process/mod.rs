@@ -10,7 +10,10 @@ use kclvm_ast::ast;
 mod tests;
 
 pub use config::{fix_config_expr_nest_attr, merge_program};
-pub use identifier::{fix_qualified_identifier, fix_raw_identifier_prefix};
+pub use identifier::{
+    collect_schema_attr_names, fix_qualified_identifier, fix_raw_identifier_prefix,
+    fix_selective_import_identifier, SchemaAttrNames,
+};
 pub use lit_ty_default_value::fix_lit_ty_default_value;
 pub use multi_assign::transform_multi_assign;
 
@@ -18,6 +21,7 @@ use crate::resolver::Options;
 
 /// Pre-process AST program.
 pub fn pre_process_program(program: &mut ast::Program, opts: &Options) {
+    let schema_attr_names = collect_schema_attr_names(program);
     for (pkgpath, modules) in program.pkgs.iter() {
         let mut import_names = IndexMap::default();
         if pkgpath == kclvm_ast::MAIN_PKG {
@@ -44,6 +48,7 @@ pub fn pre_process_program(program: &mut ast::Program, opts: &Options) {
             }
             // First we should transform the raw identifier to avoid raw identifier that happens to be a package path.
             fix_raw_identifier_prefix(&mut module);
+            fix_selective_import_identifier(&mut module, pkgpath, &schema_attr_names);
             fix_qualified_identifier(&mut module, &mut import_names);
             fix_config_expr_nest_attr(&mut module);
             fix_lit_ty_default_value(&mut module);
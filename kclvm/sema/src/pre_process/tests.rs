@@ -46,6 +46,7 @@ fn test_fix_lit_ty_default_value() {
                 ast::Expr::NumberLit(ast::NumberLit {
                     value: ast::NumberLitValue::Int(1),
                     binary_suffix: None,
+                    raw_value: None,
                 })
             )
         } else {
@@ -57,6 +58,7 @@ fn test_fix_lit_ty_default_value() {
                 ast::Expr::NumberLit(ast::NumberLit {
                     value: ast::NumberLitValue::Int(1),
                     binary_suffix: Some(ast::NumberBinarySuffix::Ki),
+                    raw_value: None,
                 })
             )
         } else {
@@ -68,6 +70,7 @@ fn test_fix_lit_ty_default_value() {
                 ast::Expr::NumberLit(ast::NumberLit {
                     value: ast::NumberLitValue::Float(2.0),
                     binary_suffix: None,
+                    raw_value: None,
                 })
             )
         } else {
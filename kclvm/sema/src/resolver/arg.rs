@@ -1,5 +1,5 @@
 use crate::resolver::Resolver;
-use crate::ty::FunctionType;
+use crate::ty::{FunctionType, Parameter};
 use compiler_base_error::unit_type::{TypeWithUnit, UnitUsize};
 use indexmap::IndexSet;
 use kclvm_ast::ast;
@@ -157,6 +157,42 @@ impl<'ctx> Resolver<'_> {
         }
     }
 
+    /// Returns the parameters of the lambda which are not given by the call arguments, when
+    /// any of them is required i.e., the call is a partial application returning a new
+    /// lambda of these parameters. Only the functions typed as lambdas can be partially
+    /// applied, see [FunctionType::is_lambda].
+    pub(crate) fn get_partial_params(
+        &self,
+        args: &[ast::NodeRef<ast::Expr>],
+        kwargs: &[ast::NodeRef<ast::Keyword>],
+        func_ty: &FunctionType,
+    ) -> Option<Vec<Parameter>> {
+        if !func_ty.is_lambda {
+            return None;
+        }
+        let params: Vec<Parameter> = func_ty
+            .params
+            .iter()
+            .skip(args.len())
+            .filter(|param| {
+                !kwargs.iter().any(|kw| {
+                    kw.node
+                        .arg
+                        .node
+                        .names
+                        .first()
+                        .map_or(false, |name| name.node == param.name)
+                })
+            })
+            .cloned()
+            .collect();
+        if params.iter().any(|param| !param.has_default) {
+            Some(params)
+        } else {
+            None
+        }
+    }
+
     /// Generate suggestions for keyword argument errors.
     pub(crate) fn get_arg_kw_err_suggestion_from_name(
         &self,
@@ -27,6 +27,7 @@ impl<'ctx> Resolver<'_> {
                     ),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
         }
@@ -68,6 +69,9 @@ impl<'ctx> Resolver<'_> {
             TypeKind::Schema(schema_ty) => {
                 let (result, schema_attr_ty) = self.schema_load_attr(schema_ty, attr);
                 if result {
+                    if let Some(attr_obj) = schema_ty.get_obj_of_attr(attr) {
+                        self.check_deprecated(attr, &attr_obj.decorators, &range);
+                    }
                     (result, schema_attr_ty)
                 } else if schema_ty.is_member_functions(attr) {
                     (
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::lint::LintPass;
 use crate::resolver::Resolver;
 use crate::ty::{
     has_any_type, is_upper_bound, sup, Type, TypeInferMethods, TypeKind, TypeRef, ZERO_LIT_TYPES,
@@ -296,6 +297,7 @@ impl<'ctx> Resolver<'ctx> {
                 .is_primitive_type_or_primitive_union_type(t2.clone())
             && matches!(op, ast::CmpOp::Eq | ast::CmpOp::NotEq)
         {
+            self.check_incompatible_comparison(&t1, &t2, op, range);
             return self.bool_ty();
         }
         if matches!(op, ast::CmpOp::Eq) && t1.is_list() && t2.is_list() {
@@ -330,4 +332,28 @@ impl<'ctx> Resolver<'ctx> {
         );
         self.any_ty()
     }
+
+    /// Check the comparison with the operand types inferred to the variable types by the
+    /// lint passes e.g., the incompatible comparison lint for `1 == "1"`.
+    fn check_incompatible_comparison(
+        &mut self,
+        t1: &TypeRef,
+        t2: &TypeRef,
+        op: &ast::CmpOp,
+        range: Range,
+    ) {
+        if !self.options.lint_check {
+            return;
+        }
+        let t1 = self.ctx.ty_ctx.infer_to_variable_type(t1.clone());
+        let t2 = self.ctx.ty_ctx.infer_to_variable_type(t2.clone());
+        self.linter.pass.check_compare_types(
+            &mut self.linter.handler,
+            &mut self.linter.ctx,
+            &t1,
+            &t2,
+            op,
+            &range,
+        );
+    }
 }
@@ -302,17 +302,13 @@ impl<'ctx> Resolver<'_> {
     ///
     /// Args:
     ///     name: the name of key
-    ///     key: the ast node of key
+    ///     range: the exact range of the key name
     ///     check_rules: the constraints, such as 'check_defined'
-    pub(crate) fn check_config_expr_by_key_name(
-        &mut self,
-        name: &str,
-        key: &'ctx ast::NodeRef<ast::Expr>,
-    ) {
+    pub(crate) fn check_config_expr_by_key_name(&mut self, name: &str, range: &Range) {
         if !name.is_empty() {
             if let Some(Some(obj)) = self.ctx.config_expr_context.last() {
                 let ty = obj.ty.clone();
-                self.must_check_config_attr(name, &ty, &key.get_span_pos(), None);
+                self.must_check_config_attr(name, &ty, range, None);
             }
         }
     }
@@ -381,17 +377,25 @@ impl<'ctx> Resolver<'_> {
     ) -> Option<TypeRef> {
         if let Some(key) = key {
             if let Some(Some(_)) = self.ctx.config_expr_context.last() {
-                let names: Vec<String> = match &key.node {
-                    ast::Expr::Identifier(identifier) => identifier.get_names(),
+                // The name of each key part and its exact range.
+                let identifier_names = |identifier: &ast::Identifier| -> Vec<(String, Range)> {
+                    identifier
+                        .names
+                        .iter()
+                        .map(|name| (name.node.clone(), name.get_span_pos()))
+                        .collect()
+                };
+                let names: Vec<(String, Range)> = match &key.node {
+                    ast::Expr::Identifier(identifier) => identifier_names(identifier),
                     ast::Expr::Subscript(subscript) => {
                         if let ast::Expr::Identifier(identifier) = &subscript.value.node {
                             if let Some(index) = &subscript.index {
                                 if matches!(index.node, ast::Expr::NumberLit(_)) {
-                                    identifier.get_names()
+                                    identifier_names(identifier)
                                 } else if let ast::Expr::Unary(unary_expr) = &index.node {
                                     // Negative index constant
                                     if matches!(unary_expr.operand.node, ast::Expr::NumberLit(_)) {
-                                        identifier.get_names()
+                                        identifier_names(identifier)
                                     } else {
                                         return None;
                                     }
@@ -405,12 +409,14 @@ impl<'ctx> Resolver<'_> {
                             return None;
                         }
                     }
-                    ast::Expr::StringLit(string_lit) => vec![string_lit.value.clone()],
+                    ast::Expr::StringLit(string_lit) => {
+                        vec![(string_lit.value.clone(), key.get_span_pos())]
+                    }
                     _ => return None,
                 };
                 let mut stack_depth = 0;
-                for name in &names {
-                    self.check_config_expr_by_key_name(name, key);
+                for (name, range) in &names {
+                    self.check_config_expr_by_key_name(name, range);
                     stack_depth += self.switch_config_expr_context_by_name(name);
                 }
                 let mut val_ty = self.expr(value);
@@ -446,26 +452,37 @@ impl<'ctx> Resolver<'_> {
         &self,
         attr: &str,
         schema_ty: &SchemaType,
-    ) -> (Vec<String>, String) {
+    ) -> (Option<Vec<String>>, String) {
         self.get_config_attr_err_suggestion(attr, schema_ty.attrs.keys())
     }
 
+    /// Get the suggested replacement and the "did you mean" message for the undeclared
+    /// config attribute. The close attributes in the message are ordered by the edit
+    /// distance, and the replacement is only suggested when there is exactly one close
+    /// attribute, which makes it safe to be applied automatically.
     pub(crate) fn get_config_attr_err_suggestion<I, T>(
         &self,
         attr: &str,
         keys: I,
-    ) -> (Vec<String>, String)
+    ) -> (Option<Vec<String>>, String)
     where
         T: AsRef<str>,
         I: IntoIterator<Item = T>,
     {
-        let mut suggestion = String::new();
+        let keys: Vec<String> = keys
+            .into_iter()
+            .map(|k| k.as_ref().to_string())
+            .filter(|k| !k.starts_with("__"))
+            .collect();
         // Calculate the closest miss attributes.
-        let suggs = suggestions::provide_suggestions(attr, keys);
-        if suggs.len() > 0 {
-            suggestion = format!(", did you mean '{:?}'?", suggs);
+        let mut suggs = suggestions::provide_suggestions(attr, &keys);
+        suggs.sort_by_key(|k| edit_distance(attr, k));
+        if suggs.is_empty() {
+            (None, String::new())
+        } else {
+            let suggestion = format!(", did you mean '{:?}'?", suggs);
+            ((suggs.len() == 1).then_some(suggs), suggestion)
         }
-        (suggs, suggestion)
     }
 
     /// Check config attr has been defined.
@@ -520,16 +537,27 @@ impl<'ctx> Resolver<'_> {
                         },
                     ),
                     note: None,
-                    suggested_replacement: Some(total_suggs),
+                    ..Default::default()
                 }];
+                // Only the single close attribute is safe to be applied. The range denotes
+                // the config value instead of the key when the attribute range is given,
+                // thus the replacement is attached to the message on the key range.
+                let suggs = if total_suggs.len() == 1 {
+                    Some(total_suggs)
+                } else {
+                    None
+                };
                 if let Some(attr_range) = attr_range {
                     msgs.push(Message {
                         range: attr_range.clone(),
                         style: Style::LineAndColumn,
                         message: "config attribute is defined here".to_string(),
                         note: None,
-                        suggested_replacement: None,
+                        suggested_replacement: suggs,
+                        ..Default::default()
                     });
+                } else {
+                    msgs[0].suggested_replacement = suggs;
                 }
                 self.handler.add_error(ErrorKind::CompileError, &msgs);
             }
@@ -576,6 +604,9 @@ impl<'ctx> Resolver<'_> {
             let (suggs, msg) = self.get_config_attr_err_suggestion_from_schema(attr, schema_ty);
             self.add_config_attr_error(attr, schema_ty, range, attr_range, suggs, msg);
         }
+        if let Some(attr_obj) = schema_ty.get_obj_of_attr(attr) {
+            self.check_deprecated(attr, &attr_obj.decorators, attr_range.unwrap_or(range));
+        }
         if let Some(index_signature) = &schema_ty.index_signature {
             // Here we need to check whether the key of the index signature is a string literal type or a string literal union types
             if !index_signature.any_other {
@@ -614,7 +645,7 @@ impl<'ctx> Resolver<'_> {
         schema_ty: &SchemaType,
         range: &Range,
         attr_range: Option<&Range>,
-        suggs: Vec<String>,
+        suggs: Option<Vec<String>>,
         msg: String,
     ) {
         let mut msgs = vec![Message {
@@ -625,16 +656,21 @@ impl<'ctx> Resolver<'_> {
                 attr, schema_ty.name, msg,
             ),
             note: None,
-            suggested_replacement: Some(suggs),
+            ..Default::default()
         }];
+        // The range denotes the config value instead of the key when the attribute range
+        // is given, thus the replacement is attached to the message on the key range.
         if let Some(attr_range) = attr_range {
             msgs.push(Message {
                 range: attr_range.clone(),
                 style: Style::LineAndColumn,
                 message: "config attribute is defined here".to_string(),
                 note: None,
-                suggested_replacement: None,
+                suggested_replacement: suggs,
+                ..Default::default()
             });
+        } else {
+            msgs[0].suggested_replacement = suggs;
         }
         self.handler.add_error(ErrorKind::CompileError, &msgs);
     }
@@ -867,6 +903,7 @@ impl<'ctx> Resolver<'_> {
                         ),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
             }
@@ -878,3 +915,18 @@ impl<'ctx> Resolver<'_> {
         Type::dict_ref_with_attrs(key_ty, val_ty, attrs)
     }
 }
+
+/// Calculate the Levenshtein edit distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
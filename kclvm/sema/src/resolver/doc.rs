@@ -245,6 +245,11 @@ fn parse_summary(doc: &mut Reader) -> String {
         .to_string()
 }
 
+/// Strip the quotes and the common indentation of the docstring, e.g., the module docstring.
+pub fn parse_doc_string(ori: &str) -> String {
+    clean_doc(strip_quotes(ori)).trim().to_string()
+}
+
 /// Parse the schema docstring to Doc.
 /// The summary of the schema content will be concatenated to a single line string by whitespace.
 /// The description of each attribute will be returned as separate lines.
@@ -2,14 +2,16 @@ use std::cell::RefCell;
 use std::sync::Arc;
 
 use crate::info::is_private_field;
+use crate::lint::REDUNDANT_ATTRIBUTE;
 use crate::resolver::Resolver;
 use crate::ty::{
     full_ty_str, is_upper_bound, DecoratorTarget, FunctionType, Parameter, SchemaAttr,
-    SchemaIndexSignature, SchemaType, Type, TypeKind, RESERVED_TYPE_IDENTIFIERS,
+    SchemaIndexSignature, SchemaType, Type, TypeKind, TypeRef, RESERVED_TYPE_IDENTIFIERS,
 };
 use indexmap::IndexMap;
 use kclvm_ast::ast;
 use kclvm_ast_pretty::{print_ast_node, print_schema_expr, ASTNode};
+use kclvm_error::diagnostic::Range;
 use kclvm_error::*;
 
 use super::doc::parse_schema_doc_string;
@@ -84,6 +86,16 @@ impl<'ctx> Resolver<'_> {
                                     message: format!("Unique key error name '{}'", name),
                                     note: None,
                                     suggested_replacement: None,
+                                    related: vec![(
+                                        self.scope
+                                            .borrow()
+                                            .elems
+                                            .get(name)
+                                            .unwrap()
+                                            .borrow()
+                                            .get_span_pos(),
+                                        format!("'{}' is first defined here", name),
+                                    )],
                                 }],
                             );
                             continue;
@@ -110,6 +122,7 @@ impl<'ctx> Resolver<'_> {
                                 return_ty: Arc::new(Type::VOID),
                                 is_variadic: false,
                                 kw_only_index: None,
+                                is_lambda: false,
                             }),
                             index_signature: None,
                             decorators: vec![],
@@ -219,6 +232,7 @@ impl<'ctx> Resolver<'_> {
                         message: format!("pkgpath {} not found in the program", self.ctx.pkgpath),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
             }
@@ -268,6 +282,7 @@ impl<'ctx> Resolver<'_> {
                         ),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         },
                         Message {
                             range: self
@@ -285,6 +300,7 @@ impl<'ctx> Resolver<'_> {
                                 name
                             )),
                             suggested_replacement: None,
+                            ..Default::default()
                         },
                     ],
                 );
@@ -309,6 +325,7 @@ impl<'ctx> Resolver<'_> {
                                     ),
                                     note: None,
                                     suggested_replacement: None,
+                                    ..Default::default()
                                 },
                                 Message {
                                     range: obj.get_span_pos(),
@@ -316,6 +333,7 @@ impl<'ctx> Resolver<'_> {
                                     message: format!("expected {}", obj.ty.ty_str()),
                                     note: None,
                                     suggested_replacement: None,
+                                    ..Default::default()
                                 },
                             ],
                         );
@@ -384,6 +402,7 @@ impl<'ctx> Resolver<'_> {
                             ),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                     None
@@ -408,6 +427,7 @@ impl<'ctx> Resolver<'_> {
                         message: "only schema mixin can inherit from protocol".to_string(),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
                 return None;
@@ -430,6 +450,7 @@ impl<'ctx> Resolver<'_> {
                             ),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                     None
@@ -464,6 +485,7 @@ impl<'ctx> Resolver<'_> {
                             ),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                     None
@@ -474,6 +496,105 @@ impl<'ctx> Resolver<'_> {
         }
     }
 
+    /// Whether the [REDUNDANT_ATTRIBUTE] lint is checked, which needs the printed default
+    /// values of the schema attributes.
+    #[inline]
+    fn is_redundant_attribute_enabled(&self) -> bool {
+        self.options.lint_check && self.linter.pass.is_enabled(REDUNDANT_ATTRIBUTE)
+    }
+
+    /// Report the [REDUNDANT_ATTRIBUTE] lint for the schema attribute redeclared with the same
+    /// type, optionality and default value as the attribute inherited from the parent schema
+    /// or the mixins of it. The attributes without the default values or with the decorators
+    /// are not reported, nor are the ones reassigned in the bodies of the parent schemas or
+    /// their mixins, whose redeclarations reset the values. The default values are compared
+    /// by the printed code, which only denotes the same value for the constant literals or
+    /// the default values in the same module, where the names are resolved in the same way.
+    fn check_redundant_attribute(
+        &mut self,
+        schema_name: &str,
+        schema_attr: &ast::SchemaAttr,
+        ty: &TypeRef,
+        default: &Option<String>,
+        range: Range,
+        parent_ty: &Option<Box<SchemaType>>,
+    ) {
+        if !self.is_redundant_attribute_enabled() {
+            return;
+        }
+        let name = &schema_attr.name.node;
+        let (parent_ty, parent_attr) = match parent_ty
+            .as_ref()
+            .and_then(|parent_ty| get_inherited_attr(parent_ty, name).map(|attr| (parent_ty, attr)))
+        {
+            Some(parent) => parent,
+            None => return,
+        };
+        let is_same_value = match &schema_attr.value {
+            Some(value) => {
+                is_constant_literal(&value.node) || parent_attr.range.0.filename == range.0.filename
+            }
+            None => false,
+        };
+        if default.is_none()
+            || !is_same_value
+            || default != &parent_attr.default
+            || schema_attr.is_optional != parent_attr.is_optional
+            || !schema_attr.decorators.is_empty()
+            || !parent_attr.decorators.is_empty()
+            || ty.full_ty_str() != parent_attr.ty.full_ty_str()
+            || self.is_attr_reassigned_in_parents(name, parent_ty)
+        {
+            return;
+        }
+        let mut related = vec![];
+        if !parent_attr.range.0.filename.is_empty() {
+            related.push((
+                parent_attr.range.clone(),
+                format!("the attribute '{}' is declared here", name),
+            ));
+        }
+        self.linter.handler.add_warning(
+            WarningKind::RedundantAttributeWarning,
+            &[Message {
+                range,
+                style: Style::LineAndColumn,
+                message: format!(
+                    "the attribute '{}' of schema '{}' is redundant, it is declared with the same type and default value in the parent schema '{}'",
+                    name, schema_name, parent_ty.name
+                ),
+                note: REDUNDANT_ATTRIBUTE.note.map(|note| note.to_string()),
+                suggested_replacement: None,
+                related,
+            }],
+        );
+    }
+
+    /// Whether the attribute is assigned in the bodies of the schema, its parent schemas or
+    /// the mixins of them.
+    fn is_attr_reassigned_in_parents(&self, name: &str, schema_ty: &SchemaType) -> bool {
+        let mut current = Some(schema_ty);
+        while let Some(schema_ty) = current {
+            for ty in std::iter::once(schema_ty).chain(schema_ty.mixins.iter()) {
+                let module = match self.program.get_module(&ty.filename) {
+                    Ok(Some(module)) => module,
+                    _ => continue,
+                };
+                let reassigned = module.body.iter().any(|stmt| match &stmt.node {
+                    ast::Stmt::Schema(schema_stmt) if schema_stmt.name.node == ty.name => {
+                        is_attr_assigned_in_stmts(name, &schema_stmt.body)
+                    }
+                    _ => false,
+                });
+                if reassigned {
+                    return true;
+                }
+            }
+            current = schema_ty.base.as_deref();
+        }
+        false
+    }
+
     pub(crate) fn build_schema_type(
         &mut self,
         schema_stmt: &'ctx ast::SchemaStmt,
@@ -520,6 +641,7 @@ impl<'ctx> Resolver<'_> {
                     message: format!("mixin inheritance {} is prohibited", parent_name),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
         }
@@ -539,6 +661,7 @@ impl<'ctx> Resolver<'_> {
                             message: format!("index signature attribute name '{}' cannot have the same name as schema attributes", index_sign_name.node),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                 }
@@ -564,6 +687,7 @@ impl<'ctx> Resolver<'_> {
                         message: format!("invalid index signature key type: '{}'", key_ty.ty_str()),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
             }
@@ -610,8 +734,9 @@ impl<'ctx> Resolver<'_> {
                     let ty = self
                         .parse_ty_with_scope(Some(&schema_attr.ty), schema_attr.ty.get_span_pos());
                     let is_optional = schema_attr.is_optional;
+                    // The default values are also compared by the redundant attribute lint.
                     let default = schema_attr.value.as_ref().map(|v| {
-                        if self.options.resolve_val {
+                        if self.options.resolve_val || self.is_redundant_attribute_enabled() {
                             print_ast_node(ASTNode::Expr(v))
                         } else {
                             "".to_string()
@@ -623,6 +748,14 @@ impl<'ctx> Resolver<'_> {
                         DecoratorTarget::Attribute,
                         &name,
                     );
+                    self.check_redundant_attribute(
+                        &schema_stmt.name.node,
+                        schema_attr,
+                        &ty,
+                        &default,
+                        stmt.get_span_pos(),
+                        &parent_ty,
+                    );
                     (
                         name,
                         ty,
@@ -694,6 +827,7 @@ impl<'ctx> Resolver<'_> {
                             message: format!("the type '{}' of schema attribute '{}' does not meet the index signature definition {}", ty.ty_str(), name, index_signature_obj.ty_str()),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                 }
@@ -701,6 +835,8 @@ impl<'ctx> Resolver<'_> {
         }
         // Mixin types
         let mut mixin_types: Vec<SchemaType> = vec![];
+        // The mixin name and the definition of each attribute defined in mixins.
+        let mut mixin_attrs: IndexMap<String, (String, SchemaAttr)> = IndexMap::default();
         for mixin in &schema_stmt.mixins {
             let mixin_names = &mixin.node.get_names();
             if !mixin_names[mixin_names.len() - 1].ends_with(MIXIN_SUFFIX) {
@@ -715,6 +851,7 @@ impl<'ctx> Resolver<'_> {
                         ),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     }],
                 );
             }
@@ -737,6 +874,7 @@ impl<'ctx> Resolver<'_> {
                             ),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                     None
@@ -745,6 +883,66 @@ impl<'ctx> Resolver<'_> {
 
             if let Some(mixin_ty) = mixin_ty {
                 for (name, attr) in &mixin_ty.attrs {
+                    // Check the attribute type conflicts with the former mixins and the parent schema.
+                    let mut defined_attrs = vec![];
+                    if let Some((mixin_name, defined_attr)) = mixin_attrs.get(name) {
+                        defined_attrs.push((format!("mixin '{}'", mixin_name), defined_attr));
+                    }
+                    if let Some(parent_ty) = &parent_ty {
+                        if let Some(defined_attr) = parent_ty.get_obj_of_attr(name) {
+                            defined_attrs.push((
+                                format!("parent schema '{}'", parent_ty.name),
+                                defined_attr,
+                            ));
+                        }
+                    }
+                    for (defined_in, defined_attr) in defined_attrs {
+                        if is_upper_bound(defined_attr.ty.clone(), attr.ty.clone())
+                            || is_upper_bound(attr.ty.clone(), defined_attr.ty.clone())
+                        {
+                            continue;
+                        }
+                        let mut msgs = vec![Message {
+                            range: mixin.get_span_pos(),
+                            style: Style::LineAndColumn,
+                            message: format!(
+                                "conflicting types of the attribute '{}' in schema '{}': {} in mixin '{}' and {} in {}",
+                                name,
+                                schema_stmt.name.node,
+                                attr.ty.ty_str(),
+                                mixin_ty.name,
+                                defined_attr.ty.ty_str(),
+                                defined_in
+                            ),
+                            note: None,
+                            suggested_replacement: None,
+                            ..Default::default()
+                        }];
+                        for (site, site_attr) in [
+                            (format!("mixin '{}'", mixin_ty.name), attr),
+                            (defined_in, defined_attr),
+                        ] {
+                            if !site_attr.range.0.filename.is_empty() {
+                                msgs.push(Message {
+                                    range: site_attr.range.clone(),
+                                    style: Style::LineAndColumn,
+                                    message: format!(
+                                        "the attribute '{}' is defined in {} with type {}",
+                                        name,
+                                        site,
+                                        site_attr.ty.ty_str()
+                                    ),
+                                    note: None,
+                                    suggested_replacement: None,
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        self.handler.add_error(ErrorKind::TypeError, &msgs);
+                    }
+                    if !mixin_attrs.contains_key(name) {
+                        mixin_attrs.insert(name.to_string(), (mixin_ty.name.clone(), attr.clone()));
+                    }
                     if !attr_obj_map.contains_key(name) {
                         attr_obj_map.insert(name.to_string(), attr.clone());
                     }
@@ -846,6 +1044,7 @@ impl<'ctx> Resolver<'_> {
                 return_ty: Arc::new(Type::ANY),
                 is_variadic: false,
                 kw_only_index: None,
+                is_lambda: false,
             }),
             index_signature,
             decorators,
@@ -890,6 +1089,7 @@ impl<'ctx> Resolver<'_> {
                             message: format!("illegal rule type '{}'", ty.ty_str()),
                             note: None,
                             suggested_replacement: None,
+                            ..Default::default()
                         }],
                     );
                     None
@@ -998,9 +1198,56 @@ impl<'ctx> Resolver<'_> {
                 return_ty: Arc::new(Type::ANY),
                 is_variadic: false,
                 kw_only_index: None,
+                is_lambda: false,
             }),
             index_signature,
             decorators,
         }
     }
 }
+
+/// Get the attribute inherited from the schema, its mixins or the parent schemas of them,
+/// and the attributes declared in the schema take precedence over the ones in the mixins.
+fn get_inherited_attr<'a>(schema_ty: &'a SchemaType, name: &str) -> Option<&'a SchemaAttr> {
+    let mut current = Some(schema_ty);
+    while let Some(schema_ty) = current {
+        if let Some(attr) = std::iter::once(schema_ty)
+            .chain(schema_ty.mixins.iter())
+            .find_map(|ty| ty.attrs.get(name))
+        {
+            return Some(attr);
+        }
+        current = schema_ty.base.as_deref();
+    }
+    None
+}
+
+/// Whether the expression is a constant literal e.g., `1`, `-1.5`, `"app"` and `True`,
+/// whose value does not depend on where it is evaluated.
+fn is_constant_literal(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::NumberLit(_) | ast::Expr::StringLit(_) | ast::Expr::NameConstantLit(_) => true,
+        ast::Expr::Unary(unary_expr) => {
+            matches!(unary_expr.op, ast::UnaryOp::UAdd | ast::UnaryOp::USub)
+                && matches!(unary_expr.operand.node, ast::Expr::NumberLit(_))
+        }
+        _ => false,
+    }
+}
+
+/// Whether the attribute is assigned in the statements including the nested `if` bodies.
+fn is_attr_assigned_in_stmts(name: &str, stmts: &[ast::NodeRef<ast::Stmt>]) -> bool {
+    stmts.iter().any(|stmt| match &stmt.node {
+        ast::Stmt::Assign(assign_stmt) => assign_stmt
+            .targets
+            .iter()
+            .any(|target| target.node.get_name() == name),
+        ast::Stmt::AugAssign(aug_assign_stmt) => aug_assign_stmt.target.node.get_name() == name,
+        ast::Stmt::Unification(unification_stmt) => unification_stmt.target.node.get_name() == name,
+        ast::Stmt::If(if_stmt) => {
+            is_attr_assigned_in_stmts(name, &if_stmt.body)
+                || is_attr_assigned_in_stmts(name, &if_stmt.orelse)
+        }
+        _ => false,
+    })
+}
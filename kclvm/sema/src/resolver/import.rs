@@ -3,7 +3,7 @@ use crate::resolver::scope::Module;
 use crate::resolver::Resolver;
 use crate::ty::ModuleKind;
 use crate::{
-    builtin::system_module::STANDARD_SYSTEM_MODULES,
+    builtin::system_module::{get_system_module_members, STANDARD_SYSTEM_MODULES},
     ty::{Type, TypeKind},
 };
 use indexmap::{IndexMap, IndexSet};
@@ -15,7 +15,9 @@ use std::{cell::RefCell, path::Path};
 
 use super::scope::{Scope, ScopeKind, ScopeObject, ScopeObjectKind};
 use kclvm_ast::pos::GetPos;
+use kclvm_error::diagnostic::Range;
 use kclvm_utils::pkgpath::parse_external_pkg_name;
+use petgraph::graph::NodeIndex;
 
 impl<'ctx> Resolver<'ctx> {
     /// Check import error
@@ -56,6 +58,7 @@ impl<'ctx> Resolver<'ctx> {
                                         ),
                                         note: None,
                                         suggested_replacement: None,
+                                        ..Default::default()
                                     }],
                                 );
                             } else {
@@ -71,6 +74,7 @@ impl<'ctx> Resolver<'ctx> {
                                         ),
                                         note: None,
                                         suggested_replacement: None,
+                                        ..Default::default()
                                     }],
                                 );
                                 let mut suggestions = vec![format!(
@@ -102,6 +106,7 @@ impl<'ctx> Resolver<'ctx> {
                                         ),
                                         note: None,
                                         suggested_replacement: None,
+                                        ..Default::default()
                                     }],
                                 );
                             }
@@ -125,7 +130,7 @@ impl<'ctx> Resolver<'ctx> {
         let modules = self.program.pkgs.get(&self.ctx.pkgpath);
         match modules {
             Some(modules) => {
-                let mut import_table: IndexMap<String, String> = IndexMap::default();
+                let mut import_table: IndexMap<String, (String, Range)> = IndexMap::default();
                 for module in modules {
                     let module = self
                         .program
@@ -138,20 +143,24 @@ impl<'ctx> Resolver<'ctx> {
                             // 'import sub as s' and 'import sub.sub as s' will raise this error.
                             // 'import sub' and 'import sub' will not raise this error.
                             // 'import sub as s' and 'import sub as s' will not raise this error.
-                            if let Some(path) = import_table.get(&import_stmt.name) {
+                            if let Some((path, range)) = import_table.get(&import_stmt.name) {
                                 if path != &import_stmt.path.node {
-                                    self.handler.add_compile_error(
+                                    self.handler.add_compile_error_with_related(
                                         &format!(
                                             "the name '{}' is defined multiple times, '{}' must be defined only once",
                                             import_stmt.name, import_stmt.name
                                         ),
                                         stmt.get_span_pos(),
+                                        vec![(
+                                            range.clone(),
+                                            format!("'{}' is first defined here", import_stmt.name),
+                                        )],
                                     );
                                 }
                             } else {
                                 import_table.insert(
                                     import_stmt.name.clone(),
-                                    import_stmt.path.node.clone(),
+                                    (import_stmt.path.node.clone(), stmt.get_span_pos()),
                                 );
                             }
                             match self.ctx.import_names.get_mut(&self.ctx.filename) {
@@ -244,6 +253,7 @@ impl<'ctx> Resolver<'ctx> {
                                     }
                                 };
                                 if !is_user_module {
+                                    self.check_selected_names(import_stmt);
                                     continue;
                                 }
                             }
@@ -272,12 +282,14 @@ impl<'ctx> Resolver<'ctx> {
                                         .collect();
                                     for node in &cycle {
                                         if let Some(range) = self.ctx.ty_ctx.get_node_range(node) {
-                                            self.handler.add_compile_error(
+                                            let related = self.cyclic_import_related(&cycle, node);
+                                            self.handler.add_compile_error_with_related(
                                                 &format!(
                                                     "There is a circular reference between modules {}",
                                                     node_names.join(", "),
                                                 ),
-                                                range
+                                                range,
+                                                related,
                                             );
                                         }
                                     }
@@ -289,6 +301,7 @@ impl<'ctx> Resolver<'ctx> {
                             }
                             // Restore the current context
                             self.change_package_context(&current_pkgpath, &current_filename);
+                            self.check_selected_names(import_stmt);
                         }
                     }
                 }
@@ -297,6 +310,63 @@ impl<'ctx> Resolver<'ctx> {
         }
     }
 
+    /// Get the import ranges of the other modules in the import cycle with their labels,
+    /// which are the related ranges of the circular reference error of the module `node`.
+    fn cyclic_import_related(&self, cycle: &[NodeIndex], node: &NodeIndex) -> Vec<(Range, String)> {
+        cycle
+            .iter()
+            .filter(|idx| *idx != node)
+            .filter_map(|idx| {
+                let range = self.ctx.ty_ctx.get_node_range(idx)?;
+                let name = self.ctx.ty_ctx.dep_graph.node_weight(*idx)?;
+                Some((
+                    range,
+                    format!("the module '{}' in the cycle is imported here", name),
+                ))
+            })
+            .collect()
+    }
+
+    /// Check the selected names of the selective import e.g., `from pkg import Name`
+    /// are defined in the imported package.
+    fn check_selected_names(&mut self, import_stmt: &ast::ImportStmt) {
+        let pkgpath = &import_stmt.path.node;
+        // The plugin module members are dynamic.
+        if pkgpath.starts_with(PLUGIN_MODULE_PREFIX) {
+            return;
+        }
+        for name in &import_stmt.selected_names {
+            let is_defined = if STANDARD_SYSTEM_MODULES.contains(&pkgpath.as_str()) {
+                get_system_module_members(pkgpath).contains(&name.node.as_str())
+            } else {
+                match self.scope_map.get(pkgpath) {
+                    // The modules imported by the package are not its members.
+                    Some(scope) => scope.borrow().elems.get(&name.node).map_or(false, |obj| {
+                        !matches!(obj.borrow().kind, ScopeObjectKind::Module(_))
+                    }),
+                    // The invalid package has been reported.
+                    None => true,
+                }
+            };
+            if !is_defined {
+                self.handler.add_error(
+                    ErrorKind::NameError,
+                    &[Message {
+                        range: name.get_span_pos(),
+                        style: Style::LineAndColumn,
+                        message: format!(
+                            "cannot import name '{}' from the module '{}'",
+                            name.node, import_stmt.rawpath
+                        ),
+                        note: None,
+                        suggested_replacement: None,
+                        ..Default::default()
+                    }],
+                );
+            }
+        }
+    }
+
     pub(crate) fn change_package_context(&mut self, pkgpath: &str, filename: &str) {
         if pkgpath.is_empty() {
             return;
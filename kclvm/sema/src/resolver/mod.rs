@@ -31,7 +31,7 @@ use crate::resolver::ty_alias::type_alias_pass;
 use crate::resolver::ty_erasure::type_func_erasure_pass;
 use crate::ty::TypeContext;
 use crate::{resolver::scope::Scope, ty::SchemaType};
-use kclvm_ast::ast::Program;
+use kclvm_ast::ast::{AstIndex, Program};
 use kclvm_error::*;
 
 use self::scope::{builtin_scope, KCLScopeCache, NodeTyMap, ProgramScope};
@@ -55,6 +55,7 @@ impl<'ctx> Resolver<'ctx> {
     pub fn new(program: &'ctx Program, options: Options) -> Self {
         let builtin_scope = Rc::new(RefCell::new(builtin_scope()));
         let scope = Rc::clone(&builtin_scope);
+        let linter = Linter::<CombinedLintPass>::new_with_disabled_lints(&options.disabled_lints);
         Resolver {
             program,
             scope_map: IndexMap::default(),
@@ -65,7 +66,7 @@ impl<'ctx> Resolver<'ctx> {
             ctx: Context::default(),
             options,
             handler: Handler::default(),
-            linter: Linter::<CombinedLintPass>::new(),
+            linter,
         }
     }
 
@@ -106,8 +107,31 @@ impl<'ctx> Resolver<'ctx> {
         self.check(kclvm_ast::MAIN_PKG);
         self.lint_check_scope_map();
         let mut handler = self.handler.clone();
+        let all_lints = CombinedLintPass::get_lints();
+        let denied_lints: Vec<_> = all_lints
+            .iter()
+            .filter(|lint| self.options.denied_lints.iter().any(|l| lint.matches(l)))
+            .collect();
         for diag in &self.linter.handler.diagnostics {
-            handler.diagnostics.insert(diag.clone());
+            // Skip the lint warnings disabled by the lint directives in the comments.
+            if self.linter.directives.iter().any(|d| d.suppresses(diag)) {
+                continue;
+            }
+            let mut diag = diag.clone();
+            if let Some(DiagnosticId::Warning(kind)) = &diag.code {
+                // Skip the warnings of the disabled lints reported by the lintpasses
+                // that still run for their other lints.
+                if all_lints
+                    .iter()
+                    .any(|lint| lint.kind == *kind && !self.linter.pass.is_enabled(lint))
+                {
+                    continue;
+                }
+                if denied_lints.iter().any(|lint| lint.kind == *kind) {
+                    diag.level = Level::Error;
+                }
+            }
+            handler.diagnostics.insert(diag);
         }
 
         for pkg in self.program.pkgs_not_imported.keys() {
@@ -126,6 +150,7 @@ impl<'ctx> Resolver<'ctx> {
             node_ty_map: self.node_ty_map.clone(),
             handler,
             schema_mapping: self.ctx.schema_mapping.clone(),
+            partial_calls: self.ctx.partial_calls.clone(),
         };
         scope
     }
@@ -164,17 +189,23 @@ pub struct Context {
     pub type_alias_mapping: IndexMap<String, IndexMap<String, String>>,
     /// invalid pkg scope, remove when after resolve
     pub invalid_pkg_scope: IndexSet<String>,
+    /// The AST ids of the callee expressions of the calls which partially apply lambdas.
+    pub partial_calls: IndexSet<AstIndex>,
 }
 
 /// Resolve options.
 /// - lint_check: whether to run lint passes
 /// - resolve_val: whether to resolve and print their AST to value for some nodes.
+/// - disabled_lints: the lint names or codes to skip when running lint passes.
+/// - denied_lints: the lint names or codes whose warnings are reported as errors.
 #[derive(Clone, Debug)]
 pub struct Options {
     pub lint_check: bool,
     pub resolve_val: bool,
     pub merge_program: bool,
     pub type_erasure: bool,
+    pub disabled_lints: Vec<String>,
+    pub denied_lints: Vec<String>,
 }
 
 impl Default for Options {
@@ -184,6 +215,8 @@ impl Default for Options {
             resolve_val: false,
             merge_program: true,
             type_erasure: true,
+            disabled_lints: vec![],
+            denied_lints: vec![],
         }
     }
 }
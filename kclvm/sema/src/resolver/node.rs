@@ -6,6 +6,8 @@ use kclvm_ast_pretty::{print_ast_node, ASTNode};
 use kclvm_error::*;
 use std::sync::Arc;
 
+use crate::builtin::option::parse_option_type;
+use crate::builtin::BUILTIN_FUNCTIONS;
 use crate::info::is_private_field;
 use crate::ty::{
     sup, DictType, FunctionType, Parameter, Type, TypeInferMethods, TypeKind, TypeRef,
@@ -246,6 +248,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 message: format!("Immutable variable '{}' is modified during compiling", name),
                 note: None,
                 suggested_replacement: None,
+                ..Default::default()
             }];
             if let Some(pos) = self.get_global_name_pos(name) {
                 msgs.push(Message {
@@ -257,6 +260,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                         name
                     )),
                     suggested_replacement: None,
+                    ..Default::default()
                 })
             }
             self.handler.add_error(ErrorKind::ImmutableError, &msgs);
@@ -579,13 +583,37 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
             self.any_ty()
         } else if let TypeKind::Function(func_ty) = &call_ty.kind {
+            if let Some(params) =
+                self.get_partial_params(&call_expr.args, &call_expr.keywords, func_ty)
+            {
+                self.ctx.partial_calls.insert(call_expr.func.id.clone());
+                // The rest parameters are not required in this call.
+                let mut partial_func_ty = func_ty.clone();
+                for param in partial_func_ty.params.iter_mut() {
+                    param.has_default = true;
+                }
+                self.do_arguments_type_check(
+                    &call_expr.func,
+                    &call_expr.args,
+                    &call_expr.keywords,
+                    &partial_func_ty,
+                );
+                return Arc::new(Type::lambda(
+                    func_ty.return_ty.clone(),
+                    &params,
+                    &func_ty.doc,
+                ));
+            }
             self.do_arguments_type_check(
                 &call_expr.func,
                 &call_expr.args,
                 &call_expr.keywords,
                 &func_ty,
             );
-            func_ty.return_ty.clone()
+            match self.option_call_ty(call_expr, &call_ty) {
+                Some(ty) => ty,
+                None => func_ty.return_ty.clone(),
+            }
         } else if let TypeKind::Schema(schema_ty) = &call_ty.kind {
             if schema_ty.is_instance {
                 self.handler.add_compile_error(
@@ -594,6 +622,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 );
                 self.any_ty()
             } else {
+                self.check_deprecated(&schema_ty.name, &schema_ty.decorators, &range);
                 self.do_arguments_type_check(
                     &call_expr.func,
                     &call_expr.args,
@@ -877,6 +906,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             }
             TypeKind::Schema(schema_ty) => {
                 if !schema_ty.is_instance {
+                    self.check_deprecated(&schema_ty.name, &schema_ty.decorators, &range);
                     let name = schema_expr.name.node.get_name();
                     if !self.ctx.local_vars.contains(&name) {
                         let ty_annotation_str = ty_str_replace_pkgpath(
@@ -1072,14 +1102,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             ret_ty = real_ret_ty;
         }
         let doc = extract_doc_from_body(&lambda_expr.body);
-        Arc::new(Type::function(
-            None,
-            ret_ty,
-            &params,
-            &doc.unwrap_or_default(),
-            false,
-            None,
-        ))
+        Arc::new(Type::lambda(ret_ty, &params, &doc.unwrap_or_default()))
     }
 
     fn walk_keyword(&mut self, keyword: &'ctx ast::Keyword) -> Self::Result {
@@ -1322,6 +1345,47 @@ impl<'ctx> Resolver<'_> {
         ident_ty
     }
 
+    /// Get the value type of the builtin `option` function call from the string literal
+    /// `type` argument e.g., `int` in `option("key", type="int")`, and report the invalid
+    /// type string. It returns `None` when it is not an `option` call with the type.
+    fn option_call_ty(
+        &mut self,
+        call_expr: &'ctx ast::CallExpr,
+        func_ty: &TypeRef,
+    ) -> Option<TypeRef> {
+        let is_option_call = match &call_expr.func.node {
+            ast::Expr::Identifier(identifier) => {
+                identifier.pkgpath.is_empty()
+                    && identifier.names.len() == 1
+                    && identifier.names[0].node == "option"
+            }
+            _ => false,
+        };
+        let builtin_option_ty = BUILTIN_FUNCTIONS.get("option")?;
+        if !is_option_call || func_ty.ty_str() != builtin_option_ty.ty_str() {
+            return None;
+        }
+        let type_arg = match call_expr
+            .keywords
+            .iter()
+            .find(|keyword| keyword.node.arg.node.get_name() == "type")
+        {
+            Some(keyword) => keyword.node.value.as_ref()?,
+            None => call_expr.args.get(1)?,
+        };
+        match &type_arg.node {
+            ast::Expr::StringLit(string_lit) => match parse_option_type(&string_lit.value) {
+                Ok(ty) => Some(ty),
+                Err(msg) => {
+                    self.handler
+                        .add_compile_error(&msg, type_arg.get_span_pos());
+                    Some(self.any_ty())
+                }
+            },
+            _ => None,
+        }
+    }
+
     pub fn walk_target_expr(&mut self, target: &'ctx ast::NodeRef<ast::Target>) -> ResolvedResult {
         let tys = self.resolve_target(&target.node, target.get_span_pos());
         if let Some(ty) = tys.first() {
@@ -23,6 +23,7 @@ impl<'ctx> Resolver<'_> {
                                         .to_string(),
                                     note: Some("A default argument".to_string()),
                                     suggested_replacement: None,
+                                    ..Default::default()
                                 }],
                             );
                         }
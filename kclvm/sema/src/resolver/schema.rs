@@ -10,7 +10,8 @@ use kclvm_ast::pos::GetPos;
 use kclvm_ast::walker::MutSelfTypedResultWalker;
 use kclvm_ast_pretty::{print_ast_node, ASTNode};
 use kclvm_error::diagnostic::Range;
-use kclvm_error::{ErrorKind, Message, Position, Style};
+use kclvm_error::{ErrorKind, Message, Position, Style, WarningKind};
+use kclvm_runtime::DEPRECATED_DECORATOR;
 
 use super::node::ResolvedResult;
 use super::scope::{ScopeKind, ScopeObject, ScopeObjectKind};
@@ -38,6 +39,7 @@ impl<'ctx> Resolver<'_> {
                     message: format!("expected schema type, got {}", ty.ty_str()),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
             return ty;
@@ -166,6 +168,7 @@ impl<'ctx> Resolver<'_> {
                     message: format!("expected rule type, got {}", ty.ty_str()),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 }],
             );
             return ty;
@@ -227,9 +230,12 @@ impl<'ctx> Resolver<'_> {
                                 &decorator.node.keywords,
                                 &func_ty,
                             );
+                            // The deprecated decorator arguments are always recorded
+                            // to report the deprecation warnings of the usages.
                             let (arguments, keywords) = self.arguments_to_string(
                                 &decorator.node.args,
                                 &decorator.node.keywords,
+                                name == DEPRECATED_DECORATOR,
                             );
                             decorator_objs.push(Decorator {
                                 target: target.clone(),
@@ -301,12 +307,52 @@ impl<'ctx> Resolver<'_> {
         value_ty
     }
 
+    /// Emit a deprecation warning at the usage `range` of the schema or schema
+    /// attribute `name` when it is decorated with `@deprecated`.
+    pub(crate) fn check_deprecated(&mut self, name: &str, decorators: &[Decorator], range: &Range) {
+        let decorator = match decorators
+            .iter()
+            .find(|d| d.name == DEPRECATED_DECORATOR && d.key == name)
+        {
+            Some(decorator) => decorator,
+            None => return,
+        };
+        let get_arg = |index: usize, key: &str| {
+            decorator
+                .keywords
+                .get(key)
+                .or_else(|| decorator.arguments.get(index))
+                .map(|v| v.trim_matches('"').to_string())
+                .unwrap_or_default()
+        };
+        let (version, reason) = (get_arg(0, "version"), get_arg(1, "reason"));
+        let mut message = format!("{name} was deprecated");
+        if !version.is_empty() {
+            message.push_str(&format!(" since version {version}"));
+        }
+        if !reason.is_empty() {
+            message.push_str(&format!(", {reason}"));
+        }
+        self.handler.add_warning(
+            WarningKind::DeprecatedWarning,
+            &[Message {
+                range: range.clone(),
+                style: Style::LineAndColumn,
+                message,
+                note: None,
+                suggested_replacement: None,
+                ..Default::default()
+            }],
+        );
+    }
+
     fn arguments_to_string(
         &mut self,
         args: &'ctx [ast::NodeRef<ast::Expr>],
         kwargs: &'ctx [ast::NodeRef<ast::Keyword>],
+        force: bool,
     ) -> (Vec<String>, HashMap<String, String>) {
-        if self.options.resolve_val {
+        if self.options.resolve_val || force {
             (
                 args.iter()
                     .map(|a| print_ast_node(ASTNode::Expr(a)))
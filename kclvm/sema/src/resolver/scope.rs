@@ -288,6 +288,9 @@ pub struct ProgramScope {
     pub schema_mapping: IndexMap<String, Arc<RefCell<SchemaType>>>,
     pub node_ty_map: Rc<RefCell<NodeTyMap>>,
     pub handler: Handler,
+    /// The AST ids of the callee expressions of the calls which partially apply lambdas,
+    /// which are only supported by the evaluator.
+    pub partial_calls: IndexSet<AstIndex>,
 }
 
 unsafe impl Send for ProgramScope {}
@@ -169,6 +169,7 @@ fn test_resolve_program_fail() {
         "lambda_schema_ty_1.k",
         "lambda_schema_ty_2.k",
         "lambda_schema_ty_3.k",
+        "mixin_attr_conflict.k",
         "module_optional_select.k",
         "mutable_error_0.k",
         "mutable_error_1.k",
@@ -217,6 +218,13 @@ fn test_resolve_program_redefine() {
         diag.messages[0].message,
         "the name 's' is defined multiple times, 's' must be defined only once"
     );
+    assert_eq!(diag.messages[0].range.0.line, 3);
+    assert_eq!(diag.messages[0].related.len(), 1);
+    assert_eq!(diag.messages[0].related[0].0 .0.line, 1);
+    assert_eq!(
+        diag.messages[0].related[0].1,
+        "'s' is first defined here".to_string()
+    );
 }
 
 #[test]
@@ -233,6 +241,32 @@ fn test_resolve_program_mismatch_type_fail() {
     );
 }
 
+#[test]
+fn test_resolve_program_mixin_attr_conflict_fail() {
+    let mut program = parse_program("./src/resolver/test_fail_data/mixin_attr_conflict.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let diags: Vec<_> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.code == Some(DiagnosticId::Error(ErrorKind::TypeError)))
+        .collect();
+    assert_eq!(diags.len(), 2);
+    assert_eq!(
+        diags[0].messages[0].message,
+        "conflicting types of the attribute 'port' in schema 'Server': str in mixin 'PortStrMixin' and int in mixin 'PortMixin'"
+    );
+    assert_eq!(diags[0].messages.len(), 3);
+    assert_eq!(diags[0].messages[0].range.0.line, 14);
+    assert_eq!(diags[0].messages[1].range.0.line, 8);
+    assert_eq!(diags[0].messages[2].range.0.line, 5);
+    assert_eq!(
+        diags[1].messages[0].message,
+        "conflicting types of the attribute 'name' in schema 'Server': int in mixin 'NameMixin' and str in parent schema 'Base'"
+    );
+    assert_eq!(diags[1].messages[2].range.0.line, 2);
+}
+
 #[test]
 fn test_resolve_program_cycle_reference_fail() {
     let sess = Arc::new(ParseSession::default());
@@ -262,6 +296,13 @@ fn test_resolve_program_cycle_reference_fail() {
     for (diag, msg) in scope.handler.diagnostics.iter().zip(err_messages.iter()) {
         assert_eq!(diag.messages[0].message, msg.to_string(),);
     }
+    // The circular import errors are related to the imports of the other modules in the cycle.
+    for diag in &scope.handler.diagnostics[..2] {
+        let related = &diag.messages[0].related;
+        assert_eq!(related.len(), 1);
+        assert_ne!(related[0].0, diag.messages[0].range);
+        assert!(related[0].1.ends_with("in the cycle is imported here"));
+    }
 }
 
 #[test]
@@ -356,6 +397,32 @@ fn test_resolve_program_unmatched_args_fail() {
     assert_eq!(diag.messages[0].message, expect_err_msg);
 }
 
+#[test]
+fn test_resolve_program_option_type_fail() {
+    let mut program = parse_program("./src/resolver/test_fail_data/option_type.k").unwrap();
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 2);
+    let diag = &scope.handler.diagnostics[0];
+    assert_eq!(diag.messages[0].range.0.line, 2);
+    assert!(diag.messages[0].message.contains("expected str, got int"));
+    let diag = &scope.handler.diagnostics[1];
+    assert_eq!(
+        diag.code,
+        Some(DiagnosticId::Error(ErrorKind::CompileError))
+    );
+    assert_eq!(diag.messages[0].range.0.line, 3);
+    assert_eq!(
+        diag.messages[0].message,
+        "invalid option type '[int]', expected one of 'bool', 'int', 'float', 'str', 'list', 'dict'"
+    );
+    let main_scope = scope.main_scope().unwrap();
+    let main_scope = main_scope.borrow();
+    let ty_str = |name: &str| main_scope.elems.get(name).unwrap().borrow().ty.ty_str();
+    assert_eq!(ty_str("d"), "float");
+    assert_eq!(ty_str("e"), "any");
+    assert_eq!(ty_str("f"), "[any]");
+}
+
 #[test]
 fn test_resolve_program_module_optional_select_fail() {
     let mut program =
@@ -426,6 +493,7 @@ fn test_lint() {
             message: format!("The import stmt should be placed at the top of the module"),
             note: Some("Consider moving tihs statement to the top of the file".to_string()),
             suggested_replacement: None,
+            ..Default::default()
         }],
     );
     handler.add_warning(
@@ -447,6 +515,7 @@ fn test_lint() {
             message: format!("Module 'a' is reimported multiple times"),
             note: Some("Consider removing this statement".to_string()),
             suggested_replacement: None,
+            ..Default::default()
         }],
     );
     handler.add_warning(
@@ -468,6 +537,7 @@ fn test_lint() {
             message: format!("Module 'a' imported but unused"),
             note: Some("Consider removing this statement".to_string()),
             suggested_replacement: None,
+            ..Default::default()
         }],
     );
     for (d1, d2) in resolver
@@ -1149,3 +1219,193 @@ fn clear_cache_test() {
         second_scope.schema_mapping.len()
     );
 }
+
+#[test]
+fn test_undeclared_attr_suggestion() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/undeclared_attr_suggestion.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert_eq!(scope.handler.diagnostics.len(), 3);
+    // The close attribute is suggested with the exact range of the key name.
+    let diag = &scope.handler.diagnostics[0];
+    assert_eq!(
+        diag.messages[0].message,
+        "Cannot add member 'replica' to schema 'Spec', did you mean '[\"replicas\"]'?"
+    );
+    assert_eq!(diag.messages[0].range.0.line, 9);
+    assert_eq!(diag.messages[0].range.0.column, Some(9));
+    assert_eq!(diag.messages[0].range.1.column, Some(16));
+    assert_eq!(
+        diag.messages[0].suggested_replacement,
+        Some(vec!["replicas".to_string()])
+    );
+    // No replacement is suggested when there are no close attributes.
+    let diag = &scope.handler.diagnostics[1];
+    assert_eq!(
+        diag.messages[0].message,
+        "Cannot add member 'foo' to schema 'Spec'"
+    );
+    assert_eq!(diag.messages[0].suggested_replacement, None);
+    // No replacement is suggested when there are more than one close attributes.
+    let diag = &scope.handler.diagnostics[2];
+    assert_eq!(
+        diag.messages[0].message,
+        "Cannot add member 'imag' to schema 'Spec', did you mean '[\"image\", \"images\"]'?"
+    );
+    assert_eq!(diag.messages[0].suggested_replacement, None);
+}
+
+#[test]
+fn test_undeclared_attr_suggestion_on_key_range() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/undeclared_attr_suggestion_unpack.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    let diag = scope
+        .handler
+        .diagnostics
+        .iter()
+        .find(|diag| diag.messages[0].message.starts_with("Cannot add member"))
+        .unwrap();
+    assert_eq!(
+        diag.messages[0].message,
+        "Cannot add member 'replica' to schema 'Spec', did you mean '[\"replicas\"]'?"
+    );
+    // The range of the first message denotes the unpacked config value, thus the
+    // replacement is suggested on the message with the range of the key name.
+    assert_eq!(diag.messages[0].suggested_replacement, None);
+    assert_eq!(diag.messages[1].message, "config attribute is defined here");
+    assert_eq!(diag.messages[1].range.0.line, 4);
+    assert_eq!(diag.messages[1].range.0.column, Some(16));
+    assert_eq!(
+        diag.messages[1].suggested_replacement,
+        Some(vec!["replicas".to_string()])
+    );
+}
+
+#[test]
+fn test_deprecated_warnings() {
+    let mut program = parse_program("./src/resolver/test_data/deprecated.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let diags: Vec<_> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.code == Some(DiagnosticId::Warning(WarningKind::DeprecatedWarning)))
+        .collect();
+    assert_eq!(
+        diags
+            .iter()
+            .map(|diag| (
+                diag.messages[0].range.0.line,
+                diag.messages[0].message.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                11,
+                "App was deprecated since version 1.16, use Server instead"
+            ),
+            (14, "replica was deprecated, use replicas instead"),
+            (16, "replica was deprecated, use replicas instead"),
+            (
+                17,
+                "App was deprecated since version 1.16, use Server instead"
+            ),
+        ]
+    );
+    assert!(diags.iter().all(|diag| diag.level == Level::Warning));
+}
+
+#[test]
+fn test_selective_import() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/selective_import.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert!(scope.handler.diagnostics.is_empty());
+    let main_scope = scope.main_scope().unwrap();
+    let main_scope = main_scope.borrow();
+    // Only the selected names are exposed instead of the package name.
+    assert!(!main_scope.elems.contains_key("pkg"));
+    let alice = main_scope
+        .elems
+        .get("alice")
+        .unwrap()
+        .borrow()
+        .ty
+        .full_ty_str();
+    assert_eq!(alice, "pkg.Person");
+
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/selective_import_fail.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    let messages: Vec<&str> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .map(|diag| diag.messages[0].message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "cannot import name 'NotFound' from the module 'pkg'",
+            "cannot import name 'not_found' from the module 'math'",
+            "name 'pkg' is not defined",
+        ]
+    );
+
+    // The selected names are shadowed by the inherited schema attributes and the names
+    // assigned in the top-level if statements, and the unused selective imports are
+    // reported with the selected names.
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/selective_import_shadow.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    assert!(!scope.handler.has_errors());
+    let mut messages: Vec<&str> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .map(|diag| diag.messages[0].message.as_str())
+        .collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "'Name' imported from module 'pkg' but unused",
+            "'log' imported from module 'math' but unused",
+        ]
+    );
+}
@@ -125,6 +125,7 @@ impl<'ctx> Resolver<'_> {
                 message: format!("expected {}, got {}", expected_ty.ty_str(), ty.ty_str(),),
                 note: None,
                 suggested_replacement: None,
+                ..Default::default()
             }];
 
             if let Some(def_range) = def_range {
@@ -141,6 +142,7 @@ impl<'ctx> Resolver<'_> {
                         ),
                         note: None,
                         suggested_replacement: None,
+                        ..Default::default()
                     });
                 }
             }
@@ -151,6 +153,7 @@ impl<'ctx> Resolver<'_> {
                     message: "config attribute is defined here".to_string(),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 });
             }
             self.handler.add_error(ErrorKind::TypeError, &msgs);
@@ -237,6 +240,7 @@ impl<'ctx> Resolver<'_> {
                                         ),
                                         note: None,
                                         suggested_replacement: None,
+                                        ..Default::default()
                                     },
                                     Message {
                                         range: obj.get_span_pos(),
@@ -244,6 +248,7 @@ impl<'ctx> Resolver<'_> {
                                         message: format!("expected {}", obj.ty.ty_str()),
                                         note: None,
                                         suggested_replacement: None,
+                                        ..Default::default()
                                     },
                                 ],
                             );
@@ -193,6 +193,7 @@ impl<'ctx> Resolver<'_> {
                 message: format!("Unique key error name '{}'", name),
                 note: None,
                 suggested_replacement: None,
+                ..Default::default()
             }];
             if let Some(pos) = self.get_global_name_pos(name) {
                 msgs.push(Message {
@@ -201,6 +202,7 @@ impl<'ctx> Resolver<'_> {
                     message: format!("The variable '{}' is declared here", name),
                     note: None,
                     suggested_replacement: None,
+                    ..Default::default()
                 });
             }
             self.handler.add_error(ErrorKind::UniqueKeyError, &msgs);
@@ -188,6 +188,24 @@ impl Type {
                 return_ty,
                 is_variadic,
                 kw_only_index,
+                is_lambda: false,
+            }),
+            flags: TypeFlags::FUNCTION,
+            is_type_alias: false,
+        }
+    }
+    /// Construct a lambda function type.
+    #[inline]
+    pub fn lambda(return_ty: TypeRef, params: &[Parameter], doc: &str) -> Type {
+        Type {
+            kind: TypeKind::Function(FunctionType {
+                doc: doc.to_string(),
+                params: params.to_owned(),
+                self_ty: None,
+                return_ty,
+                is_variadic: false,
+                kw_only_index: None,
+                is_lambda: true,
             }),
             flags: TypeFlags::FUNCTION,
             is_type_alias: false,
@@ -107,6 +107,29 @@ impl Type {
         }
     }
 
+    /// Returns the allowed values in the JSON format when the type is a literal type or
+    /// a union of literal types e.g., `"a" | "b"`, otherwise returns `None`.
+    pub fn literal_values(&self) -> Option<Vec<String>> {
+        match &self.kind {
+            TypeKind::BoolLit(v) => Some(vec![v.to_string()]),
+            TypeKind::IntLit(v) => Some(vec![v.to_string()]),
+            TypeKind::FloatLit(v) => Some(vec![serde_json::Value::from(*v).to_string()]),
+            TypeKind::StrLit(v) => Some(vec![serde_json::Value::from(v.as_str()).to_string()]),
+            TypeKind::Union(types) => {
+                let mut values: Vec<String> = vec![];
+                for ty in types {
+                    for value in ty.literal_values()? {
+                        if !values.contains(&value) {
+                            values.push(value);
+                        }
+                    }
+                }
+                Some(values)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the full type string with the package path used for the error handler.
     pub fn full_ty_str(&self) -> String {
         match &self.kind {
@@ -501,6 +524,14 @@ pub struct FunctionType {
     pub return_ty: TypeRef,
     pub is_variadic: bool,
     pub kw_only_index: Option<usize>,
+    /// Whether the function is a lambda, which returns a new lambda of the rest parameters
+    /// when it is called without all the required arguments i.e., the partial application.
+    /// The partial application is only supported when the callee is statically typed as a
+    /// lambda e.g., `add` in `add = lambda x, y {x + y}`. The builtin functions, the schema
+    /// member functions and the values of the function type annotations e.g., `f` in
+    /// `f: (int, int) -> int` must be called with all the required arguments, and calling
+    /// any function with extra arguments is an error.
+    pub is_lambda: bool,
 }
 
 impl FunctionType {
@@ -540,6 +571,7 @@ impl FunctionType {
             return_ty: Type::any_ref(),
             is_variadic: true,
             kw_only_index: None,
+            is_lambda: false,
         }
     }
 }
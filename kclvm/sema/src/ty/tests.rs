@@ -68,6 +68,32 @@ fn test_sup() {
     }
 }
 
+#[test]
+fn test_literal_values() {
+    let cases = [
+        (Arc::new(Type::STR), None),
+        (Arc::new(Type::int_lit(1)), Some(vec!["1"])),
+        (Arc::new(Type::float_lit(1.5)), Some(vec!["1.5"])),
+        (Arc::new(Type::bool_lit(true)), Some(vec!["true"])),
+        (
+            Type::union_ref(&[
+                Arc::new(Type::str_lit("a")),
+                Arc::new(Type::str_lit("b")),
+                Type::union_ref(&[Arc::new(Type::str_lit("a")), Arc::new(Type::int_lit(1))]),
+            ]),
+            Some(vec!["\"a\"", "\"b\"", "1"]),
+        ),
+        (
+            Type::union_ref(&[Arc::new(Type::str_lit("a")), Arc::new(Type::STR)]),
+            None,
+        ),
+    ];
+    for (ty, expected) in cases {
+        let expected = expected.map(|values| values.iter().map(|v| v.to_string()).collect());
+        assert_eq!(ty.literal_values(), expected, "{}", ty.ty_str());
+    }
+}
+
 #[test]
 fn test_type_walker() {
     fn walk_fn(ty: &Type) -> TypeRef {
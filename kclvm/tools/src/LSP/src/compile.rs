@@ -3,8 +3,8 @@ use kclvm_ast::ast::Program;
 use kclvm_driver::{lookup_compile_workspace, toolchain};
 use kclvm_error::Diagnostic;
 use kclvm_parser::{
-    entry::get_normalized_k_files_from_paths, load_all_files_under_paths, KCLModuleCache,
-    LoadProgramOptions, ParseSessionRef,
+    entry::get_normalized_k_files_from_paths, load_all_files_under_paths, split_missing_entries,
+    KCLModuleCache, LoadProgramOptions, ParseSessionRef,
 };
 use kclvm_query::query::filter_pkg_schemas;
 use kclvm_sema::{
@@ -19,7 +19,7 @@ use std::path::PathBuf;
 
 use crate::{
     state::{KCLGlobalStateCache, KCLVfs},
-    util::load_files_code_from_vfs,
+    util::{affected_files, load_files_code_from_vfs},
 };
 
 pub struct Params {
@@ -41,14 +41,30 @@ pub fn compile(
     // Ignore the kcl plugin sematic check.
     let mut opts = opts.unwrap_or_default();
     opts.load_plugins = true;
+    // The entry files removed or renamed in the editor are skipped with diagnostics
+    // instead of failing the whole compilation.
+    opts.tolerate_missing_entry = true;
+    // They are moved after the other files to keep the code list loaded from the VFS
+    // aligned with the existing files.
+    let (existing_files, missing_files) = split_missing_entries(files.to_vec(), &opts);
+    for (file, path) in files
+        .iter_mut()
+        .zip(existing_files.iter().chain(missing_files.iter()))
+    {
+        *file = path.clone();
+    }
     // Get input files code from vfs
-    let normalized_files = match get_normalized_k_files_from_paths(files, &opts) {
-        Ok(file_list) => file_list,
-        Err(e) => {
-            return (
-                IndexSet::new(),
-                Err(anyhow::anyhow!("Compile failed: {:?}", e)),
-            )
+    let normalized_files = if existing_files.is_empty() {
+        vec![]
+    } else {
+        match get_normalized_k_files_from_paths(&existing_files, &opts) {
+            Ok(file_list) => file_list,
+            Err(e) => {
+                return (
+                    IndexSet::new(),
+                    Err(anyhow::anyhow!("Compile failed: {:?}", e)),
+                )
+            }
         }
     };
     let normalized_files: Vec<&str> = normalized_files.iter().map(|s| s.as_str()).collect();
@@ -109,7 +125,11 @@ pub fn compile(
     if let Some(cached_scope) = params.scope_cache.as_ref() {
         if let Some(file) = &params.file {
             if let Some(mut cached_scope) = cached_scope.try_write() {
-                let mut invalidate_pkg_modules = HashSet::new();
+                // Only the packages of the files affected by the changed file in the
+                // package file graph are resolved again, and the others are reused
+                // from the scope cache.
+                let mut invalidate_pkg_modules =
+                    affected_files(&program, file).unwrap_or_default();
                 invalidate_pkg_modules.insert(file.clone());
                 cached_scope.invalidate_pkg_modules = Some(invalidate_pkg_modules);
             }
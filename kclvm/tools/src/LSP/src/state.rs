@@ -689,29 +689,23 @@ impl LanguageServerState {
                     }
                 }
 
-                for (file, diags) in old_diags_maps {
-                    if !new_diags_maps.contains_key(&file) {
-                        if let Ok(uri) = url_from_path(file) {
-                            sender.send(Task::Notify(lsp_server::Notification {
-                                method: PublishDiagnostics::METHOD.to_owned(),
-                                params: to_json(PublishDiagnosticsParams {
-                                    uri: uri.clone(),
-                                    diagnostics: vec![],
-                                    version: None,
-                                })
-                                .unwrap(),
-                            }));
-                        }
+                // Every file whose diagnostics changed is republished, including the files
+                // outside the package graph of the changed file e.g., the ones reported by
+                // the whole workspace lints, and the unchanged ones are skipped. The files
+                // which become error-free are cleared with the empty diagnostics.
+                let mut files: IndexSet<&String> = old_diags_maps.keys().collect();
+                files.extend(new_diags_maps.keys());
+                for file in files {
+                    let diagnostics = new_diags_maps.get(file);
+                    if old_diags_maps.get(file) == diagnostics {
+                        continue;
                     }
-                }
-
-                for (filename, diagnostics) in new_diags_maps {
-                    if let Ok(uri) = url_from_path(filename) {
+                    if let Ok(uri) = url_from_path(file) {
                         sender.send(Task::Notify(lsp_server::Notification {
                             method: PublishDiagnostics::METHOD.to_owned(),
                             params: to_json(PublishDiagnosticsParams {
                                 uri: uri.clone(),
-                                diagnostics,
+                                diagnostics: diagnostics.cloned().unwrap_or_default(),
                                 version: None,
                             })
                             .unwrap(),
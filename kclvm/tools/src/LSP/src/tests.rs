@@ -61,9 +61,11 @@ use std::thread;
 use std::time::Duration;
 
 use kclvm_ast::ast::Program;
+use kclvm_ast::MAIN_PKG;
 use kclvm_error::Diagnostic as KCLDiagnostic;
 use kclvm_error::Position as KCLPos;
 use kclvm_parser::KCLModuleCache;
+use kclvm_parser::ParseSession;
 
 use lsp_types::Diagnostic;
 use lsp_types::DiagnosticRelatedInformation;
@@ -76,6 +78,7 @@ use proc_macro_crate::bench_test;
 
 use lsp_server::{Connection, Message, Notification, Request};
 
+use crate::compile::compile;
 use crate::compile::compile_with_params;
 use crate::completion::completion;
 use crate::from_lsp::file_path_from_url;
@@ -87,6 +90,8 @@ use crate::hover::hover;
 use crate::state::KCLGlobalStateCache;
 use crate::state::KCLVfs;
 use crate::to_lsp::kcl_diag_to_lsp_diags_by_file;
+use crate::to_lsp::ToLspDiagnostics;
+use crate::util::affected_files;
 use crate::util::apply_document_changes;
 use crate::util::to_json;
 
@@ -365,6 +370,139 @@ fn diagnostics_test() {
     }
 }
 
+#[test]
+fn test_affected_files() {
+    let (file, program, ..) = compile_test_file("src/test_data/affected_files/main.k");
+    let pkg_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/test_data/affected_files/pkg/pkg.k")
+        .canonicalize()
+        .unwrap()
+        .adjust_canonicalization();
+    let base_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/test_data/affected_files/pkg/base.k")
+        .canonicalize()
+        .unwrap()
+        .adjust_canonicalization();
+
+    // The other files of the same package are affected too.
+    let pkg_affected = Some(HashSet::from([
+        pkg_file.clone(),
+        base_file.clone(),
+        file.clone(),
+    ]));
+    assert_eq!(affected_files(&program, &pkg_file), pkg_affected);
+    assert_eq!(affected_files(&program, &base_file), pkg_affected);
+    assert_eq!(
+        affected_files(&program, &file),
+        Some(HashSet::from([file.clone()]))
+    );
+    assert_eq!(affected_files(&program, "not_found.k"), None);
+}
+
+#[test]
+fn test_compile_invalidates_affected_pkgs() {
+    let (main_file, ..) = compile_test_file("src/test_data/affected_files/main.k");
+    let base_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/test_data/affected_files/pkg/base.k")
+        .canonicalize()
+        .unwrap()
+        .adjust_canonicalization();
+    let module_cache = KCLModuleCache::default();
+    let scope_cache = KCLScopeCache::default();
+    let compile_changed_file = |file: &str| {
+        let (_, compile_res) = compile(
+            Params {
+                file: Some(file.to_string()),
+                module_cache: Some(module_cache.clone()),
+                scope_cache: Some(scope_cache.clone()),
+                vfs: Some(KCLVfs::default()),
+                gs_cache: Some(KCLGlobalStateCache::default()),
+            },
+            &mut vec![main_file.clone()],
+            None,
+        );
+        compile_res.unwrap();
+        scope_cache.read().invalidate_pkgs.clone()
+    };
+    compile_changed_file(&main_file);
+    // The change of the main file does not resolve the imported package again.
+    assert_eq!(
+        compile_changed_file(&main_file),
+        HashSet::from([MAIN_PKG.to_string()])
+    );
+    // The change of the package file resolves the package and its importers again.
+    assert!(compile_changed_file(&base_file)
+        .iter()
+        .any(|pkgpath| pkgpath.ends_with("pkg")));
+}
+
+#[test]
+fn test_compile_tolerate_missing_entry() {
+    let (main_file, ..) = compile_test_file("src/test_data/affected_files/main.k");
+    let missing_file = Path::new(&main_file)
+        .with_file_name("missing.k")
+        .adjust_canonicalization();
+    let params = || Params {
+        file: Some(main_file.clone()),
+        module_cache: None,
+        scope_cache: None,
+        vfs: Some(KCLVfs::default()),
+        gs_cache: None,
+    };
+    // The missing entry is reported as a diagnostic, and the existing entries are still compiled.
+    let (diags, compile_res) = compile(
+        params(),
+        &mut vec![missing_file.clone(), main_file.clone()],
+        None,
+    );
+    let (program, ..) = compile_res.unwrap();
+    assert_eq!(program.get_main_files(), vec![main_file.clone()]);
+    assert!(diags
+        .iter()
+        .any(|diag| diag.messages[0].range.0.filename == missing_file));
+    // An empty program is compiled when all the entries are missing.
+    let (diags, compile_res) = compile(params(), &mut vec![missing_file.clone()], None);
+    assert!(compile_res.unwrap().0.get_main_files().is_empty());
+    assert_eq!(diags.len(), 1);
+}
+
+#[test]
+fn test_session_to_lsp_diagnostics() {
+    let sess = ParseSession::default();
+    let pos = KCLPos {
+        filename: "main.k".to_string(),
+        line: 1,
+        column: Some(7),
+    };
+    let mut diags = IndexSet::new();
+    diags.insert(KCLDiagnostic::new_with_code(
+        kclvm_error::Level::Suggestions,
+        "try 'kcl mod add k8s' to download the missing package",
+        None,
+        (pos.clone(), pos),
+        Some(kclvm_error::DiagnosticId::Suggestions),
+        Some(vec!["kcl mod add k8s".to_string()]),
+    ));
+    sess.append_diagnostic(diags);
+
+    let diagnostics = sess.to_lsp_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+    assert_eq!(
+        diagnostic.code,
+        Some(NumberOrString::String("suggestion".to_string()))
+    );
+    assert_eq!(
+        diagnostic.range,
+        Range::new(Position::new(0, 7), Position::new(0, 7))
+    );
+    assert_eq!(
+        diagnostic.data,
+        Some(serde_json::json!({ "suggested_replacement": ["kcl mod add k8s"] }))
+    );
+}
+
 #[test]
 #[bench_test]
 fn test_apply_document_changes() {
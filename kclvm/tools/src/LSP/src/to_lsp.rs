@@ -4,6 +4,7 @@ use kclvm_error::DiagnosticId;
 use kclvm_error::Level;
 use kclvm_error::Message;
 use kclvm_error::Position as KCLPos;
+use kclvm_parser::ParseSession;
 use kclvm_utils::path::PathPrefix;
 use lsp_types::*;
 use serde_json::json;
@@ -63,24 +64,28 @@ pub fn kcl_msg_to_lsp_diags(
             }
         });
 
-    let related_information = if related_msg.is_empty() {
+    let related_information = if related_msg.is_empty() && msg.related.is_empty() {
         None
     } else {
         Some(
             related_msg
                 .iter()
-                .filter_map(|m| match Url::from_file_path(m.range.0.filename.clone()) {
-                    Ok(uri) => Some(DiagnosticRelatedInformation {
-                        location: Location {
-                            uri,
-                            range: Range {
-                                start: lsp_pos(&m.range.0),
-                                end: lsp_pos(&m.range.1),
+                .map(|m| (&m.range, &m.message))
+                .chain(msg.related.iter().map(|(range, label)| (range, label)))
+                .filter_map(|(range, message)| {
+                    match Url::from_file_path(range.0.filename.clone()) {
+                        Ok(uri) => Some(DiagnosticRelatedInformation {
+                            location: Location {
+                                uri,
+                                range: Range {
+                                    start: lsp_pos(&range.0),
+                                    end: lsp_pos(&range.1),
+                                },
                             },
-                        },
-                        message: m.message.clone(),
-                    }),
-                    Err(_) => None,
+                            message: message.clone(),
+                        }),
+                        Err(_) => None,
+                    }
                 })
                 .collect(),
         )
@@ -141,10 +146,20 @@ pub fn kcl_diag_to_lsp_diags(diag: &KCLDiagnostic) -> HashMap<String, Vec<Diagno
 pub(crate) fn kcl_diag_to_lsp_diags_by_file(
     diag: &KCLDiagnostic,
     file_name: &str,
+) -> Vec<Diagnostic> {
+    kcl_diag_to_lsp_diags_with_filter(diag, |msg| {
+        msg.range.0.filename.adjust_canonicalization() == file_name.adjust_canonicalization()
+    })
+}
+
+/// Convert KCL Diagnostic messages matched by the filter to LSP Diagnostics.
+fn kcl_diag_to_lsp_diags_with_filter(
+    diag: &KCLDiagnostic,
+    filter: impl Fn(&Message) -> bool,
 ) -> Vec<Diagnostic> {
     let mut diags = vec![];
     for (idx, msg) in diag.messages.iter().enumerate() {
-        if msg.range.0.filename.adjust_canonicalization() == file_name.adjust_canonicalization() {
+        if filter(msg) {
             let mut related_msg = diag.messages.clone();
             related_msg.remove(idx);
             let code = if diag.code.is_some() {
@@ -166,6 +181,31 @@ pub(crate) fn kcl_diag_to_lsp_diags_by_file(
     diags
 }
 
+/// ToLspDiagnostics converts KCL diagnostics into LSP diagnostics including the severity,
+/// range, code, related information and the suggested replacement stored in the `data`
+/// field, which can be used to construct the quick fix code actions.
+pub trait ToLspDiagnostics {
+    /// Convert self into LSP diagnostics.
+    fn to_lsp_diagnostics(&self) -> Vec<Diagnostic>;
+}
+
+impl ToLspDiagnostics for KCLDiagnostic {
+    fn to_lsp_diagnostics(&self) -> Vec<Diagnostic> {
+        kcl_diag_to_lsp_diags_with_filter(self, |_| true)
+    }
+}
+
+impl ToLspDiagnostics for ParseSession {
+    fn to_lsp_diagnostics(&self) -> Vec<Diagnostic> {
+        self.1
+            .read()
+            .diagnostics
+            .iter()
+            .flat_map(|diag| diag.to_lsp_diagnostics())
+            .collect()
+    }
+}
+
 /// Convert KCL Diagnostic ID to LSP Diagnostics code.
 /// Todo: use unique id/code instead of name()
 pub(crate) fn kcl_diag_id_to_lsp_diag_code(id: DiagnosticId) -> NumberOrString {
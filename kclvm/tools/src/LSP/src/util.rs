@@ -7,6 +7,8 @@ use kclvm_ast::pos::ContainsPos;
 
 use kclvm_error::Position as KCLPos;
 use kclvm_parser::entry::get_dir_files;
+use kclvm_parser::file_graph::{PkgFile, PkgFileGraph};
+use kclvm_utils::path::PathPrefix;
 
 use crate::from_lsp;
 use crate::state::KCLVfs;
@@ -15,6 +17,7 @@ use parking_lot::RwLockReadGuard;
 use ra_ap_vfs::{FileId, Vfs};
 use serde::{de::DeserializeOwned, Serialize};
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -119,6 +122,55 @@ pub(crate) fn filter_kcl_config_file(paths: &[PathBuf]) -> Vec<PathBuf> {
         .collect()
 }
 
+/// Build the file graph of the program, where each file depends on all the files
+/// of the packages it imports.
+pub(crate) fn build_file_graph(prog: &Program) -> PkgFileGraph {
+    let mut graph = PkgFileGraph::default();
+    for (pkgpath, filenames) in &prog.pkgs {
+        for filename in filenames {
+            let mut deps = vec![];
+            if let Ok(Some(module)) = prog.get_module(filename) {
+                for stmt in &module.body {
+                    if let Stmt::Import(import_stmt) = &stmt.node {
+                        let pkg = &import_stmt.path.node;
+                        for dep in prog.pkgs.get(pkg).into_iter().flatten() {
+                            deps.push(PkgFile::new(PathBuf::from(dep), pkg.clone()));
+                        }
+                    }
+                }
+            }
+            graph.update_file(
+                &PkgFile::new(PathBuf::from(filename), pkgpath.clone()),
+                &deps,
+            );
+        }
+    }
+    graph
+}
+
+/// Get the changed file, the other files of the same package and the files which import
+/// the package directly or transitively, whose diagnostics may change with the changed
+/// file. Returns `None` when the changed file is not in the program.
+pub(crate) fn affected_files(prog: &Program, filename: &str) -> Option<HashSet<String>> {
+    let graph = build_file_graph(prog);
+    let filename = filename.adjust_canonicalization();
+    let file = graph
+        .paths()
+        .into_iter()
+        .find(|file| file.get_path().adjust_canonicalization() == filename)?;
+    // The files of the same package share the package scope, so they are affected too.
+    let pkg_files = graph
+        .paths()
+        .into_iter()
+        .filter(|f| f.pkg_path == file.pkg_path);
+    Some(
+        pkg_files
+            .flat_map(|f| graph.affected_by(&f))
+            .map(|f| f.get_path().adjust_canonicalization())
+            .collect(),
+    )
+}
+
 macro_rules! walk_if_contains {
     ($expr: expr, $pos: expr, $schema_def: expr) => {
         if $expr.contains_pos($pos) {
@@ -2,10 +2,21 @@ mod replace;
 #[cfg(test)]
 mod tests;
 use anyhow::{ensure, Error};
+use indexmap::{IndexMap, IndexSet};
 use kclvm_error::{diagnostic::Range as KCLRange, Diagnostic};
+use kclvm_parser::{
+    load_program, FileSystemProvider, LoadProgramOptions, ParseSession, SourceProvider,
+};
+use kclvm_sema::resolver::{resolve_program_with_opts, Options};
 use std::collections::HashMap;
 use std::fs;
 use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// The maximum number of fix passes. Each pass reloads the program with the fixed
+/// sources and stops when no more fix can be applied.
+const MAX_FIX_PASSES: usize = 8;
 
 /// A structure for handling code fixes.
 pub struct CodeFix {
@@ -169,3 +180,139 @@ pub fn fix(diags: Vec<Diagnostic>) -> Result<(), Error> {
     }
     Ok(())
 }
+
+/// The fixes applied to a program by [fix_program].
+#[derive(Debug, Clone, Default)]
+pub struct FixResult {
+    /// The original and the fixed source code of each changed file.
+    pub changes: IndexMap<String, (String, String)>,
+    /// All the applied fixes.
+    pub applied: Vec<Suggestion>,
+    /// Notes of the fixes that were skipped, e.g., conflicting fixes in one region.
+    pub skipped: Vec<String>,
+}
+
+/// Load and resolve the program, then apply all the non-overlapping machine-applicable
+/// fixes in memory, repeating until the sources are stable. Files are not written,
+/// the fixed sources are returned in [FixResult::changes].
+pub fn fix_program(files: &[&str], opts: Option<LoadProgramOptions>) -> Result<FixResult, Error> {
+    let overlay = Arc::new(OverlayProvider::default());
+    let mut opts = opts.unwrap_or_default();
+    opts.load_plugins = true;
+    opts.source_provider = overlay.clone();
+    let mut result = FixResult::default();
+    for _ in 0..MAX_FIX_PASSES {
+        let sess = Arc::new(ParseSession::default());
+        let mut program = load_program(sess.clone(), files, Some(opts.clone()), None)?.program;
+        let scope = resolve_program_with_opts(
+            &mut program,
+            Options {
+                merge_program: false,
+                ..Default::default()
+            },
+            None,
+        );
+        sess.append_diagnostic(scope.handler.diagnostics);
+
+        let mut suggestions: IndexMap<String, IndexSet<Suggestion>> = IndexMap::new();
+        for fix in sess.collect_fixable_diagnostics() {
+            if !fix.machine_applicable {
+                continue;
+            }
+            let file_name = fix.range.0.filename.clone();
+            let src = overlay.read(Path::new(&file_name))?;
+            suggestions
+                .entry(file_name.clone())
+                .or_default()
+                .insert(Suggestion {
+                    message: fix.message,
+                    replacement: Replacement {
+                        snippet: Snippet {
+                            file_name,
+                            range: text_range(&src, &fix.range)?,
+                        },
+                        replacement: fix.replacements[0].clone(),
+                    },
+                });
+        }
+
+        // Notes of the previous pass are stale once the program is reloaded.
+        result.skipped.clear();
+        let mut changed = false;
+        for (file_name, suggestions) in suggestions {
+            let mut suggestions: Vec<Suggestion> = suggestions.into_iter().collect();
+            suggestions.sort_by_key(|s| {
+                let range = &s.replacement.snippet.range;
+                (range.start, range.end)
+            });
+            let source = overlay.read(Path::new(&file_name))?;
+            let mut code_fix = CodeFix::new(&source);
+            let mut applied_end: Option<usize> = None;
+            for suggestion in suggestions {
+                let range = suggestion.replacement.snippet.range.clone();
+                if matches!(applied_end, Some(end) if range.start < end) {
+                    result.skipped.push(format!(
+                        "{}: skipped the fix '{}' conflicting with another fix at bytes {}..{}",
+                        file_name, suggestion.message, range.start, range.end
+                    ));
+                    continue;
+                }
+                code_fix.apply(&suggestion)?;
+                applied_end = Some(range.end);
+                result.applied.push(suggestion);
+            }
+            let fixed = code_fix.finish()?;
+            if fixed != source {
+                changed = true;
+                result
+                    .changes
+                    .entry(file_name.clone())
+                    .or_insert_with(|| (source.clone(), String::new()))
+                    .1 = fixed.clone();
+                overlay.insert(&file_name, fixed);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+/// A [SourceProvider] that reads the fixed sources in memory and falls back to the filesystem.
+#[derive(Debug, Default)]
+struct OverlayProvider {
+    sources: RwLock<HashMap<PathBuf, String>>,
+}
+
+impl OverlayProvider {
+    fn key(path: &Path) -> PathBuf {
+        path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    fn insert(&self, path: &str, source: String) {
+        self.sources
+            .write()
+            .unwrap()
+            .insert(Self::key(Path::new(path)), source);
+    }
+}
+
+impl SourceProvider for OverlayProvider {
+    fn read(&self, path: &Path) -> anyhow::Result<String> {
+        match self.sources.read().unwrap().get(&Self::key(path)) {
+            Some(source) => Ok(source.clone()),
+            None => FileSystemProvider.read(path),
+        }
+    }
+
+    #[inline]
+    fn list_dir(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        FileSystemProvider.list_dir(path)
+    }
+
+    #[inline]
+    fn exists(&self, path: &Path) -> bool {
+        FileSystemProvider.exists(path)
+    }
+}
@@ -2,7 +2,7 @@ use std::fs;
 
 use crate::lint::lint_files;
 
-use super::fix;
+use super::{fix, fix_program};
 
 #[test]
 fn test_lint() {
@@ -40,3 +40,42 @@ a = math.pow(1, 1)"#,
         Err(e) => panic!("fix failed: {:?}", e),
     }
 }
+
+#[test]
+fn test_fix_program() {
+    let file = "./src/fix/test_data/fix_attr.k";
+    let original = fs::read_to_string(file).unwrap();
+    let result = fix_program(&[file], None).unwrap();
+    assert_eq!(result.applied.len(), 1);
+    assert!(result.skipped.is_empty());
+    assert_eq!(result.changes.len(), 1);
+    let (before, after) = result.changes.values().next().unwrap();
+    assert_eq!(before, &original);
+    assert_eq!(after, &original.replace("nmae", "name"));
+    // The fixes are applied in memory only.
+    assert_eq!(fs::read_to_string(file).unwrap(), original);
+}
+
+#[test]
+fn test_fix_check_block() {
+    let cases = [
+        (
+            "./src/fix/test_data/fix_check_block_0.k",
+            "schema Person:\n    name: str\n    age: int\n    check:\n        len(name) > 0\n\np = Person {\n    name = \"Alice\"\n    age = 1\n}\n",
+        ),
+        (
+            "./src/fix/test_data/fix_check_block_1.k",
+            "schema Person:\n    name: str\n\n    check:\n        len(name) > 0\n\np = Person {\n    name = \"Alice\"\n}\n",
+        ),
+        (
+            "./src/fix/test_data/fix_check_block_2.k",
+            "schema Person:\n    name: str\n    check:\n        len(name) > 0\n\np = Person {\n    name = \"Alice\"\n}\n",
+        ),
+    ];
+    for (file, expected) in cases {
+        let result = fix_program(&[file], None).unwrap();
+        assert_eq!(result.applied.len(), 1, "{file}");
+        let (_, after) = result.changes.values().next().unwrap();
+        assert_eq!(after, expected, "{file}");
+    }
+}
@@ -6,8 +6,10 @@
 //! AST Module, and then use the AST printer [kclvm_tools::printer::print_ast_module]
 //! to print it as source code string.
 use anyhow::Result;
-use kclvm_ast_pretty::print_ast_module;
+use kclvm_ast::ast::{Module, Stmt};
+use kclvm_ast_pretty::{print_ast_module_with_config, Config};
 use kclvm_parser::get_kcl_files;
+use std::ops::Range;
 use std::path::Path;
 
 use kclvm_parser::{parse_file_force_errors, parse_single_file};
@@ -15,15 +17,44 @@ use kclvm_parser::{parse_file_force_errors, parse_single_file};
 #[cfg(test)]
 mod tests;
 
-/// FormatOptions contains two options:
+/// FormatOptions contains these options:
 /// - is_stdout: whether to output the formatted result to stdout.
 /// - recursively: whether to recursively traverse a folder and format all KCL files in it.
 /// - omit_errors: whether to omit the parse errors when format the KCL code.
-#[derive(Debug, Default)]
+/// - verify_roundtrip: whether to re-parse the formatted code and keep the original code
+///   when its AST differs from the original AST ignoring the positions.
+/// - indent_width: the number of spaces of each indentation level, defaults to 4.
+/// - use_tabs: whether to indent with one tab per indentation level instead of spaces.
+#[derive(Debug)]
 pub struct FormatOptions {
     pub is_stdout: bool,
     pub recursively: bool,
     pub omit_errors: bool,
+    pub verify_roundtrip: bool,
+    pub indent_width: usize,
+    pub use_tabs: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            is_stdout: false,
+            recursively: false,
+            omit_errors: false,
+            verify_roundtrip: false,
+            indent_width: 4,
+            use_tabs: false,
+        }
+    }
+}
+
+/// A text edit which replaces the source lines `start_line..end_line` (0-based and
+/// exclusive at the end) with `new_text`. An empty line range denotes an insertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub new_text: String,
 }
 
 /// Formats kcl file or directory path contains kcl files and
@@ -83,12 +114,166 @@ pub fn format_file(file: &str, opts: &FormatOptions) -> Result<bool> {
 /// Formats a code source and returns the formatted source and
 /// whether the source is changed.
 pub fn format_source(file: &str, src: &str, opts: &FormatOptions) -> Result<(String, bool)> {
-    let module = if opts.omit_errors {
+    let module = parse_source(file, src, opts)?;
+    let formatted_src = print_module(&module, opts);
+    // Keep the original source when the formatting changes the semantics.
+    if opts.verify_roundtrip && !is_roundtrip_equal(file, &module, &formatted_src)? {
+        return Ok((src.to_string(), false));
+    }
+    let is_formatted = src != formatted_src;
+    Ok((formatted_src, is_formatted))
+}
+
+/// Formats only the top-level statements overlapping the edited source lines
+/// `edited_lines` (0-based and exclusive at the end, an empty range denotes the
+/// line of the cursor) and returns the minimal edits, which is used to format the
+/// code on typing in the editors. The edited region is the same as the region in
+/// the fully formatted source, and the other regions are kept unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_tools::format::{format_incremental, FormatOptions, TextEdit};
+///
+/// let src = "a=1\nb=2\n";
+/// let edits = format_incremental("", src, 1..1, &FormatOptions::default()).unwrap();
+/// assert_eq!(
+///     edits,
+///     vec![TextEdit {
+///         start_line: 1,
+///         end_line: 2,
+///         new_text: "b = 2\n".to_string(),
+///     }]
+/// );
+/// ```
+pub fn format_incremental(
+    file: &str,
+    src: &str,
+    edited_lines: Range<usize>,
+    opts: &FormatOptions,
+) -> Result<Vec<TextEdit>> {
+    let module = parse_source(file, src, opts)?;
+    let src_lines: Vec<&str> = src.split_inclusive('\n').collect();
+    let src_ranges = stmt_line_ranges(&module);
+    let edited_lines = edited_lines.start..edited_lines.end.max(edited_lines.start + 1);
+    let overlapped: Vec<usize> = src_ranges
+        .iter()
+        .enumerate()
+        .filter(|(_, range)| range.start < edited_lines.end && edited_lines.start < range.end)
+        .map(|(i, _)| i)
+        .collect();
+    let (first, last) = match (overlapped.first(), overlapped.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Ok(vec![]),
+    };
+    let src_range = src_ranges[first].start..src_ranges[last].end.min(src_lines.len());
+    // Print only the overlapped statements with the comments in their lines. The blank
+    // lines between the top-level statements only depend on the adjacent statements, so
+    // the printed region is the same as the region in the fully formatted source.
+    let region = Module {
+        filename: module.filename.clone(),
+        body: module.body[first..=last].to_vec(),
+        comments: module
+            .comments
+            .iter()
+            .filter(|comment| {
+                let line = comment.line as usize;
+                src_range.start < line && line <= src_range.end
+            })
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+    let formatted_region = print_module(&region, opts);
+    // Keep the original region when the formatting changes the semantics.
+    if opts.verify_roundtrip && !is_roundtrip_equal(file, &region, &formatted_region)? {
+        return Ok(vec![]);
+    }
+    let old = &src_lines[src_range.clone()];
+    let new: Vec<&str> = formatted_region.split_inclusive('\n').collect();
+    // Trim the unchanged lines at both ends to keep the edit minimal.
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix + suffix == old.len() && prefix + suffix == new.len() {
+        return Ok(vec![]);
+    }
+    Ok(vec![TextEdit {
+        start_line: src_range.start + prefix,
+        end_line: src_range.end - suffix,
+        new_text: new[prefix..new.len() - suffix].concat(),
+    }])
+}
+
+fn print_module(module: &Module, opts: &FormatOptions) -> String {
+    print_ast_module_with_config(
+        module,
+        Config {
+            indent_len: opts.indent_width,
+            use_spaces: !opts.use_tabs,
+            ..Default::default()
+        },
+    )
+}
+
+fn parse_source(file: &str, src: &str, opts: &FormatOptions) -> Result<Module> {
+    Ok(if opts.omit_errors {
         parse_single_file(file, Some(src.to_string()))?.module
     } else {
         parse_file_force_errors(file, Some(src.to_string()))?
-    };
-    let formatted_src = print_ast_module(&module);
-    let is_formatted = src != formatted_src;
-    Ok((formatted_src, is_formatted))
+    })
+}
+
+/// Returns the 0-based line ranges of the top-level statements including their decorators.
+fn stmt_line_ranges(module: &Module) -> Vec<Range<usize>> {
+    module
+        .body
+        .iter()
+        .map(|stmt| {
+            let decorators = match &stmt.node {
+                Stmt::Schema(schema_stmt) => schema_stmt.decorators.as_slice(),
+                Stmt::Rule(rule_stmt) => rule_stmt.decorators.as_slice(),
+                _ => &[],
+            };
+            let start_line = decorators
+                .iter()
+                .map(|decorator| decorator.line)
+                .fold(stmt.line, u64::min);
+            (start_line as usize).saturating_sub(1)..stmt.end_line as usize
+        })
+        .collect()
+}
+
+/// The position fields of AST nodes which are ignored in the roundtrip comparison.
+const POSITION_KEYS: &[&str] = &["filename", "line", "column", "end_line", "end_column"];
+
+/// Re-parses the formatted source and returns whether its AST is equal to the
+/// original AST `module` ignoring all the node and comment positions.
+fn is_roundtrip_equal(file: &str, module: &Module, formatted_src: &str) -> Result<bool> {
+    let result = parse_single_file(file, Some(formatted_src.to_string()))?;
+    if result.errors.iter().any(|d| d.is_error()) {
+        return Ok(false);
+    }
+    let mut expected = serde_json::to_value(module)?;
+    let mut got = serde_json::to_value(&result.module)?;
+    strip_positions(&mut expected);
+    strip_positions(&mut got);
+    Ok(expected == got)
+}
+
+fn strip_positions(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for key in POSITION_KEYS {
+                map.remove(*key);
+            }
+            map.values_mut().for_each(strip_positions);
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(strip_positions),
+        _ => {}
+    }
 }
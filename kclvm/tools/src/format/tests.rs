@@ -5,7 +5,7 @@ use walkdir::WalkDir;
 
 const FILE_INPUT_SUFFIX: &str = ".input";
 const FILE_OUTPUT_SUFFIX: &str = ".golden";
-const TEST_CASES: &[&str; 22] = &[
+const TEST_CASES: &[&str; 23] = &[
     "assert",
     "check",
     "blankline",
@@ -20,6 +20,7 @@ const TEST_CASES: &[&str; 22] = &[
     "indent",
     "inline_comment",
     "lambda",
+    "number",
     "quant",
     "schema",
     "string",
@@ -82,6 +83,7 @@ fn test_format_with_stdout_option() {
         is_stdout: true,
         recursively: false,
         omit_errors: false,
+        ..Default::default()
     };
     let changed_files = format("./src/format/test_data/format_path_data/if.k", &opts).unwrap();
     assert_eq!(changed_files.len(), 1);
@@ -91,6 +93,7 @@ fn test_format_with_stdout_option() {
         is_stdout: true,
         recursively: true,
         omit_errors: false,
+        ..Default::default()
     };
     let changed_files = format("./src/format/test_data/format_path_data/", &opts).unwrap();
     assert_eq!(changed_files.len(), 2);
@@ -102,6 +105,7 @@ fn test_format_with_omit_error_option() {
         is_stdout: false,
         recursively: false,
         omit_errors: true,
+        ..Default::default()
     };
     let cases = [
         (
@@ -173,6 +177,152 @@ a: {
     }
 }
 
+#[test]
+fn test_format_with_indent_options() {
+    // The mixed indentation is normalized to the configured style.
+    let code = r#"schema Person:
+  name: str
+  labels: {str:str}
+
+  check:
+        len(name) > 0
+
+person = Person {
+    name = "Alice"
+    labels = {
+      app = "web"
+    }
+}
+names = [
+  "a"
+      "b"
+]
+"#;
+    let opts = FormatOptions {
+        indent_width: 2,
+        ..Default::default()
+    };
+    let (formatted, _) = format_source("", code, &opts).unwrap();
+    assert_eq!(
+        formatted,
+        r#"schema Person:
+  name: str
+  labels: {str:str}
+
+  check:
+    len(name) > 0
+
+person = Person {
+  name = "Alice"
+  labels = {
+    app = "web"
+  }
+}
+names = [
+  "a"
+  "b"
+]
+"#
+    );
+    let opts = FormatOptions {
+        use_tabs: true,
+        ..Default::default()
+    };
+    let (formatted, _) = format_source("", code, &opts).unwrap();
+    assert_eq!(
+        formatted,
+        "schema Person:\n\tname: str\n\tlabels: {str:str}\n\n\tcheck:\n\t\tlen(name) > 0\n\nperson = Person {\n\tname = \"Alice\"\n\tlabels = {\n\t\tapp = \"web\"\n\t}\n}\nnames = [\n\t\"a\"\n\t\"b\"\n]\n"
+    );
+}
+
+#[test]
+fn test_format_with_verify_roundtrip_option() {
+    let opts = FormatOptions {
+        verify_roundtrip: true,
+        ..Default::default()
+    };
+    let (formatted, is_formatted) = format_source("", "a=1+2*3\n", &opts).unwrap();
+    assert_eq!(formatted, "a = 1 + 2 * 3\n");
+    assert!(is_formatted);
+
+    let module = parse_file_force_errors("", Some("a = (1 + 2) * 3\n".to_string())).unwrap();
+    assert!(is_roundtrip_equal("", &module, "a = (1 + 2) * 3\n").unwrap());
+    assert!(!is_roundtrip_equal("", &module, "a = 1 + 2 * 3\n").unwrap());
+    assert!(!is_roundtrip_equal("", &module, "a = (1 + 2\n").unwrap());
+}
+
+#[test]
+fn test_format_incremental() {
+    let code = r#"a=1
+# The person.
+person = {
+  name="Alice"
+}
+
+
+@deprecated
+schema Person:
+  name:str
+b=[1,2]
+"#;
+    let apply = |edits: &[TextEdit]| {
+        let mut lines: Vec<String> = code.split_inclusive('\n').map(|l| l.to_string()).collect();
+        for edit in edits.iter().rev() {
+            lines.splice(edit.start_line..edit.end_line, [edit.new_text.clone()]);
+        }
+        lines.concat()
+    };
+    let opts = FormatOptions::default();
+    // Only the statement overlapping the edit is formatted.
+    let edits = format_incremental("", code, 3..4, &opts).unwrap();
+    assert_eq!(
+        edits,
+        vec![TextEdit {
+            start_line: 3,
+            end_line: 4,
+            new_text: "    name = \"Alice\"\n".to_string(),
+        }]
+    );
+    assert_eq!(
+        apply(&edits),
+        "a=1\n# The person.\nperson = {\n    name = \"Alice\"\n}\n\n\n@deprecated\nschema Person:\n  name:str\nb=[1,2]\n"
+    );
+    // The decorators belong to the schema statement, and the formatted region is the
+    // same as the region in the fully formatted source.
+    let (formatted, _) = format_source("", code, &opts).unwrap();
+    let edits = format_incremental("", code, 7..7, &opts).unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].start_line, 9);
+    assert_eq!(edits[0].end_line, 10);
+    assert!(formatted.contains("@deprecated\nschema Person:\n    name: str\n"));
+    assert!(apply(&edits).contains("@deprecated\nschema Person:\n    name: str\nb=[1,2]\n"));
+    // The edits across the statements.
+    let edits = format_incremental("", code, 0..11, &opts).unwrap();
+    assert_eq!(apply(&edits), formatted);
+    // No edits for the blank lines and the formatted statements.
+    assert!(format_incremental("", code, 5..6, &opts)
+        .unwrap()
+        .is_empty());
+    assert!(format_incremental("", &formatted, 0..1, &opts)
+        .unwrap()
+        .is_empty());
+    // The comments in the formatted region are kept.
+    let code = "# a\na=1\nconfig = {\n  # name\n  name=\"Alice\"\n}\n";
+    let opts = FormatOptions {
+        verify_roundtrip: true,
+        ..Default::default()
+    };
+    let edits = format_incremental("", code, 2..3, &opts).unwrap();
+    assert_eq!(
+        edits,
+        vec![TextEdit {
+            start_line: 3,
+            end_line: 5,
+            new_text: "    # name\n    name = \"Alice\"\n".to_string(),
+        }]
+    );
+}
+
 #[test]
 fn test_format_integration_konfig() -> Result<()> {
     let konfig_path = Path::new(".")
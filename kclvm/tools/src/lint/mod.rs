@@ -4,10 +4,26 @@ use indexmap::IndexSet;
 use kclvm_error::{Diagnostic, Handler};
 use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
 use kclvm_runtime::PanicInfo;
+use kclvm_sema::lint::{CombinedLintPass, LintArray};
 use kclvm_sema::resolver::resolve_program_with_opts;
 #[cfg(test)]
 mod tests;
 
+/// Lint options.
+/// - disabled_lints: the lint names e.g., `UNUSED_IMPORT` or codes e.g., `W0411` to skip.
+/// - denied_lints: the lint names or codes whose warnings are reported as errors.
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    pub disabled_lints: Vec<String>,
+    pub denied_lints: Vec<String>,
+}
+
+/// Get all the registered lints including their names, codes and descriptions.
+#[inline]
+pub fn get_lints() -> LintArray {
+    CombinedLintPass::get_lints()
+}
+
 /// KCL Lint tools API, check a set of files, skips execute, divides and returns diagnostics into error and warning
 ///
 /// # Parameters
@@ -68,6 +84,16 @@ mod tests;
 pub fn lint_files(
     files: &[&str],
     opts: Option<LoadProgramOptions>,
+) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
+    lint_files_with_opts(files, opts, LintOptions::default())
+}
+
+/// Lint a set of files with the lint options, which are able to disable some of the lint
+/// passes. See [lint_files] and [LintOptions]
+pub fn lint_files_with_opts(
+    files: &[&str],
+    opts: Option<LoadProgramOptions>,
+    lint_opts: LintOptions,
 ) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
     // Parse AST program.
     let sess = Arc::new(ParseSession::default());
@@ -86,6 +112,8 @@ pub fn lint_files(
             &mut program,
             kclvm_sema::resolver::Options {
                 merge_program: false,
+                disabled_lints: lint_opts.disabled_lints,
+                denied_lints: lint_opts.denied_lints,
                 ..Default::default()
             },
             None,
@@ -1,4 +1,5 @@
-use super::lint_files;
+use super::{get_lints, lint_files, lint_files_with_opts, LintOptions};
+use kclvm_sema::lint::CombinedLintPass;
 use std::path::PathBuf;
 
 #[test]
@@ -68,3 +69,154 @@ fn test_unused_check_for_each_file() {
         path.to_str().unwrap().to_string()
     );
 }
+
+#[test]
+fn test_lint_with_disabled_lints() {
+    let (_, warnings) = lint_files_with_opts(
+        &["./src/lint/test_data/lint.k"],
+        None,
+        LintOptions {
+            disabled_lints: vec!["W0411".to_string(), "reimport".to_string()],
+            ..Default::default()
+        },
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].messages[0].message,
+        "The import stmt should be placed at the top of the module"
+    );
+    let codes = get_lints().iter().map(|l| l.code).collect::<Vec<&str>>();
+    assert_eq!(codes, vec!["W0413", "W0411", "W0404", "W0416", "W0417"]);
+}
+
+#[test]
+fn test_disable_lints() {
+    let mut pass = CombinedLintPass::new();
+    assert!(pass.disable("unused_import"));
+    assert!(pass.disable("W0404"));
+    assert!(!pass.disable("W9999"));
+    let disabled = get_lints()
+        .into_iter()
+        .filter(|lint| !pass.is_enabled(lint))
+        .map(|lint| lint.code)
+        .collect::<Vec<&str>>();
+    assert_eq!(disabled, vec!["W0411", "W0404"]);
+}
+
+#[test]
+fn test_lint_incompatible_comparison() {
+    let file = "./src/lint/test_data/incompatible_comparison.k";
+    let (errors, warnings) = lint_files(&[file], None);
+    assert_eq!(errors.len(), 0);
+    let msgs = [
+        "comparison '==' between incompatible types 'int' and 'str' is always False",
+        "comparison '!=' between incompatible types 'str' and 'bool' is always True",
+    ];
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|w| w.messages[0].message.as_str())
+            .collect::<Vec<&str>>(),
+        msgs
+    );
+
+    // The lint can be reported as errors.
+    let (errors, warnings) = lint_files_with_opts(
+        &[file],
+        None,
+        LintOptions {
+            denied_lints: vec!["incompatible_comparison".to_string()],
+            ..Default::default()
+        },
+    );
+    assert_eq!(errors.len(), 2);
+    assert_eq!(warnings.len(), 0);
+
+    // The ordering and arithmetic operators are type errors instead of the lint.
+    let file = "./src/lint/test_data/incompatible_ordering.k";
+    let (errors, warnings) = lint_files(&[file], None);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(warnings.len(), 0);
+}
+
+#[test]
+fn test_lint_redundant_attribute() {
+    let file = "./src/lint/test_data/redundant_attribute.k";
+    let (errors, warnings) = lint_files(&[file], None);
+    assert_eq!(errors.len(), 0);
+    // The attributes reassigned in the parent schemas or their mixins are not redundant.
+    let msgs = [
+        "the attribute 'replicas' of schema 'App' is redundant, it is declared with the same type and default value in the parent schema 'Base'",
+        "the attribute 'replicas' of schema 'Server' is redundant, it is declared with the same type and default value in the parent schema 'App'",
+        "the attribute 'name' of schema 'Server' is redundant, it is declared with the same type and default value in the parent schema 'App'",
+        "the attribute 'port' of schema 'WebReset' is redundant, it is declared with the same type and default value in the parent schema 'Web'",
+        "the attribute 'port' of schema 'LocalService' is redundant, it is declared with the same type and default value in the parent schema 'Service'",
+    ];
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|w| w.messages[0].message.as_str())
+            .collect::<Vec<&str>>(),
+        msgs
+    );
+    // The inherited attribute declaration is related to the warning.
+    assert_eq!(warnings[0].messages[0].range.0.line, 7);
+    assert_eq!(warnings[0].messages[0].related[0].0 .0.line, 3);
+    // The attribute declared in the mixin of the parent schema.
+    assert_eq!(warnings[3].messages[0].related[0].0 .0.line, 32);
+
+    // The same non-literal default value in another module may denote a different value.
+    let other = "./src/lint/test_data/redundant_attribute_other.k";
+    let (errors, warnings) = lint_files(&[file, other], None);
+    assert_eq!(errors.len(), 0);
+    let other_msgs: Vec<&str> = warnings
+        .iter()
+        .filter(|w| {
+            w.messages[0]
+                .range
+                .0
+                .filename
+                .ends_with("redundant_attribute_other.k")
+        })
+        .map(|w| w.messages[0].message.as_str())
+        .collect();
+    assert_eq!(
+        other_msgs,
+        ["the attribute 'port' of schema 'OtherWebReset' is redundant, it is declared with the same type and default value in the parent schema 'Web'"]
+    );
+
+    let (_, warnings) = lint_files_with_opts(
+        &[file],
+        None,
+        LintOptions {
+            disabled_lints: vec!["W0417".to_string()],
+            ..Default::default()
+        },
+    );
+    assert_eq!(warnings.len(), 0);
+}
+
+#[test]
+fn test_lint_directive() {
+    let file = "./src/lint/test_data/lint_directive.k";
+    let (errors, warnings) = lint_files(&[file], None);
+    assert_eq!(errors.len(), 0);
+    let mut msgs = warnings
+        .iter()
+        .map(|w| (w.messages[0].range.0.line, w.messages[0].message.as_str()))
+        .collect::<Vec<(u64, &str)>>();
+    msgs.sort();
+    assert_eq!(
+        msgs,
+        [
+            (3, "Module 'net' imported but unused"),
+            (4, "unknown lint 'W9999' in the lint directive"),
+            (11, "the attribute 'name' of schema 'App' is redundant, it is declared with the same type and default value in the parent schema 'Base'"),
+            // The trailing directive only applies to the statement on the same line.
+            (17, "the attribute 'name' of schema 'Worker' is redundant, it is declared with the same type and default value in the parent schema 'Base'"),
+            // The directive without the lint names or codes disables nothing.
+            (17, "the lint directive 'kcl-lint-disable' disables no lints, expected the lint names or codes"),
+            (20, "the attribute 'name' of schema 'Job' is redundant, it is declared with the same type and default value in the parent schema 'Base'"),
+        ]
+    );
+}
@@ -8,12 +8,18 @@
 //! [kclvm_runner::Artifact], which is regard as a new compilation entry point. Then,
 //! it executes each test case separately and collects information about the test cases,
 //! such as the execution time and whether the test passes or fails.
-pub use crate::testing::suite::{load_test_suites, TestSuite};
+//!
+//! The test cases can be tagged by the `# @tags: slow, integration` comment directly above
+//! the lambda, and the tags are used to select the test cases to run with the
+//! [TestOptions::tags] and [TestOptions::skip_tags] options.
+pub use crate::testing::reporter::{JsonReporter, Reporter, TestStatus, TestSummary, TextReporter};
+pub use crate::testing::suite::{load_test_suites, TestCase, TestSuite};
 use anyhow::{Error, Result};
 use indexmap::IndexMap;
 use kclvm_runner::ExecProgramArgs;
 use std::time::Duration;
 
+mod reporter;
 mod suite;
 
 #[cfg(test)]
@@ -33,6 +39,36 @@ pub trait TestRun {
 pub struct TestResult {
     /// This field stores test case information in an [IndexMap], where the key is a [String] and the value is a [TestCaseInfo] struct.
     pub info: IndexMap<String, TestCaseInfo>,
+    /// This field stores the schema attribute coverage aggregated across all the test cases,
+    /// only set when the [TestOptions::coverage] option is enabled.
+    pub coverage: IndexMap<String, SchemaCoverage>,
+}
+
+/// Represents the attribute coverage of a schema.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaCoverage {
+    /// This field stores the assignment count of each schema attribute across all the test cases.
+    pub attrs: IndexMap<String, usize>,
+}
+
+impl SchemaCoverage {
+    /// Get the attributes assigned by at least one test case.
+    pub fn covered_attrs(&self) -> Vec<&str> {
+        self.attrs
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(attr, _)| attr.as_str())
+            .collect()
+    }
+
+    /// Get the ratio of the covered attributes in `[0, 1]`.
+    pub fn ratio(&self) -> f64 {
+        if self.attrs.is_empty() {
+            1.0
+        } else {
+            self.covered_attrs().len() as f64 / self.attrs.len() as f64
+        }
+    }
 }
 
 /// Represents information about a test case.
@@ -55,4 +91,12 @@ pub struct TestOptions {
     pub run_regexp: String,
     /// This field determines whether the test run should stop on the first failure.
     pub fail_fast: bool,
+    /// This field determines whether to record the schema attributes assigned by the test cases.
+    pub coverage: bool,
+    /// This field stores the tags to select the test cases, only the test cases with any of the
+    /// tags are run. All the test cases are run when it is empty.
+    pub tags: Vec<String>,
+    /// This field stores the tags to skip the test cases, the test cases with any of the tags
+    /// are skipped even though they are selected by [TestOptions::tags].
+    pub skip_tags: Vec<String>,
 }
@@ -0,0 +1,185 @@
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::testing::{TestResult, TestSuite};
+
+/// Represents the status of a test case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    /// The test case is not run e.g., the suite is skipped or stopped by the `fail_fast` option.
+    Skipped,
+}
+
+impl TestStatus {
+    /// Returns the lowercase status name e.g., `passed`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestStatus::Passed => "passed",
+            TestStatus::Failed => "failed",
+            TestStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// Represents the summary of the test results of all the test suites.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TestSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// The total duration of all the test cases.
+    pub duration: Duration,
+}
+
+impl TestSummary {
+    /// Summarize the test results of the test suites.
+    pub fn new(results: &[(TestSuite, TestResult)]) -> Self {
+        let mut summary = TestSummary::default();
+        for (suite, result) in results {
+            for (name, _) in &suite.cases {
+                summary.total += 1;
+                match case_status(suite, result, name) {
+                    TestStatus::Passed => summary.passed += 1,
+                    TestStatus::Failed => summary.failed += 1,
+                    TestStatus::Skipped => summary.skipped += 1,
+                }
+            }
+            summary.duration += result.info.values().map(|info| info.duration).sum();
+        }
+        summary
+    }
+
+    /// Whether there is no failed test case.
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Trait for reporting the test results.
+pub trait Reporter {
+    /// Write the report of the test results of the test suites to the writer.
+    fn report(&self, results: &[(TestSuite, TestResult)], writer: &mut dyn Write) -> Result<()>;
+}
+
+/// Reports the test results as a human-readable table followed by the summary line.
+#[derive(Debug, Default)]
+pub struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn report(&self, results: &[(TestSuite, TestResult)], writer: &mut dyn Write) -> Result<()> {
+        for (suite, result) in results {
+            for (name, _) in &suite.cases {
+                let status = case_status(suite, result, name);
+                let duration = result
+                    .info
+                    .get(name)
+                    .map(|info| info.duration)
+                    .unwrap_or_default();
+                writeln!(
+                    writer,
+                    "{:<8} {:<10} {} {}",
+                    status.name().to_uppercase(),
+                    format!("{:.3}s", duration.as_secs_f64()),
+                    suite.pkg,
+                    name
+                )?;
+                if let Some(info) = result.info.get(name) {
+                    if let Some(err) = &info.error {
+                        writeln!(writer, "{}", err)?;
+                    }
+                }
+            }
+        }
+        let summary = TestSummary::new(results);
+        writeln!(
+            writer,
+            "{}: {} total, {} passed, {} failed, {} skipped in {:.3}s",
+            if summary.is_success() { "ok" } else { "FAIL" },
+            summary.total,
+            summary.passed,
+            summary.failed,
+            summary.skipped,
+            summary.duration.as_secs_f64()
+        )?;
+        Ok(())
+    }
+}
+
+/// Reports the test results as a JSON object including the summary counts and
+/// the details of each test suite and test case for the CI systems, e.g.,
+///
+/// ```json
+/// {
+///   "total": 2, "passed": 1, "failed": 1, "skipped": 0, "duration_ms": 1.2,
+///   "suites": [{
+///     "pkg": "./pkg", "duration_ms": 1.2,
+///     "cases": [{"name": "test_a", "status": "passed", "duration_ms": 0.6, "log_message": "", "error": null}, ...]
+///   }]
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, results: &[(TestSuite, TestResult)], writer: &mut dyn Write) -> Result<()> {
+        let summary = TestSummary::new(results);
+        let suites = results
+            .iter()
+            .map(|(suite, result)| {
+                let cases = suite
+                    .cases
+                    .keys()
+                    .map(|name| {
+                        let info = result.info.get(name);
+                        json!({
+                            "name": name,
+                            "status": case_status(suite, result, name).name(),
+                            "duration_ms": duration_ms(info.map(|info| info.duration).unwrap_or_default()),
+                            "log_message": info.map(|info| info.log_message.as_str()).unwrap_or_default(),
+                            "error": info.and_then(|info| info.error.as_ref().map(|err| err.to_string())),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "pkg": suite.pkg,
+                    "duration_ms": duration_ms(result.info.values().map(|info| info.duration).sum()),
+                    "cases": cases,
+                })
+            })
+            .collect::<Vec<_>>();
+        let report = json!({
+            "total": summary.total,
+            "passed": summary.passed,
+            "failed": summary.failed,
+            "skipped": summary.skipped,
+            "duration_ms": duration_ms(summary.duration),
+            "suites": suites,
+        });
+        writeln!(writer, "{}", serde_json::to_string_pretty(&report)?)?;
+        Ok(())
+    }
+}
+
+/// Get the status of the test case `name` in the test suite.
+fn case_status(suite: &TestSuite, result: &TestResult, name: &str) -> TestStatus {
+    if suite.skip {
+        return TestStatus::Skipped;
+    }
+    match result.info.get(name) {
+        Some(info) if info.error.is_some() => TestStatus::Failed,
+        Some(_) => TestStatus::Passed,
+        None => TestStatus::Skipped,
+    }
+}
+
+#[inline]
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -1,4 +1,4 @@
-use std::{fs::remove_file, path::Path};
+use std::{collections::HashMap, fs::remove_file, path::Path};
 
 use crate::testing::{TestCaseInfo, TestOptions, TestResult, TestRun};
 use anyhow::{anyhow, Result};
@@ -19,6 +19,8 @@ use std::time::Instant;
 pub const TEST_FILE_SUFFIX: &str = "_test.k";
 /// Prefix for test suite names.
 pub const TEST_SUITE_PREFIX: &str = "test_";
+/// Prefix of the comment directive to tag the test case e.g., `# @tags: slow, integration`.
+pub const TEST_TAGS_DIRECTIVE: &str = "@tags:";
 
 const TEST_MAIN_FILE: &str = "_kcl_test.k";
 const TEST_CASE_RUN_OPTION: &str = "_kcl_test_case_run";
@@ -60,9 +62,11 @@ impl TestRun for TestSuite {
             k_filename_list: self.get_input_files(&main_file),
             overrides: vec![],
             disable_yaml_result: true,
+            record_attr_coverage: opts.coverage,
             ..opts.exec_args.clone()
         };
-        let is_fast_eval_mode = std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok();
+        // The attribute coverage is only recorded by the evaluator.
+        let is_fast_eval_mode = std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok() || opts.coverage;
         // Build the program
         let artifact: Option<Artifact> = if is_fast_eval_mode {
             None
@@ -80,7 +84,10 @@ impl TestRun for TestSuite {
         // Save the user argument options.
         let user_args = args.args;
         // Test every case in the suite.
-        for (name, _) in &self.cases {
+        for (name, case) in &self.cases {
+            if case.skip {
+                continue;
+            }
             args.args = vec![ast::Argument {
                 name: TEST_CASE_RUN_OPTION.into(),
                 value: format!("{:?}", name),
@@ -104,6 +111,13 @@ impl TestRun for TestSuite {
             } else {
                 Some(anyhow!("{}", exec_result.err_message))
             };
+            // Aggregate the schema attribute coverage of all the test cases.
+            for (schema, attrs) in &exec_result.attr_coverage {
+                let coverage = result.coverage.entry(schema.clone()).or_default();
+                for (attr, count) in attrs {
+                    *coverage.attrs.entry(attr.clone()).or_default() += count;
+                }
+            }
             // Check if the fail_fast option is enabled and there was an error.
             let fail_fast = error.is_some() && opts.fail_fast;
             // Add test case information to the result.
@@ -155,7 +169,13 @@ impl TestSuite {
     }
 }
 
-pub struct TestCase;
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TestCase {
+    /// Tags of the test case from the `# @tags: ...` comment directly above it.
+    pub tags: Vec<String>,
+    /// Flag indicating whether the test case is skipped by the tag options.
+    pub skip: bool,
+}
 
 /// Load test suite from path
 pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Vec<TestSuite>> {
@@ -166,25 +186,34 @@ pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Ve
         let mut cases = IndexMap::new();
         for file in &test_files {
             let module = parse_file_force_errors(file, None)?;
+            let comments: HashMap<u64, &str> = module
+                .comments
+                .iter()
+                .map(|comment| (comment.line, comment.node.text.as_str()))
+                .collect();
             for stmt in &module.body {
                 if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
                     if let ast::Expr::Lambda(_lambda_expr) = &assign_stmt.value.node {
                         for target in &assign_stmt.targets {
                             let func_name = target.node.get_name();
                             if is_test_suite(func_name) && should_run(&opts.run_regexp, func_name) {
-                                cases.insert(func_name.to_string(), TestCase {});
+                                let tags = get_test_case_tags(&comments, stmt.line);
+                                let skip = !should_run_tags(opts, &tags);
+                                cases.insert(func_name.to_string(), TestCase { tags, skip });
                             }
                         }
                     }
                 }
             }
         }
+        // Skip the test suite when all of its test cases are skipped by the tag options.
+        let skip = !cases.is_empty() && cases.values().all(|case| case.skip);
         suites.push(TestSuite {
             pkg: pkg.clone(),
             cases,
             normal_files,
             test_files,
-            skip: false,
+            skip,
         });
     }
     Ok(suites)
@@ -211,6 +240,32 @@ fn is_test_suite(name: &str) -> bool {
     name.starts_with(TEST_SUITE_PREFIX)
 }
 
+/// Get the tags of the test case at the line from the `# @tags: ...` comments in the
+/// consecutive comment lines directly above it.
+fn get_test_case_tags(comments: &HashMap<u64, &str>, line: u64) -> Vec<String> {
+    let mut tags = vec![];
+    let mut line = line;
+    while let Some(text) = line.checked_sub(1).and_then(|l| comments.get(&l)) {
+        let text = text.trim_start_matches('#').trim();
+        if let Some(directive) = text.strip_prefix(TEST_TAGS_DIRECTIVE) {
+            tags.extend(
+                directive
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty()),
+            );
+        }
+        line -= 1;
+    }
+    tags
+}
+
+#[inline]
+fn should_run_tags(opts: &TestOptions, tags: &[String]) -> bool {
+    (opts.tags.is_empty() || tags.iter().any(|tag| opts.tags.contains(tag)))
+        && !tags.iter().any(|tag| opts.skip_tags.contains(tag))
+}
+
 #[inline]
 fn should_run(run_regexp: &str, name: &str) -> bool {
     if !run_regexp.is_empty() {
@@ -47,3 +47,76 @@ fn test_load_test_suites_and_run() {
         test_result.info[2].error
     );
 }
+
+#[test]
+fn test_run_with_coverage() {
+    let opts = TestOptions {
+        coverage: true,
+        ..Default::default()
+    };
+    let suites = load_test_suites(
+        Path::new(".")
+            .join("src")
+            .join("testing")
+            .join("test_data")
+            .join("module")
+            .join("coverage")
+            .to_str()
+            .unwrap(),
+        &opts,
+    )
+    .unwrap();
+    assert_eq!(suites.len(), 1);
+    let test_result = suites[0].run(&opts).unwrap();
+    assert_eq!(test_result.info.len(), 2);
+    assert!(test_result.info.values().all(|info| info.error.is_none()));
+    let coverage = &test_result.coverage["__main__.Person"];
+    assert_eq!(coverage.attrs["name"], 2);
+    assert_eq!(coverage.attrs["age"], 1);
+    assert_eq!(coverage.attrs["email"], 0);
+    assert_eq!(coverage.covered_attrs(), vec!["name", "age"]);
+}
+
+#[test]
+fn test_load_test_suites_with_tags() {
+    let path = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("tags");
+    let opts = TestOptions {
+        tags: vec!["slow".to_string()],
+        skip_tags: vec!["integration".to_string()],
+        ..Default::default()
+    };
+    let suites = load_test_suites(path.to_str().unwrap(), &opts).unwrap();
+    assert_eq!(suites.len(), 1);
+    assert!(!suites[0].skip);
+    let cases: Vec<(&str, Vec<&str>, bool)> = suites[0]
+        .cases
+        .iter()
+        .map(|(name, case)| {
+            let tags = case.tags.iter().map(|tag| tag.as_str()).collect();
+            (name.as_str(), tags, case.skip)
+        })
+        .collect();
+    let expected: Vec<(&str, Vec<&str>, bool)> = vec![
+        ("test_slow", vec!["slow"], false),
+        ("test_integration", vec!["integration", "slow"], true),
+        ("test_untagged", vec![], true),
+    ];
+    assert_eq!(cases, expected);
+    // Only the selected test case is run.
+    let test_result = suites[0].run(&opts).unwrap();
+    assert_eq!(test_result.info.len(), 1);
+    assert!(test_result.info["test_slow"].error.is_none());
+
+    // The test suite is skipped when all of its test cases are skipped.
+    let opts = TestOptions {
+        tags: vec!["not_found".to_string()],
+        ..Default::default()
+    };
+    let suites = load_test_suites(path.to_str().unwrap(), &opts).unwrap();
+    assert!(suites[0].skip);
+}
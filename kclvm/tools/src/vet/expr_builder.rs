@@ -96,7 +96,8 @@ impl ExprGenerator<serde_yaml::Value> for ExprBuilder {
 
                     Ok(node_ref!(Expr::NumberLit(NumberLit {
                         binary_suffix: None,
-                        value: NumberLitValue::Float(number_lit)
+                        value: NumberLitValue::Float(number_lit),
+                        raw_value: None
                     })))
                 } else if j_num.is_i64() {
                     let number_lit = match j_num.as_i64() {
@@ -108,7 +109,8 @@ impl ExprGenerator<serde_yaml::Value> for ExprBuilder {
 
                     Ok(node_ref!(Expr::NumberLit(NumberLit {
                         binary_suffix: None,
-                        value: NumberLitValue::Int(number_lit)
+                        value: NumberLitValue::Int(number_lit),
+                        raw_value: None
                     })))
                 } else {
                     bail!("{FAIL_LOAD_VALIDATED_ERR_MSG}, Unsupported Unsigned 64");
@@ -220,7 +222,8 @@ impl ExprGenerator<located_yaml::Yaml> for ExprBuilder {
                     Ok(node_ref!(
                         Expr::NumberLit(NumberLit {
                             binary_suffix: None,
-                            value: NumberLitValue::Int(*j_int)
+                            value: NumberLitValue::Int(*j_int),
+                            raw_value: None
                         }),
                         loc
                     ))
@@ -236,7 +239,8 @@ impl ExprGenerator<located_yaml::Yaml> for ExprBuilder {
                     Ok(node_ref!(
                         Expr::NumberLit(NumberLit {
                             binary_suffix: None,
-                            value: NumberLitValue::Float(number_lit)
+                            value: NumberLitValue::Float(number_lit),
+                            raw_value: None
                         }),
                         loc
                     ))
@@ -374,7 +378,8 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
                     Ok(node_ref!(
                         Expr::NumberLit(NumberLit {
                             binary_suffix: None,
-                            value: NumberLitValue::Float(number_lit)
+                            value: NumberLitValue::Float(number_lit),
+                            raw_value: None
                         }),
                         loc
                     ))
@@ -389,7 +394,8 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
                     Ok(node_ref!(
                         Expr::NumberLit(NumberLit {
                             binary_suffix: None,
-                            value: NumberLitValue::Int(number_lit)
+                            value: NumberLitValue::Int(number_lit),
+                            raw_value: None
                         }),
                         loc
                     ))
@@ -519,7 +525,8 @@ impl ExprGenerator<serde_json::Value> for ExprBuilder {
 
                     Ok(node_ref!(Expr::NumberLit(NumberLit {
                         binary_suffix: None,
-                        value: NumberLitValue::Float(number_lit)
+                        value: NumberLitValue::Float(number_lit),
+                        raw_value: None
                     })))
                 } else if j_num.is_i64() {
                     let number_lit = match j_num.as_i64() {
@@ -531,7 +538,8 @@ impl ExprGenerator<serde_json::Value> for ExprBuilder {
 
                     Ok(node_ref!(Expr::NumberLit(NumberLit {
                         binary_suffix: None,
-                        value: NumberLitValue::Int(number_lit)
+                        value: NumberLitValue::Int(number_lit),
+                        raw_value: None
                     })))
                 } else {
                     bail!("{FAIL_LOAD_VALIDATED_ERR_MSG}, Unsupported Unsigned 64");
@@ -319,7 +319,7 @@ mod test_validater {
         util::loader::LoaderKind,
         vet::{
             tests::deal_windows_filepath,
-            validator::{validate, ValidateOption},
+            validator::{validate, SchemaValidator, ValidateOption},
         },
     };
 
@@ -609,6 +609,37 @@ mod test_validater {
             }
         }
     }
+
+    #[test]
+    fn test_schema_validator() {
+        let kcl_code = fs::read_to_string(
+            construct_full_path(&format!("{}/{}", "validate_cases", "test.k")).unwrap(),
+        )
+        .unwrap();
+        let validator = SchemaValidator::compile(&kcl_code, None).unwrap();
+
+        for (i, file_suffix) in VALIDATED_FILE_TYPE.iter().enumerate() {
+            let record = fs::read_to_string(
+                construct_full_path(&format!("validate_cases/test.k.{}", file_suffix)).unwrap(),
+            )
+            .unwrap();
+            let result = validator.validate(&record, *LOADER_KIND[i]);
+            assert!(result.success, "{}", result.err_message);
+        }
+        // The same validator is reused for each record.
+        for record in [
+            r#"{"name": "Bob", "age": 18}"#,
+            r#"{"name": "Alice", "age": 1}"#,
+        ] {
+            let result = validator.validate(record, LoaderKind::JSON);
+            assert!(!result.success);
+            assert!(result.err_message.contains("Check failed"));
+        }
+        let result = validator.validate(r#"{"name": "Alice", "age": 18}"#, LoaderKind::JSON);
+        assert!(result.success, "{}", result.err_message);
+
+        assert!(SchemaValidator::compile("schema User:\n    name: UnknownType\n", None).is_err());
+    }
 }
 
 /// Deal with windows filepath
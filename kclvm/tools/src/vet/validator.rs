@@ -69,11 +69,17 @@ use super::expr_builder::ExprBuilder;
 pub use crate::util::loader::LoaderKind;
 use anyhow::Result;
 use kclvm_ast::{
-    ast::{AssignStmt, Expr, Node, NodeRef, Program, SchemaStmt, Stmt, Target},
+    ast::{AssignStmt, Expr, Module, Node, NodeRef, Program, SchemaStmt, Stmt, Target},
     node_ref,
 };
+use kclvm_error::Handler;
 use kclvm_parser::{LoadProgramOptions, ParseSessionRef};
-use kclvm_runner::{execute, ExecProgramArgs, MapErrorResult};
+use kclvm_runner::{execute, runner::FastRunner, ExecProgramArgs, MapErrorResult};
+use kclvm_sema::resolver::resolve_program;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 const TMP_FILE: &str = "validationTempKCLCode.k";
 
@@ -207,31 +213,155 @@ pub fn validate(val_opt: ValidateOption) -> Result<bool> {
 
     let assign_stmt = build_assign(&val_opt.attribute_name, validated_expr);
 
-    match compile_res.program.pkgs.get(kclvm_ast::MAIN_PKG) {
+    insert_stmt_into_main_module(&compile_res.program, assign_stmt)?;
+
+    execute(
+        ParseSessionRef::default(),
+        compile_res.program,
+        &ExecProgramArgs::default(),
+    )
+    .map_err_to_result()
+    .map(|_| true)
+}
+
+/// The attribute name of the validated data record in [SchemaValidator].
+const VALIDATED_ATTR_NAME: &str = "value";
+
+/// SchemaValidator compiles a KCL schema program once and validates many data records
+/// against it. The schema program is parsed, loaded and resolved only once, and each
+/// record is evaluated on a copy of the resolved program with the AST evaluator without
+/// resolving it again, which is much faster than [validate] for bulk validation.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_tools::vet::validator::{LoaderKind, SchemaValidator};
+///
+/// let code = "schema User:\n    age: int\n\n    check:\n        age > 10\n";
+/// let validator = SchemaValidator::compile(code, Some("User".to_string())).unwrap();
+/// assert!(validator.validate(r#"{"age": 18}"#, LoaderKind::JSON).success);
+/// let result = validator.validate(r#"{"age": 1}"#, LoaderKind::JSON);
+/// assert!(!result.success);
+/// assert!(result.err_message.contains("Check failed"));
+/// ```
+pub struct SchemaValidator {
+    program: Program,
+    schema_name: Option<String>,
+}
+
+/// The validation result of a data record, see [SchemaValidator::validate].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidateResult {
+    pub success: bool,
+    /// The error message e.g., the failed schema checks when the validation fails.
+    pub err_message: String,
+}
+
+impl SchemaValidator {
+    /// Compile the KCL schema code. When `schema_name` is [None], the first schema in the
+    /// code is used. Returns an error when the schema code has compile errors.
+    pub fn compile(schema_code: &str, schema_name: Option<String>) -> Result<Self> {
+        let sess = ParseSessionRef::default();
+        let compile_res = kclvm_parser::load_program(
+            sess.clone(),
+            &[TMP_FILE],
+            Some(LoadProgramOptions {
+                k_code_list: vec![schema_code.to_string()],
+                load_plugins: true,
+                ..Default::default()
+            }),
+            None,
+        )?;
+        // Resolve the schema code once instead of resolving it again for each record.
+        let mut program = compile_res.program;
+        let scope = resolve_program(&mut program);
+        let (errors, _) = sess
+            .append_diagnostic(scope.handler.diagnostics)
+            .classification();
+        if !errors.is_empty() {
+            let mut handler = Handler::default();
+            handler.diagnostics = errors;
+            return Err(anyhow::anyhow!(handler.emit_to_string()?));
+        }
+        let schema_name = match schema_name {
+            Some(name) => Some(name),
+            None => filter_schema_stmt_from_prog(&program)
+                .first()
+                .map(|schema| schema.name.node.clone()),
+        };
+        Ok(Self {
+            program,
+            schema_name,
+        })
+    }
+
+    /// Validate a JSON or YAML data record against the compiled schema.
+    pub fn validate(&self, record: &str, kind: LoaderKind) -> ValidateResult {
+        match self.validate_record(record, kind) {
+            Ok(_) => ValidateResult {
+                success: true,
+                err_message: "".to_string(),
+            },
+            Err(err) => ValidateResult {
+                success: false,
+                err_message: err.to_string(),
+            },
+        }
+    }
+
+    fn validate_record(&self, record: &str, kind: LoaderKind) -> Result<()> {
+        let expr_builder = ExprBuilder::new_with_str(kind, record.to_string())?;
+        let validated_expr = expr_builder.build(self.schema_name.clone())?;
+        // Insert the record into a copy of the resolved program, which is evaluated directly
+        // because the resolver can't run twice on the same AST.
+        let program = deep_clone_program(&self.program);
+        insert_stmt_into_main_module(&program, build_assign(VALIDATED_ATTR_NAME, validated_expr))?;
+        FastRunner::new(None)
+            .run(&program, &ExecProgramArgs::default())
+            .map_err_to_result()
+            .map(|_| ())
+    }
+}
+
+/// Clone the program including the module ASTs, which are shared by [Program::clone].
+fn deep_clone_program(program: &Program) -> Program {
+    let clone_modules = |modules: &HashMap<String, Arc<RwLock<Module>>>| {
+        modules
+            .iter()
+            .map(|(name, module)| {
+                let module = module
+                    .read()
+                    .expect("Failed to acquire module lock")
+                    .clone();
+                (name.clone(), Arc::new(RwLock::new(module)))
+            })
+            .collect()
+    };
+    Program {
+        root: program.root.clone(),
+        pkgs: program.pkgs.clone(),
+        pkgs_not_imported: program.pkgs_not_imported.clone(),
+        modules: clone_modules(&program.modules),
+        modules_not_imported: clone_modules(&program.modules_not_imported),
+    }
+}
+
+fn insert_stmt_into_main_module(program: &Program, stmt: NodeRef<Stmt>) -> Result<()> {
+    match program.pkgs.get(kclvm_ast::MAIN_PKG) {
         Some(pkg) => {
             if let Some(module) = pkg.first() {
-                let mut m = compile_res
-                    .program
+                let mut m = program
                     .get_module_mut(module)
                     .expect("Failed to acquire module lock")
                     .expect(&format!("module {:?} not found in program", module));
-                m.body.insert(0, assign_stmt);
+                m.body.insert(0, stmt);
+                Ok(())
             } else {
-                return Err(anyhow::anyhow!("No main module found"));
+                Err(anyhow::anyhow!("No main module found"))
             }
         }
-        None => {
-            return Err(anyhow::anyhow!("No main package found"));
-        }
+        None => Err(anyhow::anyhow!("No main package found")),
     }
-
-    execute(
-        ParseSessionRef::default(),
-        compile_res.program,
-        &ExecProgramArgs::default(),
-    )
-    .map_err_to_result()
-    .map(|_| true)
 }
 
 fn build_assign(attr_name: &str, node: NodeRef<Expr>) -> NodeRef<Stmt> {
@@ -100,6 +100,32 @@ pub fn convert_windows_drive_letter(path: &str) -> String {
     }
 }
 
+/// Normalize the path in the results and diagnostics uniformly across the platforms
+/// when `forward_slash` is true, which makes the outputs reproducible across the
+/// platforms. On Windows, the verbatim prefix `\\?\` is removed, the drive letter is
+/// converted to upper case and the backslash separators are replaced with forward
+/// slashes. The backslashes are kept on the other platforms, where they are valid
+/// file name characters instead of separators. The path is returned unchanged when
+/// `forward_slash` is false.
+///
+/// # Examples
+///
+/// ```rust
+/// use kclvm_utils::path::normalize_path;
+///
+/// assert_eq!(normalize_path("pkg/main.k", false), "pkg/main.k");
+/// assert_eq!(normalize_path("pkg/main.k", true), "pkg/main.k");
+/// ```
+pub fn normalize_path(path: &str, forward_slash: bool) -> String {
+    if forward_slash && cfg!(target_os = "windows") {
+        convert_windows_drive_letter(path)
+            .adjust_canonicalization()
+            .replace('\\', "/")
+    } else {
+        path.to_string()
+    }
+}
+
 #[test]
 fn test_convert_drive_letter() {
     #[cfg(target_os = "windows")]
@@ -117,6 +143,25 @@ fn test_convert_drive_letter() {
     }
 }
 
+#[test]
+fn test_normalize_path() {
+    #[cfg(target_os = "windows")]
+    {
+        assert_eq!(
+            normalize_path(r"\\?\d:\xx\main.k", false),
+            r"\\?\d:\xx\main.k"
+        );
+        assert_eq!(normalize_path(r"\\?\d:\xx\main.k", true), "D:/xx/main.k");
+        assert_eq!(normalize_path(r"xx\main.k", true), "xx/main.k");
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        assert_eq!(normalize_path("/xx/main.k", false), "/xx/main.k");
+        assert_eq!(normalize_path("/xx/main.k", true), "/xx/main.k");
+        assert_eq!(normalize_path(r"xx\main.k", true), r"xx\main.k");
+    }
+}
+
 #[test]
 #[cfg(target_os = "windows")]
 fn test_adjust_canonicalization() {